@@ -13,4 +13,5 @@ pub enum ConfigurationObjectType {
     Location,
     TextOutput,
     Container,
+    Text,
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::util::rc_refcell::MutRcRefCell;
 
 use super::configuration_object::{AbstractConfigurationObject, Abstractable};
@@ -23,62 +25,93 @@ pub fn observe_configuration<C: Abstractable, F: FnMut() -> () + 'static>(
     // Whether to invoke after a change finished, instead of as soon as it happens (for batching)
     after_change: bool,
 ) -> impl FnOnce() -> () {
-    let on_change = MutRcRefCell::new(on_change);
-    let mut remove_dirty_ids = Vec::<(AbstractConfigurationObject, usize)>::new();
-    let mut remove_change_ids = Vec::<(AbstractConfigurationObject, usize)>::new();
-    let dirty = MutRcRefCell::new(false);
+    let observer = ConfigurationObserver {
+        on_change: MutRcRefCell::new(Box::new(on_change)),
+        dirty: MutRcRefCell::new(false),
+        listeners: MutRcRefCell::new(Vec::new()),
+        observed: MutRcRefCell::new(HashSet::new()),
+        after_change,
+    };
 
     // Run through all elements in the config, and setup a listener for each
-    let c = config.get_abstract();
-    let mut queue = vec![c];
-    while let Some(mut config_el) = queue.pop() {
-        let local_dirty = dirty.clone();
-        let local_after_change = on_change.clone();
-        remove_dirty_ids.push((
-            config_el.clone(),
-            config_el.add_dirty_listener(move || {
-                if !*local_dirty.get() {
-                    *local_dirty.get() = true;
-
-                    if !after_change {
-                        (local_after_change.get())();
-                    }
-                }
-            }),
-        ));
-
-        let local_dirty = dirty.clone();
-        let local_after_change = on_change.clone();
-        remove_change_ids.push((
-            config_el.clone(),
-            config_el.add_change_listener(move || {
-                if *local_dirty.get() {
-                    *local_dirty.get() = false;
-
-                    if after_change {
-                        (local_after_change.get())();
-                    }
-                }
-            }),
-        ));
-
-        for child in config_el.get_children() {
-            queue.push(child);
-        }
-    }
+    observer.observe(config.get_abstract());
 
     // Init if needed
     if init {
-        (on_change.get())();
+        (observer.on_change.get())();
     }
 
     // Cleanup function
     move || {
-        for (mut config_el, id) in remove_dirty_ids {
-            config_el.remove_dirty_listener(id);
+        let listeners = observer.listeners.get().drain(..).collect::<Vec<_>>();
+        for (mut config_el, dirty_id, change_id) in listeners {
+            config_el.remove_dirty_listener(dirty_id);
+            config_el.remove_change_listener(change_id);
+        }
+    }
+}
+
+struct ConfigurationObserver {
+    on_change: MutRcRefCell<Box<dyn FnMut() -> ()>>,
+    dirty: MutRcRefCell<bool>,
+    /// The observed elements, together with the ids of their dirty and change listeners
+    listeners: MutRcRefCell<Vec<(AbstractConfigurationObject, usize, usize)>>,
+    /// The ids of the observed elements, such that children that are added later on, e.g. to lists, can be observed as well
+    observed: MutRcRefCell<HashSet<String>>,
+    after_change: bool,
+}
+
+impl Clone for ConfigurationObserver {
+    fn clone(&self) -> Self {
+        ConfigurationObserver {
+            on_change: self.on_change.clone(),
+            dirty: self.dirty.clone(),
+            listeners: self.listeners.clone(),
+            observed: self.observed.clone(),
+            after_change: self.after_change,
         }
-        for (mut config_el, id) in remove_change_ids {
-            config_el.remove_change_listener(id);
+    }
+}
+
+impl ConfigurationObserver {
+    /// Sets up the listeners of the given element and its descendants that are not observed yet
+    fn observe(&self, config: AbstractConfigurationObject) {
+        let mut queue = vec![config];
+        while let Some(mut config_el) = queue.pop() {
+            if !self.observed.get().insert(config_el.get_id()) {
+                continue;
+            }
+
+            let observer = self.clone();
+            let parent = config_el.clone();
+            let dirty_id = config_el.add_dirty_listener(move || {
+                for child in parent.get_children() {
+                    observer.observe(child);
+                }
+                if !*observer.dirty.read() {
+                    *observer.dirty.get() = true;
+
+                    if !observer.after_change {
+                        (observer.on_change.get())();
+                    }
+                }
+            });
+
+            let observer = self.clone();
+            let change_id = config_el.add_change_listener(move || {
+                if *observer.dirty.read() {
+                    *observer.dirty.get() = false;
+
+                    if observer.after_change {
+                        (observer.on_change.get())();
+                    }
+                }
+            });
+
+            self.listeners
+                .get()
+                .push((config_el.clone(), dirty_id, change_id));
+            queue.extend(config_el.get_children());
         }
     }
 }
@@ -73,6 +73,10 @@ impl<C: Clone + 'static> ChoiceConfig<C> {
         })
     }
 
+    pub fn get_index(&self) -> usize {
+        self.data.with_value(|v| v.selected)
+    }
+
     pub fn set_options<const L: usize>(&mut self, choices: [Choice<C>; L]) -> Mutator<(), ()> {
         self.data.set_value(|cur| {
             Some(ChoiceValue {
@@ -95,6 +95,12 @@ impl<A: Abstractable + Clone + 'static> GetConfigChildren for A {
     }
 }
 
+impl<A: GetConfigChildren> GetConfigChildren for Vec<A> {
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        self.iter().flat_map(|item| item.get_children()).collect()
+    }
+}
+
 impl<A: GetConfigChildren, B: GetConfigChildren> GetConfigChildren for (A, B) {
     fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
         let mut out = self.0.get_children();
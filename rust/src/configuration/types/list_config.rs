@@ -0,0 +1,196 @@
+use std::rc::Rc;
+
+use itertools::Itertools;
+use wasm_bindgen::JsValue;
+
+use crate::configuration::{
+    configuration_object::{
+        AbstractConfigurationObject, Abstractable, ConfigurationObject, ValueMapping,
+    },
+    configuration_object_types::ConfigurationObjectType,
+    mutator::Mutator,
+};
+
+use super::{button_config::ButtonConfig, composite_config::CompositeConfig};
+
+///
+/// A list configuration, whose items can be added and removed by the user
+#[derive(Clone)]
+pub struct ListConfig<C: Abstractable + Clone + 'static> {
+    data: ConfigurationObject<ListConfig<C>, ListValue<C>>,
+    create_item: Rc<dyn Fn() -> C>,
+}
+
+#[derive(Clone)]
+struct ListValue<C: Abstractable + Clone + 'static> {
+    items: Vec<ListItem<C>>,
+    add_button: ButtonConfig,
+    next_id: usize,
+}
+
+/// An item of the list, which is shown next to the button that removes it
+#[derive(Clone)]
+struct ListItem<C: Abstractable + Clone + 'static> {
+    id: usize,
+    item: C,
+    row: CompositeConfig<(C, ButtonConfig)>,
+}
+
+impl<C: Abstractable + Clone + 'static> ListConfig<C> {
+    /// Creates a new list with the given items, where `create_item` creates the items that the user adds
+    pub fn new<F: Fn() -> C + 'static>(items: Vec<C>, create_item: F) -> ListConfig<C> {
+        let mut list = ListConfig {
+            data: ConfigurationObject::new(ListValue {
+                items: Vec::new(),
+                add_button: ButtonConfig::new_labeled("Add"),
+                next_id: 0,
+            }),
+            create_item: Rc::new(create_item),
+        };
+        for item in items {
+            list.add_item(item).commit();
+        }
+
+        let mut add_button = list.data.with_value(|v| v.add_button.clone());
+        let mut data = list.clone();
+        add_button.add_press_listener(move || {
+            let item = (data.create_item)();
+            data.add_item(item).commit();
+        });
+        list
+    }
+
+    pub fn get_items(&self) -> Vec<C> {
+        self.data
+            .with_value(|v| v.items.iter().map(|item| item.item.clone()).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.with_value(|v| v.items.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds the given item to the end of the list
+    pub fn add_item(&mut self, item: C) -> Mutator<(), ()> {
+        let list = self.clone();
+        self.data.set_value(move |cur| {
+            let mut value = cur.clone();
+            value.items.push(list.create_list_item(cur.next_id, item));
+            value.next_id += 1;
+            Some(value)
+        })
+    }
+
+    /// Removes the item at the given index, if it exists
+    pub fn remove_item(&mut self, index: usize) -> Mutator<(), ()> {
+        self.data.set_value(move |cur| {
+            if index >= cur.items.len() {
+                return None;
+            }
+            let mut value = cur.clone();
+            value.items.remove(index);
+            Some(value)
+        })
+    }
+
+    /// Adds or removes items at the end of the list, such that it contains the given number of items
+    pub fn set_len(&mut self, len: usize) -> Mutator<(), ()> {
+        let list = self.clone();
+        self.data.set_value(move |cur| {
+            if cur.items.len() == len {
+                return None;
+            }
+            let mut value = cur.clone();
+            value.items.truncate(len);
+            while value.items.len() < len {
+                let item = (list.create_item)();
+                value.items.push(list.create_list_item(value.next_id, item));
+                value.next_id += 1;
+            }
+            Some(value)
+        })
+    }
+
+    fn create_list_item(&self, id: usize, item: C) -> ListItem<C> {
+        let mut remove_button = ButtonConfig::new_labeled("Remove");
+        let mut data = self.data.clone();
+        remove_button.add_press_listener(move || {
+            data.set_value(move |cur| {
+                let mut value = cur.clone();
+                value.items.retain(|item| item.id != id);
+                Some(value)
+            })
+            .commit();
+        });
+        ListItem {
+            id,
+            item: item.clone(),
+            row: CompositeConfig::new_horizontal((item, remove_button), |(item, remove)| {
+                vec![Box::new(item.clone()), Box::new(remove.clone())]
+            }),
+        }
+    }
+}
+
+impl<C: Abstractable + Clone + 'static> Abstractable for ListConfig<C> {
+    fn get_abstract(&self) -> AbstractConfigurationObject {
+        // The list is shown as a vertical composite of its items, followed by the add button
+        AbstractConfigurationObject::new(ConfigurationObjectType::Composite, self.data.clone())
+    }
+}
+
+impl<C: Abstractable + Clone + 'static> ValueMapping<ListValue<C>> for ListConfig<C> {
+    fn to_js_value(_val: &ListValue<C>) -> JsValue {
+        JsValue::from_bool(false)
+    }
+
+    fn get_children(val: &ListValue<C>) -> Option<Vec<AbstractConfigurationObject>> {
+        Some(
+            val.items
+                .iter()
+                .map(|item| item.row.get_abstract())
+                .chain(Some(val.add_button.get_abstract()))
+                .collect_vec(),
+        )
+    }
+
+    fn from_js_value(_js_val: JsValue, _cur_val: &ListValue<C>) -> Option<ListValue<C>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::{
+            observe_configuration::on_configuration_change, types::int_config::IntConfig,
+        },
+        util::rc_refcell::MutRcRefCell,
+    };
+
+    #[test]
+    fn added_items_are_observed() {
+        let mut list = ListConfig::new(vec![IntConfig::new(1)], || IntConfig::new(0));
+        let changes = MutRcRefCell::new(0);
+        let counter = changes.clone();
+        let _ = on_configuration_change(&list, move || *counter.get() += 1);
+        assert_eq!(*changes.read(), 1);
+
+        list.set_len(2).commit();
+        assert_eq!(*changes.read(), 2);
+        let mut added = list.get_items()[1].clone();
+        added.set(3).commit();
+        assert_eq!(*changes.read(), 3);
+
+        list.remove_item(0).commit();
+        assert_eq!(*changes.read(), 4);
+        assert_eq!(
+            list.get_items().iter().map(|item| item.get()).collect_vec(),
+            vec![3]
+        );
+    }
+}
@@ -5,6 +5,8 @@ pub mod container_config;
 pub mod float_config;
 pub mod int_config;
 pub mod label_config;
+pub mod list_config;
 pub mod location_config;
 pub mod panel_config;
+pub mod text_config;
 pub mod text_output_config;
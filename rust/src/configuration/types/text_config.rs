@@ -0,0 +1,90 @@
+use wasm_bindgen::JsValue;
+
+use crate::configuration::{
+    configuration_object::{
+        AbstractConfigurationObject, Abstractable, ConfigObjectGetter, ConfigurationObject,
+        ValueMapping,
+    },
+    configuration_object_types::ConfigurationObjectType,
+    mutator::Mutator,
+    util::js_object::JsObject,
+};
+
+/// A text input config, which may optionally span multiple lines
+#[derive(Clone)]
+pub struct TextConfig {
+    data: ConfigurationObject<TextConfig, TextValue>,
+}
+
+#[derive(Clone)]
+struct TextValue {
+    value: String,
+    multiline: bool,
+}
+
+impl TextConfig {
+    pub fn new(val: String) -> TextConfig {
+        TextConfig {
+            data: ConfigurationObject::new(TextValue {
+                value: val,
+                multiline: false,
+            }),
+        }
+    }
+    pub fn new_multiline(val: String) -> TextConfig {
+        TextConfig {
+            data: ConfigurationObject::new(TextValue {
+                value: val,
+                multiline: true,
+            }),
+        }
+    }
+
+    pub fn get(&self) -> String {
+        self.data.with_value(|v| v.value.clone())
+    }
+    pub fn set(&mut self, value: String) -> Mutator<(), ()> {
+        self.data.set_value(move |cur| {
+            Some(TextValue {
+                value,
+                ..cur.clone()
+            })
+        })
+    }
+}
+impl Abstractable for TextConfig {
+    fn get_abstract(&self) -> AbstractConfigurationObject {
+        AbstractConfigurationObject::new(ConfigurationObjectType::Text, self.data.clone())
+    }
+}
+impl ConfigObjectGetter<TextConfig, TextValue> for TextConfig {
+    fn with_config_object<O, U: FnOnce(&mut ConfigurationObject<TextConfig, TextValue>) -> O>(
+        &mut self,
+        e: U,
+    ) -> O {
+        e(&mut self.data)
+    }
+}
+
+impl ValueMapping<TextValue> for TextConfig {
+    fn to_js_value(val: &TextValue) -> JsValue {
+        JsObject::new()
+            .set("value", val.value.clone())
+            .set("multiline", val.multiline)
+            .into()
+    }
+    fn from_js_value(js_val: JsValue, cur: &TextValue) -> Option<TextValue> {
+        let value = JsObject::load(js_val)
+            .get("value")
+            .and_then(|v| v.as_string())
+            .unwrap_or_default();
+        Some(TextValue {
+            value,
+            ..cur.clone()
+        })
+    }
+
+    fn get_children(_val: &TextValue) -> Option<Vec<AbstractConfigurationObject>> {
+        None
+    }
+}
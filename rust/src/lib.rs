@@ -12,11 +12,13 @@ use oxidd::{bdd::BDDManagerRef, ManagerRef};
 use util::{logging::console, panic_hook::set_panic_hook};
 // use utils::*;
 use wasm_bindgen::prelude::*;
-use web_sys::{Document, Element, HtmlElement, Window};
 
 use configuration::configuration_object::ConfigurationObject;
 use oxidd::{bdd::BDDFunction, util::AllocResult, BooleanFunction};
-use types::{mtbdd::mtbdd_drawer::MTBDDDiagram, qdd::qdd_drawer::QDDDiagram};
+pub use types::{
+    bcdd::bcdd_drawer::BCDDDiagram, mtbdd::mtbdd_drawer::MTBDDDiagram, qdd::qdd_drawer::QDDDiagram,
+    zbdd::zbdd_drawer::ZBDDDiagram,
+};
 
 use swash::{
     proxy::{CharmapProxy, MetricsProxy},
@@ -30,6 +32,29 @@ use crate::{
     wasm_interface::DiagramBox,
 };
 
+// Used by the convert_data tool, to convert diagrams without a browser
+pub use crate::{
+    types::util::drawing::renderers::latex_renderer::latex_headers,
+    util::{
+        conversion::{convert, ConvertedDiagram, DiagramFormat},
+        parse_error::ParseError,
+        vis_error::VisError,
+    },
+};
+
+// The diagram types can also be used directly, to visualize the functions of a live oxidd manager
+pub use crate::{
+    traits::{Diagram, DiagramSection, DiagramSectionDrawer},
+    types::util::graph_structure::oxidd_graph_structure::IntoTerminalLabel,
+    util::dummy_mtbdd::MTBDDTerminal,
+};
+
+// Measures the quality of the layouts of a drawer, to compare layout rules in benchmarks
+pub use crate::types::util::drawing::layout_metrics::LayoutMetrics;
+
+// The state of a drawer, whose settings can be adjusted before restoring it
+pub use crate::types::util::storage::state_model::{SettingValue, VisualizationState};
+
 #[wasm_bindgen]
 pub fn create_qdd_diagram() -> Option<DiagramBox> // And some DD type param
 {
@@ -37,6 +62,20 @@ pub fn create_qdd_diagram() -> Option<DiagramBox> // And some DD type param
     Some(DiagramBox::new(Box::new(QDDDiagram::new())))
 }
 
+#[wasm_bindgen]
+pub fn create_bcdd_diagram() -> Option<DiagramBox> // And some DD type param
+{
+    set_panic_hook();
+    Some(DiagramBox::new(Box::new(BCDDDiagram::new())))
+}
+
+#[wasm_bindgen]
+pub fn create_zbdd_diagram() -> Option<DiagramBox> // And some DD type param
+{
+    set_panic_hook();
+    Some(DiagramBox::new(Box::new(ZBDDDiagram::new())))
+}
+
 #[wasm_bindgen]
 pub fn create_mtbdd_diagram() -> Option<DiagramBox> // And some DD type param
 {
@@ -1,36 +1,61 @@
+use oxidd::LevelNo;
+
 use crate::{
     configuration::configuration_object::AbstractConfigurationObject,
-    types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
-    util::rectangle::Rectangle, wasm_interface::NodeID,
+    types::util::{
+        drawing::layout_metrics::LayoutMetrics,
+        graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
+        storage::state_model::VisualizationState,
+    },
+    util::{rectangle::Rectangle, vis_error::VisError},
+    wasm_interface::NodeID,
 };
 
 use super::wasm_interface::{NodeGroupID, StepData, TargetID};
+#[cfg(feature = "web")]
 use web_sys::HtmlCanvasElement;
 
 pub trait Diagram {
-    fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<Box<dyn DiagramSection>>; // TODO: error type
+    fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<Box<dyn DiagramSection>, VisError>;
     fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn DiagramSection>>; // TODO: error type
+    ) -> Result<Box<dyn DiagramSection>, VisError>;
+    /// Creates a section for the `;` separated propositional formulas, where the optional variable order lists variables from the top level downwards
+    fn create_section_from_formula(
+        &mut self,
+        formula: String,
+        var_order: Option<String>,
+    ) -> Result<Box<dyn DiagramSection>, VisError>;
     fn create_section_from_ids(
         &self,
         id: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
-    ) -> Option<Box<dyn DiagramSection>>;
+    ) -> Result<Box<dyn DiagramSection>, VisError>;
 }
 
 pub trait DiagramSection {
+    #[cfg(feature = "web")]
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer>;
+    /// Creates a drawer that renders LaTeX code instead of drawing to a canvas, such that it can also be used outside of the browser
+    fn create_latex_drawer(&self) -> Box<dyn DiagramSectionTextDrawer>;
     fn get_level_labels(&self) -> Vec<String>;
     fn get_node_labels(&self, node: NodeID) -> Vec<String>;
     fn get_meta(&self) -> i128;
+    /// Loads a trace of the computation that created this section, which drawers created afterwards can step through
+    fn load_step_trace(&mut self, trace: String) -> Result<(), VisError>;
 }
 
 pub trait DiagramSectionDrawer {
     fn render(&mut self, time: u32) -> ();
     fn layout(&mut self, time: u32) -> ();
+    /// Computes the quality metrics of the current layout, by which the results of different layout rules can be compared
+    fn get_layout_metrics(&self) -> LayoutMetrics;
     fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> ();
+    /// Shows the diagram as it was after the given step of the loaded trace, a negative step shows the full diagram again
     fn set_step(&mut self, step: i32) -> Option<StepData>;
 
     /* Grouping */
@@ -40,6 +65,8 @@ pub trait DiagramSectionDrawer {
     /** Tools */
     /// Splits the edges of a given group such that each edge type goes to a unique group, if fully is specified it also ensures that each group that an edge goes to only contains a single node
     fn split_edges(&mut self, nodes: &[NodeID], fully: bool) -> ();
+    /// Reveals the most informative neighbourhood of the given nodes, while keeping previously revealed nodes as context as far as the visible node budget allows and grouping the remaining far-away nodes back into summary groups
+    fn focus_nodes(&mut self, nodes: &[NodeID]) -> ();
 
     /** Node interaction */
     /// Retrieves the nodes in the given rectangle, expanding each node group up to at most max_group_expansion nodes of the nodes it contains
@@ -51,10 +78,24 @@ pub trait DiagramSectionDrawer {
     /// Retrieves the local nodes representing the collection of sources
     fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID>;
 
+    /** Level interaction */
+    /// Retrieves the level whose layer is shown at the given height in screen space (-0.5 to 0.5), if the layer consists of exactly that level
+    fn get_level(&self, y: f32) -> Option<LevelNo>;
+    /// Moves the given level to the position of the target level, and rebuilds the diagram under the resulting variable order. The nodes move from their old to their new position during the next layout
+    fn move_level(&mut self, level: LevelNo, to: LevelNo) -> Result<(), VisError>;
+
     /** Storage */
-    fn serialize_state(&self) -> Vec<u8>;
-    fn deserialize_state(&mut self, state: Vec<u8>) -> ();
+    /// Retrieves the complete state of the visualization, including its groups, settings and view
+    fn get_state(&self) -> VisualizationState;
+    /// Restores a state obtained from `get_state`, which may have been edited by hand in the meantime
+    fn set_state(&mut self, state: VisualizationState) -> Result<(), VisError>;
 
     /** Settings */
     fn get_configuration(&self) -> AbstractConfigurationObject;
 }
+
+/// A drawer whose renderer produces text, such as the LaTeX renderer, rather than drawing to a canvas
+pub trait DiagramSectionTextDrawer: DiagramSectionDrawer {
+    /// Lays out the diagram and retrieves the rendered output
+    fn get_output(&mut self) -> String;
+}
@@ -0,0 +1,860 @@
+use itertools::Itertools;
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+#[cfg(feature = "web")]
+use web_sys::HtmlCanvasElement;
+
+use oxidd::{Edge, Function, LevelNo, Manager, ManagerRef, NodeID};
+use oxidd_core::HasLevel;
+use oxidd_rules_bdd::complement_edge::EdgeTag;
+
+#[cfg(feature = "web")]
+use crate::types::util::drawing::renderers::webgl_renderer::{WebglNodeStyle, WebglRenderer};
+use crate::{
+    configuration::{
+        observe_configuration::on_configuration_change,
+        types::{
+            button_config::ButtonConfig,
+            choice_config::{Choice, ChoiceConfig},
+            composite_config::CompositeConfig,
+            container_config::{ContainerConfig, ContainerStyle},
+            int_config::IntConfig,
+            label_config::{LabelConfig, LabelKind},
+            text_config::TextConfig,
+            text_output_config::TextOutputConfig,
+        },
+    },
+    traits::{Diagram, DiagramSection, DiagramSectionDrawer, DiagramSectionTextDrawer},
+    types::util::{
+        assignment_path::{describe_path, follow_assignment, parse_assignment, retrace_path},
+        drawing::{
+            diagram_drawer::{
+                DiagramDrawer, DiagramGraph, DiagramStyle, GroupedDiagramGraph, PresenceGraph,
+                TOP_MARGIN,
+            },
+            diagram_layout::NodeStyle,
+            drawer::Drawer,
+            layouts::{
+                layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
+                layer_orderings::{
+                    combinators::sequence_ordering::SequenceOrdering,
+                    edge_layer_ordering::EdgeLayerOrdering,
+                    pseudo_random_layer_ordering::PseudoRandomLayerOrdering,
+                    sugiyama_ordering::SugiyamaOrdering,
+                },
+                layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+                layered_layout::LayeredLayout,
+                layered_layout_traits::WidthLabel,
+                transition::transition_layout::TransitionLayout,
+            },
+            renderer::Renderer,
+            renderers::{
+                latex_renderer::{LatexNodeStyle, LatexRenderer},
+                svg_renderer::SvgNodeStyle,
+                util::{
+                    rendering_config::{
+                        EdgeRenderingType, LayerRenderingColorConfig, NodeRenderingColorConfig,
+                        RenderingColorConfig,
+                    },
+                    Font::Font,
+                },
+            },
+        },
+        graph_structure::{
+            graph_manipulators::{
+                complement_expansion_adjuster::ComplementExpansionAdjuster,
+                node_presence_adjuster::{PresenceGroups, PresenceLabel, PresenceRemainder},
+                pointer_node_adjuster::{PointerLabel, PointerNodeAdjuster},
+                rc_graph::RCGraph,
+                terminal_level_adjuster::TerminalLevelAdjuster,
+            },
+            graph_structure::{EdgeType, GraphStructure},
+            oxidd_graph_structure::{NodeLabel, NodeType, OxiddGraphStructure},
+        },
+        step_trace::StepTrace,
+        storage::snapshot::stored,
+    },
+    util::{
+        color::{Color, TransparentColor},
+        dummy_bcdd::{DummyBCDDEdge, DummyBCDDFunction, DummyBCDDManager, DummyBCDDManagerRef},
+        dummy_bdd::DummyFunction,
+        rc_refcell::MutRcRefCell,
+        transition::Interpolatable,
+        vis_error::VisError,
+    },
+};
+
+// The drawer for BDDs with complemented edges
+pub struct BCDDDiagram<MR: ManagerRef>
+where
+    for<'id> <<MR as oxidd::ManagerRef>::Manager<'id> as Manager>::InnerNode: HasLevel,
+{
+    manager_ref: MR,
+}
+impl BCDDDiagram<DummyBCDDManagerRef> {
+    pub fn new() -> BCDDDiagram<DummyBCDDManagerRef> {
+        let manager_ref = DummyBCDDManagerRef::from(&DummyBCDDManager::new());
+        BCDDDiagram { manager_ref }
+    }
+}
+
+impl Diagram for BCDDDiagram<DummyBCDDManagerRef> {
+    fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let (roots, levels) = DummyBCDDFunction::from_dddmp(&mut self.manager_ref, &dddmp)?;
+        Ok(Box::new(BCDDDiagramSection::new(roots, levels)))
+    }
+
+    // Does not support other imports
+    fn create_section_from_other(
+        &mut self,
+        data: String,
+        vars: Option<String>,
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        Err(VisError::unsupported(
+            "BCDDs can only be loaded from dddmp files",
+        ))
+    }
+
+    fn create_section_from_formula(
+        &mut self,
+        formula: String,
+        var_order: Option<String>,
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        Err(VisError::unsupported(
+            "BCDDs can only be loaded from dddmp files",
+        ))
+    }
+
+    fn create_section_from_ids(
+        &self,
+        sources: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let mut levels = Vec::new();
+        let roots = sources
+            .iter()
+            .map(|&(id, section)| {
+                let root_edge = DummyBCDDEdge::new(Arc::new(id), self.manager_ref.clone());
+                levels = section.get_level_labels();
+                (DummyFunction(root_edge), section.get_node_labels(id))
+            })
+            .collect_vec();
+        Ok(Box::new(BCDDDiagramSection::new(roots, levels)))
+    }
+}
+
+pub struct BCDDDiagramSection<F: Function>
+where
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+{
+    roots: Vec<(F, Vec<String>)>,
+    labels: HashMap<NodeID, Vec<String>>,
+    levels: Vec<String>,
+    trace: Option<Rc<StepTrace>>,
+}
+impl<F: Function> BCDDDiagramSection<F>
+where
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+{
+    fn new(roots: Vec<(F, Vec<String>)>, levels: Vec<String>) -> Self {
+        let mut labels = HashMap::<NodeID, Vec<String>>::new();
+        for (f, names) in &roots {
+            labels
+                .entry(f.with_manager_shared(|_, edge| edge.node_id()))
+                .or_default()
+                .extend(names.iter().cloned());
+        }
+        BCDDDiagramSection {
+            labels,
+            roots,
+            levels,
+            trace: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct BCDDColors {
+    edge_true: Color,
+    edge_false: Color,
+    edge_label: Color,
+    node_true: Color,
+    node_false: Color,
+    node_group: Color,
+    node_default: Color,
+    node_text: Color,
+    node_label: Color,
+    layer_background1: Color,
+    layer_background2: Color,
+    layer_text: Color,
+    selection: TransparentColor,
+    selection_partial: TransparentColor,
+    selection_hover: TransparentColor,
+    selection_hover_partial: TransparentColor,
+}
+impl BCDDColors {
+    const DARK: BCDDColors = BCDDColors {
+        edge_true: Color(0.631, 0.749, 0.423),
+        edge_false: Color(0.835, 0.341, 0.341),
+        edge_label: Color(0.6, 0.6, 0.6),
+        node_true: Color(0.631, 0.749, 0.423),
+        node_false: Color(0.835, 0.341, 0.341),
+        node_group: Color(0.45, 0.45, 0.45),
+        node_default: Color(0.35, 0.35, 0.35),
+        node_text: Color(0.0, 0.0, 0.0),
+        node_label: Color(0.5, 0.5, 1.0),
+        layer_background1: Color(0.125, 0.125, 0.125),
+        layer_background2: Color(0.1875, 0.1875, 0.1875),
+        layer_text: Color(1.0, 1.0, 1.0),
+        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
+        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
+    };
+
+    const LIGHT: BCDDColors = BCDDColors {
+        edge_true: Color(0.2, 1.0, 0.2),
+        edge_false: Color(1.0, 0.2, 0.2),
+        edge_label: Color(0.6, 0.6, 0.6),
+        node_true: Color(0.2, 1.0, 0.2),
+        node_false: Color(1.0, 0.2, 0.2),
+        node_group: Color(0.45, 0.45, 0.45),
+        node_default: Color(0.1, 0.1, 0.1),
+        node_text: Color(0.0, 0.0, 0.0),
+        node_label: Color(0.5, 0.5, 1.0),
+        layer_background1: Color(0.98, 0.98, 0.98),
+        layer_background2: Color(0.9, 0.9, 0.9),
+        layer_text: Color(0.0, 0.0, 0.0),
+        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
+        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
+    };
+
+    fn get_edge_types(&self) -> HashMap<EdgeType<EdgeTag>, EdgeRenderingType> {
+        let edge_rendering_type =
+            |color: Color,
+             width: f32,
+             dash_solid: f32,
+             dash_transparent: f32,
+             marker_radius: f32| EdgeRenderingType {
+                select_color: color.mix_transparent(&self.selection),
+                partial_select_color: color.mix_transparent(&self.selection_partial),
+                hover_color: color.mix_transparent(&self.selection_hover),
+                partial_hover_color: color.mix_transparent(&self.selection_hover_partial),
+                color,
+                width,
+                dash_solid,
+                dash_transparent,
+                marker_radius,
+            };
+        // Complemented edges are drawn like their regular counterpart, with a dot near the node they point to
+        let complement_marker = 0.2;
+
+        HashMap::from([
+            // True edge
+            (
+                EdgeType::new(EdgeTag::None, 0),
+                edge_rendering_type(
+                    self.edge_true,
+                    0.2,
+                    1.0,
+                    0.0, // No dashing
+                    0.0,
+                ),
+            ),
+            (
+                EdgeType::new(EdgeTag::Complemented, 0),
+                edge_rendering_type(self.edge_true, 0.2, 1.0, 0.0, complement_marker),
+            ),
+            // False edge
+            (
+                EdgeType::new(EdgeTag::None, 1),
+                edge_rendering_type(self.edge_false, 0.2, 0.3, 0.15, 0.0),
+            ),
+            (
+                EdgeType::new(EdgeTag::Complemented, 1),
+                edge_rendering_type(self.edge_false, 0.2, 0.3, 0.15, complement_marker),
+            ),
+            // Label edge
+            (
+                EdgeType::new(EdgeTag::None, 2),
+                edge_rendering_type(self.edge_label, 0.15, 1.0, 0.0, 0.0),
+            ),
+            (
+                EdgeType::new(EdgeTag::Complemented, 2),
+                edge_rendering_type(self.edge_label, 0.15, 1.0, 0.0, complement_marker),
+            ),
+        ])
+    }
+
+    fn get_layer_colors(&self) -> LayerRenderingColorConfig {
+        LayerRenderingColorConfig {
+            background1: self.layer_background1.into(),
+            background2: self.layer_background2.into(),
+            text: self.layer_text,
+        }
+    }
+
+    fn get_node_colors(&self) -> NodeRenderingColorConfig {
+        NodeRenderingColorConfig {
+            select: self.selection,
+            partial_select: self.selection_partial,
+            hover: self.selection_hover,
+            partial_hover: self.selection_hover_partial,
+            text: self.node_text,
+        }
+    }
+
+    fn get_rendering_colors(&self) -> RenderingColorConfig<EdgeTag> {
+        RenderingColorConfig {
+            edge_types: self.get_edge_types(),
+            nodes: self.get_node_colors(),
+            layers: self.get_layer_colors(),
+        }
+    }
+}
+
+impl DiagramSection for BCDDDiagramSection<DummyBCDDFunction> {
+    fn get_level_labels(&self) -> Vec<String> {
+        self.levels.clone()
+    }
+    fn get_node_labels(&self, node: NodeID) -> Vec<String> {
+        self.labels.get(&node).cloned().unwrap_or_else(|| vec![])
+    }
+    #[cfg(feature = "web")]
+    fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
+        let graph =
+            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = BCDDDiagramDrawer::new(graph, level_count, self.trace.clone(), canvas);
+        Box::new(diagram)
+    }
+    fn create_latex_drawer(&self) -> Box<dyn DiagramSectionTextDrawer> {
+        let graph =
+            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = BCDDDiagramDrawer::new_latex(graph, level_count, self.trace.clone());
+        Box::new(diagram)
+    }
+    fn get_meta(&self) -> i128 {
+        1
+    }
+    fn load_step_trace(&mut self, trace: String) -> Result<(), VisError> {
+        self.trace = Some(Rc::new(StepTrace::parse(&trace)?));
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct NodeData {
+    color: Color,
+    border_color: TransparentColor,
+    width: f32,
+    name: Option<String>,
+    is_terminal: Option<usize>,
+    is_group: bool,
+}
+
+impl Interpolatable for NodeData {
+    fn mix(&self, other: &Self, frac: f32) -> Self {
+        NodeData {
+            color: self.color.mix(&other.color, frac),
+            border_color: self.border_color.mix(&other.border_color, frac),
+            width: self.width * (1.0 - frac) + other.width * frac,
+            name: other.name.clone(),
+            is_terminal: other.is_terminal.clone(),
+            is_group: other.is_group,
+        }
+    }
+}
+impl LatexNodeStyle for NodeData {
+    fn is_terminal(&self) -> Option<(String, Option<String>)> {
+        self.is_terminal.map(|v| (format!("terminal{}", v), None))
+    }
+
+    fn is_group(&self) -> bool {
+        self.is_group
+    }
+
+    fn get_label(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+#[cfg(feature = "web")]
+impl WebglNodeStyle for NodeData {
+    fn get_color(&self) -> Color {
+        self.color.clone()
+    }
+
+    fn get_outline_color(&self) -> TransparentColor {
+        self.border_color.clone()
+    }
+
+    fn get_label(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+impl SvgNodeStyle for NodeData {
+    fn get_color(&self) -> Color {
+        self.color.clone()
+    }
+
+    fn get_outline_color(&self) -> TransparentColor {
+        self.border_color.clone()
+    }
+
+    fn get_label(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+impl WidthLabel for NodeData {
+    fn get_width(&self) -> f32 {
+        self.width
+    }
+}
+impl NodeStyle for NodeData {}
+
+type GroupedGraph = GroupedDiagramGraph<PointerGraph, NodeData>;
+type Graph = DiagramGraph<PointerGraph>;
+type PresenceAdjuster = PresenceGraph<PointerGraph>;
+type PointerGraph = RCGraph<PointerNodeAdjuster<TerminalLevelAdjuster<ExpansionAdjuster>>>;
+type ExpansionAdjuster = RCGraph<ComplementExpansionAdjuster<BaseGraph>>;
+type BaseGraph = OxiddGraphStructure<EdgeTag, DummyBCDDFunction, String>;
+
+type Layout = TransitionLayout<
+    LayeredLayout<
+        GroupedGraph,
+        SequenceOrdering<
+            GroupedGraph,
+            PseudoRandomLayerOrdering,
+            SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
+        >,
+        OrderingGroupAlignment,
+        BrandesKopfPositioningCorrected,
+    >,
+>;
+
+type BCDDSettings = (
+    ContainerConfig<LabelConfig<LabelConfig<ChoiceConfig<bool>>>>,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<PresenceRemainder>>,
+                LabelConfig<ChoiceConfig<PresenceRemainder>>,
+            )>,
+        >,
+    >,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<TextConfig>,
+                ButtonConfig,
+                ButtonConfig,
+                LabelConfig<TextOutputConfig>,
+            )>,
+        >,
+    >,
+    ContainerConfig<LabelConfig<LabelConfig<IntConfig>>>,
+);
+
+pub type BCDDDiagramDrawer<R> = DiagramDrawer<R, Layout, PointerGraph, NodeData, BCDDSettings>;
+
+#[cfg(feature = "web")]
+impl BCDDDiagramDrawer<WebglRenderer<EdgeTag>> {
+    pub fn new(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        trace: Option<Rc<StepTrace>>,
+        canvas: HtmlCanvasElement,
+    ) -> Self {
+        let colors = &BCDDColors::LIGHT;
+
+        let font = Rc::new(Font::new(
+            include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
+            1.0,
+        ));
+        let renderer = WebglRenderer::from_canvas(
+            canvas,
+            colors.get_edge_types(),
+            colors.get_node_colors(),
+            colors.get_layer_colors(),
+            font.clone(),
+        )
+        .unwrap();
+
+        Self::with_renderer(graph, level_count, trace, renderer, font)
+    }
+}
+
+impl BCDDDiagramDrawer<LatexRenderer<Layout>> {
+    /// Creates a drawer that does not need a canvas, from which the LaTeX code of the initial layout can be obtained
+    pub fn new_latex(graph: BaseGraph, level_count: LevelNo, trace: Option<Rc<StepTrace>>) -> Self {
+        let font = Rc::new(Font::new(
+            include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
+            1.0,
+        ));
+        Self::with_renderer(graph, level_count, trace, LatexRenderer::new(), font)
+    }
+}
+
+impl<R: Renderer<Layout> + 'static> BCDDDiagramDrawer<R> {
+    fn with_renderer(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        trace: Option<Rc<StepTrace>>,
+        renderer: R,
+        font: Rc<Font>,
+    ) -> Self {
+        let colors = &BCDDColors::LIGHT;
+        let layout = LayeredLayout::new(
+            SequenceOrdering::new(
+                PseudoRandomLayerOrdering::new(2),
+                SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
+            ),
+            OrderingGroupAlignment,
+            BrandesKopfPositioningCorrected,
+            0.3,
+        );
+        let layout = TransitionLayout::new(layout);
+
+        let expansion_adjuster: ExpansionAdjuster =
+            RCGraph::new(ComplementExpansionAdjuster::new(graph));
+        let base_graph = TerminalLevelAdjuster::new(expansion_adjuster.clone()); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
+        let pointer_adjuster: PointerGraph = RCGraph::new(
+            PointerNodeAdjuster::new(
+                base_graph,
+                EdgeType::new(EdgeTag::None, 2),
+                true,
+                "".to_string(),
+            )
+            .with_complemented_pointer_edge(EdgeType::new(EdgeTag::Complemented, 2)),
+        );
+
+        let settings: BCDDSettings = (
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Complements",
+                    LabelKind::Category,
+                    LabelConfig::new(
+                        "Edges",
+                        ChoiceConfig::new([
+                            Choice::new(false, "complemented"),
+                            Choice::new(true, "expanded"),
+                        ]),
+                    ),
+                ),
+            ),
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Terminals",
+                    LabelKind::Category,
+                    CompositeConfig::new((
+                        LabelConfig::new("False visibility", {
+                            let mut c = ChoiceConfig::new([
+                                Choice::new(PresenceRemainder::Show, "show"),
+                                Choice::new(PresenceRemainder::Duplicate, "duplicate"),
+                                Choice::new(PresenceRemainder::Hide, "hide"),
+                            ]);
+                            c.set_index(2).commit();
+                            c
+                        }),
+                        LabelConfig::new(
+                            "True visibility",
+                            ChoiceConfig::new([
+                                Choice::new(PresenceRemainder::Show, "show"),
+                                Choice::new(PresenceRemainder::Duplicate, "duplicate"),
+                                Choice::new(PresenceRemainder::Hide, "hide"),
+                            ]),
+                        ),
+                    )),
+                ),
+            ),
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Assignment path",
+                    LabelKind::Category,
+                    CompositeConfig::new((
+                        LabelConfig::new("Assignment", TextConfig::new("".into())),
+                        ButtonConfig::new_labeled("Highlight path"),
+                        ButtonConfig::new_labeled("Clear highlight"),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                    )),
+                ),
+            ),
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Layout",
+                    LabelKind::Category,
+                    LabelConfig::new("Seed", IntConfig::new_min_max(0, Some(0), None)),
+                ),
+            ),
+        );
+
+        let style: DiagramStyle<PointerGraph, NodeData> = DiagramStyle {
+            node_style: Box::new(
+                move |nodes: Vec<PresenceLabel<PointerLabel<NodeLabel<String>>>>| {
+                    let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label:
+                                    PointerLabel::Node(NodeLabel {
+                                        kind: NodeType::Terminal(ref terminal),
+                                        ..
+                                    }),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => {
+                            if terminal == "T" {
+                                (Some(1), false, colors.node_true)
+                            } else {
+                                (Some(0), false, colors.node_false)
+                            }
+                        }
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(_),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => (None, false, colors.node_label),
+                        (Some(_), None) => (None, false, colors.node_default),
+                        _ => (None, true, colors.node_group),
+                    };
+                    let name: Option<String> = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(ref text),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => Some(text.clone()),
+                        (Some(_), None) => None,
+                        _ => Some(format!("{} nodes", nodes.len())),
+                    };
+
+                    NodeData {
+                        color,
+                        border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
+                        width: 1.
+                            + match name {
+                                Some(ref text) => font.measure_width(&text),
+                                None => 0.,
+                            },
+                        name,
+                        is_terminal,
+                        is_group,
+                    }
+                },
+            ),
+            colors: Box::new(move || colors.get_rendering_colors()),
+            legend: Box::new(Vec::new),
+            terminal: Box::new(|label: PointerLabel<NodeLabel<String>>| match label {
+                PointerLabel::Node(NodeLabel {
+                    kind: NodeType::Terminal(terminal),
+                    ..
+                }) => Some((terminal, None)),
+                _ => None,
+            }),
+        };
+        let out = DiagramDrawer::from_graph(
+            pointer_adjuster.clone(),
+            level_count,
+            trace,
+            renderer,
+            layout,
+            settings,
+            style,
+        )
+        .with_stored_settings(|settings: &BCDDSettings| {
+            let (complements, terminals, assignment_path, layout) = settings;
+            let expand_complements = &****complements;
+            let (false_visibility, true_visibility) = &****terminals;
+            let (assignment, _highlight_path, _clear_highlight, _path_output) =
+                &****assignment_path;
+            let seed = &****layout;
+            vec![
+                ("expand_complements", stored(expand_complements)),
+                ("false_visibility", stored(&**false_visibility)),
+                ("true_visibility", stored(&**true_visibility)),
+                ("assignment", stored(&**assignment)),
+                ("seed", stored(seed)),
+            ]
+            .into_iter()
+            .map(|(name, setting)| (name.to_string(), setting))
+            .collect()
+        });
+
+        let (complements, terminals, assignment_path, layout) = out.get_diagram_settings();
+        let expand_complements = &****complements;
+        let (false_visibility, true_visibility) = &****terminals;
+        let (assignment, highlight_path, clear_highlight, path_output) = &****assignment_path;
+        let seed = &****layout;
+
+        let path_drawer = out.get_drawer().clone();
+        let path_graph = out.get_graph().clone();
+        let path_pointer_adjuster = pointer_adjuster.clone();
+        let assignment_copy = assignment.clone();
+        let path_output_copy = path_output.clone();
+        highlight_path.clone().add_press_listener(move || {
+            let mut output = path_output_copy.clone();
+            match highlight_assignment(
+                &path_drawer,
+                &path_graph,
+                &path_pointer_adjuster,
+                level_count,
+                &assignment_copy.get(),
+            ) {
+                Ok((terminal, description)) => {
+                    output
+                        .set_label(&format!("Reached terminal: {}", terminal))
+                        .commit();
+                    (*output).clone().set(description).commit();
+                }
+                Err(err) => {
+                    path_drawer.get().highlight_path(None);
+                    output
+                        .set_label(&format!("Invalid assignment: {}", err))
+                        .commit();
+                    (*output).clone().reset().commit();
+                }
+            }
+        });
+
+        let path_drawer = out.get_drawer().clone();
+        let path_output_copy = path_output.clone();
+        clear_highlight.clone().add_press_listener(move || {
+            path_drawer.get().highlight_path(None);
+            let mut output = path_output_copy.clone();
+            output.set_label("").commit();
+            (*output).clone().reset().commit();
+        });
+
+        fn set_terminal_presence(
+            presence_adjuster: &PresenceAdjuster,
+            target_terminal: &str,
+            presence: PresenceRemainder,
+        ) -> () {
+            let mut adjuster = presence_adjuster.get();
+            let terminals = adjuster.get_terminals();
+            let mut terminals = terminals.iter().filter_map(|&node| {
+                match adjuster.get_node_label(node).original_label {
+                    PointerLabel::Node(NodeLabel {
+                        kind: NodeType::Terminal(t),
+                        ..
+                    }) if t == target_terminal => Some(node),
+                    _ => None,
+                }
+            });
+            let Some(target_terminal) = terminals.next() else {
+                return;
+            };
+
+            adjuster.set_node_presence(target_terminal, PresenceGroups::remainder(presence));
+        }
+
+        let false_presence_adjuster = out.get_presence_adjuster().clone();
+        let false_visibility_copy = false_visibility.clone();
+        let _ = on_configuration_change(&*false_visibility, move || {
+            set_terminal_presence(&false_presence_adjuster, "F", false_visibility_copy.get());
+        });
+        let true_presence_adjuster = out.get_presence_adjuster().clone();
+        let true_visibility_copy = true_visibility.clone();
+        let _ = on_configuration_change(&*true_visibility, move || {
+            set_terminal_presence(&true_presence_adjuster, "T", true_visibility_copy.get());
+        });
+
+        let expand_complements_copy = expand_complements.clone();
+        let presence_adjuster = out.get_presence_adjuster().clone();
+        let false_visibility_copy = false_visibility.clone();
+        let true_visibility_copy = true_visibility.clone();
+        let _ = on_configuration_change(&*expand_complements, move || {
+            expansion_adjuster
+                .get()
+                .set_enabled(expand_complements_copy.get());
+
+            // Expanding complements may introduce the complemented terminal, to which the visibility settings also apply
+            set_terminal_presence(&presence_adjuster, "F", false_visibility_copy.get());
+            set_terminal_presence(&presence_adjuster, "T", true_visibility_copy.get());
+        });
+
+        let drawer = out.get_drawer().clone();
+        let seed_copy = seed.clone();
+        let _ = on_configuration_change(&*seed, move || {
+            drawer.get().set_seed(seed_copy.get() as u64);
+        });
+
+        out
+    }
+}
+
+/// Highlights the path that the assignment takes from the first selected node, or from the root if no node is selected and the diagram has a single root. Retrieves the function value that the path reaches, which is the value of its terminal if an even number of the edges along the path is complemented, together with the tested variables along the path
+fn highlight_assignment<R: Renderer<Layout>>(
+    drawer: &MutRcRefCell<Drawer<R, Layout, GroupedGraph>>,
+    graph: &Graph,
+    pointer_adjuster: &PointerGraph,
+    level_count: LevelNo,
+    assignment: &str,
+) -> Result<(String, String), VisError> {
+    let assignment = parse_assignment(assignment)?;
+    let mut graph = graph.clone();
+    let root = match drawer.read().get_selected_nodes().first() {
+        Some(&node) => node,
+        None => match &graph.get_roots()[..] {
+            &[root] => root,
+            _ => {
+                return Err(VisError::unsupported(
+                    "Select the node to start the path from",
+                ))
+            }
+        },
+    };
+
+    // The path is followed before terminals are hidden or duplicated, such that the edges to all terminals are known
+    let mut pointer_adjuster = pointer_adjuster.clone();
+    let path = follow_assignment(
+        &mut pointer_adjuster,
+        graph.get_node_label(root).original_id,
+        level_count,
+        &assignment,
+    )?;
+    let terminal = match path
+        .nodes
+        .last()
+        .map(|&end| pointer_adjuster.get_node_label(end))
+    {
+        Some(PointerLabel::Node(NodeLabel {
+            kind: NodeType::Terminal(terminal),
+            ..
+        })) => terminal,
+        _ => return Err(VisError::unsupported("The path does not reach a terminal")),
+    };
+    let value = match (terminal.as_str(), path.complemented) {
+        ("T", false) | ("F", true) => "T",
+        ("F", false) | ("T", true) => "F",
+        (other, _) => {
+            return Err(VisError::unsupported(format!(
+                "The path reaches the unknown terminal {}",
+                other
+            )))
+        }
+    };
+
+    let shown_path = retrace_path(&mut graph, root, &path, |graph, node| {
+        graph.get_node_label(node).original_id
+    });
+    drawer.get().highlight_path(Some(shown_path));
+
+    let reached = if path.complemented {
+        format!("¬{}", terminal)
+    } else {
+        terminal
+    };
+    Ok((value.to_string(), describe_path(&path, &reached)))
+}
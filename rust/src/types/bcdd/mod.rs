@@ -0,0 +1 @@
+pub mod bcdd_drawer;
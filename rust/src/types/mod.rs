@@ -1,4 +1,6 @@
 // pub mod bdd_drawer;
+pub mod bcdd;
 pub mod mtbdd;
 pub mod qdd;
 pub mod util;
+pub mod zbdd;
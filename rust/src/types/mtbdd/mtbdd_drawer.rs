@@ -1,15 +1,16 @@
 use itertools::Itertools;
-use std::{collections::HashMap, io::Cursor, rc::Rc, sync::Arc};
+use std::{cmp::Reverse, collections::HashMap, rc::Rc, sync::Arc};
+#[cfg(feature = "web")]
 use web_sys::HtmlCanvasElement;
 
-use oxidd::{Edge, Function, InnerNode, Manager, ManagerRef, NodeID};
+use oxidd::{Edge, Function, InnerNode, LevelNo, Manager, ManagerRef, NodeID};
 use oxidd_core::{DiagramRules, HasLevel};
 
+#[cfg(feature = "web")]
+use crate::types::util::drawing::renderers::webgl_renderer::{WebglNodeStyle, WebglRenderer};
 use crate::{
     configuration::{
-        configuration::Configuration,
-        configuration_object::{AbstractConfigurationObject, Abstractable},
-        observe_configuration::{after_configuration_change, on_configuration_change},
+        observe_configuration::on_configuration_change,
         types::{
             button_config::{ButtonConfig, ButtonStyle},
             choice_config::{Choice, ChoiceConfig},
@@ -18,18 +19,20 @@ use crate::{
             float_config::FloatConfig,
             int_config::IntConfig,
             label_config::{LabelConfig, LabelKind},
-            location_config::{Location, LocationConfig},
-            panel_config::{OpenSide, PanelConfig},
+            list_config::ListConfig,
+            text_config::TextConfig,
             text_output_config::TextOutputConfig,
         },
     },
-    traits::{Diagram, DiagramSection, DiagramSectionDrawer},
+    traits::{Diagram, DiagramSection, DiagramSectionDrawer, DiagramSectionTextDrawer},
     types::{
         qdd::qdd_drawer::QDDDiagramDrawer,
         util::{
             drawing::{
-                diagram_layout::{LayerStyle, NodeStyle},
-                drawer::Drawer,
+                diagram_drawer::{
+                    DiagramDrawer, DiagramStyle, GroupedDiagramGraph, PresenceGraph, TOP_MARGIN,
+                },
+                diagram_layout::NodeStyle,
                 layout_rules::LayoutRules,
                 layouts::{
                     layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
@@ -42,75 +45,115 @@ use crate::{
                     layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
                     layered_layout::LayeredLayout,
                     layered_layout_traits::WidthLabel,
+                    level_collapsing_layout::LevelCollapsingLayout,
                     transition::transition_layout::TransitionLayout,
                 },
-                renderer::Renderer,
+                renderer::{LegendEntry, Renderer},
                 renderers::{
-                    latex_renderer::{
-                        latex_headers, LatexLayerStyle, LatexNodeStyle, LatexRenderer,
-                    },
+                    latex_renderer::{LatexNodeStyle, LatexRenderer},
+                    svg_renderer::SvgNodeStyle,
                     util::Font::Font,
-                    webgl::{
-                        edge_renderer::EdgeRenderingType, node_renderer::NodeRenderingColorConfig,
-                    },
-                    webgl_renderer::{
-                        LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle, WebglRenderer,
-                    },
                 },
+                theme::{Theme, ThemeKind},
             },
             graph_structure::{
                 graph_manipulators::{
-                    group_presence_adjuster::GroupPresenceAdjuster,
-                    label_adjusters::group_label_adjuster::GroupLabelAdjuster,
-                    node_presence_adjuster::{
-                        NodePresenceAdjuster, PresenceGroups, PresenceLabel, PresenceRemainder,
-                    },
+                    abstracted_graph::AbstractedGraph,
+                    node_presence_adjuster::{PresenceGroups, PresenceLabel, PresenceRemainder},
                     pointer_node_adjuster::{PointerLabel, PointerNodeAdjuster},
                     rc_graph::RCGraph,
                     terminal_level_adjuster::TerminalLevelAdjuster,
                 },
                 graph_structure::{DrawTag, EdgeType, GraphStructure},
-                grouped_graph_structure::GroupedGraphStructure,
-                oxidd_graph_structure::{NodeLabel, NodeType, OxiddGraphStructure},
+                oxidd_graph_structure::{
+                    get_reachable_nodes, IntoTerminalLabel, NodeLabel, NodeType,
+                    OxiddGraphStructure,
+                },
             },
-            group_manager::GroupManager,
-            storage::state_storage::{Serializable, StateStorage},
+            step_trace::StepTrace,
+            storage::snapshot::stored,
         },
     },
     util::{
         color::{Color, TransparentColor},
+        color_scale::{ColorScale, Palette, ScaleType},
         dummy_mtbdd::{
             DummyMTBDDEdge, DummyMTBDDFunction, DummyMTBDDManager, DummyMTBDDManagerRef,
             MTBDDTerminal,
         },
         logging::console,
         rc_refcell::MutRcRefCell,
-        rectangle::Rectangle,
         transition::Interpolatable,
+        vis_error::VisError,
     },
-    wasm_interface::{NodeGroupID, StepData, TargetID, TargetIDType},
+    wasm_interface::{TargetID, TargetIDType},
 };
 
-pub struct MTBDDDiagram<MR: ManagerRef>
+pub struct MTBDDDiagram<F: Function>
 where
-    for<'id> <<MR as oxidd::ManagerRef>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
 {
-    manager_ref: MR,
+    manager_ref: F::ManagerRef,
+    dddmp_loader: Option<fn(&mut F::ManagerRef, &str) -> MTBDDLoadResult<F>>,
+    functions: Vec<F>, // The roots of all sections, from which nodes can be looked up by their ID
 }
-impl MTBDDDiagram<DummyMTBDDManagerRef> {
-    pub fn new() -> MTBDDDiagram<DummyMTBDDManagerRef> {
+/// The loaded roots together with their names, and the level names
+type MTBDDLoadResult<F> = Result<(Vec<(F, Vec<String>)>, Vec<String>), VisError>;
+
+impl MTBDDDiagram<DummyMTBDDFunction> {
+    pub fn new() -> MTBDDDiagram<DummyMTBDDFunction> {
         let manager_ref = DummyMTBDDManagerRef::from(&DummyMTBDDManager::new());
-        MTBDDDiagram { manager_ref }
+        MTBDDDiagram {
+            manager_ref,
+            dddmp_loader: Some(DummyMTBDDFunction::from_dddmp),
+            functions: Vec::new(),
+        }
+    }
+}
+
+impl<F: Function + 'static> MTBDDDiagram<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<MTBDDTerminal>,
+{
+    /// Creates a diagram for the functions of an existing manager. Such a diagram can not load files, its sections are instead created from functions using `create_section_from_functions`
+    pub fn from_manager(manager_ref: F::ManagerRef) -> MTBDDDiagram<F> {
+        MTBDDDiagram {
+            manager_ref,
+            dddmp_loader: None,
+            functions: Vec::new(),
+        }
+    }
+
+    /// Creates a section showing the given functions with their names, where `levels` provides the names of the variables per level
+    pub fn create_section_from_functions(
+        &mut self,
+        roots: Vec<(F, Vec<String>)>,
+        levels: Vec<String>,
+    ) -> Box<dyn DiagramSection> {
+        self.functions.extend(roots.iter().map(|(f, _)| f.clone()));
+        Box::new(MTBDDDiagramSection::new(roots, levels))
     }
 }
 
-impl Diagram for MTBDDDiagram<DummyMTBDDManagerRef> {
+impl<F: Function + 'static> Diagram for MTBDDDiagram<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<MTBDDTerminal>,
+{
     fn create_section_from_dddmp(
         &mut self,
         dddmp: String,
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
-        let (roots, levels) = DummyMTBDDFunction::from_dddmp(&mut self.manager_ref, &dddmp);
-        Some(Box::new(MTBDDDiagramSection::new(roots, levels)))
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VisError> {
+        let Some(load) = self.dddmp_loader else {
+            return Err(VisError::unsupported(
+                "This diagram can not load dddmp files",
+            ));
+        };
+        let (roots, levels) = load(&mut self.manager_ref, &dddmp)?;
+        Ok(self.create_section_from_functions(roots, levels))
     }
 
     // Does not support other imports
@@ -118,24 +161,43 @@ impl Diagram for MTBDDDiagram<DummyMTBDDManagerRef> {
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
-        todo!()
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VisError> {
+        Err(VisError::unsupported(
+            "MTBDDs can only be loaded from dddmp files",
+        ))
+    }
+
+    fn create_section_from_formula(
+        &mut self,
+        formula: String,
+        var_order: Option<String>,
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VisError> {
+        Err(VisError::unsupported(
+            "MTBDDs can only be loaded from dddmp files",
+        ))
     }
 
     fn create_section_from_ids(
         &self,
         sources: &[(oxidd::NodeID, &Box<dyn crate::traits::DiagramSection>)],
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VisError> {
+        let nodes: HashMap<NodeID, F> = get_reachable_nodes(&self.functions)
+            .into_iter()
+            .map(|(id, f, _)| (id, f))
+            .collect();
         let mut levels = Vec::new();
         let roots = sources
             .iter()
             .map(|&(id, section)| {
-                let root_edge = DummyMTBDDEdge::new(Arc::new(id), self.manager_ref.clone());
+                let f = nodes
+                    .get(&id)
+                    .cloned()
+                    .ok_or(VisError::UnknownNode { node: id })?;
                 levels = section.get_level_labels();
-                (DummyMTBDDFunction(root_edge), section.get_node_labels(id))
+                Ok((f, section.get_node_labels(id)))
             })
-            .collect_vec();
-        Some(Box::new(MTBDDDiagramSection::new(roots, levels)))
+            .collect::<Result<Vec<_>, VisError>>()?;
+        Ok(Box::new(MTBDDDiagramSection::new(roots, levels)))
     }
 }
 
@@ -146,6 +208,7 @@ where
     roots: Vec<(F, Vec<String>)>,
     labels: HashMap<NodeID, Vec<String>>,
     levels: Vec<String>,
+    trace: Option<Rc<StepTrace>>,
 }
 impl<F: Function> MTBDDDiagramSection<F>
 where
@@ -164,6 +227,7 @@ where
                 .collect(),
             roots,
             levels,
+            trace: None,
         };
         console::log!(
             "init {}",
@@ -176,81 +240,53 @@ where
     }
 }
 
-#[derive(Clone)]
-struct MTBDDColors {
-    edge_true: Color,
-    edge_false: Color,
-    edge_label: Color,
-    node_true: Color,
-    node_false: Color,
-    node_group: Color,
-    node_default: Color,
-    node_text: Color,
-    node_label: Color,
-    layer_background1: Color,
-    layer_background2: Color,
-    layer_text: Color,
-    selection: TransparentColor,
-    selection_partial: TransparentColor,
-    selection_hover: TransparentColor,
-    selection_hover_partial: TransparentColor,
-}
-impl MTBDDColors {
-    const DARK: MTBDDColors = MTBDDColors {
-        edge_true: Color(0.631, 0.749, 0.423),
-        edge_false: Color(0.835, 0.341, 0.341),
-        edge_label: Color(0.6, 0.6, 0.6),
-        node_true: Color(0.631, 0.749, 0.423),
-        node_false: Color(0.835, 0.341, 0.341),
-        node_group: Color(0.45, 0.45, 0.45),
-        node_default: Color(0.35, 0.35, 0.35),
-        node_text: Color(0.0, 0.0, 0.0),
-        node_label: Color(0.5, 0.5, 1.0),
-        layer_background1: Color(0.125, 0.125, 0.125),
-        layer_background2: Color(0.1875, 0.1875, 0.1875),
-        layer_text: Color(1.0, 1.0, 1.0),
-        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
-        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
-    };
-
-    const LIGHT: MTBDDColors = MTBDDColors {
-        edge_true: Color(0.2, 1.0, 0.2),
-        edge_false: Color(1.0, 0.2, 0.2),
-        edge_label: Color(0.6, 0.6, 0.6),
-        node_true: Color(0.2, 1.0, 0.2),
-        node_false: Color(1.0, 0.2, 0.2),
-        node_group: Color(0.45, 0.45, 0.45),
-        node_default: Color(0.1, 0.1, 0.1),
-        node_text: Color(0.0, 0.0, 0.0),
-        node_label: Color(0.5, 0.5, 1.0),
-        layer_background1: Color(0.98, 0.98, 0.98),
-        layer_background2: Color(0.9, 0.9, 0.9),
-        layer_text: Color(0.0, 0.0, 0.0),
-        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
-        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
-    };
-}
-
-impl DiagramSection for MTBDDDiagramSection<DummyMTBDDFunction> {
+impl<F: Function + 'static> DiagramSection for MTBDDDiagramSection<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<MTBDDTerminal>,
+{
     fn get_level_labels(&self) -> Vec<String> {
         self.levels.clone()
     }
     fn get_node_labels(&self, node: NodeID) -> Vec<String> {
         self.labels.get(&node).cloned().unwrap_or_else(|| vec![])
     }
+    #[cfg(feature = "web")]
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
-        let graph =
-            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
-        let diagram = MTBDDDiagramDrawer::new(graph, canvas);
+        let graph = OxiddGraphStructure::<(), F, MTBDDTerminal>::new(
+            self.roots.iter().cloned().collect(),
+            self.levels.clone(),
+        );
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = MTBDDDiagramDrawer::new(
+            AbstractedGraph::new(graph),
+            level_count,
+            self.trace.clone(),
+            canvas,
+        );
+        Box::new(diagram)
+    }
+    fn create_latex_drawer(&self) -> Box<dyn DiagramSectionTextDrawer> {
+        let graph = OxiddGraphStructure::<(), F, MTBDDTerminal>::new(
+            self.roots.iter().cloned().collect(),
+            self.levels.clone(),
+        );
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = MTBDDDiagramDrawer::new_latex(
+            AbstractedGraph::new(graph),
+            level_count,
+            self.trace.clone(),
+        );
         Box::new(diagram)
     }
     fn get_meta(&self) -> i128 {
         0
     }
+    fn load_step_trace(&mut self, trace: String) -> Result<(), VisError> {
+        self.trace = Some(Rc::new(StepTrace::parse(&trace)?));
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -288,7 +324,12 @@ impl LatexNodeStyle for NodeData {
     fn get_label(&self) -> Option<String> {
         self.name.clone()
     }
+
+    fn get_fill_color(&self) -> Option<Color> {
+        self.is_terminal.map(|_| self.color)
+    }
 }
+#[cfg(feature = "web")]
 impl WebglNodeStyle for NodeData {
     fn get_color(&self) -> Color {
         self.color.clone()
@@ -302,164 +343,133 @@ impl WebglNodeStyle for NodeData {
         self.name.clone()
     }
 }
-impl WidthLabel for NodeData {
-    fn get_width(&self) -> f32 {
-        self.width
+impl SvgNodeStyle for NodeData {
+    fn get_color(&self) -> Color {
+        self.color.clone()
     }
-}
-impl NodeStyle for NodeData {}
 
-#[derive(Clone)]
-pub struct LayerData {
-    name: String,
-}
-impl Interpolatable for LayerData {
-    fn mix(&self, other: &Self, frac: f32) -> Self {
-        LayerData {
-            name: self.name.clone(),
-        }
-    }
-}
-impl LayerStyle for LayerData {
-    fn squash(layers: Vec<Self>) -> Self {
-        LayerData {
-            name: layers.into_iter().map(|s| s.name).join(", \n"),
-        }
+    fn get_outline_color(&self) -> TransparentColor {
+        self.border_color.clone()
     }
-}
-impl WebglLayerStyle for LayerData {
-    fn get_label(&self) -> String {
+
+    fn get_label(&self) -> Option<String> {
         self.name.clone()
     }
 }
-impl LatexLayerStyle for LayerData {
-    fn get_label(&self) -> String {
-        self.name.clone()
+impl WidthLabel for NodeData {
+    fn get_width(&self) -> f32 {
+        self.width
     }
 }
+impl NodeStyle for NodeData {}
 
-type GroupedGraph =
-    GroupPresenceAdjuster<GroupLabelAdjuster<NodeData, LayerData, GroupManager<Graph>>>;
-type Graph = RCGraph<TerminalLevelAdjuster<PresenceAdjuster>>;
-type PresenceAdjuster =
-    RCGraph<NodePresenceAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>>;
-type BaseGraph = OxiddGraphStructure<(), DummyMTBDDFunction, MTBDDTerminal>;
+type GroupedGraph = GroupedDiagramGraph<PointerGraph, NodeData>;
+type PresenceAdjuster = PresenceGraph<PointerGraph>;
+type PointerGraph = RCGraph<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>;
+type BaseGraph = AbstractedGraph<(), NodeLabel<MTBDDTerminal>, String>;
 
 type Layout = TransitionLayout<
-    LayeredLayout<
-        GroupedGraph,
-        SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
-        OrderingGroupAlignment,
-        BrandesKopfPositioningCorrected,
+    LevelCollapsingLayout<
+        LayeredLayout<
+            GroupedGraph,
+            SequenceOrdering<
+                GroupedGraph,
+                PseudoRandomLayerOrdering,
+                SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
+            >,
+            OrderingGroupAlignment,
+            BrandesKopfPositioningCorrected,
+        >,
     >,
 >;
 
-pub struct MTBDDDiagramDrawer {
-    graph: Graph,
-    group_manager: MutRcRefCell<GroupManager<Graph>>,
-    presence_adjuster: PresenceAdjuster,
-    time: MutRcRefCell<u32>,
-    drawer: MutRcRefCell<Drawer<WebglRenderer<()>, Layout, GroupedGraph>>,
-    config: Configuration<
-        LocationConfig<
-            PanelConfig<
-                CompositeConfig<(
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                LabelConfig<IntConfig>,
-                                LabelConfig<IntConfig>,
-                                ButtonConfig,
-                            )>,
-                        >,
-                    >,
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                ButtonConfig,
-                                LabelConfig<ChoiceConfig<PresenceRemainder>>,
-                                LabelConfig<ChoiceConfig<PresenceRemainder>>,
-                                LabelConfig<CompositeConfig<(FloatConfig, FloatConfig)>>,
-                            )>,
-                        >,
-                    >,
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                ButtonConfig,
-                                TextOutputConfig,
-                                LabelConfig<TextOutputConfig>,
-                            )>,
-                        >,
-                    >,
-                )>,
-            >,
+type MTBDDSettings = (
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<bool>>,
+                LabelConfig<FloatConfig>,
+                LabelConfig<IntConfig>,
+            )>,
         >,
     >,
-}
-
-impl MTBDDDiagramDrawer {
-    pub fn new(graph: BaseGraph, canvas: HtmlCanvasElement) -> Self {
-        let colors = &MTBDDColors::LIGHT;
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                ButtonConfig,
+                LabelConfig<TerminalRulesConfig>,
+                LabelConfig<ChoiceConfig<Option<Palette>>>,
+                LabelConfig<ChoiceConfig<ScaleType>>,
+                LabelConfig<CompositeConfig<(FloatConfig, FloatConfig)>>,
+                ButtonConfig,
+            )>,
+        >,
+    >,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<ThemeKind>>,
+                LabelConfig<TextConfig>,
+                LabelConfig<TextOutputConfig>,
+            )>,
+        >,
+    >,
+);
 
-        let edge_rendering_type =
-            |color: Color, width: f32, dash_solid: f32, dash_transparent: f32| EdgeRenderingType {
-                select_color: color.mix_transparent(&colors.selection),
-                partial_select_color: color.mix_transparent(&colors.selection_partial),
-                hover_color: color.mix_transparent(&colors.selection_hover),
-                partial_hover_color: color.mix_transparent(&colors.selection_hover_partial),
-                color,
-                width,
-                dash_solid,
-                dash_transparent,
-            };
+pub type MTBDDDiagramDrawer<R> = DiagramDrawer<R, Layout, PointerGraph, NodeData, MTBDDSettings>;
 
+#[cfg(feature = "web")]
+impl MTBDDDiagramDrawer<WebglRenderer<()>> {
+    pub fn new(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        trace: Option<Rc<StepTrace>>,
+        canvas: HtmlCanvasElement,
+    ) -> Self {
+        let theme = &Theme::LIGHT;
         let font = Rc::new(Font::new(
             include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
             1.0,
         ));
         let renderer = WebglRenderer::from_canvas(
             canvas,
-            HashMap::from([
-                // True edge
-                (
-                    EdgeType::new((), 0),
-                    edge_rendering_type(
-                        colors.edge_true,
-                        0.2,
-                        1.0,
-                        0.0, // No dashing
-                    ),
-                ),
-                // False edge
-                (
-                    EdgeType::new((), 1),
-                    edge_rendering_type(colors.edge_false, 0.2, 0.3, 0.15),
-                ),
-                // Label edge
-                (
-                    EdgeType::new((), 2),
-                    edge_rendering_type(colors.edge_label, 0.15, 1.0, 0.0),
-                ),
-            ]),
-            NodeRenderingColorConfig {
-                select: colors.selection,
-                partial_select: colors.selection_partial,
-                hover: colors.selection_hover,
-                partial_hover: colors.selection_hover_partial,
-                text: colors.node_text,
-            },
-            LayerRenderingColorConfig {
-                background1: colors.layer_background1.into(),
-                background2: colors.layer_background2.into(),
-                text: colors.layer_text,
-            },
+            theme.get_edge_types(),
+            theme.get_node_colors(),
+            theme.get_layer_colors(),
             font.clone(),
         )
         .unwrap();
+
+        Self::with_renderer(graph, level_count, trace, renderer, font)
+    }
+}
+
+impl MTBDDDiagramDrawer<LatexRenderer<Layout>> {
+    /// Creates a drawer that does not need a canvas, from which the LaTeX code of the initial layout can be obtained
+    pub fn new_latex(graph: BaseGraph, level_count: LevelNo, trace: Option<Rc<StepTrace>>) -> Self {
+        let font = Rc::new(Font::new(
+            include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
+            1.0,
+        ));
+        Self::with_renderer(graph, level_count, trace, LatexRenderer::new(), font)
+    }
+}
+
+impl<R: Renderer<Layout> + 'static> MTBDDDiagramDrawer<R> {
+    fn with_renderer(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        trace: Option<Rc<StepTrace>>,
+        renderer: R,
+        font: Rc<Font>,
+    ) -> Self {
+        let theme = MutRcRefCell::new(Theme::LIGHT);
         let layout = LayeredLayout::new(
             // SugiyamaOrdering::new(2, 2),
-            SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
+            SequenceOrdering::new(
+                PseudoRandomLayerOrdering::new(2),
+                SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
+            ),
             // AverageGroupAlignment,
             OrderingGroupAlignment,
             // BrandesKopfPositioning,
@@ -467,99 +477,58 @@ impl MTBDDDiagramDrawer {
             // DummyLayerPositioning,
             0.3,
         );
-        let layout = TransitionLayout::new(layout);
+        let layout = TransitionLayout::new(LevelCollapsingLayout::new(layout, 1.0));
 
-        let original_roots = graph.get_roots().clone();
         let base_graph = TerminalLevelAdjuster::new(graph); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
-        let pointer_adjuster = PointerNodeAdjuster::new(
+        let pointer_adjuster: PointerGraph = RCGraph::new(PointerNodeAdjuster::new(
             base_graph,
             EdgeType { tag: (), index: 2 },
             true,
             "".to_string(),
-        );
-        let presence_adjuster = RCGraph::new(NodePresenceAdjuster::new(pointer_adjuster));
-        let modified_graph = RCGraph::new(TerminalLevelAdjuster::new(presence_adjuster.clone()));
-        let roots = modified_graph.get_roots();
-        let group_manager = MutRcRefCell::new(GroupManager::new(modified_graph.clone()));
-
-        let (terminal_min, terminal_max) = (FloatConfig::new(0.), FloatConfig::new(1.));
-        let (terminal_min_ref, terminal_max_ref) = (terminal_min.clone(), terminal_max.clone());
-        let mut grouped_graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new_shared(
-            group_manager.clone(),
-            move |nodes| {
-                let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
-                    (
-                        Some(&PresenceLabel {
-                            original_label:
-                                PointerLabel::Node(NodeLabel {
-                                    pointers: _,
-                                    kind: NodeType::Terminal(ref terminal),
-                                }),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => {
-                        let min = terminal_min_ref.get();
-                        let max = terminal_max_ref.get();
-                        let per = ((terminal.0 - min) / (max - min)).max(0.0).min(1.0);
-                        (
-                            Some(*terminal),
-                            false,
-                            colors.node_false.mix(&colors.node_true, per),
-                        )
-                    }
-                    (
-                        Some(&PresenceLabel {
-                            original_label: PointerLabel::Pointer(_),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => (None, false, colors.node_label),
-                    (Some(_), None) => (None, false, colors.node_default),
-                    _ => (None, true, colors.node_group),
-                };
-                let name: Option<String> = match (nodes.get(0), nodes.get(1)) {
-                    (
-                        Some(&PresenceLabel {
-                            original_label: PointerLabel::Pointer(ref text),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => Some(text.clone()),
-                    _ => None,
-                }
-                .or_else(|| is_terminal.map(|t| format!("{}", t)));
-
-                NodeData {
-                    color,
-                    border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
-                    width: 1.
-                        + match name {
-                            Some(ref text) => font.measure_width(&text),
-                            None => 0.,
-                        },
-                    name,
-                    is_terminal,
-                    is_group,
-                }
-            },
-            move |layer_label| LayerData {
-                name: layer_label.clone(),
-            },
         ));
-        grouped_graph.hide(0);
 
-        const TOP_MARGIN: f32 = 40.0;
-        let composite_config = CompositeConfig::new((
+        let (range_min, range_max) = get_terminal_range(&pointer_adjuster).unwrap_or((0., 1.));
+        let terminal_colors = TerminalColorConfig {
+            palette: ChoiceConfig::new([
+                Choice::new(None, "theme (false-true)"),
+                Choice::new(Some(Palette::Viridis), "viridis"),
+                Choice::new(Some(Palette::Blues), "blues"),
+                Choice::new(Some(Palette::Greys), "greys"),
+                Choice::new(Some(Palette::RedBlue), "red-blue (diverging)"),
+                Choice::new(Some(Palette::PurpleGreen), "purple-green (diverging)"),
+            ]),
+            scale: ChoiceConfig::new([
+                Choice::new(ScaleType::Linear, "linear"),
+                Choice::new(ScaleType::Logarithmic, "logarithmic"),
+            ]),
+            min: FloatConfig::new(range_min),
+            max: FloatConfig::new(range_max),
+            theme: theme.clone(),
+            has_nan: get_terminal_values(&pointer_adjuster)
+                .iter()
+                .any(|value| value.is_nan()),
+        };
+
+        let settings: MTBDDSettings = (
             ContainerConfig::new(
-                ContainerStyle::new(),
+                ContainerStyle::new().margin_top(TOP_MARGIN),
                 LabelConfig::new_styled(
-                    "Node expansion",
+                    "Layout",
                     LabelKind::Category,
                     CompositeConfig::new((
-                        LabelConfig::new("Layers", IntConfig::new_min_max(4, Some(1), None)),
-                        LabelConfig::new("Max nodes", IntConfig::new_min_max(100, Some(1), None)),
-                        ButtonConfig::new_labeled("Expand initial group"),
+                        LabelConfig::new(
+                            "Collapse idle levels",
+                            ChoiceConfig::new([
+                                Choice::new(false, "disabled"),
+                                Choice::new(true, "enabled"),
+                            ]),
+                        ),
+                        LabelConfig::new("Collapsed height", {
+                            let mut c = FloatConfig::new(1.0);
+                            c.set_min(Some(0.1)).commit();
+                            c
+                        }),
+                        LabelConfig::new("Seed", IntConfig::new_min_max(0, Some(0), None)),
                     )),
                 ),
             ),
@@ -570,109 +539,165 @@ impl MTBDDDiagramDrawer {
                     LabelKind::Category,
                     CompositeConfig::new((
                         ButtonConfig::new_labeled("Expand"),
-                        LabelConfig::new("0 visibility", {
-                            let mut c = ChoiceConfig::new([
-                                Choice::new(PresenceRemainder::Show, "show"),
-                                Choice::new(PresenceRemainder::Duplicate, "duplicate"),
-                                Choice::new(PresenceRemainder::Hide, "hide"),
-                            ]);
-                            c.set_index(2).commit();
-                            c
-                        }),
-                        LabelConfig::new(
-                            "1 visibility",
-                            ChoiceConfig::new([
-                                Choice::new(PresenceRemainder::Show, "show"),
-                                Choice::new(PresenceRemainder::Duplicate, "duplicate"),
-                                Choice::new(PresenceRemainder::Hide, "hide"),
-                            ]),
-                        ),
+                        LabelConfig::new("visibility rules", create_terminal_rules()),
+                        LabelConfig::new("palette", terminal_colors.palette.clone()),
+                        LabelConfig::new("scale", terminal_colors.scale.clone()),
                         LabelConfig::new(
                             "range",
                             CompositeConfig::new_horizontal(
-                                (terminal_min, terminal_max),
+                                (terminal_colors.min.clone(), terminal_colors.max.clone()),
                                 |(f1, f2)| vec![Box::new(f1.clone()), Box::new(f2.clone())],
                             ),
                         ),
+                        ButtonConfig::new_labeled("Fit range"),
                     )),
                 ),
             ),
             ContainerConfig::new(
                 ContainerStyle::new().margin_top(TOP_MARGIN),
                 LabelConfig::new_styled(
-                    "Latex",
+                    "Theme",
                     LabelKind::Category,
                     CompositeConfig::new((
-                        ButtonConfig::new_labeled("Generate"),
-                        TextOutputConfig::new(true),
-                        LabelConfig::new("Headers", TextOutputConfig::new(false)),
+                        LabelConfig::new(
+                            "Colors",
+                            ChoiceConfig::new([
+                                Choice::new(ThemeKind::Light, "light"),
+                                Choice::new(ThemeKind::Dark, "dark"),
+                                Choice::new(ThemeKind::HighContrast, "high contrast"),
+                                Choice::new(ThemeKind::Custom, "custom"),
+                            ]),
+                        ),
+                        LabelConfig::new(
+                            "Custom theme (JSON)",
+                            TextConfig::new_multiline(Theme::LIGHT.to_json()),
+                        ),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
                     )),
                 ),
             ),
-        ));
-        let config = Configuration::new(LocationConfig::new(
-            Location::BOTTOM_RIGHT,
-            PanelConfig::builder()
-                .set_button_icon("Settings")
-                .set_button_icon_description("Open visualization settings")
-                .set_name("Settings")
-                .set_category("visualization-settings")
-                .set_open_side(OpenSide::Right)
-                .set_open_size(0.3)
-                .build(composite_config.clone()),
-        ));
+        );
 
-        let mut out = MTBDDDiagramDrawer {
-            group_manager,
-            graph: modified_graph,
-            presence_adjuster,
-            time: MutRcRefCell::new(0),
-            drawer: MutRcRefCell::new(Drawer::new(
-                renderer,
-                layout,
-                MutRcRefCell::new(grouped_graph),
-            )),
-            config,
-        };
+        let terminal_colors_ref = terminal_colors.clone();
+        let theme_ref = theme.clone();
+        let theme_colors = theme.clone();
+        let legend_colors = terminal_colors.clone();
+        let style: DiagramStyle<PointerGraph, NodeData> = DiagramStyle {
+            node_style: Box::new(
+                move |nodes: Vec<PresenceLabel<PointerLabel<NodeLabel<MTBDDTerminal>>>>| {
+                    let colors = theme_ref.read();
+                    let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label:
+                                    PointerLabel::Node(NodeLabel {
+                                        kind: NodeType::Terminal(ref terminal),
+                                        ..
+                                    }),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => (
+                            Some(*terminal),
+                            false,
+                            terminal_colors_ref.get_scale().get_color(terminal.0),
+                        ),
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(_),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => (None, false, colors.node_label),
+                        (Some(_), None) => (None, false, colors.node_default),
+                        _ => (None, true, colors.node_group),
+                    };
+                    let name: Option<String> = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(ref text),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => Some(text.clone()),
+                        (Some(_), None) => None,
+                        _ => Some(format!("{} nodes", nodes.len())),
+                    }
+                    .or_else(|| is_terminal.map(|t| format!("{}", t)));
 
-        let (expansion, terminals, latex_config) = &*composite_config;
-        let (_max_expand_layers, _max_expand_nodes, expand_all) = &****expansion;
-        let (generate_latex, latex_output, latex_headers_output) = &****latex_config;
-        let (expand_terminals, zero_visibility, one_visibility, terminal_range) = &****terminals;
-        let (_terminal_range_start, _terminal_range_end) = &***terminal_range;
-
-        let drawer = out.drawer.clone();
-        let mut latex_renderer = LatexRenderer::<Layout>::new();
-        let mut output = latex_output.clone();
-        generate_latex.clone().add_press_listener(move || {
-            latex_renderer.update_layout(&drawer.get().get_current_layout());
-            latex_renderer.render(u32::MAX);
-            let out = latex_renderer.get_output();
-            output.set(out.into()).commit();
+                    NodeData {
+                        color,
+                        border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
+                        width: 1.
+                            + match name {
+                                Some(ref text) => font.measure_width(&text),
+                                None => 0.,
+                            },
+                        name,
+                        is_terminal,
+                        is_group,
+                    }
+                },
+            ),
+            colors: Box::new(move || theme_colors.read().get_rendering_colors()),
+            legend: Box::new(move || legend_colors.get_legend()),
+            terminal: Box::new(
+                |label: PointerLabel<NodeLabel<MTBDDTerminal>>| match label {
+                    PointerLabel::Node(NodeLabel {
+                        kind: NodeType::Terminal(terminal),
+                        ..
+                    }) => Some((format!("{}", terminal), None)),
+                    _ => None,
+                },
+            ),
+        };
+        let out = DiagramDrawer::from_graph(
+            pointer_adjuster.clone(),
+            level_count,
+            trace,
+            renderer,
+            layout,
+            settings,
+            style,
+        )
+        .with_stored_settings(|settings: &MTBDDSettings| {
+            let (layout_settings, terminals, theme_config) = settings;
+            let (collapse_levels, collapsed_height, seed) = &****layout_settings;
+            let (_expand_terminals, visibility_rules, palette, scale, terminal_range, _fit_range) =
+                &****terminals;
+            let (theme_kind, theme_json, _theme_error) = &****theme_config;
+            let (range_start, range_end) = &***terminal_range;
+            vec![
+                ("collapse_levels", stored(&**collapse_levels)),
+                ("collapsed_height", stored(&**collapsed_height)),
+                ("seed", stored(&**seed)),
+                ("palette", stored(&**palette)),
+                ("scale", stored(&**scale)),
+                ("range_start", stored(range_start)),
+                ("range_end", stored(range_end)),
+                ("theme", stored(&**theme_kind)),
+                ("theme_json", stored(&**theme_json)),
+                ("visibility_rules", stored(&**visibility_rules)),
+            ]
+            .into_iter()
+            .map(|(name, setting)| (name.to_string(), setting))
+            .collect_vec()
         });
-        latex_headers_output
-            .clone()
-            .set(latex_headers.to_string())
-            .commit();
 
-        let from = out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
-        // let from = 0;
-        for root in roots {
-            out.create_group(vec![TargetID(TargetIDType::NodeID, root)]);
-        }
+        let (layout_settings, terminals, theme_config) = out.get_diagram_settings();
+        let (collapse_levels, collapsed_height, seed) = &****layout_settings;
+        let (theme_kind, theme_json, theme_error) = &****theme_config;
+        let (
+            expand_terminals,
+            visibility_rules,
+            _palette,
+            _scale,
+            _terminal_range,
+            fit_terminal_range,
+        ) = &****terminals;
 
-        let max = 500;
-        if out.group_manager.read().get_nodes_of_group(from).len() < max {
-            reveal_all(&out.group_manager, from, max);
-        }
-
-        let group_manager = out.group_manager.clone();
-        expand_all
-            .clone()
-            .add_press_listener(move || reveal_all(&group_manager, from, 10_000_000));
-
-        let group_manager = out.group_manager.clone();
-        let mut graph = out.graph.clone();
+        let group_manager = out.get_group_manager().clone();
+        let mut graph = out.get_graph().clone();
         expand_terminals.clone().add_press_listener(move || {
             for t in graph.get_terminals() {
                 if graph.get_known_parents(t).len() > 0 {
@@ -683,151 +708,381 @@ impl MTBDDDiagramDrawer {
             }
         });
 
-        fn set_terminal_presence(
-            presence_adjuster: &PresenceAdjuster,
-            terminal: MTBDDTerminal,
-            presence: PresenceRemainder,
-        ) -> () {
-            let mut adjuster = presence_adjuster.get();
-            let terminals = adjuster.get_terminals();
-            let mut terminals = terminals.iter().filter_map(|&node| {
-                match adjuster.get_node_label(node).original_label {
-                    PointerLabel::Node(NodeLabel {
-                        pointers: _,
-                        kind: NodeType::Terminal(t),
-                    }) if t == terminal => Some(node),
-                    _ => None,
-                }
-            });
-            let Some(target_terminal) = terminals.next() else {
-                return;
-            };
+        let rules = visibility_rules.clone();
+        let presence_adjuster = out.get_presence_adjuster().clone();
+        let _ = on_configuration_change(visibility_rules, move || {
+            apply_terminal_rules(&presence_adjuster, &get_terminal_rules(&rules));
+        });
 
-            adjuster.set_node_presence(target_terminal, PresenceGroups::remainder(presence));
-        }
-        let false_config = zero_visibility.clone();
-        let false_presence_adjuster = out.presence_adjuster.clone();
-        let _ = on_configuration_change(zero_visibility, move || {
-            set_terminal_presence(
-                &false_presence_adjuster,
-                MTBDDTerminal(0.),
-                false_config.get(),
-            );
+        let mut range_colors = terminal_colors.clone();
+        fit_terminal_range.clone().add_press_listener(move || {
+            if let Some((min, max)) = get_terminal_range(&pointer_adjuster) {
+                range_colors.min.set(min).commit();
+                range_colors.max.set(max).commit();
+            }
         });
-        let true_config = one_visibility.clone();
-        let true_presence_adjuster = out.presence_adjuster.clone();
-        let _ = on_configuration_change(one_visibility, move || {
-            set_terminal_presence(
-                &true_presence_adjuster,
-                MTBDDTerminal(1.),
-                true_config.get(),
-            );
+
+        let drawer = out.get_drawer().clone();
+        let collapse_levels_copy = collapse_levels.clone();
+        let collapsed_height_copy = collapsed_height.clone();
+        let _ = on_configuration_change(&*layout_settings, move || {
+            let mut drawer = drawer.get();
+            let collapsing_layout = drawer.get_layout_rules().get_layout_rules();
+            collapsing_layout.set_enabled(collapse_levels_copy.get());
+            collapsing_layout.set_collapsed_height(collapsed_height_copy.get());
+        });
+
+        let drawer = out.get_drawer().clone();
+        let seed_copy = seed.clone();
+        let _ = on_configuration_change(&*seed, move || {
+            drawer.get().set_seed(seed_copy.get() as u64);
         });
 
-        // Redraw on interaction
-        let time = out.time.clone();
-        let drawer = out.drawer.clone();
-        let _ = after_configuration_change(&composite_config, move || {
-            drawer.get().layout(*time.get());
+        let drawer = out.get_drawer().clone();
+        let theme_kind_copy = theme_kind.clone();
+        let theme_json_copy = theme_json.clone();
+        let theme_error_copy = theme_error.clone();
+        let apply_theme = Rc::new(move || {
+            let mut output = theme_error_copy.clone();
+            match theme_kind_copy.get().get_theme(&theme_json_copy.get()) {
+                Ok(new_theme) => {
+                    // The node colors are obtained from the theme in the next layout, and transition from the old colors
+                    drawer
+                        .get()
+                        .get_renderer_mut()
+                        .set_colors(new_theme.get_rendering_colors());
+                    *theme.get() = new_theme;
+                    output.set_label("").commit();
+                }
+                Err(err) => {
+                    output
+                        .set_label(&format!("Invalid theme: {}", err))
+                        .commit();
+                }
+            }
         });
+        // The error output is part of the theme settings, hence only the inputs are observed
+        let apply_kind = apply_theme.clone();
+        let _ = on_configuration_change(&**theme_kind, move || apply_kind());
+        let _ = on_configuration_change(&**theme_json, move || apply_theme());
 
         out
     }
 }
 
-fn reveal_all<G: GraphStructure>(
-    group_manager: &MutRcRefCell<GroupManager<G>>,
-    from_id: NodeGroupID,
-    limit: usize,
-) {
-    let nodes = {
-        let mut gm = group_manager.get();
-        if !gm.get_groups().contains_key(&from_id) {
-            return;
-        }
-        let explored_group = gm.create_group(vec![TargetID(TargetIDType::NodeGroupID, from_id)]);
-        gm.get_nodes_of_group(explored_group)
-    };
-
-    let mut count = 0;
-    let mut group_manager = group_manager.get();
-    for node_id in nodes.into_iter().rev() {
-        // console::log!("{node_id}");
-        group_manager.create_group(vec![TargetID(TargetIDType::NodeID, node_id)]);
-
-        count = count + 1;
-        if limit > 0 && count >= limit {
-            break;
+type TerminalRuleConfig = CompositeConfig<(
+    ChoiceConfig<TerminalCondition>,
+    FloatConfig,
+    ChoiceConfig<PresenceRemainder>,
+)>;
+/// The list of visibility rules, of which the first rule that a terminal satisfies determines its presence
+type TerminalRulesConfig = ListConfig<TerminalRuleConfig>;
+
+/// The conditions that visibility rules can select terminals with, most of which compare the terminal value to the value of the rule
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TerminalCondition {
+    /// Disables the rule
+    None,
+    Less,
+    LessEqual,
+    Equal,
+    GreaterEqual,
+    Greater,
+    NaN,
+    /// Selects the terminals that are among the given number of terminals with the most parents
+    TopReferenced,
+    /// Selects all terminals, which can be used as a final rule
+    Any,
+}
+
+impl TerminalCondition {
+    /// Checks whether a terminal with the given value, that is the `rank`-th most referenced terminal (starting at 0), satisfies this condition
+    fn matches(&self, value: f32, rank: usize, parameter: f32) -> bool {
+        match self {
+            TerminalCondition::None => false,
+            TerminalCondition::Less => value < parameter,
+            TerminalCondition::LessEqual => value <= parameter,
+            TerminalCondition::Equal => {
+                (value - parameter).abs() <= f32::EPSILON * parameter.abs().max(1.0)
+            }
+            TerminalCondition::GreaterEqual => value >= parameter,
+            TerminalCondition::Greater => value > parameter,
+            TerminalCondition::NaN => value.is_nan(),
+            TerminalCondition::TopReferenced => (rank as f32) < parameter,
+            TerminalCondition::Any => true,
         }
     }
 }
 
-impl DiagramSectionDrawer for MTBDDDiagramDrawer {
-    fn render(&mut self, time: u32) -> () {
-        *self.time.get() = time;
-        self.drawer.get().render(time);
-    }
+/// Creates the visibility rules, where initially only the terminals with value 0 are hidden. Rules that are added by the user are disabled until their condition is chosen
+fn create_terminal_rules() -> TerminalRulesConfig {
+    let hide_zero = create_terminal_rule(TerminalCondition::Equal, 0., PresenceRemainder::Hide);
+    ListConfig::new(vec![hide_zero], || {
+        create_terminal_rule(TerminalCondition::None, 0., PresenceRemainder::Show)
+    })
+}
 
-    fn layout(&mut self, time: u32) -> () {
-        self.drawer.get().layout(time);
-    }
+fn create_terminal_rule(
+    condition: TerminalCondition,
+    value: f32,
+    presence: PresenceRemainder,
+) -> TerminalRuleConfig {
+    let mut condition_config = ChoiceConfig::new([
+        Choice::new(TerminalCondition::None, "-"),
+        Choice::new(TerminalCondition::Less, "value <"),
+        Choice::new(TerminalCondition::LessEqual, "value <="),
+        Choice::new(TerminalCondition::Equal, "value =="),
+        Choice::new(TerminalCondition::GreaterEqual, "value >="),
+        Choice::new(TerminalCondition::Greater, "value >"),
+        Choice::new(TerminalCondition::NaN, "value is NaN"),
+        Choice::new(TerminalCondition::TopReferenced, "top-k referenced"),
+        Choice::new(TerminalCondition::Any, "any"),
+    ]);
+    condition_config.set(condition).commit();
+    let mut presence_config = ChoiceConfig::new([
+        Choice::new(PresenceRemainder::Show, "show"),
+        Choice::new(PresenceRemainder::Duplicate, "duplicate"),
+        Choice::new(PresenceRemainder::DuplicateParent, "duplicate per parent"),
+        Choice::new(PresenceRemainder::Hide, "hide"),
+    ]);
+    presence_config.set(presence).commit();
+    CompositeConfig::new_horizontal(
+        (condition_config, FloatConfig::new(value), presence_config),
+        |(condition, value, presence)| {
+            vec![
+                Box::new(condition.clone()),
+                Box::new(value.clone()),
+                Box::new(presence.clone()),
+            ]
+        },
+    )
+}
 
-    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
-        self.drawer.get().set_transform(width, height, x, y, scale);
-    }
+fn get_terminal_rules(
+    rules: &TerminalRulesConfig,
+) -> Vec<(TerminalCondition, f32, PresenceRemainder)> {
+    rules
+        .get_items()
+        .iter()
+        .map(|rule| {
+            let (condition, value, presence) = &***rule;
+            (condition.get(), value.get(), presence.get())
+        })
+        .filter(|(condition, _, _)| *condition != TerminalCondition::None)
+        .collect()
+}
 
-    fn set_step(&mut self, step: i32) -> Option<StepData> {
-        todo!()
-    }
+/// Sets the presence of every terminal according to the first rule that its value satisfies, terminals that satisfy no rule are shown
+fn apply_terminal_rules(
+    presence_adjuster: &PresenceAdjuster,
+    rules: &[(TerminalCondition, f32, PresenceRemainder)],
+) {
+    let mut adjuster = presence_adjuster.get();
+    // Copies of a terminal share the presence of their original, but distinct terminals may share a value as well
+    let terminals = adjuster
+        .get_terminals()
+        .into_iter()
+        .filter_map(|node| {
+            let label = adjuster.get_node_label(node);
+            match label.original_label {
+                PointerLabel::Node(NodeLabel {
+                    kind: NodeType::Terminal(t),
+                    ..
+                }) => Some((label.original_id, node, t.0)),
+                _ => None,
+            }
+        })
+        .unique_by(|&(original, _, _)| original)
+        .into_group_map_by(|&(_, _, value)| value.to_bits());
 
-    fn set_group(&mut self, from: Vec<TargetID>, to: NodeGroupID) -> bool {
-        self.group_manager.get().set_group(from, to)
-    }
+    // The values are ranked by the total number of parents of their terminals
+    let values = terminals
+        .into_iter()
+        .sorted_by_key(|&(bits, _)| bits)
+        .map(|(_, terminals)| {
+            let parent_count: usize = terminals
+                .iter()
+                .map(|&(_, node, _)| adjuster.get_source_parent_count(node))
+                .sum();
+            (terminals, parent_count)
+        })
+        .collect_vec()
+        .into_iter()
+        .sorted_by_key(|&(_, parent_count)| Reverse(parent_count));
 
-    fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
-        self.group_manager.get().create_group(from)
+    for (rank, (terminals, _)) in values.enumerate() {
+        let value = terminals[0].2;
+        let presence = rules
+            .iter()
+            .find(|(condition, parameter, _)| condition.matches(value, rank, *parameter))
+            .map(|(_, _, presence)| presence.clone())
+            .unwrap_or(PresenceRemainder::Show);
+        let presence = PresenceGroups::remainder(presence);
+        for (_, node, _) in terminals {
+            let current = adjuster
+                .get_node_presence(node)
+                .unwrap_or_else(|| PresenceGroups::remainder(PresenceRemainder::Show));
+            if current != presence {
+                adjuster.set_node_presence(node, presence.clone());
+            }
+        }
     }
+}
 
-    fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
-        let (expansion, _terminals, _latex_config) = &****self.config;
-        let (max_expand_layers, max_expand_nodes, _expand_all) = &****expansion;
-        self.group_manager.get().split_edges(
-            nodes,
-            max_expand_layers.get().unsigned_abs(),
-            max_expand_nodes.get().unsigned_abs(),
-        );
-    }
+/// The configuration of the colors that terminals are given based on their value
+#[derive(Clone)]
+struct TerminalColorConfig {
+    /// The palette to use, or `None` to use the false and true colors of the theme
+    palette: ChoiceConfig<Option<Palette>>,
+    scale: ChoiceConfig<ScaleType>,
+    min: FloatConfig,
+    max: FloatConfig,
+    theme: MutRcRefCell<Theme>,
+    /// Whether some terminal is NaN, which is then listed in the legend
+    has_nan: bool,
+}
 
-    fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
-        self.drawer.read().get_nodes(area, max_group_expansion)
+impl TerminalColorConfig {
+    fn get_scale(&self) -> ColorScale {
+        let palette = self.palette.get().unwrap_or_else(|| {
+            let theme = self.theme.read();
+            Palette::Gradient(theme.node_false, theme.node_true)
+        });
+        ColorScale::new(palette, self.scale.get(), self.min.get(), self.max.get())
     }
 
-    fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
-        self.drawer.get().select_nodes(selected_ids, hovered_ids);
+    fn get_legend(&self) -> Vec<LegendEntry> {
+        let mut legend = self
+            .get_scale()
+            .get_samples(5)
+            .into_iter()
+            .map(|(value, color)| LegendEntry {
+                label: if (value - value.round()).abs() < 0.005 {
+                    format!("{}", value.round() + 0.0) // Adding 0 turns -0 into 0
+                } else {
+                    format!("{:.2}", value)
+                },
+                color,
+            })
+            .collect_vec();
+        if self.has_nan {
+            legend.push(LegendEntry {
+                label: "NaN".to_string(),
+                color: ColorScale::NAN_COLOR,
+            });
+        }
+        legend
     }
+}
+
+/// Retrieves the values of the terminals in the graph
+fn get_terminal_values(pointer_adjuster: &PointerGraph) -> Vec<f32> {
+    let adjuster = pointer_adjuster.read();
+    adjuster
+        .get_terminals()
+        .into_iter()
+        .filter_map(|node| match adjuster.get_node_label(node) {
+            PointerLabel::Node(NodeLabel {
+                kind: NodeType::Terminal(t),
+                ..
+            }) => Some(t.0),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Retrieves the smallest and largest value of the terminals in the graph, ignoring NaN
+fn get_terminal_range(pointer_adjuster: &PointerGraph) -> Option<(f32, f32)> {
+    get_terminal_values(pointer_adjuster)
+        .into_iter()
+        .filter(|value| !value.is_nan())
+        .fold(None, |range, value| match range {
+            None => Some((value, value)),
+            Some((min, max)) => Some((f32::min(min, value), f32::max(max, value))),
+        })
+}
 
-    fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
-        self.graph
-            .local_nodes_to_sources(nodes.iter().cloned().collect())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::util::storage::snapshot::StoredSetting;
+
+    #[test]
+    fn comparisons() {
+        assert!(TerminalCondition::Less.matches(1.0, 0, 2.0));
+        assert!(!TerminalCondition::Less.matches(2.0, 0, 2.0));
+        assert!(TerminalCondition::LessEqual.matches(2.0, 0, 2.0));
+        assert!(TerminalCondition::GreaterEqual.matches(2.0, 0, 2.0));
+        assert!(!TerminalCondition::Greater.matches(2.0, 0, 2.0));
+        assert!(TerminalCondition::Greater.matches(3.0, 0, 2.0));
+        assert!(!TerminalCondition::Less.matches(f32::NAN, 0, 2.0));
+        assert!(!TerminalCondition::None.matches(1.0, 0, 1.0));
+        assert!(TerminalCondition::Any.matches(f32::NAN, 5, 0.0));
+        assert!(TerminalCondition::NaN.matches(f32::NAN, 0, 0.0));
+        assert!(!TerminalCondition::NaN.matches(0.0, 0, 0.0));
     }
 
-    fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID> {
-        self.graph
-            .source_nodes_to_local(nodes.iter().cloned().collect())
+    #[test]
+    fn equality_is_relative_to_the_value() {
+        let equal =
+            |value: f32, parameter: f32| TerminalCondition::Equal.matches(value, 0, parameter);
+        assert!(equal(0.3, 0.1 + 0.2));
+        assert!(equal(0.0, 1e-8));
+        assert!(!equal(0.0, 1e-3));
+        // The tolerance grows with the value, such that it covers the neighbouring floats of 1000
+        assert!(equal(1000.0, 1000.0 + 500.0 * f32::EPSILON));
+        assert!(!equal(1000.0, 1000.01));
+        assert!(!equal(f32::NAN, f32::NAN));
     }
-    fn serialize_state(&self) -> Vec<u8> {
-        let mut out = Vec::new();
-        let _ = self.group_manager.read().write(&mut Cursor::new(&mut out));
-        out
+
+    #[test]
+    fn top_referenced_rank() {
+        let top_two = |rank: usize| TerminalCondition::TopReferenced.matches(0.0, rank, 2.0);
+        assert!(top_two(0));
+        assert!(top_two(1));
+        assert!(!top_two(2));
+        assert!(!TerminalCondition::TopReferenced.matches(0.0, 0, 0.0));
     }
 
-    fn deserialize_state(&mut self, state: Vec<u8>) -> () {
-        let _ = self.group_manager.get().read(&mut Cursor::new(&state));
-        let time = *self.time.get();
-        self.layout(time);
+    #[test]
+    fn initial_rules_hide_zero() {
+        let rules = create_terminal_rules();
+        assert_eq!(rules.len(), 1);
+        let active = get_terminal_rules(&rules);
+        assert_eq!(active.len(), 1);
+        let (condition, value, presence) = &active[0];
+        assert!(*condition == TerminalCondition::Equal);
+        assert_eq!(*value, 0.0);
+        assert!(*presence == PresenceRemainder::Hide);
     }
 
-    fn get_configuration(&self) -> AbstractConfigurationObject {
-        self.config.get_abstract()
+    #[test]
+    fn rules_can_be_added_and_removed() {
+        let mut rules = create_terminal_rules();
+        rules
+            .add_item(create_terminal_rule(
+                TerminalCondition::Greater,
+                2.,
+                PresenceRemainder::Duplicate,
+            ))
+            .commit();
+        // Added rules are disabled until their condition is chosen
+        rules.set_len(3).commit();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(get_terminal_rules(&rules).len(), 2);
+
+        let stored = rules.get_value();
+        rules.remove_item(0).commit();
+        let active = get_terminal_rules(&rules);
+        assert_eq!(active.len(), 1);
+        assert!(active[0].0 == TerminalCondition::Greater);
+
+        // Restoring the rules restores the removed rule at its original position
+        rules.set_value(&stored);
+        assert_eq!(rules.len(), 3);
+        let active = get_terminal_rules(&rules);
+        assert!(active[0].0 == TerminalCondition::Equal);
+        assert!(active[1].0 == TerminalCondition::Greater);
+        assert_eq!(active[1].1, 2.);
     }
 }
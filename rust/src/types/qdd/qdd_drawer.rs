@@ -7,18 +7,13 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::LinkedList;
 use std::hash::Hash;
-use std::io::Cursor;
 use std::iter::FromIterator;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::u32;
-use web_sys::console::log;
 
-use crate::configuration::configuration::Configuration;
-use crate::configuration::configuration_object::AbstractConfigurationObject;
-use crate::configuration::configuration_object::Abstractable;
 use crate::configuration::configuration_object::ConfigObjectGetter;
 use crate::configuration::observe_configuration::after_configuration_change;
 use crate::configuration::observe_configuration::on_configuration_change;
@@ -29,25 +24,26 @@ use crate::configuration::types::composite_config;
 use crate::configuration::types::composite_config::CompositeConfig;
 use crate::configuration::types::container_config::ContainerConfig;
 use crate::configuration::types::container_config::ContainerStyle;
+use crate::configuration::types::float_config::FloatConfig;
 use crate::configuration::types::int_config::IntConfig;
 use crate::configuration::types::label_config::LabelConfig;
 use crate::configuration::types::label_config::LabelKind;
-use crate::configuration::types::location_config::Location;
-use crate::configuration::types::location_config::LocationConfig;
-use crate::configuration::types::panel_config::OpenSide;
-use crate::configuration::types::panel_config::PanelConfig;
+use crate::configuration::types::text_config::TextConfig;
 use crate::configuration::types::text_output_config::TextOutputConfig;
 use crate::traits::Diagram;
 use crate::traits::DiagramSection;
 use crate::traits::DiagramSectionDrawer;
+use crate::traits::DiagramSectionTextDrawer;
 use crate::types::util::drawing::layouts::layer_orderings::edge_layer_ordering::EdgeLayerOrdering;
-use crate::types::util::drawing::renderers::latex_renderer::latex_headers;
-use crate::types::util::drawing::renderers::webgl_renderer::LayerRenderingColorConfig;
-use crate::types::util::drawing::renderers::webgl_renderer::WebglLayerStyle;
+use crate::types::util::drawing::theme::Theme;
+use crate::types::util::drawing::theme::ThemeKind;
+use crate::types::util::graph_structure::graph_manipulators::abstracted_graph::AbstractedGraph;
 use crate::types::util::graph_structure::graph_manipulators::child_edge_adjuster::ChildEdgeAdjuster;
 use crate::types::util::graph_structure::graph_manipulators::edge_to_adjuster::EdgeToAdjuster;
 use crate::types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceGroups;
 use crate::types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder;
+use crate::types::util::graph_structure::oxidd_graph_structure::get_reachable_nodes;
+use crate::types::util::graph_structure::oxidd_graph_structure::IntoTerminalLabel;
 use crate::types::util::graph_structure::oxidd_graph_structure::NodeType;
 use crate::util::color::Color;
 use crate::util::color::TransparentColor;
@@ -57,30 +53,38 @@ use crate::util::dummy_bdd::DummyBDDManager;
 use crate::util::dummy_bdd::DummyBDDManagerRef;
 use crate::util::dummy_bdd::DummyBDDNode;
 use crate::util::free_id_manager::FreeIdManager;
+use crate::util::level_swap::set_level_order;
 use crate::util::logging::console;
 use crate::util::rc_refcell::MutRcRefCell;
-use crate::util::rectangle::Rectangle;
 use crate::util::transition::Interpolatable;
-use crate::wasm_interface::NodeGroupID;
+use crate::util::vis_error::VisError;
 use crate::wasm_interface::NodeID;
-use crate::wasm_interface::StepData;
-use crate::wasm_interface::TargetID;
-use crate::wasm_interface::TargetIDType;
 use oxidd::bdd::BDDFunction;
 use oxidd::util::Borrowed;
 use oxidd::BooleanFunction;
 use oxidd::Edge;
 use oxidd::Function;
 use oxidd::InnerNode;
+use oxidd::LevelNo;
 use oxidd::{Manager, ManagerRef};
 use oxidd_core::HasApplyCache;
 use oxidd_core::HasLevel;
 use oxidd_core::Node;
 use oxidd_core::{util::DropWith, Tag};
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlCanvasElement, WebGl2RenderingContext};
+#[cfg(feature = "web")]
+use web_sys::HtmlCanvasElement;
 
-use super::super::util::drawing::diagram_layout::LayerStyle;
+use super::super::util::assignment_path::{
+    describe_path, follow_assignment, parse_assignment, retrace_path,
+};
+use super::super::util::chain_grouping::find_chains;
+use super::super::util::chain_grouping::get_chain_label;
+use super::super::util::chain_grouping::group_chains;
+use super::super::util::drawing::diagram_drawer::{
+    DiagramDrawer, DiagramGraph, DiagramStyle, GroupedDiagramGraph, LevelMover, PresenceGraph,
+    TOP_MARGIN,
+};
 use super::super::util::drawing::diagram_layout::NodeStyle;
 use super::super::util::drawing::drawer::Drawer;
 use super::super::util::drawing::layout_rules::LayoutRules;
@@ -95,6 +99,7 @@ use super::super::util::drawing::layouts::layer_positionings::brandes_kopf_posit
 use super::super::util::drawing::layouts::layer_positionings::dummy_layer_positioning::DummyLayerPositioning;
 use super::super::util::drawing::layouts::layered_layout::LayeredLayout;
 use super::super::util::drawing::layouts::layered_layout_traits::WidthLabel;
+use super::super::util::drawing::layouts::level_collapsing_layout::LevelCollapsingLayout;
 use super::super::util::drawing::layouts::random_test_layout::RandomTestLayout;
 use super::super::util::drawing::layouts::sugiyama_lib_layout::SugiyamaLibLayout;
 use super::super::util::drawing::layouts::toggle_layout::IndexedSelect;
@@ -102,17 +107,14 @@ use super::super::util::drawing::layouts::toggle_layout::ToggleLayout;
 use super::super::util::drawing::layouts::toggle_layout::ToggleLayoutUnit;
 use super::super::util::drawing::layouts::transition::transition_layout::TransitionLayout;
 use super::super::util::drawing::renderer::Renderer;
-use super::super::util::drawing::renderers::latex_renderer::LatexLayerStyle;
 use super::super::util::drawing::renderers::latex_renderer::LatexNodeStyle;
 use super::super::util::drawing::renderers::latex_renderer::LatexRenderer;
+use super::super::util::drawing::renderers::svg_renderer::SvgNodeStyle;
 use super::super::util::drawing::renderers::util::Font::Font;
-use super::super::util::drawing::renderers::webgl::edge_renderer::EdgeRenderingType;
-use super::super::util::drawing::renderers::webgl::node_renderer::NodeRenderingColorConfig;
+#[cfg(feature = "web")]
 use super::super::util::drawing::renderers::webgl_renderer::WebglNodeStyle;
+#[cfg(feature = "web")]
 use super::super::util::drawing::renderers::webgl_renderer::WebglRenderer;
-use super::super::util::graph_structure::graph_manipulators::group_presence_adjuster::GroupPresenceAdjuster;
-use super::super::util::graph_structure::graph_manipulators::label_adjusters::group_label_adjuster::GroupLabelAdjuster;
-use super::super::util::graph_structure::graph_manipulators::node_presence_adjuster::NodePresenceAdjuster;
 use super::super::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceLabel;
 use super::super::util::graph_structure::graph_manipulators::pointer_node_adjuster::PointerLabel;
 use super::super::util::graph_structure::graph_manipulators::pointer_node_adjuster::PointerNodeAdjuster;
@@ -123,54 +125,204 @@ use super::super::util::graph_structure::grouped_graph_structure::GroupedGraphSt
 use super::super::util::graph_structure::oxidd_graph_structure::NodeLabel;
 use super::super::util::graph_structure::oxidd_graph_structure::OxiddGraphStructure;
 use super::super::util::group_manager::GroupManager;
-use super::super::util::storage::state_storage::Serializable;
-use super::super::util::storage::state_storage::StateStorage;
+use super::super::util::step_trace::StepTrace;
+use super::super::util::storage::snapshot::stored;
 
 // The drawers for QDD and BDD decision diagrams
-// Note that we should eventually add reusable helper structure to reduce the perceived complexity of the entries to different diagram visualization implementations
-pub struct QDDDiagram<MR: ManagerRef>
+pub struct QDDDiagram<F: Function>
 where
-    for<'id> <<MR as oxidd::ManagerRef>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
 {
-    manager_ref: MR,
+    manager_ref: F::ManagerRef,
+    dddmp_loader: Option<fn(&mut F::ManagerRef, &str) -> QDDLoadResult<F>>,
+    buddy_loader: Option<fn(&mut F::ManagerRef, &str, Option<&str>) -> QDDLoadResult<F>>,
+    formula_loader: Option<fn(&mut F::ManagerRef, &str, Option<&str>) -> QDDLoadResult<F>>,
+    reorderer: Option<(QDDReorderer<F>, QDDReleaser<F>)>,
+    functions: Vec<F>, // The roots of all sections, from which nodes can be looked up by their ID
 }
-impl QDDDiagram<DummyBDDManagerRef> {
-    pub fn new() -> QDDDiagram<DummyBDDManagerRef> {
+/// The loaded roots together with their names, the level names, and whether the diagram is a BDD
+type QDDLoadResult<F> = Result<(Vec<(F, Vec<String>)>, Vec<String>, bool), VisError>;
+/// Reorders the levels of the given roots according to the given order, retrieving the new roots together with the node of the given diagram that each new node originates from
+type QDDReorderer<F> = fn(
+    &mut <F as Function>::ManagerRef,
+    &[(F, Vec<String>)],
+    &[LevelNo],
+) -> Result<(Vec<(F, Vec<String>)>, HashMap<NodeID, NodeID>), VisError>;
+/// Frees the nodes of roots obtained from the reorderer, once they have been replaced by another reordering
+type QDDReleaser<F> = fn(&mut <F as Function>::ManagerRef, &[(F, Vec<String>)]);
+
+impl QDDDiagram<DummyBDDFunction> {
+    pub fn new() -> QDDDiagram<DummyBDDFunction> {
         let manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
-        QDDDiagram { manager_ref }
+        QDDDiagram {
+            manager_ref,
+            dddmp_loader: Some(DummyBDDFunction::from_dddmp),
+            buddy_loader: Some(DummyBDDFunction::from_buddy),
+            formula_loader: Some(DummyBDDFunction::from_formula),
+            reorderer: Some((DummyBDDFunction::reorder, DummyBDDFunction::release)),
+            functions: Vec::new(),
+        }
+    }
+}
+
+impl<F: Function + 'static> QDDDiagram<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<String>,
+{
+    /// Creates a diagram for the functions of an existing manager. Such a diagram can not load files, its sections are instead created from functions using `create_section_from_functions`
+    pub fn from_manager(manager_ref: F::ManagerRef) -> QDDDiagram<F> {
+        QDDDiagram {
+            manager_ref,
+            dddmp_loader: None,
+            buddy_loader: None,
+            formula_loader: None,
+            reorderer: Some((reorder_in_place::<F>, release_nothing::<F>)),
+            functions: Vec::new(),
+        }
+    }
+
+    /// Creates a section showing the given functions with their names, where `levels` provides the names of the variables per level
+    pub fn create_section_from_functions(
+        &mut self,
+        roots: Vec<(F, Vec<String>)>,
+        levels: Vec<String>,
+    ) -> Box<dyn DiagramSection> {
+        let functions = roots.iter().map(|(f, _)| f.clone()).collect_vec();
+        let is_bdd = get_reachable_nodes(&functions)
+            .iter()
+            .all(|&(_, _, children)| children <= 2);
+        self.create_section(roots, is_bdd, levels)
     }
+
+    fn create_section(
+        &mut self,
+        roots: Vec<(F, Vec<String>)>,
+        is_bdd: bool,
+        levels: Vec<String>,
+    ) -> Box<dyn DiagramSection> {
+        self.functions.extend(roots.iter().map(|(f, _)| f.clone()));
+        Box::new(self.with_reordering(QDDDiagramSection::new(roots, is_bdd, levels)))
+    }
+
+    fn with_reordering(&self, section: QDDDiagramSection<F>) -> QDDDiagramSection<F> {
+        match self.reorderer {
+            Some((reorderer, releaser)) => {
+                section.with_reordering(self.manager_ref.clone(), reorderer, releaser)
+            }
+            None => section,
+        }
+    }
+}
+
+/// Swaps the levels of the manager of the given roots in place, such that the roots remain the same and every node that was part of the diagram is its own source. Other diagrams of the manager are reordered as well
+fn reorder_in_place<F: Function>(
+    manager_ref: &mut F::ManagerRef,
+    roots: &[(F, Vec<String>)],
+    order: &[LevelNo],
+) -> Result<(Vec<(F, Vec<String>)>, HashMap<NodeID, NodeID>), VisError>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+{
+    let is_permutation = order.iter().cloned().sorted().eq(0..order.len() as LevelNo);
+    let level_count = manager_ref.with_manager_shared(|manager| manager.num_levels());
+    if !is_permutation || order.len() as LevelNo > level_count {
+        return Err(VisError::unsupported(
+            "The level order has to contain every level exactly once",
+        ));
+    }
+
+    let functions = roots.iter().map(|(f, _)| f.clone()).collect_vec();
+    let old_nodes: HashSet<NodeID> = get_reachable_nodes(&functions)
+        .into_iter()
+        .map(|(id, _, _)| id)
+        .collect();
+    manager_ref
+        .with_manager_exclusive(|manager| set_level_order(manager, order))
+        .map_err(|_| VisError::unsupported("Not enough memory to reorder the diagram"))?;
+    let sources = get_reachable_nodes(&functions)
+        .into_iter()
+        .map(|(id, _, _)| id)
+        .filter(|id| old_nodes.contains(id))
+        .map(|id| (id, id))
+        .collect();
+    Ok((roots.to_vec(), sources))
 }
 
-impl Diagram for QDDDiagram<DummyBDDManagerRef> {
-    fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<Box<dyn DiagramSection>> {
-        let (roots, levels, is_bdd) = DummyBDDFunction::from_dddmp(&mut self.manager_ref, &dddmp);
-        Some(Box::new(QDDDiagramSection::new(roots, is_bdd, levels)))
+/// The roots of an in-place reordering are those of the section itself, hence nothing is freed
+fn release_nothing<F: Function>(_manager_ref: &mut F::ManagerRef, _roots: &[(F, Vec<String>)]) {}
+
+impl<F: Function + 'static> Diagram for QDDDiagram<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<String>,
+{
+    fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let Some(load) = self.dddmp_loader else {
+            return Err(VisError::unsupported(
+                "This diagram can not load dddmp files",
+            ));
+        };
+        let (roots, levels, is_bdd) = load(&mut self.manager_ref, &dddmp)?;
+        Ok(self.create_section(roots, is_bdd, levels))
     }
     // Other == Buddy
     fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn DiagramSection>> {
-        let (roots, levels, is_bdd) =
-            DummyBDDFunction::from_buddy(&mut self.manager_ref, &data, vars.as_deref());
-        Some(Box::new(QDDDiagramSection::new(roots, is_bdd, levels)))
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let Some(load) = self.buddy_loader else {
+            return Err(VisError::unsupported(
+                "This diagram can not load buddy files",
+            ));
+        };
+        let (roots, levels, is_bdd) = load(&mut self.manager_ref, &data, vars.as_deref())?;
+        Ok(self.create_section(roots, is_bdd, levels))
+    }
+    fn create_section_from_formula(
+        &mut self,
+        formula: String,
+        var_order: Option<String>,
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let Some(load) = self.formula_loader else {
+            return Err(VisError::unsupported(
+                "This diagram can not be created from formulas",
+            ));
+        };
+        let (roots, levels, is_bdd) = load(&mut self.manager_ref, &formula, var_order.as_deref())?;
+        Ok(self.create_section(roots, is_bdd, levels))
     }
     fn create_section_from_ids(
         &self,
         sources: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
-    ) -> Option<Box<dyn DiagramSection>> {
+    ) -> Result<Box<dyn DiagramSection>, VisError> {
+        let nodes: HashMap<NodeID, F> = get_reachable_nodes(&self.functions)
+            .into_iter()
+            .map(|(id, f, _)| (id, f))
+            .collect();
         let mut levels = Vec::new();
         let roots = sources
             .iter()
             .map(|&(id, section)| {
-                let root_edge = DummyBDDEdge::new(Arc::new(id), self.manager_ref.clone());
+                let f = nodes
+                    .get(&id)
+                    .cloned()
+                    .ok_or(VisError::UnknownNode { node: id })?;
                 levels = section.get_level_labels();
-                (DummyBDDFunction(root_edge), section.get_node_labels(id))
+                Ok((f, section.get_node_labels(id)))
             })
-            .collect_vec();
+            .collect::<Result<Vec<_>, VisError>>()?;
         let is_bdd = sources.iter().all(|&(_, section)| section.get_meta() == 1);
-        Some(Box::new(QDDDiagramSection::new(roots, is_bdd, levels)))
+        Ok(Box::new(self.with_reordering(QDDDiagramSection::new(
+            roots, is_bdd, levels,
+        ))))
     }
 }
 
@@ -182,13 +334,15 @@ where
     labels: HashMap<NodeID, Vec<String>>,
     levels: Vec<String>,
     is_bdd: bool,
+    trace: Option<Rc<StepTrace>>,
+    reordering: Option<(F::ManagerRef, QDDReorderer<F>, QDDReleaser<F>)>,
 }
 
 impl<F: Function> QDDDiagramSection<F>
 where
     for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
 {
-    fn new(roots: Vec<(F, Vec<String>)>, is_bdd: bool, levels: Vec<String>) -> Self {
+    pub fn new(roots: Vec<(F, Vec<String>)>, is_bdd: bool, levels: Vec<String>) -> Self {
         let s = QDDDiagramSection {
             labels: roots
                 .iter()
@@ -202,6 +356,8 @@ where
             roots,
             is_bdd,
             levels,
+            trace: None,
+            reordering: None,
         };
         console::log!(
             "init {}",
@@ -212,87 +368,124 @@ where
         );
         s
     }
-}
 
-#[derive(Clone)]
-struct QDDColors {
-    edge_true: Color,
-    edge_false: Color,
-    edge_both: Color,
-    node_true: Color,
-    node_false: Color,
-    node_group: Color,
-    node_default: Color,
-    node_text: Color,
-    node_label: Color,
-    layer_background1: Color,
-    layer_background2: Color,
-    layer_text: Color,
-    selection: TransparentColor,
-    selection_partial: TransparentColor,
-    selection_hover: TransparentColor,
-    selection_hover_partial: TransparentColor,
+    /// Allows drawers of this section to move levels, using the given functions to rebuild the diagram in the given manager, and to free the rebuilt diagrams that have been replaced
+    pub fn with_reordering(
+        mut self,
+        manager_ref: F::ManagerRef,
+        reorderer: QDDReorderer<F>,
+        releaser: QDDReleaser<F>,
+    ) -> Self {
+        self.reordering = Some((manager_ref, reorderer, releaser));
+        self
+    }
 }
-impl QDDColors {
-    const DARK: QDDColors = QDDColors {
-        edge_true: Color(0.631, 0.749, 0.423),
-        edge_false: Color(0.835, 0.341, 0.341),
-        edge_both: Color(0.6, 0.6, 0.6),
-        node_true: Color(0.631, 0.749, 0.423),
-        node_false: Color(0.835, 0.341, 0.341),
-        node_group: Color(0.45, 0.45, 0.45),
-        node_default: Color(0.35, 0.35, 0.35),
-        node_text: Color(0.0, 0.0, 0.0),
-        node_label: Color(0.5, 0.5, 1.0),
-        layer_background1: Color(0.125, 0.125, 0.125),
-        layer_background2: Color(0.1875, 0.1875, 0.1875),
-        layer_text: Color(1.0, 1.0, 1.0),
-        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
-        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
-    };
 
-    const LIGHT: QDDColors = QDDColors {
-        edge_true: Color(0.2, 1.0, 0.2),
-        edge_false: Color(1.0, 0.2, 0.2),
-        edge_both: Color(0.6, 0.6, 0.6),
-        node_true: Color(0.2, 1.0, 0.2),
-        node_false: Color(1.0, 0.2, 0.2),
-        node_group: Color(0.45, 0.45, 0.45),
-        node_default: Color(0.1, 0.1, 0.1),
-        node_text: Color(0.0, 0.0, 0.0),
-        node_label: Color(0.5, 0.5, 1.0),
-        layer_background1: Color(0.98, 0.98, 0.98),
-        layer_background2: Color(0.9, 0.9, 0.9),
-        layer_text: Color(0.0, 0.0, 0.0),
-        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
-        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
-        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
-    };
+impl<F: Function + 'static> QDDDiagramSection<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<String>,
+{
+    fn create_graph(&self) -> (BaseGraph, Option<LevelMover>) {
+        let graph = RCGraph::new(OxiddGraphStructure::<(), F, String>::new(
+            self.roots.iter().cloned().collect(),
+            self.levels.clone(),
+        ));
+
+        // Only BDDs can be rebuilt under a different order, since rebuilding would reduce the redundant nodes of QDDs
+        let level_mover = match &self.reordering {
+            Some((manager_ref, reorderer, releaser)) if self.is_bdd => {
+                let (reorder, release) = (*reorderer, *releaser);
+                let mut manager_ref = manager_ref.clone();
+                let graph = graph.clone();
+                let mut roots = self.roots.clone();
+                let mut levels = self.levels.clone();
+                // The roots of the section itself are shared with other drawers, only the rebuilt roots are owned by this mover
+                let mut reordered = false;
+                let level_mover: LevelMover = Box::new(move |level, to| {
+                    let (level, to) = (level as usize, to as usize);
+                    if level >= levels.len() || to >= levels.len() {
+                        return Err(VisError::unsupported(
+                            "Only the levels of variables can be moved",
+                        ));
+                    }
+                    let mut order = (0..levels.len() as LevelNo).collect_vec();
+                    let moved = order.remove(level);
+                    order.insert(to, moved);
+
+                    let (new_roots, sources) = reorder(&mut manager_ref, &roots, &order)?;
+                    let new_levels = order
+                        .iter()
+                        .map(|&level| levels[level as usize].clone())
+                        .collect_vec();
+                    graph
+                        .get()
+                        .replace_roots(new_roots.clone(), new_levels.clone(), &sources);
+                    let old_roots = std::mem::replace(&mut roots, new_roots);
+                    if reordered {
+                        release(&mut manager_ref, &old_roots);
+                    }
+                    reordered = true;
+                    levels = new_levels;
+                    Ok(())
+                });
+                Some(level_mover)
+            }
+            _ => None,
+        };
+        (AbstractedGraph::new(graph), level_mover)
+    }
 }
 
-impl DiagramSection for QDDDiagramSection<DummyBDDFunction> {
+impl<F: Function + 'static> DiagramSection for QDDDiagramSection<F>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = ()>,
+    for<'id> <<F as oxidd::Function>::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<String>,
+{
     fn get_level_labels(&self) -> Vec<String> {
         self.levels.clone()
     }
     fn get_node_labels(&self, node: NodeID) -> Vec<String> {
         self.labels.get(&node).cloned().unwrap_or_else(|| vec![])
     }
+    #[cfg(feature = "web")]
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
-        let graph =
-            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
-
-        let diagram = QDDDiagramDrawer::new(graph, self.is_bdd, canvas);
+        let (graph, level_mover) = self.create_graph();
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = QDDDiagramDrawer::new(
+            graph,
+            level_count,
+            level_mover,
+            self.is_bdd,
+            self.trace.clone(),
+            canvas,
+        );
+        Box::new(diagram)
+    }
+    fn create_latex_drawer(&self) -> Box<dyn DiagramSectionTextDrawer> {
+        let (graph, level_mover) = self.create_graph();
+        let level_count = self.levels.len() as LevelNo;
+        let diagram = QDDDiagramDrawer::new_latex(
+            graph,
+            level_count,
+            level_mover,
+            self.is_bdd,
+            self.trace.clone(),
+        );
         Box::new(diagram)
     }
     fn get_meta(&self) -> i128 {
         self.is_bdd as i128
     }
+    fn load_step_trace(&mut self, trace: String) -> Result<(), VisError> {
+        self.trace = Some(Rc::new(StepTrace::parse(&trace)?));
+        Ok(())
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct NodeData {
     color: Color,
     border_color: TransparentColor,
@@ -327,6 +520,7 @@ impl LatexNodeStyle for NodeData {
         self.name.clone()
     }
 }
+#[cfg(feature = "web")]
 impl WebglNodeStyle for NodeData {
     fn get_color(&self) -> Color {
         self.color.clone()
@@ -340,56 +534,37 @@ impl WebglNodeStyle for NodeData {
         self.name.clone()
     }
 }
-impl WidthLabel for NodeData {
-    fn get_width(&self) -> f32 {
-        self.width
+impl SvgNodeStyle for NodeData {
+    fn get_color(&self) -> Color {
+        self.color.clone()
     }
-}
-impl NodeStyle for NodeData {}
 
-#[derive(Clone)]
-pub struct LayerData {
-    name: String,
-}
-impl Interpolatable for LayerData {
-    fn mix(&self, other: &Self, frac: f32) -> Self {
-        LayerData {
-            name: self.name.clone(),
-        }
-    }
-}
-impl LayerStyle for LayerData {
-    fn squash(layers: Vec<Self>) -> Self {
-        LayerData {
-            name: layers.into_iter().map(|s| s.name).join(", \n"),
-        }
+    fn get_outline_color(&self) -> TransparentColor {
+        self.border_color.clone()
     }
-}
-impl WebglLayerStyle for LayerData {
-    fn get_label(&self) -> String {
+
+    fn get_label(&self) -> Option<String> {
         self.name.clone()
     }
 }
-impl LatexLayerStyle for LayerData {
-    fn get_label(&self) -> String {
-        self.name.clone()
+impl WidthLabel for NodeData {
+    fn get_width(&self) -> f32 {
+        self.width
     }
 }
+impl NodeStyle for NodeData {}
 
-type GroupedGraph =
-    GroupPresenceAdjuster<GroupLabelAdjuster<NodeData, LayerData, GroupManager<Graph>>>;
-type Graph = RCGraph<TerminalLevelAdjuster<PresenceAdjuster>>;
-type PresenceAdjuster = RCGraph<
-    NodePresenceAdjuster<
-        RCGraph<
-            EdgeToAdjuster<
-                RCGraph<ChildEdgeAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>>,
-            >,
-        >,
+type GroupedGraph = GroupedDiagramGraph<EdgeToGraph, NodeData>;
+type Graph = DiagramGraph<EdgeToGraph>;
+type PresenceAdjuster = PresenceGraph<EdgeToGraph>;
+type EdgeToGraph = RCGraph<
+    EdgeToAdjuster<
+        RCGraph<ChildEdgeAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>>,
     >,
 >;
-type BaseGraph = OxiddGraphStructure<(), DummyBDDFunction, String>;
-type Layout = TransitionLayout<ToggleLayout<Layout1, ToggleLayoutUnit<Layout2>>>;
+type BaseGraph = AbstractedGraph<(), NodeLabel<String>, String>;
+type Layout =
+    TransitionLayout<LevelCollapsingLayout<ToggleLayout<Layout1, ToggleLayoutUnit<Layout2>>>>;
 type Layout1 = LayeredLayout<
     GroupedGraph,
     SequenceOrdering<
@@ -411,119 +586,135 @@ type Layout2 = LayeredLayout<
     BrandesKopfPositioning,
 >;
 
-pub struct QDDDiagramDrawer {
-    graph: Graph,
-    group_manager: MutRcRefCell<GroupManager<Graph>>,
-    presence_adjuster: PresenceAdjuster,
-    time: MutRcRefCell<u32>,
-    drawer: MutRcRefCell<Drawer<WebglRenderer<()>, Layout, GroupedGraph>>,
-    config: Configuration<
-        LocationConfig<
-            PanelConfig<
-                CompositeConfig<(
-                    ContainerConfig<
-                        CompositeConfig<(
-                            LabelConfig<ChoiceConfig<bool>>,
-                            LabelConfig<IntConfig>,
-                            ButtonConfig,
-                            LabelConfig<ChoiceConfig<usize>>,
-                        )>,
-                    >,
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                LabelConfig<IntConfig>,
-                                LabelConfig<IntConfig>,
-                                ButtonConfig,
-                            )>,
-                        >,
-                    >,
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                LabelConfig<ChoiceConfig<PresenceRemainder>>,
-                                LabelConfig<ChoiceConfig<PresenceRemainder>>,
-                                ContainerConfig<LabelConfig<ChoiceConfig<bool>>>,
-                            )>,
-                        >,
-                    >,
-                    ContainerConfig<
-                        LabelConfig<
-                            CompositeConfig<(
-                                ButtonConfig,
-                                TextOutputConfig,
-                                LabelConfig<TextOutputConfig>,
-                            )>,
-                        >,
-                    >,
-                )>,
-            >,
+type QDDSettings = (
+    ContainerConfig<
+        CompositeConfig<(
+            LabelConfig<ChoiceConfig<bool>>,
+            LabelConfig<IntConfig>,
+            ButtonConfig,
+            LabelConfig<ChoiceConfig<usize>>,
+        )>,
+    >,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<bool>>,
+                LabelConfig<IntConfig>,
+                ButtonConfig,
+            )>,
         >,
     >,
-}
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<TextConfig>,
+                ButtonConfig,
+                ButtonConfig,
+                LabelConfig<TextOutputConfig>,
+            )>,
+        >,
+    >,
+    ContainerConfig<
+        LabelConfig<CompositeConfig<(LabelConfig<ChoiceConfig<bool>>, LabelConfig<FloatConfig>)>>,
+    >,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<PresenceRemainder>>,
+                LabelConfig<ChoiceConfig<PresenceRemainder>>,
+                ContainerConfig<LabelConfig<ChoiceConfig<bool>>>,
+            )>,
+        >,
+    >,
+    ContainerConfig<
+        LabelConfig<
+            CompositeConfig<(
+                LabelConfig<ChoiceConfig<ThemeKind>>,
+                LabelConfig<TextConfig>,
+                LabelConfig<TextOutputConfig>,
+            )>,
+        >,
+    >,
+);
 
-impl QDDDiagramDrawer {
-    pub fn new(graph: BaseGraph, is_bdd: bool, canvas: HtmlCanvasElement) -> Self {
-        let colors = &QDDColors::LIGHT;
-        let edge_rendering_type =
-            |color: Color, width: f32, dash_solid: f32, dash_transparent: f32| EdgeRenderingType {
-                select_color: color.mix_transparent(&colors.selection),
-                partial_select_color: color.mix_transparent(&colors.selection_partial),
-                hover_color: color.mix_transparent(&colors.selection_hover),
-                partial_hover_color: color.mix_transparent(&colors.selection_hover_partial),
-                color,
-                width,
-                dash_solid,
-                dash_transparent,
-            };
+pub type QDDDiagramDrawer<R> = DiagramDrawer<R, Layout, EdgeToGraph, NodeData, QDDSettings>;
+
+#[cfg(feature = "web")]
+impl QDDDiagramDrawer<WebglRenderer<()>> {
+    pub fn new(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        level_mover: Option<LevelMover>,
+        is_bdd: bool,
+        trace: Option<Rc<StepTrace>>,
+        canvas: HtmlCanvasElement,
+    ) -> Self {
+        let theme = &Theme::LIGHT;
         let font = Rc::new(Font::new(
             include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
             1.0,
         ));
         let renderer = WebglRenderer::from_canvas(
             canvas,
-            HashMap::from([
-                // True edge
-                (
-                    EdgeType::new((), 0),
-                    edge_rendering_type(
-                        colors.edge_true,
-                        0.2,
-                        1.0,
-                        0.0, // No dashing
-                    ),
-                ),
-                // False edge
-                (
-                    EdgeType::new((), 1),
-                    edge_rendering_type(colors.edge_false, 0.2, 0.3, 0.15),
-                ),
-                // Label edge
-                (
-                    EdgeType::new((), 2),
-                    edge_rendering_type(colors.edge_both, 0.15, 1.0, 0.0),
-                ),
-            ]),
-            NodeRenderingColorConfig {
-                select: colors.selection,
-                partial_select: colors.selection_partial,
-                hover: colors.selection_hover,
-                partial_hover: colors.selection_hover_partial,
-                text: colors.node_text,
-            },
-            LayerRenderingColorConfig {
-                background1: colors.layer_background1.into(),
-                background2: colors.layer_background2.into(),
-                text: colors.layer_text,
-            },
+            theme.get_edge_types(),
+            theme.get_node_colors(),
+            theme.get_layer_colors(),
             font.clone(),
         )
         .unwrap();
 
+        Self::with_renderer(
+            graph,
+            level_count,
+            level_mover,
+            is_bdd,
+            trace,
+            renderer,
+            font,
+        )
+    }
+}
+
+impl QDDDiagramDrawer<LatexRenderer<Layout>> {
+    /// Creates a drawer that does not need a canvas, from which the LaTeX code of the initial layout can be obtained
+    pub fn new_latex(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        level_mover: Option<LevelMover>,
+        is_bdd: bool,
+        trace: Option<Rc<StepTrace>>,
+    ) -> Self {
+        let font = Rc::new(Font::new(
+            include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
+            1.0,
+        ));
+        Self::with_renderer(
+            graph,
+            level_count,
+            level_mover,
+            is_bdd,
+            trace,
+            LatexRenderer::new(),
+            font,
+        )
+    }
+}
+
+impl<R: Renderer<Layout> + 'static> QDDDiagramDrawer<R> {
+    fn with_renderer(
+        graph: BaseGraph,
+        level_count: LevelNo,
+        level_mover: Option<LevelMover>,
+        is_bdd: bool,
+        trace: Option<Rc<StepTrace>>,
+        renderer: R,
+        font: Rc<Font>,
+    ) -> Self {
+        let theme = MutRcRefCell::new(Theme::LIGHT);
         let layout_opt1: Layout1 = LayeredLayout::new(
             // SugiyamaOrdering::new(2, 2),
             SequenceOrdering::new(
-                PseudoRandomLayerOrdering::new(2, 0),
+                PseudoRandomLayerOrdering::new(2),
                 SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
             ),
             // AverageGroupAlignment,
@@ -536,7 +727,7 @@ impl QDDDiagramDrawer {
         let layout_opt2: Layout2 = LayeredLayout::new(
             // SugiyamaOrdering::new(2, 2),
             SequenceOrdering::new(
-                PseudoRandomLayerOrdering::new(2, 0),
+                PseudoRandomLayerOrdering::new(2),
                 SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
             ),
             // AverageGroupAlignment,
@@ -547,9 +738,9 @@ impl QDDDiagramDrawer {
             0.1,
         );
         let layout = ToggleLayout::new(layout_opt1, ToggleLayoutUnit::new(layout_opt2));
+        let layout = LevelCollapsingLayout::new(layout, 1.0);
         let layout: Layout = TransitionLayout::new(layout);
 
-        let original_roots = graph.get_roots().clone();
         let base_graph = TerminalLevelAdjuster::new(graph); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
         let pointer_adjuster = PointerNodeAdjuster::new(
             base_graph,
@@ -559,81 +750,13 @@ impl QDDDiagramDrawer {
         );
         let child_edge_adjuster =
             RCGraph::new(ChildEdgeAdjuster::new(pointer_adjuster, move_shared_edge));
-        let edge_to_adjuster = RCGraph::new(EdgeToAdjuster::new(child_edge_adjuster.clone()));
-        let presence_adjuster: PresenceAdjuster =
-            RCGraph::new(NodePresenceAdjuster::new(edge_to_adjuster.clone()));
-        let modified_graph: Graph =
-            RCGraph::new(TerminalLevelAdjuster::new(presence_adjuster.clone()));
-        let roots = modified_graph.get_roots();
-        let group_manager = MutRcRefCell::new(GroupManager::new(modified_graph.clone()));
-
-        let mut grouped_graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new_shared(
-            group_manager.clone(),
-            move |nodes| {
-                // TODO: make this adjuster lazy, e.g. don't recompute for the same list of nodes
-                let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
-                    (
-                        Some(&PresenceLabel {
-                            original_label:
-                                PointerLabel::Node(NodeLabel {
-                                    pointers: _,
-                                    kind: NodeType::Terminal(ref terminal),
-                                }),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => {
-                        if terminal == "T" || terminal == "B" {
-                            (Some(1), false, colors.node_true)
-                        } else {
-                            (Some(0), false, colors.node_false)
-                        }
-                    }
-                    (
-                        Some(&PresenceLabel {
-                            original_label: PointerLabel::Pointer(_),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => (None, false, colors.node_label),
-                    (Some(_), None) => (None, false, colors.node_default),
-                    _ => (None, true, colors.node_group),
-                };
-                let name: Option<String> = match (nodes.get(0), nodes.get(1)) {
-                    (
-                        Some(&PresenceLabel {
-                            original_label: PointerLabel::Pointer(ref text),
-                            original_id: _,
-                        }),
-                        None,
-                    ) => Some(text.clone()),
-                    _ => None,
-                };
-
-                NodeData {
-                    color,
-                    border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
-                    width: 1.
-                        + match name {
-                            Some(ref text) => font.measure_width(&text),
-                            None => 0.,
-                        },
-                    name,
-                    is_terminal,
-                    is_group,
-                }
-            },
-            move |layer_label| LayerData {
-                name: layer_label.clone(),
-            },
-        ));
-        grouped_graph.hide(0);
+        let edge_to_adjuster: EdgeToGraph =
+            RCGraph::new(EdgeToAdjuster::new(child_edge_adjuster.clone()));
 
-        const TOP_MARGIN: f32 = 40.0;
-        let composite_config = CompositeConfig::new((
+        let settings: QDDSettings = (
             ContainerConfig::new(
                 // Only show these testing options for QDDs
-                ContainerStyle::new().hidden(is_bdd),
+                ContainerStyle::new().hidden(is_bdd).margin_top(TOP_MARGIN),
                 CompositeConfig::new((
                     LabelConfig::new(
                         "Move shared",
@@ -651,14 +774,57 @@ impl QDDDiagramDrawer {
                 )),
             ),
             ContainerConfig::new(
-                ContainerStyle::new().margin_top(if is_bdd { 0.0 } else { TOP_MARGIN }),
+                ContainerStyle::new().margin_top(TOP_MARGIN),
                 LabelConfig::new_styled(
-                    "Node expansion",
+                    "Grouping",
                     LabelKind::Category,
                     CompositeConfig::new((
-                        LabelConfig::new("Layers", IntConfig::new_min_max(4, Some(1), None)),
-                        LabelConfig::new("Max nodes", IntConfig::new_min_max(100, Some(1), None)),
-                        ButtonConfig::new_labeled("Expand initial group"),
+                        LabelConfig::new(
+                            "Group chains on load",
+                            ChoiceConfig::new([
+                                Choice::new(false, "disabled"),
+                                Choice::new(true, "enabled"),
+                            ]),
+                        ),
+                        LabelConfig::new(
+                            "Min chain length",
+                            IntConfig::new_min_max(3, Some(2), None),
+                        ),
+                        ButtonConfig::new_labeled("Group chains"),
+                    )),
+                ),
+            ),
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Assignment path",
+                    LabelKind::Category,
+                    CompositeConfig::new((
+                        LabelConfig::new("Assignment", TextConfig::new("".into())),
+                        ButtonConfig::new_labeled("Highlight path"),
+                        ButtonConfig::new_labeled("Clear highlight"),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                    )),
+                ),
+            ),
+            ContainerConfig::new(
+                ContainerStyle::new().margin_top(TOP_MARGIN),
+                LabelConfig::new_styled(
+                    "Layout",
+                    LabelKind::Category,
+                    CompositeConfig::new((
+                        LabelConfig::new(
+                            "Collapse idle levels",
+                            ChoiceConfig::new([
+                                Choice::new(false, "disabled"),
+                                Choice::new(true, "enabled"),
+                            ]),
+                        ),
+                        LabelConfig::new("Collapsed height", {
+                            let mut c = FloatConfig::new(1.0);
+                            c.set_min(Some(0.1)).commit();
+                            c
+                        }),
                     )),
                 ),
             ),
@@ -703,58 +869,192 @@ impl QDDDiagramDrawer {
             ContainerConfig::new(
                 ContainerStyle::new().margin_top(TOP_MARGIN),
                 LabelConfig::new_styled(
-                    "Latex",
+                    "Theme",
                     LabelKind::Category,
                     CompositeConfig::new((
-                        ButtonConfig::new_labeled("Generate"),
-                        TextOutputConfig::new(true),
-                        LabelConfig::new("Headers", TextOutputConfig::new(false)),
+                        LabelConfig::new(
+                            "Colors",
+                            ChoiceConfig::new([
+                                Choice::new(ThemeKind::Light, "light"),
+                                Choice::new(ThemeKind::Dark, "dark"),
+                                Choice::new(ThemeKind::HighContrast, "high contrast"),
+                                Choice::new(ThemeKind::Custom, "custom"),
+                            ]),
+                        ),
+                        LabelConfig::new(
+                            "Custom theme (JSON)",
+                            TextConfig::new_multiline(Theme::LIGHT.to_json()),
+                        ),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
                     )),
                 ),
             ),
-        ));
-        let config = Configuration::new(LocationConfig::new(
-            Location::BOTTOM_RIGHT,
-            PanelConfig::builder()
-                .set_button_icon("Settings")
-                .set_button_icon_description("Open visualization settings")
-                .set_name("Settings")
-                .set_category("visualization-settings")
-                .set_open_side(OpenSide::Right)
-                .set_open_size(0.3)
-                .build(composite_config.clone()),
-        ));
+        );
 
-        let mut out = QDDDiagramDrawer {
-            group_manager,
-            presence_adjuster,
-            graph: modified_graph,
-            time: MutRcRefCell::new(0),
-            drawer: MutRcRefCell::new(Drawer::new(
-                renderer,
-                layout,
-                MutRcRefCell::new(grouped_graph),
-            )),
-            config,
+        let chain_labels = MutRcRefCell::new(HashMap::<Vec<NodeID>, String>::new());
+        let theme_ref = theme.clone();
+        let theme_colors = theme.clone();
+        let chain_labels_ref = chain_labels.clone();
+        let style: DiagramStyle<EdgeToGraph, NodeData> = DiagramStyle {
+            node_style: Box::new(
+                move |nodes: Vec<PresenceLabel<PointerLabel<NodeLabel<String>>>>| {
+                    // TODO: make this adjuster lazy, e.g. don't recompute for the same list of nodes
+                    let colors = theme_ref.read();
+                    let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label:
+                                    PointerLabel::Node(NodeLabel {
+                                        kind: NodeType::Terminal(ref terminal),
+                                        ..
+                                    }),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => {
+                            if get_terminal_value(terminal) == Some(true) {
+                                (Some(1), false, colors.node_true)
+                            } else {
+                                (Some(0), false, colors.node_false)
+                            }
+                        }
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(_),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => (None, false, colors.node_label),
+                        (Some(_), None) => (None, false, colors.node_default),
+                        _ => (None, true, colors.node_group),
+                    };
+                    let name: Option<String> = match (nodes.get(0), nodes.get(1)) {
+                        (
+                            Some(&PresenceLabel {
+                                original_label: PointerLabel::Pointer(ref text),
+                                original_id: _,
+                            }),
+                            None,
+                        ) => Some(text.clone()),
+                        (Some(_), None) => None,
+                        // Groups of chains are labeled by the formula of the chain, other groups by their node count
+                        _ => chain_labels_ref
+                            .read()
+                            .get(
+                                &nodes
+                                    .iter()
+                                    .map(|label| label.original_id)
+                                    .sorted()
+                                    .collect_vec(),
+                            )
+                            .cloned()
+                            .or_else(|| Some(format!("{} nodes", nodes.len()))),
+                    };
+
+                    NodeData {
+                        color,
+                        border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
+                        width: 1.
+                            + match name {
+                                Some(ref text) => font.measure_width(&text),
+                                None => 0.,
+                            },
+                        name,
+                        is_terminal,
+                        is_group,
+                    }
+                },
+            ),
+            colors: Box::new(move || theme_colors.read().get_rendering_colors()),
+            legend: Box::new(Vec::new),
+            terminal: Box::new(|label: PointerLabel<NodeLabel<String>>| match label {
+                PointerLabel::Node(NodeLabel {
+                    kind: NodeType::Terminal(terminal),
+                    ..
+                }) => {
+                    let value = get_terminal_value(&terminal);
+                    Some((terminal, value))
+                }
+                _ => None,
+            }),
         };
+        let out = DiagramDrawer::from_graph(
+            edge_to_adjuster.clone(),
+            level_count,
+            trace,
+            renderer,
+            layout,
+            settings,
+            style,
+        )
+        .with_level_mover(level_mover)
+        .with_stored_settings(|settings: &QDDSettings| {
+            let (
+                qdd_config,
+                grouping,
+                assignment_path,
+                layout_settings,
+                terminal_config,
+                theme_config,
+            ) = settings;
+            let (move_shared, seed, _change_seed, layout_config) = &***qdd_config;
+            let (group_chains_on_load, min_chain_length, _group_chains_now) = &****grouping;
+            let (assignment, _highlight_path, _clear_highlight, _path_output) =
+                &****assignment_path;
+            let (collapse_levels, collapsed_height) = &****layout_settings;
+            let (false_visibility, true_visibility, hide_shared_true) = &****terminal_config;
+            let (theme_kind, theme_json, _theme_error) = &****theme_config;
+            vec![
+                ("move_shared", stored(&**move_shared)),
+                ("seed", stored(&**seed)),
+                ("layout", stored(&**layout_config)),
+                ("group_chains_on_load", stored(&**group_chains_on_load)),
+                ("min_chain_length", stored(&**min_chain_length)),
+                ("assignment", stored(&**assignment)),
+                ("collapse_levels", stored(&**collapse_levels)),
+                ("collapsed_height", stored(&**collapsed_height)),
+                ("false_visibility", stored(&**false_visibility)),
+                ("true_visibility", stored(&**true_visibility)),
+                ("hide_shared_true", stored(&***hide_shared_true)),
+                ("theme", stored(&**theme_kind)),
+                ("theme_json", stored(&**theme_json)),
+            ]
+            .into_iter()
+            .map(|(name, setting)| (name.to_string(), setting))
+            .collect()
+        });
 
-        let (qdd_config, expansion, terminal_config, latex_config) = &*composite_config;
+        let (qdd_config, grouping, assignment_path, layout_settings, terminal_config, theme_config) =
+            out.get_diagram_settings();
         let (move_shared, seed, change_seed, layout_config) = &***qdd_config;
-        let (_max_expand_layers, _max_expand_nodes, expand_all) = &****expansion;
+        let (group_chains_on_load, min_chain_length, group_chains_now) = &****grouping;
+        let (assignment, highlight_path, clear_highlight, path_output) = &****assignment_path;
         let (false_visibility, true_visibility, hide_shared_true) = &****terminal_config;
-        let (latex_generate, latex_output, latex_header_output) = &****latex_config;
+        let (theme_kind, theme_json, theme_error) = &****theme_config;
+        let (collapse_levels, collapsed_height) = &****layout_settings;
 
-        let drawer = out.drawer.clone();
+        let drawer = out.get_drawer().clone();
         let layout_config_copy = layout_config.clone();
         let _ = on_configuration_change(&*layout_config, move || {
             drawer
                 .get()
                 .get_layout_rules()
                 .get_layout_rules()
+                .get_layout_rules()
                 .select_layout(layout_config_copy.get());
         });
 
-        let drawer = out.drawer.clone();
+        let drawer = out.get_drawer().clone();
+        let collapse_levels_copy = collapse_levels.clone();
+        let collapsed_height_copy = collapsed_height.clone();
+        let _ = on_configuration_change(&*layout_settings, move || {
+            let mut drawer = drawer.get();
+            let collapsing_layout = drawer.get_layout_rules().get_layout_rules();
+            collapsing_layout.set_enabled(collapse_levels_copy.get());
+            collapsing_layout.set_collapsed_height(collapsed_height_copy.get());
+        });
+
+        let drawer = out.get_drawer().clone();
         let mut seed_copy = seed.clone();
         change_seed.clone().add_press_listener(move || {
             let new_seed = seed_copy.get() + 1;
@@ -763,55 +1063,96 @@ impl QDDDiagramDrawer {
 
         let seed_copy = seed.clone();
         let _ = on_configuration_change(&*seed, move || {
-            let mut drawer = drawer.get();
-            let p = drawer.get_layout_rules().get_layout_rules();
-            p.get_layout_rules1()
-                .get_ordering()
-                .get_ordering1()
-                .set_seed(seed_copy.get() as usize);
-            p.get_layout_rules2()
-                .get_layout_rules()
-                .get_ordering()
-                .get_ordering1()
-                .set_seed(seed_copy.get() as usize);
+            // The seed applies to the random choices of every layout, not only to the pseudo random orderings
+            drawer.get().set_seed(seed_copy.get() as u64);
         });
 
-        let drawer = out.drawer.clone();
-        let mut latex_renderer = LatexRenderer::<Layout>::new();
-        let mut output = latex_output.clone();
-        latex_generate.clone().add_press_listener(move || {
-            latex_renderer.update_layout(&drawer.get().get_current_layout());
-            latex_renderer.render(u32::MAX);
-            let out = latex_renderer.get_output();
-            output.set(out.into()).commit();
+        let chain_edge_to_adjuster = edge_to_adjuster.clone();
+        let chain_graph = out.get_graph().clone();
+        let chain_group_manager = out.get_group_manager().clone();
+        let chain_labels_copy = chain_labels.clone();
+        let min_chain_length_copy = min_chain_length.clone();
+        group_chains_now.clone().add_press_listener(move || {
+            group_conjunction_chains(
+                &chain_edge_to_adjuster,
+                &chain_graph,
+                &chain_group_manager,
+                &chain_labels_copy,
+                min_chain_length_copy.get().unsigned_abs(),
+            )
         });
-        latex_header_output
-            .clone()
-            .set(latex_headers.to_string())
-            .commit();
-
-        let from = out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
-        // let from = 0;
-        for root in roots {
-            out.create_group(vec![TargetID(TargetIDType::NodeID, root)]);
-        }
 
-        let max = 500;
-        if out.group_manager.read().get_nodes_of_group(from).len() < max {
-            reveal_all(&out.group_manager, from, max);
+        if group_chains_on_load.get() {
+            group_conjunction_chains(
+                &edge_to_adjuster,
+                out.get_graph(),
+                out.get_group_manager(),
+                &chain_labels,
+                min_chain_length.get().unsigned_abs(),
+            );
         }
 
-        let group_manager = out.group_manager.clone();
-        expand_all
-            .clone()
-            .add_press_listener(move || reveal_all(&group_manager, from, 10_000_000));
+        // Restoring a stored state enables the option after the drawer has been created, hence we also group once it gets enabled later on
+        let chain_edge_to_adjuster = edge_to_adjuster.clone();
+        let chain_graph = out.get_graph().clone();
+        let chain_group_manager = out.get_group_manager().clone();
+        let group_chains_on_load_copy = group_chains_on_load.clone();
+        let min_chain_length_copy = min_chain_length.clone();
+        let _ = after_configuration_change(&*group_chains_on_load, move || {
+            if group_chains_on_load_copy.get() {
+                group_conjunction_chains(
+                    &chain_edge_to_adjuster,
+                    &chain_graph,
+                    &chain_group_manager,
+                    &chain_labels,
+                    min_chain_length_copy.get().unsigned_abs(),
+                )
+            }
+        });
+
+        let path_drawer = out.get_drawer().clone();
+        let path_graph = out.get_graph().clone();
+        let path_edge_to_adjuster = edge_to_adjuster.clone();
+        let assignment_copy = assignment.clone();
+        let path_output_copy = path_output.clone();
+        highlight_path.clone().add_press_listener(move || {
+            let mut output = path_output_copy.clone();
+            match highlight_assignment(
+                &path_drawer,
+                &path_graph,
+                &path_edge_to_adjuster,
+                level_count,
+                &assignment_copy.get(),
+            ) {
+                Ok((terminal, description)) => {
+                    output
+                        .set_label(&format!("Reached terminal: {}", terminal))
+                        .commit();
+                    (*output).clone().set(description).commit();
+                }
+                Err(err) => {
+                    path_drawer.get().highlight_path(None);
+                    output
+                        .set_label(&format!("Invalid assignment: {}", err))
+                        .commit();
+                    (*output).clone().reset().commit();
+                }
+            }
+        });
+
+        let path_drawer = out.get_drawer().clone();
+        let path_output_copy = path_output.clone();
+        clear_highlight.clone().add_press_listener(move || {
+            path_drawer.get().highlight_path(None);
+            let mut output = path_output_copy.clone();
+            output.set_label("").commit();
+            (*output).clone().reset().commit();
+        });
 
         // Connect the config
-        let drawer = out.drawer.clone();
-        let time = out.time.clone();
-        fn set_terminal_presence<const P: usize>(
+        fn set_terminal_presence(
             presence_adjuster: &PresenceAdjuster,
-            target_terminals: [String; P],
+            value: bool,
             presence: PresenceRemainder,
         ) -> () {
             let mut adjuster = presence_adjuster.get();
@@ -819,9 +1160,9 @@ impl QDDDiagramDrawer {
             let mut terminals = terminals.iter().filter_map(|&node| {
                 match adjuster.get_node_label(node).original_label {
                     PointerLabel::Node(NodeLabel {
-                        pointers: _,
                         kind: NodeType::Terminal(t),
-                    }) if target_terminals.iter().any(|terminal| &t == terminal) => Some(node),
+                        ..
+                    }) if get_terminal_value(&t) == Some(value) => Some(node),
                     _ => None,
                 }
             });
@@ -832,23 +1173,15 @@ impl QDDDiagramDrawer {
             adjuster.set_node_presence(target_terminal, PresenceGroups::remainder(presence));
         }
 
-        let false_presence_adjuster = out.presence_adjuster.clone();
+        let false_presence_adjuster = out.get_presence_adjuster().clone();
         let false_visibility_copy = false_visibility.clone();
         let _ = on_configuration_change(&*false_visibility, move || {
-            set_terminal_presence(
-                &false_presence_adjuster,
-                ["F".into(), "E".into()],
-                false_visibility_copy.get(),
-            );
+            set_terminal_presence(&false_presence_adjuster, false, false_visibility_copy.get());
         });
-        let true_presence_adjuster = out.presence_adjuster.clone();
+        let true_presence_adjuster = out.get_presence_adjuster().clone();
         let true_visibility_copy = true_visibility.clone();
         let _ = on_configuration_change(&*true_visibility, move || {
-            set_terminal_presence(
-                &true_presence_adjuster,
-                ["T".into(), "B".into()],
-                true_visibility_copy.get(),
-            );
+            set_terminal_presence(&true_presence_adjuster, true, true_visibility_copy.get());
         });
 
         let hide_shared_true_copy = hide_shared_true.clone();
@@ -859,9 +1192,11 @@ impl QDDDiagramDrawer {
                     .into_iter()
                     .filter_map(|node| match edge_to_adjuster.get_node_label(node) {
                         PointerLabel::Node(NodeLabel {
-                            pointers: _,
                             kind: NodeType::Terminal(t),
-                        }) if t == "T" => Some((node, EdgeType::new((), 2))),
+                            ..
+                        }) if get_terminal_value(&t) == Some(true) => {
+                            Some((node, EdgeType::new((), 2)))
+                        }
                         _ => None,
                     })
                     .collect::<Vec<_>>()
@@ -874,6 +1209,34 @@ impl QDDDiagramDrawer {
             }
         });
 
+        let drawer = out.get_drawer().clone();
+        let theme_kind_copy = theme_kind.clone();
+        let theme_json_copy = theme_json.clone();
+        let theme_error_copy = theme_error.clone();
+        let apply_theme = Rc::new(move || {
+            let mut output = theme_error_copy.clone();
+            match theme_kind_copy.get().get_theme(&theme_json_copy.get()) {
+                Ok(new_theme) => {
+                    // The node colors are obtained from the theme in the next layout, and transition from the old colors
+                    drawer
+                        .get()
+                        .get_renderer_mut()
+                        .set_colors(new_theme.get_rendering_colors());
+                    *theme.get() = new_theme;
+                    output.set_label("").commit();
+                }
+                Err(err) => {
+                    output
+                        .set_label(&format!("Invalid theme: {}", err))
+                        .commit();
+                }
+            }
+        });
+        // The error output is part of the theme settings, hence only the inputs are observed
+        let apply_kind = apply_theme.clone();
+        let _ = on_configuration_change(&**theme_kind, move || apply_kind());
+        let _ = on_configuration_change(&**theme_json, move || apply_theme());
+
         let move_shared_copy = move_shared.clone();
         let _ = on_configuration_change(&*move_shared, move || {
             child_edge_adjuster
@@ -881,107 +1244,109 @@ impl QDDDiagramDrawer {
                 .set_enabled(move_shared_copy.get());
         });
 
-        let _ = after_configuration_change(&composite_config, move || {
-            drawer.get().layout(*time.get());
-        });
-
         out
     }
 }
 
-fn reveal_all<G: GraphStructure>(
-    group_manager: &MutRcRefCell<GroupManager<G>>,
-    from_id: NodeGroupID,
-    limit: usize,
-) {
-    let nodes = {
-        let mut gm = group_manager.get();
-        if !gm.get_groups().contains_key(&from_id) {
-            return;
-        }
-        let explored_group = gm.create_group(vec![TargetID(TargetIDType::NodeGroupID, from_id)]);
-        gm.get_nodes_of_group(explored_group)
-    };
-    let mut count = 0;
-    let mut group_manager = group_manager.get();
-    for node_id in nodes.into_iter().rev() {
-        // console::log!("{node_id}");
-        group_manager.create_group(vec![TargetID(TargetIDType::NodeID, node_id)]);
-
-        count = count + 1;
-        if limit > 0 && count >= limit {
-            break;
-        }
+/// Retrieves the truth value of a terminal label, or `None` if it is not a boolean terminal. Diagrams name their terminals "T" and "F", while "B" and "E" are accepted as the true and false terminals of older exports
+fn get_terminal_value(terminal: &str) -> Option<bool> {
+    match terminal {
+        "T" | "B" => Some(true),
+        "F" | "E" => Some(false),
+        _ => None,
     }
 }
 
-impl DiagramSectionDrawer for QDDDiagramDrawer {
-    fn render(&mut self, time: u32) -> () {
-        *self.time.get() = time;
-        self.drawer.get().render(time);
-    }
-
-    fn layout(&mut self, time: u32) -> () {
-        self.drawer.get().layout(time);
-    }
-
-    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
-        self.drawer.get().set_transform(width, height, x, y, scale);
-    }
-
-    fn set_step(&mut self, step: i32) -> Option<StepData> {
-        todo!()
-    }
-
-    fn set_group(&mut self, from: Vec<TargetID>, to: NodeGroupID) -> bool {
-        self.group_manager.get().set_group(from, to)
-    }
-
-    fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
-        // self.config.
-        self.group_manager.get().create_group(from)
-    }
-
-    fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
-        let (_qdd_config, expansion, _terminal_config, _latex_config) = &****self.config;
-        let (max_expand_layers, max_expand_nodes, _expand_all) = &****expansion;
-        self.group_manager.get().split_edges(
-            nodes,
-            max_expand_layers.get().unsigned_abs(),
-            max_expand_nodes.get().unsigned_abs(),
-        );
-    }
-
-    fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
-        self.drawer.read().get_nodes(area, max_group_expansion)
+/// Groups the conjunction and disjunction chains of at least the given length, and stores the formula of each chain as the label of its group
+fn group_conjunction_chains(
+    edge_to_adjuster: &EdgeToGraph,
+    graph: &Graph,
+    group_manager: &MutRcRefCell<GroupManager<Graph>>,
+    chain_labels: &MutRcRefCell<HashMap<Vec<NodeID>, String>>,
+    min_length: usize,
+) {
+    // Chains are detected before terminals are hidden or duplicated, such that all edges to the terminals are known
+    let mut edge_to_adjuster = edge_to_adjuster.clone();
+    let chains = find_chains(&mut edge_to_adjuster, min_length);
+    for chain in &chains {
+        let terminal_true = match edge_to_adjuster.get_node_label(chain.terminal) {
+            PointerLabel::Node(NodeLabel {
+                kind: NodeType::Terminal(t),
+                ..
+            }) => get_terminal_value(&t),
+            _ => None,
+        };
+        let Some(terminal_true) = terminal_true else {
+            continue;
+        };
+        let label = get_chain_label(&mut edge_to_adjuster, chain, terminal_true);
+        let nodes = chain
+            .nodes
+            .iter()
+            .map(|&(node, _)| node)
+            .sorted()
+            .collect_vec();
+        chain_labels.get().insert(nodes, label);
     }
 
-    fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
-        self.drawer.get().select_nodes(selected_ids, hovered_ids);
-    }
+    group_chains(&mut group_manager.get(), &chains, |node| {
+        graph
+            .source_nodes_to_local(edge_to_adjuster.local_nodes_to_sources(vec![node]))
+            .into_iter()
+            .filter(|&local| graph.get_node_label(local).original_id == node)
+            .collect()
+    });
+}
 
-    fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
-        self.graph
-            .local_nodes_to_sources(nodes.iter().cloned().collect())
-    }
+/// Highlights the path that the assignment takes from the first selected node, or from the root if no node is selected and the diagram has a single root. Retrieves the reached terminal, together with the tested variables along the path
+fn highlight_assignment<R: Renderer<Layout>>(
+    drawer: &MutRcRefCell<Drawer<R, Layout, GroupedGraph>>,
+    graph: &Graph,
+    edge_to_adjuster: &EdgeToGraph,
+    level_count: LevelNo,
+    assignment: &str,
+) -> Result<(String, String), VisError> {
+    let assignment = parse_assignment(assignment)?;
+    let mut graph = graph.clone();
+    let root = match drawer.read().get_selected_nodes().first() {
+        Some(&node) => node,
+        None => match &graph.get_roots()[..] {
+            &[root] => root,
+            _ => {
+                return Err(VisError::unsupported(
+                    "Select the node to start the path from",
+                ))
+            }
+        },
+    };
 
-    fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID> {
-        self.graph
-            .source_nodes_to_local(nodes.iter().cloned().collect())
-    }
-    fn serialize_state(&self) -> Vec<u8> {
-        let mut out = Vec::new();
-        let _ = self.group_manager.read().write(&mut Cursor::new(&mut out));
-        out
-    }
+    // The path is followed before terminals are hidden or duplicated, such that the edges to all terminals are known
+    let mut edge_to_adjuster = edge_to_adjuster.clone();
+    let path = follow_assignment(
+        &mut edge_to_adjuster,
+        graph.get_node_label(root).original_id,
+        level_count,
+        &assignment,
+    )?;
+    let terminal = match path
+        .nodes
+        .last()
+        .map(|&end| edge_to_adjuster.get_node_label(end))
+    {
+        Some(PointerLabel::Node(NodeLabel {
+            kind: NodeType::Terminal(terminal),
+            ..
+        })) => terminal,
+        _ => return Err(VisError::unsupported("The path does not reach a terminal")),
+    };
 
-    fn deserialize_state(&mut self, state: Vec<u8>) -> () {
-        let _ = self.group_manager.get().read(&mut Cursor::new(&state));
-    }
+    let shown_path = retrace_path(&mut graph, root, &path, |graph, node| {
+        graph.get_node_label(node).original_id
+    });
+    drawer.get().highlight_path(Some(shown_path));
 
-    fn get_configuration(&self) -> AbstractConfigurationObject {
-        self.config.get_abstract()
-    }
+    let description = describe_path(&path, &terminal);
+    Ok((terminal, description))
 }
 
 fn move_shared_edge<T: DrawTag + 'static>(
@@ -994,8 +1359,8 @@ fn move_shared_edge<T: DrawTag + 'static>(
         .into_iter()
         .map(|(edge, to, label)| {
             if let PointerLabel::Node(NodeLabel {
-                pointers: _,
                 kind: NodeType::Terminal(t),
+                ..
             }) = label
             {
                 (t.clone(), (edge, to))
@@ -1025,3 +1390,40 @@ fn move_shared_edge<T: DrawTag + 'static>(
     ]);
     // return Some(vec![]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::util::storage::state_model::SettingValue;
+
+    #[test]
+    fn layouts_are_reproducible() {
+        // Lays out the diagram in a new drawer, using the seed stored in its state
+        let lay_out = |seed: i64| {
+            let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+            let (roots, levels, is_bdd) = DummyBDDFunction::from_formula(
+                &mut manager_ref,
+                "f = (a & b) | (c ^ d); g = a -> d",
+                None,
+            )
+            .unwrap();
+            let level_count = levels.len() as LevelNo;
+            let section = QDDDiagramSection::new(roots, is_bdd, levels);
+            let (graph, level_mover) = section.create_graph();
+            let mut drawer =
+                QDDDiagramDrawer::new_latex(graph, level_count, level_mover, is_bdd, None);
+            let mut state = drawer.get_state();
+            state
+                .settings
+                .insert("seed".to_string(), SettingValue::Int(seed));
+            drawer.set_state(state).unwrap();
+            drawer.layout(0);
+            drawer
+        };
+        let first = lay_out(3).get_drawer().read().get_current_layout();
+        let second = lay_out(3).get_drawer().read().get_current_layout();
+        assert!(!first.groups.is_empty());
+        assert!(!first.layers.is_empty());
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    util::{parse_error::ParseError, vis_error::VisError},
+    wasm_interface::NodeID,
+};
+
+use super::{
+    drawing::drawer::NodePath,
+    graph_structure::graph_structure::{DrawTag, EdgeType, GraphStructure},
+};
+
+/// The path that a variable assignment takes through a diagram, from a root down to a node without children
+pub struct AssignmentPath<T: DrawTag> {
+    pub nodes: Vec<NodeID>,
+    pub edges: Vec<(NodeID, NodeID, EdgeType<T>)>,
+    /// Whether an odd number of the edges on the path is complemented
+    pub complemented: bool,
+    /// The variables that are tested by the nodes on the path from the top downwards, together with the value that the path followed
+    pub decided: Vec<(String, bool)>,
+}
+
+/// Parses an assignment such as `x1=1, x3=0`, where the values may also be written as `true` and `false`, and assignments may also be separated by semicolons or newlines
+pub fn parse_assignment(text: &str) -> Result<HashMap<String, bool>, ParseError> {
+    let mut assignment = HashMap::new();
+    for part in text.split(|c| c == ',' || c == ';' || c == '\n') {
+        if part.trim().is_empty() {
+            continue;
+        }
+        let offset = part.as_ptr() as usize - text.as_ptr() as usize;
+        let Some(equals) = part.find('=') else {
+            return Err(ParseError::at_offset(
+                text,
+                offset,
+                format!(
+                    "Expected an assignment of the form x=1, found \"{}\"",
+                    part.trim()
+                ),
+            ));
+        };
+        let (variable, value) = (part[..equals].trim(), &part[equals + 1..]);
+        if variable.is_empty() {
+            return Err(ParseError::at_offset(text, offset, "Missing variable name"));
+        }
+        let value = match value.trim() {
+            "1" | "true" => true,
+            "0" | "false" => false,
+            other => {
+                return Err(ParseError::at_offset(
+                    text,
+                    offset + equals + 1,
+                    format!(
+                        "Expected 0 or 1 as the value of {}, found \"{}\"",
+                        variable, other
+                    ),
+                ))
+            }
+        };
+        if assignment.insert(variable.to_string(), value) == Some(!value) {
+            return Err(ParseError::at_offset(
+                text,
+                offset,
+                format!("Conflicting values are assigned to {}", variable),
+            ));
+        }
+    }
+    Ok(assignment)
+}
+
+/// Follows the edges that the assignment selects from the given root, until a node without children is reached. Nodes with a then (0) and else (1) edge take the edge of the value of the variable of their level, and nodes with a single child, such as the pointers to the roots, are passed through. Variables of the assignment that are not among the labels of the `level_count` levels of the graph are reported as an error
+pub fn follow_assignment<G: GraphStructure>(
+    graph: &mut G,
+    root: NodeID,
+    level_count: LevelNo,
+    assignment: &HashMap<String, bool>,
+) -> Result<AssignmentPath<G::T>, VisError>
+where
+    G::LL: Display,
+{
+    let variables: HashSet<String> = (0..level_count)
+        .map(|level| format!("{}", graph.get_level_label(level)))
+        .collect();
+    let unknown = assignment
+        .keys()
+        .filter(|variable| !variables.contains(*variable))
+        .sorted()
+        .cloned()
+        .collect_vec();
+    if !unknown.is_empty() {
+        return Err(VisError::UnknownVariables { names: unknown });
+    }
+
+    let mut path = AssignmentPath {
+        nodes: vec![root],
+        edges: Vec::new(),
+        complemented: false,
+        decided: Vec::new(),
+    };
+    let mut node = root;
+    loop {
+        let children = graph
+            .get_children(node)
+            .into_iter()
+            .sorted_by_key(|(edge_type, _)| edge_type.index)
+            .collect_vec();
+        let (edge_type, child) = match &children[..] {
+            [] => return Ok(path),
+            &[edge] => edge,
+            &[(then_type, then_child), (else_type, else_child)]
+                if then_type.index == 0 && else_type.index == 1 =>
+            {
+                let level = graph.get_level(node);
+                let variable = format!("{}", graph.get_level_label(level));
+                let Some(&value) = assignment.get(&variable) else {
+                    return Err(VisError::UnassignedVariable { name: variable });
+                };
+                path.decided.push((variable, value));
+                if value {
+                    (then_type, then_child)
+                } else {
+                    (else_type, else_child)
+                }
+            }
+            _ => {
+                return Err(VisError::unsupported(
+                    "Only nodes with a then and an else edge can be followed",
+                ))
+            }
+        };
+        path.complemented ^= edge_type.tag.is_complemented();
+        path.edges.push((node, child, edge_type));
+        path.nodes.push(child);
+        node = child;
+    }
+}
+
+/// Retraces the path in a graph that shows the nodes of the graph in which the path was followed, starting from the shown node `root`, where `get_original` retrieves the node that a shown node represents. The retraced path ends early if it continues into a node that is not shown
+pub fn retrace_path<G: GraphStructure>(
+    graph: &mut G,
+    root: NodeID,
+    path: &AssignmentPath<G::T>,
+    get_original: impl Fn(&G, NodeID) -> NodeID,
+) -> NodePath<G::T> {
+    let mut nodes = vec![root];
+    let mut edges = Vec::new();
+    let mut current = root;
+    for &(_, to, edge_type) in &path.edges {
+        let children = graph.get_children(current);
+        let Some(&(_, child)) = children.iter().find(|&&(local_type, child)| {
+            local_type == edge_type && get_original(graph, child) == to
+        }) else {
+            break;
+        };
+        edges.push((current, child, edge_type));
+        nodes.push(child);
+        current = child;
+    }
+    (nodes, edges)
+}
+
+/// Describes the path as the sequence of the tested variables with their values, followed by the given description of the reached terminal
+pub fn describe_path<T: DrawTag>(path: &AssignmentPath<T>, terminal: &str) -> String {
+    path.decided
+        .iter()
+        .map(|(variable, value)| format!("{}={}", variable, *value as u8))
+        .chain([terminal.to_string()])
+        .join(" → ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::util::graph_structure::oxidd_graph_structure::{
+        NodeType, OxiddGraphStructure,
+    };
+    use crate::util::dummy_bcdd::{DummyBCDDFunction, DummyBCDDManager, DummyBCDDManagerRef};
+    use oxidd_rules_bdd::complement_edge::EdgeTag;
+
+    /// The BCDD of `a <-> b`, whose else edges are complemented
+    const EQUIVALENCE: &str = "\
+.ver DDDMP-2.0
+.mode A
+.varinfo 0
+.dd f
+.nnodes 3
+.nvars 2
+.nsuppvars 2
+.suppvarnames a b
+.orderedvarnames a b
+.ids 0 1
+.permids 0 1
+.nroots 1
+.rootids 3
+.nodes
+1 T 1 0 0
+2 b 1 1 -1
+3 a 0 2 -2
+.end
+";
+
+    fn create_graph() -> (
+        OxiddGraphStructure<EdgeTag, DummyBCDDFunction, String>,
+        NodeID,
+    ) {
+        let mut manager_ref = DummyBCDDManagerRef::from(&DummyBCDDManager::new());
+        let (roots, levels) = DummyBCDDFunction::from_dddmp(&mut manager_ref, EQUIVALENCE).unwrap();
+        let mut graph = OxiddGraphStructure::new(roots, levels);
+        let root = graph.get_roots()[0];
+        (graph, root)
+    }
+
+    /// Retrieves the terminal reached by the assignment, together with whether the path is complemented
+    fn evaluate(text: &str) -> Result<(String, bool), VisError> {
+        let (mut graph, root) = create_graph();
+        let path = follow_assignment(&mut graph, root, 2, &parse_assignment(text)?)?;
+        match graph.get_node_label(*path.nodes.last().unwrap()).kind {
+            NodeType::Terminal(terminal) => Ok((terminal, path.complemented)),
+            NodeType::Inner(_) => panic!("the path should end in a terminal"),
+        }
+    }
+
+    #[test]
+    fn parse_assignments() {
+        let assignment = parse_assignment("a=1, b = false;\nc=0").unwrap();
+        assert_eq!(
+            assignment,
+            HashMap::from([
+                ("a".to_string(), true),
+                ("b".to_string(), false),
+                ("c".to_string(), false)
+            ])
+        );
+        assert_eq!(
+            parse_assignment("a=1, b").err(),
+            Some(ParseError::at_offset(
+                "a=1, b",
+                4,
+                "Expected an assignment of the form x=1, found \"b\""
+            ))
+        );
+        assert!(parse_assignment("a=1, a=0").is_err());
+        assert!(parse_assignment("a=2").is_err());
+    }
+
+    #[test]
+    fn complement_parity() {
+        // An even number of complemented edges reaches the true terminal itself
+        assert_eq!(evaluate("a=0, b=0").unwrap(), ("T".to_string(), false));
+        assert_eq!(evaluate("a=1, b=1").unwrap(), ("T".to_string(), false));
+        assert_eq!(evaluate("a=1, b=0").unwrap(), ("T".to_string(), true));
+        assert_eq!(evaluate("a=0, b=1").unwrap(), ("T".to_string(), true));
+    }
+
+    #[test]
+    fn unknown_and_missing_variables() {
+        assert_eq!(
+            evaluate("a=1, b=1, x=0, c=1").err(),
+            Some(VisError::UnknownVariables {
+                names: vec!["c".to_string(), "x".to_string()]
+            })
+        );
+        assert_eq!(
+            evaluate("a=1").err(),
+            Some(VisError::UnassignedVariable {
+                name: "b".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+
+use crate::wasm_interface::{NodeGroupID, NodeID, TargetID, TargetIDType};
+
+use super::{graph_structure::graph_structure::GraphStructure, group_manager::GroupManager};
+
+/// A chain of nodes in which every node has an edge straight to the same terminal, as occurs for conjunctions (edges to false) and disjunctions (edges to true) of literals
+pub struct NodeChain {
+    /// The nodes from the top of the chain downwards, together with whether the first (then) edge of the node is the edge that goes to the terminal
+    pub nodes: Vec<(NodeID, bool)>,
+    pub terminal: NodeID,
+}
+
+/// The possible ways for a node to be part of a chain: the terminal it goes to, whether its then edge goes to the terminal, and the node the chain continues with
+type ChainOption = (NodeID, bool, NodeID);
+
+/// Finds the maximal chains of at least `min_length` nodes in the part of the graph reachable from its roots, where every node is part of at most one chain
+pub fn find_chains<G: GraphStructure>(graph: &mut G, min_length: usize) -> Vec<NodeChain> {
+    let terminals: HashSet<NodeID> = graph.get_terminals().into_iter().collect();
+
+    // Explore the whole graph first, such that the known parents of all nodes are complete
+    let mut options = HashMap::<NodeID, Vec<ChainOption>>::new();
+    let mut order = Vec::new();
+    let mut found: HashSet<NodeID> = graph.get_roots().into_iter().collect();
+    let mut queue: VecDeque<NodeID> = found.iter().cloned().sorted().collect();
+    while let Some(node) = queue.pop_front() {
+        let children = graph
+            .get_children(node)
+            .into_iter()
+            .sorted_by_key(|(edge_type, _)| edge_type.index)
+            .collect_vec();
+        for &(_, child) in &children {
+            if found.insert(child) {
+                queue.push_back(child);
+            }
+        }
+
+        let node_options = match &children[..] {
+            &[(then_type, then_child), (else_type, else_child)]
+                if then_type.index == 0 && else_type.index == 1 =>
+            {
+                let mut node_options = Vec::new();
+                if terminals.contains(&else_child) {
+                    node_options.push((else_child, false, then_child));
+                }
+                if terminals.contains(&then_child) {
+                    node_options.push((then_child, true, else_child));
+                }
+                node_options
+            }
+            _ => Vec::new(),
+        };
+        if !node_options.is_empty() {
+            options.insert(node, node_options);
+            order.push(node);
+        }
+    }
+
+    let parent_counts: HashMap<NodeID, usize> = order
+        .iter()
+        .map(|&node| (node, graph.get_known_parents(node).len()))
+        .collect();
+    let get_next = |node: NodeID, terminal: NodeID| -> Option<(NodeID, bool)> {
+        let &(_, _, continuation) = options
+            .get(&node)?
+            .iter()
+            .find(|&&(t, _, _)| t == terminal)?;
+        if parent_counts.get(&continuation) != Some(&1) {
+            return None;
+        }
+        options
+            .get(&continuation)?
+            .iter()
+            .find(|&&(t, _, _)| t == terminal)
+            .map(|&(_, to_terminal, _)| (continuation, to_terminal))
+    };
+    let continued: HashSet<(NodeID, NodeID)> = order
+        .iter()
+        .flat_map(|&node| {
+            options[&node]
+                .iter()
+                .filter_map(|&(terminal, _, _)| {
+                    get_next(node, terminal).map(|(next, _)| (next, terminal))
+                })
+                .collect_vec()
+        })
+        .collect();
+
+    let mut chained = HashSet::<NodeID>::new();
+    let mut chains = Vec::new();
+    for &node in &order {
+        for &(terminal, to_terminal, _) in &options[&node] {
+            if chained.contains(&node) || continued.contains(&(node, terminal)) {
+                continue;
+            }
+
+            let mut nodes = vec![(node, to_terminal)];
+            let mut current = node;
+            while let Some((next, to_terminal)) = get_next(current, terminal) {
+                if chained.contains(&next) {
+                    break;
+                }
+                nodes.push((next, to_terminal));
+                current = next;
+            }
+            if nodes.len() >= min_length {
+                chained.extend(nodes.iter().map(|&(node, _)| node));
+                chains.push(NodeChain { nodes, terminal });
+            }
+        }
+    }
+    chains
+}
+
+/// Describes the chain as a formula over the labels of its levels, given whether the terminal of the chain is the true terminal. Long chains only show their first and last literals
+pub fn get_chain_label<G: GraphStructure>(
+    graph: &mut G,
+    chain: &NodeChain,
+    terminal_true: bool,
+) -> String
+where
+    G::LL: Display,
+{
+    let literals = chain
+        .nodes
+        .iter()
+        .map(|&(node, to_terminal)| {
+            let level = graph.get_level(node);
+            let label = graph.get_level_label(level);
+            // A then edge to true or an else edge to false means that the variable itself is the literal
+            if to_terminal == terminal_true {
+                format!("{}", label)
+            } else {
+                format!("¬{}", label)
+            }
+        })
+        .collect_vec();
+    let operator = if terminal_true { " ∨ " } else { " ∧ " };
+    if literals.len() > 4 {
+        format!(
+            "{}{}…{}{}",
+            literals[..2].join(operator),
+            operator,
+            operator,
+            literals[literals.len() - 1]
+        )
+    } else {
+        literals.join(operator)
+    }
+}
+
+/// Creates a group per chain, where `to_local` retrieves the nodes of the grouped graph that represent a node of the chain
+pub fn group_chains<G: GraphStructure>(
+    group_manager: &mut GroupManager<G>,
+    chains: &[NodeChain],
+    to_local: impl Fn(NodeID) -> Vec<NodeID>,
+) -> Vec<NodeGroupID> {
+    chains
+        .iter()
+        .map(|chain| {
+            group_manager.create_group(
+                chain
+                    .nodes
+                    .iter()
+                    .flat_map(|&(node, _)| to_local(node))
+                    .map(|node| TargetID(TargetIDType::NodeID, node))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::util::graph_structure::oxidd_graph_structure::{
+        NodeType, OxiddGraphStructure,
+    };
+    use crate::util::dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef};
+
+    /// Retrieves the labels of the chains of at least the given length in the BDD of the given formula
+    fn get_chain_labels(formula: &str, min_length: usize) -> Vec<String> {
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let (roots, levels, _) =
+            DummyBDDFunction::from_formula(&mut manager_ref, formula, None).unwrap();
+        let mut graph = OxiddGraphStructure::<(), DummyBDDFunction, String>::new(roots, levels);
+        let chains = find_chains(&mut graph, min_length);
+        chains
+            .iter()
+            .map(|chain| {
+                let terminal_true = match graph.get_node_label(chain.terminal).kind {
+                    NodeType::Terminal(terminal) => terminal == "T",
+                    NodeType::Inner(_) => panic!("chains have to end in a terminal"),
+                };
+                get_chain_label(&mut graph, chain, terminal_true)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn conjunctions_and_disjunctions() {
+        assert_eq!(get_chain_labels("a & b & c & d", 3), vec!["a ∧ b ∧ c ∧ d"]);
+        assert_eq!(get_chain_labels("a | !b | c", 3), vec!["a ∨ ¬b ∨ c"]);
+        assert_eq!(
+            get_chain_labels("a & b & c & d & e", 3),
+            vec!["a ∧ b ∧ … ∧ e"]
+        );
+    }
+
+    #[test]
+    fn short_chains_are_skipped() {
+        assert_eq!(get_chain_labels("a & b", 3), Vec::<String>::new());
+        assert_eq!(
+            get_chain_labels("(a & b) | (c & d)", 3),
+            Vec::<String>::new()
+        );
+    }
+}
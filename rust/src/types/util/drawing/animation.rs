@@ -0,0 +1,94 @@
+use crate::{
+    configuration::types::{
+        choice_config::{Choice, ChoiceConfig},
+        composite_config::CompositeConfig,
+        container_config::{ContainerConfig, ContainerStyle},
+        int_config::IntConfig,
+        label_config::{LabelConfig, LabelKind},
+    },
+    types::util::storage::snapshot::{stored, StoredSetting},
+    util::transition::Easing,
+};
+
+use super::layouts::transition::transition_layout::TransitionDurations;
+
+/// The settings of the transitions between layouts, shared by the drawers of all diagram types
+pub type AnimationConfig = ContainerConfig<
+    LabelConfig<
+        CompositeConfig<(
+            LabelConfig<IntConfig>,
+            LabelConfig<IntConfig>,
+            LabelConfig<IntConfig>,
+            LabelConfig<ChoiceConfig<Easing>>,
+            LabelConfig<ChoiceConfig<bool>>,
+        )>,
+    >,
+>;
+
+/// Creates the settings category of the animations, whose defaults match the durations of the transition layout
+pub fn new_animation_config(style: ContainerStyle) -> AnimationConfig {
+    ContainerConfig::new(
+        style,
+        LabelConfig::new_styled(
+            "Animation",
+            LabelKind::Category,
+            CompositeConfig::new((
+                LabelConfig::new(
+                    "Move duration (ms)",
+                    IntConfig::new_min_max(600, Some(0), None),
+                ),
+                LabelConfig::new(
+                    "Insert duration (ms)",
+                    IntConfig::new_min_max(900, Some(0), None),
+                ),
+                LabelConfig::new(
+                    "Delete duration (ms)",
+                    IntConfig::new_min_max(300, Some(0), None),
+                ),
+                LabelConfig::new(
+                    "Easing",
+                    ChoiceConfig::new([
+                        Choice::new(Easing::Linear, "linear"),
+                        Choice::new(Easing::EaseInOut, "ease-in-out"),
+                        Choice::new(Easing::Cubic, "cubic"),
+                    ]),
+                ),
+                LabelConfig::new(
+                    "Reduce motion",
+                    ChoiceConfig::new([Choice::new(false, "off"), Choice::new(true, "on")]),
+                ),
+            )),
+        ),
+    )
+}
+
+/// Retrieves the durations and easing of the transitions that the settings describe
+pub fn get_animation(config: &AnimationConfig) -> (TransitionDurations, Easing) {
+    let (move_duration, insert_duration, delete_duration, easing, reduce_motion) = &****config;
+    if reduce_motion.get() {
+        // Changes are shown at once, such that nothing moves across the screen
+        return (TransitionDurations::new(0, 0, 0), Easing::Linear);
+    }
+    (
+        TransitionDurations::new(
+            move_duration.get() as u32,
+            delete_duration.get() as u32,
+            insert_duration.get() as u32,
+        ),
+        easing.get(),
+    )
+}
+
+/// Retrieves the animation settings that are stored in snapshots of the visualization state
+pub fn get_stored_animation_settings(
+    config: &AnimationConfig,
+) -> Vec<(&'static str, Box<dyn StoredSetting>)> {
+    let (move_duration, insert_duration, delete_duration, easing, reduce_motion) = &****config;
+    vec![
+        ("move_duration", stored(&**move_duration)),
+        ("insert_duration", stored(&**insert_duration)),
+        ("delete_duration", stored(&**delete_duration)),
+        ("easing", stored(&**easing)),
+        ("reduce_motion", stored(&**reduce_motion)),
+    ]
+}
@@ -0,0 +1,713 @@
+use std::rc::Rc;
+
+use itertools::Itertools;
+use oxidd::{LevelNo, NodeID};
+
+#[cfg(feature = "web")]
+use super::renderers::webgl_renderer::WebglLayerStyle;
+use super::{
+    animation::{
+        get_animation, get_stored_animation_settings, new_animation_config, AnimationConfig,
+    },
+    diagram_layout::{LayerStyle, NodeStyle},
+    drawer::Drawer,
+    layout_metrics::{
+        new_layout_metrics_config, show_layout_metrics, LayoutMetrics, LayoutMetricsConfig,
+    },
+    layout_rules::LayoutRules,
+    renderer::{LegendEntry, Renderer},
+    renderers::{
+        latex_renderer::{latex_headers, LatexLayerStyle, LatexNodeStyle, LatexRenderer},
+        svg_renderer::{SvgLayerStyle, SvgNodeStyle, SvgRenderer},
+        util::rendering_config::RenderingColorConfig,
+    },
+};
+use crate::{
+    configuration::{
+        configuration::Configuration,
+        configuration_object::{AbstractConfigurationObject, Abstractable},
+        observe_configuration::{after_configuration_change, on_configuration_change},
+        types::{
+            button_config::ButtonConfig,
+            composite_config::{CompositeConfig, GetConfigChildren},
+            container_config::{ContainerConfig, ContainerStyle},
+            int_config::IntConfig,
+            label_config::{LabelConfig, LabelKind},
+            location_config::{Location, LocationConfig},
+            panel_config::{OpenSide, PanelConfig},
+            text_output_config::TextOutputConfig,
+        },
+    },
+    traits::{DiagramSectionDrawer, DiagramSectionTextDrawer},
+    types::util::{
+        graph_structure::{
+            graph_manipulators::{
+                group_presence_adjuster::GroupPresenceAdjuster,
+                label_adjusters::group_label_adjuster::GroupLabelAdjuster,
+                node_presence_adjuster::{NodePresenceAdjuster, PresenceLabel},
+                rc_graph::RCGraph,
+                terminal_level_adjuster::TerminalLevelAdjuster,
+            },
+            graph_structure::GraphStructure,
+        },
+        group_manager::GroupManager,
+        node_tracker_manager::NodeTrackerM,
+        selection_stats::{SelectionStats, SelectionStatsConfig, SelectionStatsPanel},
+        step_trace::{StepTrace, StepTracker},
+        storage::{
+            snapshot::{get_settings, set_settings, stored, StoredSetting},
+            state_model::VisualizationState,
+            state_storage::StateStorage,
+        },
+    },
+    util::{
+        rc_refcell::MutRcRefCell, rectangle::Rectangle, transition::Interpolatable,
+        vis_error::VisError,
+    },
+    wasm_interface::{NodeGroupID, StepData, TargetID, TargetIDType},
+};
+
+/// The margin above each settings category, except for the first
+pub const TOP_MARGIN: f32 = 40.0;
+
+/// The style of the levels of a diagram, shared by the drawers of all diagram types
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayerData {
+    name: String,
+}
+impl LayerData {
+    pub fn new(name: String) -> LayerData {
+        LayerData { name }
+    }
+}
+impl Interpolatable for LayerData {
+    fn mix(&self, _other: &Self, _frac: f32) -> Self {
+        LayerData {
+            name: self.name.clone(),
+        }
+    }
+}
+impl LayerStyle for LayerData {
+    fn squash(layers: Vec<Self>) -> Self {
+        LayerData {
+            name: layers.into_iter().map(|s| s.name).join(", \n"),
+        }
+    }
+    fn collapse(layers: Vec<Self>) -> Self {
+        if layers.len() < 2 {
+            return LayerData::squash(layers);
+        }
+        LayerData {
+            name: format!("{}..{}", layers[0].name, layers[layers.len() - 1].name),
+        }
+    }
+}
+#[cfg(feature = "web")]
+impl WebglLayerStyle for LayerData {
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
+impl SvgLayerStyle for LayerData {
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
+impl LatexLayerStyle for LayerData {
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// The graph in which the presence of the nodes of the diagram specific graph can be changed
+pub type PresenceGraph<P> = RCGraph<NodePresenceAdjuster<P>>;
+/// The graph whose nodes are grouped by the group manager
+pub type DiagramGraph<P> = RCGraph<TerminalLevelAdjuster<PresenceGraph<P>>>;
+/// The graph of node groups that is laid out, whose groups are styled by the diagram specific node style
+pub type GroupedDiagramGraph<P, N> =
+    GroupPresenceAdjuster<GroupLabelAdjuster<N, LayerData, GroupManager<DiagramGraph<P>>>>;
+
+/// Moves the given level to the position of the target level
+pub type LevelMover = Box<dyn FnMut(LevelNo, LevelNo) -> Result<(), VisError>>;
+
+pub type ExpansionConfig = ContainerConfig<
+    LabelConfig<
+        CompositeConfig<(
+            LabelConfig<IntConfig>,
+            LabelConfig<IntConfig>,
+            LabelConfig<IntConfig>,
+            ButtonConfig,
+        )>,
+    >,
+>;
+pub type LatexConfig = ContainerConfig<
+    LabelConfig<
+        CompositeConfig<(
+            ButtonConfig,
+            TextOutputConfig,
+            LabelConfig<TextOutputConfig>,
+        )>,
+    >,
+>;
+pub type SvgConfig =
+    ContainerConfig<LabelConfig<CompositeConfig<(ButtonConfig, TextOutputConfig)>>>;
+/// The settings of a drawer, where the diagram specific settings are shown in between the shared node expansion and animation settings
+pub type DrawerSettings<S> =
+    CompositeConfig<(ExpansionConfig, S, AnimationConfig, LatexConfig, SvgConfig)>;
+type DrawerConfig<S> = Configuration<
+    CompositeConfig<(
+        LocationConfig<PanelConfig<CompositeConfig<(DrawerSettings<S>, LayoutMetricsConfig)>>>,
+        SelectionStatsConfig,
+    )>,
+>;
+
+type StoredSettingsGetter<S> = Box<dyn Fn(&S) -> Vec<(String, Box<dyn StoredSetting>)>>;
+
+/// The parts of a drawer that depend on the type of diagram, besides its graph and settings
+pub struct DiagramStyle<P: GraphStructure, N> {
+    /// Creates the style of a group from the labels of its nodes
+    pub node_style: Box<dyn Fn(Vec<PresenceLabel<P::NL>>) -> N>,
+    /// Retrieves the current colors of the edges, layers and selections, which are applied to the SVG export
+    pub colors: Box<dyn Fn() -> RenderingColorConfig<P::T>>,
+    /// Retrieves the current legend of the colors of the diagram
+    pub legend: Box<dyn Fn() -> Vec<LegendEntry>>,
+    /// Retrieves the name of a terminal together with its truth value if it has one, or `None` if the label is not the label of a terminal
+    pub terminal: Box<dyn Fn(P::NL) -> Option<(String, Option<bool>)>>,
+}
+
+/// Draws a section of a diagram, given the graph that the diagram specific adjusters produce. Takes care of everything that the drawers of all diagram types share: the terminal presence and grouping of the graph, the settings panel, exports, step traces, selection statistics and the stored state
+pub struct DiagramDrawer<R, L, P, N, S>
+where
+    R: Renderer<L>,
+    L: LayoutRules<
+        G = GroupedDiagramGraph<P, N>,
+        T = P::T,
+        NS = N,
+        LS = LayerData,
+        Tracker = NodeTrackerM,
+    >,
+    P: GraphStructure<LL = String> + 'static,
+    N: NodeStyle,
+    S: Clone + 'static,
+{
+    /// The diagram specific graph, on which the selection statistics are computed before terminals are hidden or duplicated
+    source_graph: P,
+    graph: DiagramGraph<P>,
+    presence_adjuster: PresenceGraph<P>,
+    group_manager: MutRcRefCell<GroupManager<DiagramGraph<P>>>,
+    step_tracker: Option<StepTracker>,
+    time: MutRcRefCell<u32>,
+    drawer: MutRcRefCell<Drawer<R, L, GroupedDiagramGraph<P, N>>>,
+    config: DrawerConfig<S>,
+    selection_stats: SelectionStatsPanel,
+    terminal: Box<dyn Fn(P::NL) -> Option<(String, Option<bool>)>>,
+    stored_settings: StoredSettingsGetter<S>,
+    level_mover: Option<LevelMover>,
+}
+
+impl<R, L, P, N, S> DiagramDrawer<R, L, P, N, S>
+where
+    R: Renderer<L> + 'static,
+    L: LayoutRules<
+            G = GroupedDiagramGraph<P, N>,
+            T = P::T,
+            NS = N,
+            LS = LayerData,
+            Tracker = NodeTrackerM,
+        > + 'static,
+    P: GraphStructure<LL = String> + Clone + 'static,
+    N: NodeStyle + LatexNodeStyle + SvgNodeStyle + 'static,
+    S: GetConfigChildren + Clone + 'static,
+{
+    /// Creates the drawer of the given graph, whose nodes are styled by the given style and whose diagram specific settings are shown in between the shared settings
+    pub fn from_graph(
+        graph: P,
+        level_count: LevelNo,
+        trace: Option<Rc<StepTrace>>,
+        renderer: R,
+        layout: L,
+        settings: S,
+        style: DiagramStyle<P, N>,
+    ) -> Self {
+        let DiagramStyle {
+            node_style,
+            colors,
+            legend,
+            terminal,
+        } = style;
+        let presence_adjuster = RCGraph::new(NodePresenceAdjuster::new(graph.clone()));
+        let modified_graph = RCGraph::new(TerminalLevelAdjuster::new(presence_adjuster.clone()));
+        let roots = modified_graph.get_roots();
+        let group_manager = MutRcRefCell::new(GroupManager::new(modified_graph.clone()));
+
+        let mut grouped_graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new_shared(
+            group_manager.clone(),
+            node_style,
+            LayerData::new,
+        ));
+        grouped_graph.hide(0);
+
+        let composite_config = CompositeConfig::new((
+            new_expansion_config(),
+            settings,
+            new_animation_config(ContainerStyle::new().margin_top(TOP_MARGIN)),
+            new_latex_config(),
+            new_svg_config(),
+        ));
+        let metrics_config =
+            new_layout_metrics_config(ContainerStyle::new().margin_top(TOP_MARGIN));
+        let selection_stats = SelectionStatsPanel::new(level_count);
+        let config = Configuration::new(CompositeConfig::new((
+            LocationConfig::new(
+                Location::BOTTOM_RIGHT,
+                PanelConfig::builder()
+                    .set_button_icon("Settings")
+                    .set_button_icon_description("Open visualization settings")
+                    .set_name("Settings")
+                    .set_category("visualization-settings")
+                    .set_open_side(OpenSide::Right)
+                    .set_open_size(0.3)
+                    .build(CompositeConfig::new((
+                        composite_config.clone(),
+                        metrics_config.clone(),
+                    ))),
+            ),
+            selection_stats.get_config(),
+        )));
+
+        let out = DiagramDrawer {
+            source_graph: graph,
+            graph: modified_graph,
+            presence_adjuster,
+            group_manager,
+            step_tracker: trace.map(StepTracker::new),
+            time: MutRcRefCell::new(0),
+            drawer: MutRcRefCell::new(Drawer::new(
+                renderer,
+                layout,
+                MutRcRefCell::new(grouped_graph),
+            )),
+            config,
+            selection_stats,
+            terminal,
+            stored_settings: Box::new(|_| Vec::new()),
+            level_mover: None,
+        };
+        out.drawer
+            .get()
+            .set_layout_listener(move |layout| show_layout_metrics(&metrics_config, layout));
+
+        let (expansion, _settings, animation_config, latex_config, svg_config) = &*composite_config;
+        let (_max_expand_layers, _max_expand_nodes, _max_visible_nodes, expand_all) =
+            &****expansion;
+        let (generate_latex, latex_output, latex_headers_output) = &****latex_config;
+        let (generate_svg, svg_output) = &****svg_config;
+        let legend: Rc<dyn Fn() -> Vec<LegendEntry>> = Rc::from(legend);
+
+        let drawer = out.drawer.clone();
+        let latex_legend = legend.clone();
+        let mut latex_renderer = LatexRenderer::<L>::new();
+        let mut output = latex_output.clone();
+        generate_latex.clone().add_press_listener(move || {
+            latex_renderer.set_legend(latex_legend());
+            latex_renderer.update_layout(&drawer.get().get_current_layout());
+            latex_renderer.render(u32::MAX);
+            let out = latex_renderer.get_output();
+            output.set(out.into()).commit();
+        });
+        latex_headers_output
+            .clone()
+            .set(latex_headers.to_string())
+            .commit();
+
+        let drawer = out.drawer.clone();
+        let svg_legend = legend.clone();
+        let initial_colors = colors();
+        let mut svg_renderer = SvgRenderer::<L>::new(
+            initial_colors.edge_types,
+            initial_colors.layers,
+            initial_colors.nodes.text,
+        );
+        let mut output = svg_output.clone();
+        generate_svg.clone().add_press_listener(move || {
+            svg_renderer.set_colors(colors());
+            svg_renderer.set_legend(svg_legend());
+            svg_renderer.update_layout(&drawer.get().get_current_layout());
+            svg_renderer.render(u32::MAX);
+            let out = svg_renderer.get_output();
+            output.set(out.into()).commit();
+        });
+
+        let from = out
+            .group_manager
+            .get()
+            .create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
+        for root in roots {
+            out.group_manager
+                .get()
+                .create_group(vec![TargetID(TargetIDType::NodeID, root)]);
+        }
+
+        let max = 500;
+        let initial_groups = if out.group_manager.read().get_nodes_of_group(from).len() < max {
+            reveal_all(&out.group_manager, from, max);
+            vec![from]
+        } else {
+            // Huge diagrams initially show their level profile, from which the user can expand the groups of interest
+            let max_level_groups = 100;
+            out.group_manager
+                .get()
+                .group_by_levels(from, max_level_groups)
+        };
+
+        let group_manager = out.group_manager.clone();
+        expand_all.clone().add_press_listener(move || {
+            for &group in &initial_groups {
+                reveal_all(&group_manager, group, 10_000_000);
+            }
+        });
+
+        let drawer = out.drawer.clone();
+        let animation_config_copy = animation_config.clone();
+        let _ = on_configuration_change(&*animation_config, move || {
+            let (durations, easing) = get_animation(&animation_config_copy);
+            let mut drawer = drawer.get();
+            drawer.get_layout_rules().set_durations(durations);
+            drawer.get_layout_rules().set_easing(easing);
+            drawer.get_renderer_mut().set_easing(easing);
+        });
+
+        // Redraw on interaction
+        out.drawer.get().get_renderer_mut().set_legend(legend());
+        let time = out.time.clone();
+        let drawer = out.drawer.clone();
+        let _ = after_configuration_change(&composite_config, move || {
+            let mut drawer = drawer.get();
+            drawer.get_renderer_mut().set_legend(legend());
+            drawer.layout(*time.get());
+        });
+
+        out
+    }
+
+    /// Sets the function that retrieves the diagram specific settings that are stored in snapshots of the visualization state
+    pub fn with_stored_settings<F: Fn(&S) -> Vec<(String, Box<dyn StoredSetting>)> + 'static>(
+        mut self,
+        stored_settings: F,
+    ) -> Self {
+        self.stored_settings = Box::new(stored_settings);
+        self
+    }
+
+    /// Sets the function by which the levels of the diagram are reordered, without which the levels can not be moved
+    pub fn with_level_mover(mut self, level_mover: Option<LevelMover>) -> Self {
+        self.level_mover = level_mover;
+        self
+    }
+}
+
+impl<R, L, P, N, S> DiagramDrawer<R, L, P, N, S>
+where
+    R: Renderer<L>,
+    L: LayoutRules<
+        G = GroupedDiagramGraph<P, N>,
+        T = P::T,
+        NS = N,
+        LS = LayerData,
+        Tracker = NodeTrackerM,
+    >,
+    P: GraphStructure<LL = String> + 'static,
+    N: NodeStyle,
+    S: Clone + 'static,
+{
+    pub fn get_drawer(&self) -> &MutRcRefCell<Drawer<R, L, GroupedDiagramGraph<P, N>>> {
+        &self.drawer
+    }
+
+    pub fn get_graph(&self) -> &DiagramGraph<P> {
+        &self.graph
+    }
+
+    pub fn get_presence_adjuster(&self) -> &PresenceGraph<P> {
+        &self.presence_adjuster
+    }
+
+    pub fn get_group_manager(&self) -> &MutRcRefCell<GroupManager<DiagramGraph<P>>> {
+        &self.group_manager
+    }
+
+    /// Retrieves the settings that were given to the drawer, which share their values with the shown settings
+    pub fn get_diagram_settings(&self) -> &S {
+        let (settings, _layout_metrics) = &***self.config.0;
+        let (_expansion, diagram_settings, _animation_config, _latex_config, _svg_config) =
+            &**settings;
+        diagram_settings
+    }
+
+    fn get_expansion_config(&self) -> &ExpansionConfig {
+        let (settings, _layout_metrics) = &***self.config.0;
+        let (expansion, _diagram_settings, _animation_config, _latex_config, _svg_config) =
+            &**settings;
+        expansion
+    }
+
+    /// Retrieves the settings that are stored in snapshots of the visualization state
+    fn get_stored_settings(&self) -> Vec<(String, Box<dyn StoredSetting>)> {
+        let (settings, _layout_metrics) = &***self.config.0;
+        let (expansion, diagram_settings, animation_config, _latex_config, _svg_config) =
+            &**settings;
+        let (max_expand_layers, max_expand_nodes, max_visible_nodes, _expand_all) = &****expansion;
+        let mut settings = vec![
+            ("expansion_layers".to_string(), stored(&**max_expand_layers)),
+            ("expansion_nodes".to_string(), stored(&**max_expand_nodes)),
+            ("visible_nodes".to_string(), stored(&**max_visible_nodes)),
+        ];
+        settings.extend((self.stored_settings)(diagram_settings));
+        settings.extend(
+            get_stored_animation_settings(animation_config)
+                .into_iter()
+                .map(|(name, setting)| (name.to_string(), setting)),
+        );
+        settings
+    }
+}
+
+fn new_expansion_config() -> ExpansionConfig {
+    ContainerConfig::new(
+        ContainerStyle::new(),
+        LabelConfig::new_styled(
+            "Node expansion",
+            LabelKind::Category,
+            CompositeConfig::new((
+                LabelConfig::new("Layers", IntConfig::new_min_max(4, Some(1), None)),
+                LabelConfig::new("Max nodes", IntConfig::new_min_max(100, Some(1), None)),
+                LabelConfig::new("Visible nodes", IntConfig::new_min_max(200, Some(1), None)),
+                ButtonConfig::new_labeled("Expand initial group"),
+            )),
+        ),
+    )
+}
+
+fn new_latex_config() -> LatexConfig {
+    ContainerConfig::new(
+        ContainerStyle::new().margin_top(TOP_MARGIN),
+        LabelConfig::new_styled(
+            "Latex",
+            LabelKind::Category,
+            CompositeConfig::new((
+                ButtonConfig::new_labeled("Generate"),
+                TextOutputConfig::new(true),
+                LabelConfig::new("Headers", TextOutputConfig::new(false)),
+            )),
+        ),
+    )
+}
+
+fn new_svg_config() -> SvgConfig {
+    ContainerConfig::new(
+        ContainerStyle::new().margin_top(TOP_MARGIN),
+        LabelConfig::new_styled(
+            "SVG",
+            LabelKind::Category,
+            CompositeConfig::new((
+                ButtonConfig::new_labeled("Generate"),
+                TextOutputConfig::new(true),
+            )),
+        ),
+    )
+}
+
+fn reveal_all<G: GraphStructure>(
+    group_manager: &MutRcRefCell<GroupManager<G>>,
+    from_id: NodeGroupID,
+    limit: usize,
+) {
+    let nodes = {
+        let mut gm = group_manager.get();
+        if !gm.get_groups().contains_key(&from_id) {
+            return;
+        }
+        let explored_group = gm.create_group(vec![TargetID(TargetIDType::NodeGroupID, from_id)]);
+        gm.get_nodes_of_group(explored_group)
+    };
+
+    let mut count = 0;
+    let mut group_manager = group_manager.get();
+    for node_id in nodes.into_iter().rev() {
+        group_manager.create_group(vec![TargetID(TargetIDType::NodeID, node_id)]);
+
+        count = count + 1;
+        if limit > 0 && count >= limit {
+            break;
+        }
+    }
+}
+
+impl<L, P, N, S> DiagramSectionTextDrawer for DiagramDrawer<LatexRenderer<L>, L, P, N, S>
+where
+    L: LayoutRules<
+            G = GroupedDiagramGraph<P, N>,
+            T = P::T,
+            NS = N,
+            LS = LayerData,
+            Tracker = NodeTrackerM,
+        > + 'static,
+    P: GraphStructure<LL = String> + Clone + 'static,
+    N: NodeStyle + LatexNodeStyle + 'static,
+    S: Clone + 'static,
+{
+    fn get_output(&mut self) -> String {
+        let mut drawer = self.drawer.get();
+        drawer.layout(0);
+        drawer.render(u32::MAX);
+        drawer.get_renderer().get_output()
+    }
+}
+
+impl<R, L, P, N, S> DiagramSectionDrawer for DiagramDrawer<R, L, P, N, S>
+where
+    R: Renderer<L> + 'static,
+    L: LayoutRules<
+            G = GroupedDiagramGraph<P, N>,
+            T = P::T,
+            NS = N,
+            LS = LayerData,
+            Tracker = NodeTrackerM,
+        > + 'static,
+    P: GraphStructure<LL = String> + Clone + 'static,
+    N: NodeStyle + 'static,
+    S: Clone + 'static,
+{
+    fn render(&mut self, time: u32) -> () {
+        *self.time.get() = time;
+        self.drawer.get().render(time);
+    }
+
+    fn layout(&mut self, time: u32) -> () {
+        self.drawer.get().layout(time);
+    }
+    fn get_layout_metrics(&self) -> LayoutMetrics {
+        self.drawer.read().get_layout_metrics()
+    }
+
+    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
+        self.drawer.get().set_transform(width, height, x, y, scale);
+    }
+
+    fn set_step(&mut self, step: i32) -> Option<StepData> {
+        let step_tracker = self.step_tracker.as_mut()?;
+        let active = step_tracker.set_step(
+            step,
+            &self.presence_adjuster,
+            &self.graph,
+            &self.group_manager,
+        )?;
+        let active = self.graph.source_nodes_to_local(active);
+
+        let time = *self.time.get();
+        let mut drawer = self.drawer.get();
+        drawer.layout(time);
+        drawer.select_nodes(&active, &[]);
+        step_tracker.get_step_data()
+    }
+
+    fn set_group(&mut self, from: Vec<TargetID>, to: NodeGroupID) -> bool {
+        self.group_manager.get().set_group(from, to)
+    }
+
+    fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
+        self.group_manager.get().create_group(from)
+    }
+
+    fn split_edges(&mut self, nodes: &[NodeID], _fully: bool) {
+        let (max_expand_layers, max_expand_nodes, _max_visible_nodes, _expand_all) =
+            &****self.get_expansion_config();
+        self.group_manager.get().split_edges(
+            nodes,
+            max_expand_layers.get().unsigned_abs(),
+            max_expand_nodes.get().unsigned_abs(),
+        );
+    }
+
+    fn focus_nodes(&mut self, nodes: &[NodeID]) {
+        let (_max_expand_layers, max_expand_nodes, max_visible_nodes, _expand_all) =
+            &****self.get_expansion_config();
+        self.group_manager.get().focus(
+            nodes,
+            max_expand_nodes.get().unsigned_abs(),
+            max_visible_nodes.get().unsigned_abs(),
+        );
+    }
+
+    fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
+        self.drawer.read().get_nodes(area, max_group_expansion)
+    }
+
+    fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
+        self.drawer.get().select_nodes(selected_ids, hovered_ids);
+        let graph = &self.graph;
+        let source_graph = &mut self.source_graph;
+        let terminal = &self.terminal;
+        self.selection_stats
+            .update(selected_ids, |selection, level_count| {
+                // The statistics are computed before terminals are hidden or duplicated
+                let selection = selection
+                    .iter()
+                    .map(|&node| graph.get_node_label(node).original_id)
+                    .collect_vec();
+                SelectionStats::compute(source_graph, &selection, level_count, |label| {
+                    terminal(label)
+                })
+            });
+    }
+
+    fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
+        self.graph
+            .local_nodes_to_sources(nodes.iter().cloned().collect())
+    }
+
+    fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID> {
+        self.graph
+            .source_nodes_to_local(nodes.iter().cloned().collect())
+    }
+    fn get_level(&self, y: f32) -> Option<LevelNo> {
+        self.drawer.read().get_level(y)
+    }
+    fn move_level(&mut self, level: LevelNo, to: LevelNo) -> Result<(), VisError> {
+        let Some(level_mover) = &mut self.level_mover else {
+            return Err(VisError::unsupported(
+                "The levels of this diagram can not be reordered",
+            ));
+        };
+        level_mover(level, to)
+    }
+    fn get_state(&self) -> VisualizationState {
+        let mut state = VisualizationState::new();
+        self.group_manager.read().write(&mut state.graph);
+        state.settings = get_settings(&self.get_stored_settings());
+        state.camera = Some(self.drawer.read().get_transform());
+        state
+    }
+
+    fn set_state(&mut self, state: VisualizationState) -> Result<(), VisError> {
+        // The settings are restored first, since changing them may alter the state of the graph
+        set_settings(&mut self.get_stored_settings(), &state.settings);
+        self.group_manager.get().read(&state.graph)?;
+        if let Some(camera) = state.camera {
+            self.drawer.get().set_transform(
+                camera.width as u32,
+                camera.height as u32,
+                camera.position.x,
+                camera.position.y,
+                camera.scale,
+            );
+        }
+        let time = *self.time.get();
+        self.layout(time);
+        Ok(())
+    }
+
+    fn get_configuration(&self) -> AbstractConfigurationObject {
+        self.config.get_abstract()
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
     wasm_interface::{NodeGroupID, NodeID},
 };
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct NodeGroupLayout<T: DrawTag, S: NodeStyle> {
     /// Bottom center point of the node
     pub position: Transition<Point>,
@@ -48,7 +48,7 @@ impl<T: DrawTag, S: NodeStyle> NodeGroupLayout<T, S> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct EdgeLayout {
     pub start_offset: Transition<Point>,
     pub end_offset: Transition<Point>,
@@ -57,7 +57,7 @@ pub struct EdgeLayout {
     pub curve_offset: Transition<f32>, // If no bendpoints are used, this curve offset can be used for curving the edge, in the range [-1, 1], 0 represents no bend
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct EdgePoint {
     pub point: Transition<Point>,
     // TODO: give more thought to jumps
@@ -65,7 +65,7 @@ pub struct EdgePoint {
     pub exists: Transition<f32>, // Whether this point actually exists in the output (it might be used only to transition shape)
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct LayerLayout<S: LayerStyle> {
     pub start_layer: LevelNo,
     pub end_layer: LevelNo,
@@ -76,7 +76,7 @@ pub struct LayerLayout<S: LayerStyle> {
     pub style: Transition<S>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct DiagramLayout<T: DrawTag, S: NodeStyle, LS: LayerStyle> {
     pub groups: HashMap<NodeGroupID, NodeGroupLayout<T, S>>,
     /// Note: this vector has to be sorted in increasing order of start_layer
@@ -85,5 +85,9 @@ pub struct DiagramLayout<T: DrawTag, S: NodeStyle, LS: LayerStyle> {
 
 pub trait LayerStyle: Interpolatable + Clone + Sized {
     fn squash(layers: Vec<Self>) -> Self;
+    /// Merges the styles of a run of levels that is condensed into a single compact layer
+    fn collapse(layers: Vec<Self>) -> Self {
+        Self::squash(layers)
+    }
 }
 pub trait NodeStyle: Interpolatable + Clone {}
@@ -7,15 +7,13 @@ use std::{
 };
 
 use itertools::{Either, Itertools};
-use js_sys::Date;
-use oxidd::{Function, Manager, NodeID};
+use oxidd::{Function, LevelNo, Manager, NodeID};
 use oxidd_core::Tag;
-use web_sys::WebGl2RenderingContext;
 
 use crate::{
     types::util::{
         graph_structure::{
-            graph_structure::DrawTag,
+            graph_structure::{DrawTag, EdgeType},
             grouped_graph_structure::{GroupedGraphStructure, NodeTracker, SourceReader},
         },
         group_manager::GroupManager,
@@ -23,6 +21,7 @@ use crate::{
     util::{
         logging::console,
         point::Point,
+        random::SeededRandom,
         rc_refcell::{MutRcRefCell, RcRefCell},
         rectangle::Rectangle,
         transformation::Transformation,
@@ -33,8 +32,9 @@ use crate::{
 
 use super::{
     diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+    layout_metrics::LayoutMetrics,
     layout_rules::LayoutRules,
-    renderer::{GroupSelection, Renderer},
+    renderer::{GroupPath, GroupSelection, Renderer},
 };
 
 pub struct Drawer<
@@ -52,9 +52,15 @@ pub struct Drawer<
     sources: L::Tracker,
     transform: Transformation,
     selection: SelectionData,
+    path: Option<NodePath<L::T>>,
+    seed: u64,
+    layout_listener: Option<LayoutListener<L::T, L::NS, L::LS>>,
 }
 
 type SelectionData = (Vec<NodeGroupID>, Vec<NodeGroupID>);
+/// A path through the graph, consisting of its nodes and the edges between them
+pub type NodePath<T> = (Vec<NodeID>, Vec<(NodeID, NodeID, EdgeType<T>)>);
+type LayoutListener<T, S, LS> = Box<dyn FnMut(&DiagramLayout<T, S, LS>)>;
 
 impl<
         R: Renderer<L>,
@@ -77,6 +83,9 @@ where
             },
             transform: Transformation::default(),
             selection: (Vec::new(), Vec::new()),
+            path: None,
+            seed: 0,
+            layout_listener: None,
         }
     }
 
@@ -84,15 +93,46 @@ where
         &mut self.layout_rules
     }
 
+    pub fn get_renderer(&self) -> &R {
+        &self.renderer
+    }
+
+    pub fn get_renderer_mut(&mut self) -> &mut R {
+        &mut self.renderer
+    }
+
+    /// Sets the seed of the random choices of the layout rules, which is used from the next layout onwards
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Sets the listener that is called with every newly computed layout
+    pub fn set_layout_listener(
+        &mut self,
+        listener: impl FnMut(&DiagramLayout<L::T, L::NS, L::LS>) + 'static,
+    ) {
+        self.layout_listener = Some(Box::new(listener));
+    }
+
     pub fn get_current_layout(&self) -> DiagramLayout<L::T, L::NS, L::LS> {
         self.layout.clone()
     }
 
+    pub fn get_layout_metrics(&self) -> LayoutMetrics {
+        LayoutMetrics::compute(&self.layout)
+    }
+
     pub fn layout(&mut self, time: u32) {
         self.graph.get().refresh();
-        self.layout =
-            self.layout_rules
-                .layout(&*self.graph.read(), &self.layout, &self.sources, time);
+        // Every layout starts from the seed, such that the same graph always results in the same layout
+        let mut random = SeededRandom::new(self.seed);
+        self.layout = self.layout_rules.layout(
+            &*self.graph.read(),
+            &self.layout,
+            &self.sources,
+            time,
+            &mut random,
+        );
         let used_ids = self.layout.groups.keys().collect::<HashSet<_>>();
 
         self.sources.retain(|group_id| used_ids.contains(&group_id));
@@ -102,6 +142,12 @@ where
         self.select_nodes(&[], &[]);
         self.renderer.update_layout(&self.layout);
         self.select_nodes(&old_selection.0[..], &old_selection.1[..]);
+        if self.path.is_some() {
+            self.update_path_highlight();
+        }
+        if let Some(listener) = &mut self.layout_listener {
+            listener(&self.layout);
+        }
     }
     pub fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) {
         let transform = Transformation {
@@ -115,6 +161,10 @@ where
         self.renderer.set_transform(transform);
     }
 
+    pub fn get_transform(&self) -> Transformation {
+        self.transform.clone()
+    }
+
     pub fn render(&mut self, time: u32) {
         self.renderer.render(time);
     }
@@ -139,6 +189,19 @@ where
             .collect()
     }
 
+    pub fn get_level(&self, y: f32) -> Option<LevelNo> {
+        let (_, y, _) = self.transform.get_inverse_matrix().mul_vec3((0.0, y, 0.0));
+        self.layout
+            .layers
+            .iter()
+            .find(|layer| {
+                let (top, bottom) = (layer.top.new, layer.bottom.new);
+                top.min(bottom) <= y && y <= top.max(bottom)
+            })
+            .filter(|layer| layer.end_layer == layer.start_layer + 1)
+            .map(|layer| layer.start_layer)
+    }
+
     pub fn select_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
         if selected_ids == &self.selection.0[..] && hovered_ids == &self.selection.1[..] {
             return;
@@ -170,6 +233,44 @@ where
 
         self.selection = (Vec::from(selected_ids), Vec::from(hovered_ids));
     }
+    pub fn get_selected_nodes(&self) -> &[NodeID] {
+        &self.selection.0[..]
+    }
+
+    /// Highlights the given path by dimming all other nodes and edges, where nodes of the path that are hidden inside a group highlight their group instead
+    pub fn highlight_path(&mut self, path: Option<NodePath<L::T>>) {
+        self.path = path;
+        self.update_path_highlight();
+    }
+    fn update_path_highlight(&mut self) {
+        let path = self.get_path_groups();
+        self.renderer.highlight_path(
+            path.as_ref()
+                .map(|(groups, edges)| (&groups[..], &edges[..])),
+        );
+    }
+    fn get_path_groups(
+        &self,
+    ) -> Option<(
+        Vec<NodeGroupID>,
+        Vec<(NodeGroupID, NodeGroupID, EdgeType<L::T>)>,
+    )> {
+        let (nodes, edges) = self.path.as_ref()?;
+        let graph = self.graph.read();
+        let groups = nodes
+            .iter()
+            .map(|&node_id| graph.get_group(node_id))
+            .unique()
+            .collect();
+        let group_edges = edges
+            .iter()
+            .map(|&(from, to, edge_type)| (graph.get_group(from), graph.get_group(to), edge_type))
+            // Edges between nodes of the same group are not drawn
+            .filter(|(from, to, _)| from != to)
+            .unique()
+            .collect();
+        Some((groups, group_edges))
+    }
     fn get_selection_groups(&self, node_ids: &[NodeID]) -> (Vec<NodeGroupID>, Vec<NodeGroupID>) {
         // TODO: make the graph track sources, and use this info for selection (such that duplicate nodes select all duplications)
 
@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, fmt::Display, iter};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    configuration::types::{
+        container_config::{ContainerConfig, ContainerStyle},
+        label_config::{LabelConfig, LabelKind},
+        text_output_config::TextOutputConfig,
+    },
+    types::util::graph_structure::graph_structure::DrawTag,
+    util::{point::Point, rectangle::Rectangle},
+};
+
+use super::{
+    diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+    layouts::util::layered::layer_orderer::{self, EdgeLayoutData, EdgeMap, Order},
+};
+
+/// Measures of the quality of a layout, by which different layout rules can be compared
+pub struct LayoutMetrics {
+    /// The number of edge crossings between every pair of consecutive layers, identified by the first levels of these layers
+    pub crossings: Vec<((LevelNo, LevelNo), usize)>,
+    /// The summed length of all edge segments
+    pub edge_length: f32,
+    /// The number of bend points of all edges
+    pub bends: usize,
+    pub width: f32,
+    /// The number of pairs of nodes whose areas overlap
+    pub overlaps: usize,
+}
+
+impl LayoutMetrics {
+    /// Computes the metrics of the final state of the given layout, where nodes and edges that are being removed are ignored
+    pub fn compute<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+        layout: &DiagramLayout<T, S, LS>,
+    ) -> LayoutMetrics {
+        let groups = layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.0)
+            .sorted_by_key(|(id, _)| **id)
+            .collect_vec();
+
+        let mut segments = Vec::new();
+        let mut bends = 0;
+        for (_, group) in &groups {
+            for (edge_data, edge) in &group.edges {
+                if edge.exists.new <= 0.0 {
+                    continue;
+                }
+                let Some(target) = layout.groups.get(&edge_data.to) else {
+                    continue;
+                };
+                let points = edge
+                    .points
+                    .iter()
+                    .filter(|point| point.exists.new > 0.0)
+                    .map(|point| point.point.new)
+                    .collect_vec();
+                bends += points.len();
+
+                let start = group.position.new + edge.start_offset.new;
+                let end = target.position.new + edge.end_offset.new;
+                segments.extend(
+                    iter::once(start)
+                        .chain(points)
+                        .chain(iter::once(end))
+                        .tuple_windows::<(Point, Point)>(),
+                );
+            }
+        }
+        let edge_length: f32 = segments
+            .iter()
+            .map(|(start, end)| start.distance(end))
+            .sum();
+
+        let rects = groups
+            .iter()
+            .map(|(_, group)| group.get_rect(None))
+            .sorted_by(|a, b| a.x.total_cmp(&b.x))
+            .collect_vec();
+        let width = match (
+            rects.first(),
+            rects
+                .iter()
+                .map(|rect| rect.x + rect.width)
+                .reduce(f32::max),
+        ) {
+            (Some(first), Some(right)) => right - first.x,
+            _ => 0.0,
+        };
+
+        LayoutMetrics {
+            crossings: count_crossings(layout, &segments),
+            edge_length,
+            bends,
+            width,
+            overlaps: count_overlaps(&rects),
+        }
+    }
+
+    pub fn get_total_crossings(&self) -> usize {
+        self.crossings.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl Display for LayoutMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Edge crossings: {}", self.get_total_crossings())?;
+        for ((from, to), count) in &self.crossings {
+            if *count > 0 {
+                writeln!(f, "  levels {}-{}: {}", from, to, count)?;
+            }
+        }
+        writeln!(f, "Edge length: {:.1}", self.edge_length)?;
+        writeln!(f, "Bends: {}", self.bends)?;
+        writeln!(f, "Width: {:.1}", self.width)?;
+        write!(f, "Node overlaps: {}", self.overlaps)
+    }
+}
+
+/// Counts the crossings between the segments, per area between the centers of two consecutive layers
+fn count_crossings<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+    layout: &DiagramLayout<T, S, LS>,
+    segments: &[(Point, Point)],
+) -> Vec<((LevelNo, LevelNo), usize)> {
+    let layers = layout
+        .layers
+        .iter()
+        .filter(|layer| layer.exists.new > 0.0)
+        .collect_vec();
+    layers
+        .iter()
+        .tuple_windows()
+        .map(|(upper, lower)| {
+            let upper_center = 0.5 * (upper.top.new + upper.bottom.new);
+            let lower_center = 0.5 * (lower.top.new + lower.bottom.new);
+            let (min_y, max_y) = (
+                upper_center.min(lower_center),
+                upper_center.max(lower_center),
+            );
+            (
+                (upper.start_layer, lower.start_layer),
+                count_area_crossings(segments, min_y, max_y),
+            )
+        })
+        .collect()
+}
+
+/// Counts the crossings between the segments inside the area between the given heights. Each segment is treated as an edge between the positions at which it enters and leaves the area, such that the crossings follow from the orders of these positions like those of a layered layout. Segments that merely touch, such as edges leaving the same node, do not cross
+fn count_area_crossings(segments: &[(Point, Point)], min_y: f32, max_y: f32) -> usize {
+    let area_segments = segments
+        .iter()
+        .filter(|(start, end)| start.y.max(end.y) > min_y && start.y.min(end.y) < max_y)
+        .map(|&(start, end)| (get_x_at(start, end, min_y), get_x_at(start, end, max_y)))
+        .collect_vec();
+
+    // Segments entering or leaving at the same position share a node, such that they do not cross each other
+    let get_nodes = |xs: Vec<f32>| -> Vec<f32> {
+        xs.into_iter()
+            .sorted_by(|a, b| a.total_cmp(b))
+            .dedup()
+            .collect()
+    };
+    let top = get_nodes(area_segments.iter().map(|(top, _)| *top).collect());
+    let bottom = get_nodes(area_segments.iter().map(|(_, bottom)| *bottom).collect());
+    let get_node = |nodes: &Vec<f32>, x: f32| {
+        nodes
+            .binary_search_by(|node| node.total_cmp(&x))
+            .unwrap_or_default()
+    };
+
+    let mut edges: EdgeMap = BTreeMap::new();
+    for &(top_x, bottom_x) in &area_segments {
+        edges
+            .entry(get_node(&top, top_x))
+            .or_insert_with(BTreeMap::new)
+            .entry(get_node(&bottom, bottom_x))
+            .or_insert(EdgeLayoutData {
+                weight: 0,
+                order: 0,
+            })
+            .weight += 1;
+    }
+    let get_order = |nodes: &Vec<f32>| -> Order { (0..nodes.len()).map(|i| (i, i)).collect() };
+    layer_orderer::count_crossings((&get_order(&top), &get_order(&bottom)), &edges)
+}
+
+/// Retrieves the horizontal position of the segment at the given height, where heights outside of the segment are clamped to its end points
+fn get_x_at(start: Point, end: Point, y: f32) -> f32 {
+    if start.y == end.y {
+        return if y <= start.y {
+            start.x.min(end.x)
+        } else {
+            start.x.max(end.x)
+        };
+    }
+    let per = ((y - start.y) / (end.y - start.y)).clamp(0.0, 1.0);
+    start.x + (end.x - start.x) * per
+}
+
+/// Counts the pairs of rectangles that overlap, where the rectangles have to be sorted by their left side
+fn count_overlaps(rects: &[Rectangle]) -> usize {
+    let mut count = 0;
+    for (i, rect) in rects.iter().enumerate() {
+        for other in rects[i + 1..]
+            .iter()
+            .take_while(|other| other.x < rect.x + rect.width)
+        {
+            if other.y < rect.y + rect.height && rect.y < other.y + other.height {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub type LayoutMetricsConfig = ContainerConfig<LabelConfig<TextOutputConfig>>;
+
+/// Creates the settings category that shows the metrics of the current layout
+pub fn new_layout_metrics_config(style: ContainerStyle) -> LayoutMetricsConfig {
+    ContainerConfig::new(
+        style,
+        LabelConfig::new_styled(
+            "Layout quality",
+            LabelKind::Category,
+            TextOutputConfig::new(false),
+        ),
+    )
+}
+
+/// Shows the metrics of the given layout in the settings
+pub fn show_layout_metrics<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+    config: &LayoutMetricsConfig,
+    layout: &DiagramLayout<T, S, LS>,
+) {
+    let metrics = LayoutMetrics::compute(layout);
+    (***config).clone().set(format!("{}", metrics)).commit();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: (f32, f32), end: (f32, f32)) -> (Point, Point) {
+        (
+            Point {
+                x: start.0,
+                y: start.1,
+            },
+            Point { x: end.0, y: end.1 },
+        )
+    }
+
+    #[test]
+    fn crossing_segments() {
+        let x_shape = [segment((0., 0.), (1., 1.)), segment((1., 0.), (0., 1.))];
+        assert_eq!(count_area_crossings(&x_shape, 0., 1.), 1);
+        // The crossing lies outside of the area
+        assert_eq!(count_area_crossings(&x_shape, 0., 0.4), 0);
+
+        let parallel = [segment((0., 0.), (0., 1.)), segment((1., 0.), (1., 1.))];
+        assert_eq!(count_area_crossings(&parallel, 0., 1.), 0);
+
+        // Edges leaving the same node only touch
+        let fork = [
+            segment((0.5, 0.), (0., 1.)),
+            segment((0.5, 0.), (1., 1.)),
+            segment((1., 0.), (0., 1.)),
+        ];
+        assert_eq!(count_area_crossings(&fork, 0., 1.), 1);
+    }
+}
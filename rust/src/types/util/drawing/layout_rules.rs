@@ -8,7 +8,7 @@ use crate::{
         },
         group_manager::GroupManager,
     },
-    util::transition::Interpolatable,
+    util::{random::SeededRandom, transition::Interpolatable},
     wasm_interface::NodeGroupID,
 };
 
@@ -32,5 +32,7 @@ pub trait LayoutRules {
         /* Sources for new nodes that did not yet exist in the previous layout iteration */
         new_sources: &Self::Tracker,
         time: u32,
+        /* The generator for all random choices of the layout, such that the layout only depends on its seed */
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS>;
 }
@@ -12,6 +12,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
+    util::random::SeededRandom,
     wasm_interface::NodeGroupID,
 };
 
@@ -48,6 +49,7 @@ impl<G: GroupedGraphStructure, O1: LayerOrdering<G>, O2: LayerOrdering<G>> Layer
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        random: &mut SeededRandom,
     ) -> Vec<Order> {
         let o1 = self.ordering1.order_nodes(
             graph,
@@ -56,6 +58,7 @@ impl<G: GroupedGraphStructure, O1: LayerOrdering<G>, O2: LayerOrdering<G>> Layer
             dummy_group_start_id,
             dummy_edge_start_id,
             owners,
+            random,
         );
         self.ordering2.order_nodes(
             graph,
@@ -64,6 +67,7 @@ impl<G: GroupedGraphStructure, O1: LayerOrdering<G>, O2: LayerOrdering<G>> Layer
             dummy_group_start_id,
             dummy_edge_start_id,
             owners,
+            random,
         )
     }
 }
@@ -12,6 +12,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
+    util::random::SeededRandom,
     wasm_interface::NodeGroupID,
 };
 
@@ -25,6 +26,7 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for DummyLayerOrdering {
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> Vec<Order> {
         layers.clone()
     }
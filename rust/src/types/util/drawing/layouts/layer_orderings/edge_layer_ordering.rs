@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd_core::Tag;
 
 use crate::{
@@ -14,6 +13,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
+    util::random::SeededRandom,
     wasm_interface::NodeGroupID,
 };
 
@@ -28,6 +28,7 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for EdgeLayerOrdering {
         _dummy_group_start_id: NodeGroupID,
         _dummy_edge_start_id: NodeGroupID,
         _owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> Vec<Order> {
         let reverse_edges = swap_edges(edges);
         layers
@@ -1,10 +1,5 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
-use js_sys::Math::random;
-use oxidd_core::Tag;
-use seeded_random::{Random, Seed};
-
 use crate::{
     types::util::{
         drawing::layouts::{
@@ -15,24 +10,22 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::logging::console,
+    util::{logging::console, random::SeededRandom},
     wasm_interface::NodeGroupID,
 };
+use itertools::Itertools;
+use oxidd_core::Tag;
 
+/// Swaps random nodes within their layers, using the seeded generator of the layout such that the ordering only depends on the seed of the drawer
 pub struct PseudoRandomLayerOrdering {
     swaps: usize,
-    seed: usize,
 }
 impl PseudoRandomLayerOrdering {
-    pub fn new(swaps_per_node: usize, seed: usize) -> PseudoRandomLayerOrdering {
+    pub fn new(swaps_per_node: usize) -> PseudoRandomLayerOrdering {
         PseudoRandomLayerOrdering {
             swaps: swaps_per_node,
-            seed: seed,
         }
     }
-    pub fn set_seed(&mut self, seed: usize) -> () {
-        self.seed = seed;
-    }
 }
 impl<G: GroupedGraphStructure> LayerOrdering<G> for PseudoRandomLayerOrdering {
     fn order_nodes(
@@ -43,29 +36,60 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for PseudoRandomLayerOrdering {
         _dummy_group_start_id: NodeGroupID,
         _dummy_edge_start_id: NodeGroupID,
         _owners: &HashMap<NodeGroupID, NodeGroupID>,
+        random: &mut SeededRandom,
     ) -> Vec<Order> {
-        let mut rng = Random::from_seed(Seed::unsafe_new(self.seed as u64));
-        layers
-            .iter()
-            .map(|layer| {
-                let mut layer = layer.clone();
-                for _ in 0..self.swaps * layer.len() {
-                    swap_in_layer(&mut layer, &mut rng);
-                }
-                layer
-            })
-            .collect()
+        shuffle_layers(layers, self.swaps, random)
     }
 }
 
-fn swap_in_layer(order: &mut Order, rng: &mut Random) {
-    let keys = order.keys().sorted().collect_vec();
-    let index1 = rng.range(0, keys.len() as u32) as usize;
+fn shuffle_layers(layers: &[Order], swaps: usize, random: &mut SeededRandom) -> Vec<Order> {
+    layers
+        .iter()
+        .map(|layer| {
+            let mut layer = layer.clone();
+            for _ in 0..swaps * layer.len() {
+                swap_in_layer(&mut layer, random);
+            }
+            layer
+        })
+        .collect()
+}
+
+fn swap_in_layer(order: &mut Order, random: &mut SeededRandom) {
+    let keys = order.keys().collect_vec();
+    let index1 = random.range(0, keys.len() as u32) as usize;
     let key1 = **keys.get(index1).unwrap();
     let pos1 = *order.get(&key1).unwrap();
-    let index2 = rng.range(0, keys.len() as u32) as usize;
+    let index2 = random.range(0, keys.len() as u32) as usize;
     let key2 = **keys.get(index2).unwrap();
     let pos2 = *order.get(&key2).unwrap();
     order.insert(key1, pos2);
     order.insert(key2, pos1);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers() -> Vec<Order> {
+        vec![
+            (0..8).map(|node| (node, node)).collect(),
+            (8..20).map(|node| (node, node - 8)).collect(),
+        ]
+    }
+
+    #[test]
+    fn orderings_depend_on_the_seed() {
+        let shuffle = |seed| shuffle_layers(&layers(), 2, &mut SeededRandom::new(seed));
+        assert_eq!(shuffle(0), shuffle(0));
+        assert_ne!(shuffle(0), shuffle(1));
+
+        // Every layer remains a permutation of its original positions
+        for (layer, original) in shuffle(1).iter().zip(layers().iter()) {
+            assert_eq!(
+                layer.values().sorted().collect_vec(),
+                original.values().sorted().collect_vec()
+            );
+        }
+    }
+}
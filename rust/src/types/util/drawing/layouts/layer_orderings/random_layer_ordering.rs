@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd_core::Tag;
 
 use crate::{
@@ -14,6 +13,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
+    util::random::SeededRandom,
     wasm_interface::NodeGroupID,
 };
 
@@ -36,13 +36,14 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for RandomLayerOrdering {
         _dummy_group_start_id: NodeGroupID,
         _dummy_edge_start_id: NodeGroupID,
         _owners: &HashMap<NodeGroupID, NodeGroupID>,
+        random: &mut SeededRandom,
     ) -> Vec<Order> {
         layers
             .iter()
             .map(|layer| {
                 let mut layer = layer.clone();
                 for _ in 0..self.swaps * layer.len() {
-                    swap_in_layer(&mut layer);
+                    swap_in_layer(&mut layer, random);
                 }
                 layer
             })
@@ -50,12 +51,12 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for RandomLayerOrdering {
     }
 }
 
-fn swap_in_layer(order: &mut Order) {
+fn swap_in_layer(order: &mut Order, random: &mut SeededRandom) {
     let keys = order.keys().collect_vec();
-    let index1 = (random() * keys.len() as f64).floor() as usize;
+    let index1 = (random.random() * keys.len() as f64).floor() as usize;
     let key1 = **keys.get(index1).unwrap();
     let pos1 = *order.get(&key1).unwrap();
-    let index2 = (random() * keys.len() as f64).floor() as usize;
+    let index2 = (random.random() * keys.len() as f64).floor() as usize;
     let key2 = **keys.get(index2).unwrap();
     let pos2 = *order.get(&key2).unwrap();
     order.insert(key1, pos2);
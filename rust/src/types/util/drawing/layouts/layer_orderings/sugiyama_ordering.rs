@@ -15,7 +15,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::logging::console,
+    util::{logging::console, random::SeededRandom},
     wasm_interface::{NodeGroupID, NodeID},
 };
 
@@ -55,6 +55,7 @@ impl<G: GroupedGraphStructure> LayerOrdering<G> for SugiyamaOrdering {
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> Vec<Order> {
         hierarchical_barycenter_order(
             layers,
@@ -21,7 +21,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::{logging::console, point::Point},
+    util::{logging::console, point::Point, random::SeededRandom},
     wasm_interface::NodeGroupID,
 };
 
@@ -37,6 +37,7 @@ impl<G: GroupedGraphStructure> NodePositioning<G> for BrandesKopfPositioningCorr
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> (HashMap<NodeGroupID, Point>, HashMap<LevelNo, f32>) {
         let spacing = 2.0;
         let first_used_layer = layers
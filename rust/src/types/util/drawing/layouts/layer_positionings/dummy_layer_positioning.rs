@@ -14,7 +14,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::point::Point,
+    util::{point::Point, random::SeededRandom},
     wasm_interface::NodeGroupID,
 };
 
@@ -30,6 +30,7 @@ impl<G: GroupedGraphStructure> NodePositioning<G> for DummyLayerPositioning {
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> (HashMap<NodeGroupID, Point>, HashMap<LevelNo, f32>) {
         let spacing = 2.;
 
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     iter::FromIterator,
     marker::PhantomData,
 };
@@ -26,6 +26,7 @@ use crate::{
     util::{
         logging::console,
         point::Point,
+        random::SeededRandom,
         rectangle::Rectangle,
         transition::{Interpolatable, Transition},
     },
@@ -119,10 +120,11 @@ where
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &G::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
         // Setup the layers and edges, and a way of adding o them
         let mut layers: Vec<Order> = Vec::new();
-        let mut edges: EdgeMap = HashMap::new();
+        let mut edges: EdgeMap = BTreeMap::new();
 
         let mut dummy_owners: HashMap<NodeGroupID, NodeGroupID> = HashMap::new();
         let mut next_free_id = 0; // uninitialized, will be initialized by add_groups
@@ -170,6 +172,7 @@ where
             dummy_group_start_id,
             dummy_edge_start_id,
             &dummy_owners,
+            random,
         );
 
         // Sort the groupings, such that they never cross each-other, and remove other edges that cross groups
@@ -193,6 +196,7 @@ where
             dummy_group_start_id,
             dummy_edge_start_id,
             &dummy_owners,
+            random,
         );
 
         format_layout(
@@ -210,7 +214,7 @@ where
 
 fn add_to_layer(layers: &mut Vec<Order>, layer: usize, id: NodeGroupID) {
     while layer >= layers.len() {
-        layers.push(HashMap::new());
+        layers.push(BTreeMap::new());
     }
     let layer = layers.get_mut(layer).unwrap();
     layer.insert(id, layer.len());
@@ -219,7 +223,7 @@ fn add_to_layer(layers: &mut Vec<Order>, layer: usize, id: NodeGroupID) {
 fn add_to_edges(edges: &mut EdgeMap, from: NodeGroupID, to: NodeGroupID, data: EdgeLayoutData) {
     edges
         .entry(from)
-        .or_insert_with(|| HashMap::new())
+        .or_insert_with(|| BTreeMap::new())
         .insert(to, data);
 }
 
@@ -230,16 +234,16 @@ fn add_groups_with_dummies<G: GroupedGraphStructure>(
     group_edge_data: EdgeLayoutData,
     dummy_owners: &mut HashMap<NodeGroupID, NodeGroupID>,
     next_free_id: &mut NodeGroupID,
-) -> (NodeGroupID, HashMap<NodeGroupID, HashMap<u32, usize>>)
+) -> (NodeGroupID, BTreeMap<NodeGroupID, BTreeMap<u32, usize>>)
 where
     G::GL: NodeStyle,
     G::LL: LayerStyle,
 {
-    let mut group_layers: HashMap<NodeGroupID, HashMap<u32, usize>> = HashMap::new();
+    let mut group_layers: BTreeMap<NodeGroupID, BTreeMap<u32, usize>> = BTreeMap::new();
     for group in graph.get_all_groups() {
         let (start, _end) = graph.get_level_range(group);
         add_to_layer(layers, start as usize, group);
-        group_layers.insert(group, HashMap::from([(start, group)]));
+        group_layers.insert(group, BTreeMap::from([(start, group)]));
         if group >= *next_free_id {
             *next_free_id = group + 1;
         }
@@ -272,7 +276,7 @@ fn add_edges_with_dummies<G: GroupedGraphStructure>(
     layers: &mut Vec<Order>,
     edges: &mut EdgeMap,
     dummy_owners: &mut HashMap<NodeGroupID, NodeGroupID>,
-    group_layers: &HashMap<NodeGroupID, HashMap<u32, usize>>,
+    group_layers: &BTreeMap<NodeGroupID, BTreeMap<u32, usize>>,
     next_free_id: &mut NodeGroupID,
 ) -> (
     HashMap<(NodeGroupID, EdgeData<G::T>), Vec<NodeGroupID>>,
@@ -6,7 +6,7 @@ use crate::{
     types::util::graph_structure::{
         graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
     },
-    util::point::Point,
+    util::{point::Point, random::SeededRandom},
     wasm_interface::NodeGroupID,
 };
 
@@ -25,6 +25,8 @@ pub trait LayerOrdering<G: GroupedGraphStructure> {
         dummy_edge_start_id: NodeGroupID,
         // The owner of a given dummy node, such that multiple nodes derived from the same data can be considered as a group
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        // The generator for the random choices of the ordering, which is shared by all steps of the layout
+        random: &mut SeededRandom,
     ) -> Vec<Order>;
 }
 
@@ -43,6 +45,8 @@ pub trait NodePositioning<G: GroupedGraphStructure> {
         dummy_edge_start_id: NodeGroupID,
         // The owner of a given dummy node, such that multiple nodes derived from the same data can be considered as a group
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        // The generator for the random choices of the positioning, which is shared by all steps of the layout
+        random: &mut SeededRandom,
     ) -> (HashMap<NodeGroupID, Point>, HashMap<LevelNo, f32>);
 }
 
@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    types::util::{
+        drawing::{
+            diagram_layout::{DiagramLayout, LayerStyle},
+            layout_rules::LayoutRules,
+        },
+        graph_structure::grouped_graph_structure::GroupedGraphStructure,
+    },
+    util::{point::Point, random::SeededRandom, transition::Transition},
+};
+
+///
+/// A layout that takes another layout approach, and condenses the runs of levels in which no node is visible into a single compact layer.
+/// Edges passing through such layers are shortened accordingly.
+///
+pub struct LevelCollapsingLayout<L: LayoutRules> {
+    layout: L,
+    enabled: bool,
+    /// The height of a layer that represents multiple collapsed levels
+    collapsed_height: f32,
+}
+
+impl<L: LayoutRules> LevelCollapsingLayout<L> {
+    pub fn new(layout: L, collapsed_height: f32) -> Self {
+        LevelCollapsingLayout {
+            layout,
+            enabled: false,
+            collapsed_height,
+        }
+    }
+    pub fn get_layout_rules(&mut self) -> &mut L {
+        &mut self.layout
+    }
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    pub fn set_collapsed_height(&mut self, collapsed_height: f32) {
+        self.collapsed_height = collapsed_height;
+    }
+}
+
+impl<L: LayoutRules> LayoutRules for LevelCollapsingLayout<L> {
+    type T = L::T;
+    type NS = L::NS;
+    type LS = L::LS;
+    type Tracker = L::Tracker;
+    type G = L::G;
+
+    fn layout(
+        &mut self,
+        graph: &Self::G,
+        old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
+        new_sources: &Self::Tracker,
+        time: u32,
+        random: &mut SeededRandom,
+    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+        let mut layout = self.layout.layout(graph, old, new_sources, time, random);
+        if self.enabled {
+            self.collapse_idle_levels(graph, &mut layout);
+        }
+        layout
+    }
+}
+
+impl<L: LayoutRules> LevelCollapsingLayout<L> {
+    fn collapse_idle_levels(&self, graph: &L::G, layout: &mut DiagramLayout<L::T, L::NS, L::LS>) {
+        let occupied_levels: HashSet<LevelNo> = layout
+            .groups
+            .values()
+            .flat_map(|group| group.level_range.0..=group.level_range.1)
+            .collect();
+
+        // Levels without nodes never start or end a layer, hence every run of such levels already forms a single layer
+        let collapsed_height = self.collapsed_height;
+        let collapsed_layers = layout
+            .layers
+            .iter_mut()
+            .filter(|layer| {
+                (layer.start_layer..layer.end_layer).all(|level| !occupied_levels.contains(&level))
+                    && layer.top.new - layer.bottom.new > collapsed_height
+            })
+            .map(|layer| {
+                // The style transitions from the style that the layer had before, like the other properties of the layer
+                layer.style.new = L::LS::collapse(
+                    (layer.start_layer..layer.end_layer)
+                        .map(|level| graph.get_level_label(level))
+                        .collect_vec(),
+                );
+                (layer.top.new, layer.bottom.new)
+            })
+            .collect_vec();
+        if collapsed_layers.is_empty() {
+            return;
+        }
+
+        // Every collapsed layer is scaled down to the collapsed height, and everything below it is moved up by the removed height
+        let map_y = |y: f32| -> f32 {
+            y + collapsed_layers
+                .iter()
+                .map(|&(top, bottom)| {
+                    let shrink = 1.0 - collapsed_height / (top - bottom);
+                    if y >= top {
+                        0.0
+                    } else if y > bottom {
+                        (top - y) * shrink
+                    } else {
+                        (top - bottom) * shrink
+                    }
+                })
+                .sum::<f32>()
+        };
+        // Only the new values are mapped, since the old values describe where the elements transition from, which does not depend on the current collapsing
+        let map_point = |point: &mut Transition<Point>| {
+            point.new.y = map_y(point.new.y);
+        };
+        let map_float = |value: &mut Transition<f32>| {
+            value.new = map_y(value.new);
+        };
+
+        for layer in layout.layers.iter_mut() {
+            map_float(&mut layer.top);
+            map_float(&mut layer.bottom);
+        }
+        for group in layout.groups.values_mut() {
+            map_point(&mut group.position);
+            for edge in group.edges.values_mut() {
+                for point in edge.points.iter_mut() {
+                    map_point(&mut point.point);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        types::util::{
+            drawing::{
+                diagram_drawer::LayerData,
+                diagram_layout::NodeStyle,
+                layouts::{
+                    layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
+                    layer_orderings::edge_layer_ordering::EdgeLayerOrdering,
+                    layer_positionings::dummy_layer_positioning::DummyLayerPositioning,
+                    layered_layout::LayeredLayout, layered_layout_traits::WidthLabel,
+                },
+                renderers::svg_renderer::SvgLayerStyle,
+            },
+            graph_structure::{
+                graph_manipulators::{
+                    group_presence_adjuster::GroupPresenceAdjuster,
+                    label_adjusters::group_label_adjuster::GroupLabelAdjuster,
+                },
+                oxidd_graph_structure::OxiddGraphStructure,
+            },
+            group_manager::GroupManager,
+        },
+        util::{
+            dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+            transition::Interpolatable,
+        },
+        wasm_interface::{TargetID, TargetIDType},
+    };
+
+    #[derive(Clone)]
+    struct TestNodeStyle;
+    impl Interpolatable for TestNodeStyle {
+        fn mix(&self, _other: &Self, _frac: f32) -> Self {
+            TestNodeStyle
+        }
+    }
+    impl NodeStyle for TestNodeStyle {}
+    impl WidthLabel for TestNodeStyle {
+        fn get_width(&self) -> f32 {
+            1.0
+        }
+    }
+
+    type TestGraph = GroupPresenceAdjuster<
+        GroupLabelAdjuster<
+            TestNodeStyle,
+            LayerData,
+            GroupManager<OxiddGraphStructure<(), DummyBDDFunction, String>>,
+        >,
+    >;
+    type TestLayout = LevelCollapsingLayout<
+        LayeredLayout<TestGraph, EdgeLayerOrdering, OrderingGroupAlignment, DummyLayerPositioning>,
+    >;
+
+    /// Creates the graph of `x0 & x1 & x2 & x10`, where the levels of `x3` up to `x9` contain no nodes, with every node in its own group
+    fn create_graph() -> TestGraph {
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let order = (0..=10).map(|i| format!("x{}", i)).join(" ");
+        let (roots, levels, _) = DummyBDDFunction::from_formula(
+            &mut manager_ref,
+            "f = x0 & x1 & x2 & x10",
+            Some(&order),
+        )
+        .unwrap();
+        let mut group_manager = GroupManager::new(OxiddGraphStructure::new(roots, levels));
+        let from = group_manager.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
+        let explored = group_manager.create_group(vec![TargetID(TargetIDType::NodeGroupID, from)]);
+        for node in group_manager.get_nodes_of_group(explored) {
+            group_manager.create_group(vec![TargetID(TargetIDType::NodeID, node)]);
+        }
+        let mut graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new(
+            group_manager,
+            |_| TestNodeStyle,
+            LayerData::new,
+        ));
+        graph.hide(0);
+        graph
+    }
+
+    #[test]
+    fn idle_levels_are_collapsed_into_a_band() {
+        let mut graph = create_graph();
+        let mut layout = TestLayout::new(
+            LayeredLayout::new(
+                EdgeLayerOrdering,
+                OrderingGroupAlignment,
+                DummyLayerPositioning,
+                0.3,
+            ),
+            1.0,
+        );
+        layout.set_enabled(true);
+        let sources = graph.create_node_tracker();
+        let empty = DiagramLayout {
+            groups: HashMap::new(),
+            layers: Vec::new(),
+        };
+        let result = layout.layout(&graph, &empty, &sources, 0, &mut SeededRandom::new(0));
+
+        // The levels are 2 apart, and every layer extends halfway to the next node
+        let heights = result
+            .layers
+            .iter()
+            .map(|layer| (layer.start_layer, layer.top.new - layer.bottom.new))
+            .collect_vec();
+        assert_eq!(
+            heights,
+            vec![(0, 1.5), (1, 2.0), (2, 1.5), (3, 1.0), (10, 1.5), (11, 1.5)]
+        );
+        let band = &result.layers[3];
+        assert_eq!(band.end_layer, 10);
+        assert_eq!(band.style.new.get_label(), "x3..x9");
+        // The old values are left as they were, such that the band transitions from its full height
+        assert_eq!(band.top.old - band.bottom.old, 15.0);
+
+        // The nodes below the band move up by the removed height
+        let bottom = result
+            .groups
+            .values()
+            .map(|group| group.position.new.y)
+            .fold(f32::MAX, f32::min);
+        assert_eq!(bottom, -22.5 + 14.0);
+    }
+}
@@ -3,6 +3,7 @@ pub mod layer_orderings;
 pub mod layer_positionings;
 pub mod layered_layout;
 pub mod layered_layout_traits;
+pub mod level_collapsing_layout;
 pub mod random_test_layout;
 pub mod sugiyama_lib_layout;
 pub mod toggle_layout;
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use crate::util::random::SeededRandom;
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd::{Edge, Function, InnerNode, Manager};
 use oxidd_core::{DiagramRules, Tag};
 
@@ -19,7 +19,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::transition::Transition,
+    util::transition::{Easing, Transition},
 };
 
 pub struct RandomTestLayout<G: GroupedGraphStructure>(PhantomData<G>);
@@ -46,17 +46,20 @@ where
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &G::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+        // The groups and edges are visited in a fixed order, such that they always obtain the same random values
         let groups = graph.get_all_groups();
         DiagramLayout {
             groups: groups
-                .iter()
-                .map(|&group_id| {
+                .into_iter()
+                .sorted()
+                .map(|group_id| {
                     (group_id, {
-                        let x: f32 = (random() * 20. - 10.) as f32;
-                        let y: f32 = (random() * 20. - 10.) as f32;
-                        let width: f32 = (random() * 1. + 0.5) as f32;
-                        let height: f32 = (random() * 1. + 0.5) as f32;
+                        let x: f32 = (random.random() * 20. - 10.) as f32;
+                        let y: f32 = (random.random() * 20. - 10.) as f32;
+                        let width: f32 = (random.random() * 1. + 0.5) as f32;
+                        let height: f32 = (random.random() * 1. + 0.5) as f32;
                         let group_label = graph.get_group_label(group_id);
 
                         NodeGroupLayout {
@@ -65,6 +68,7 @@ where
                                 new: Point { x, y },
                                 old_time: time,
                                 duration: 1000,
+                                easing: Easing::Linear,
                             },
                             level_range: graph.get_level_range(group_id),
                             style: Transition::plain(group_label),
@@ -76,27 +80,30 @@ where
                                 },
                                 old_time: time,
                                 duration: 1000,
+                                easing: Easing::Linear,
                             },
                             exists: Transition::plain(1.),
                             edges: graph
                                 .get_children(group_id)
                                 .into_iter()
+                                .sorted()
                                 .map(|edge_data| {
                                     (edge_data.drop_count(), {
                                         EdgeLayout {
                                             start_offset: Transition::plain(Point { x: 0., y: 0. }),
                                             end_offset: Transition::plain(Point { x: 0., y: 0. }),
-                                            points: (vec![0; (random() * 3.0) as usize])
+                                            points: (vec![0; (random.random() * 3.0) as usize])
                                                 .iter()
                                                 .map(|_| EdgePoint {
                                                     point: Transition {
                                                         old: Point { x: 0.0, y: 0.0 },
                                                         new: Point {
-                                                            x: (random() * 20. - 10.) as f32,
-                                                            y: (random() * 20. - 10.) as f32,
+                                                            x: (random.random() * 20. - 10.) as f32,
+                                                            y: (random.random() * 20. - 10.) as f32,
                                                         },
                                                         old_time: time,
                                                         duration: 1000,
+                                                        easing: Easing::Linear,
                                                     },
                                                     exists: Transition::plain(1.),
                                                 })
@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use oxidd::LevelNo;
 use oxidd_core::Tag;
 
@@ -11,7 +12,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::{logging::console, point::Point, transition::Interpolatable},
+    util::{logging::console, point::Point, random::SeededRandom, transition::Interpolatable},
     wasm_interface::NodeGroupID,
 };
 use rust_sugiyama::from_edges;
@@ -65,8 +66,9 @@ where
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &G::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
-        self.layout.layout(graph, old, sources, time)
+        self.layout.layout(graph, old, sources, time, random)
     }
 }
 struct SugiyamaLibPositioning;
@@ -80,6 +82,7 @@ impl<G: GroupedGraphStructure> NodePositioning<G> for SugiyamaLibPositioning {
         dummy_group_start_id: NodeGroupID,
         dummy_edge_start_id: NodeGroupID,
         owners: &HashMap<NodeGroupID, NodeGroupID>,
+        _random: &mut SeededRandom,
     ) -> (HashMap<NodeGroupID, Point>, HashMap<LevelNo, f32>) {
         let spacing = 2;
         // console::log!(
@@ -97,6 +100,7 @@ impl<G: GroupedGraphStructure> NodePositioning<G> for SugiyamaLibPositioning {
             &edges
                 .iter()
                 .flat_map(|(from, to_set)| to_set.keys().map(move |to| (*from as u32, *to as u32)))
+                .sorted() // The library's result depends on the order of the edges
                 .collect::<Vec<(u32, u32)>>()[..],
         )
         .vertex_spacing(spacing)
@@ -12,7 +12,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::transition::Interpolatable,
+    util::{random::SeededRandom, transition::Interpolatable},
 };
 
 ///
@@ -105,8 +105,9 @@ impl<L: LayoutRules> LayoutRules for ToggleLayoutUnit<L> {
         /* Sources for new nodes that did not yet exist in the previous layout iteration */
         new_sources: &Self::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
-        self.layout.layout(graph, old, new_sources, time)
+        self.layout.layout(graph, old, new_sources, time, random)
     }
 }
 
@@ -126,11 +127,12 @@ impl<
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         new_sources: &Self::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
         if self.is_layout_one_selected() {
-            self.layout1.layout(graph, old, new_sources, time)
+            self.layout1.layout(graph, old, new_sources, time, random)
         } else {
-            self.layout2.layout(graph, old, new_sources, time)
+            self.layout2.layout(graph, old, new_sources, time, random)
         }
     }
 }
@@ -2,13 +2,14 @@ use itertools::{EitherOrBoth, Itertools};
 
 use crate::{
     types::util::drawing::diagram_layout::{LayerLayout, LayerStyle},
-    util::transition::Transition,
+    util::transition::{Easing, Transition},
 };
 
 pub fn transition_layers<LS: LayerStyle>(
     old: &Vec<LayerLayout<LS>>,
     new: &Vec<LayerLayout<LS>>,
     duration: u32,
+    easing: Easing,
     old_time: u32,
     time: u32,
 ) -> Vec<LayerLayout<LS>> {
@@ -23,6 +24,7 @@ pub fn transition_layers<LS: LayerStyle>(
                     duration,
                     old: exists,
                     new: 0.,
+                    easing,
                 },
                 ..old_layer.clone()
             });
@@ -52,24 +54,28 @@ pub fn transition_layers<LS: LayerStyle>(
                         duration,
                         old: old_layer.bottom.get(time),
                         new: new_layer.bottom.new,
+                        easing,
                     },
                     top: Transition {
                         old_time,
                         duration,
                         old: old_layer.top.get(time),
                         new: new_layer.top.new,
+                        easing,
                     },
                     exists: Transition {
                         old_time,
                         duration,
                         old: old_layer.exists.get(time),
                         new: new_layer.exists.new,
+                        easing,
                     },
                     index: Transition {
                         old_time,
                         duration,
                         old: old_layer.index.get(time),
                         new: new_layer.index.new,
+                        easing,
                     },
                     ..new_layer.clone()
                 });
@@ -88,18 +94,21 @@ pub fn transition_layers<LS: LayerStyle>(
                     duration,
                     old: center,
                     new: new_layer.top.new,
+                    easing,
                 },
                 bottom: Transition {
                     old_time,
                     duration,
                     old: center,
                     new: new_layer.bottom.new,
+                    easing,
                 },
                 exists: Transition {
                     old_time,
                     duration,
                     old: 0.,
                     new: new_layer.exists.new,
+                    easing,
                 },
                 ..new_layer.clone()
             });
@@ -118,6 +127,7 @@ pub fn transition_layers_shift<LS: LayerStyle>(
     old: &Vec<LayerLayout<LS>>,
     new: &Vec<LayerLayout<LS>>,
     duration: u32,
+    easing: Easing,
     old_time: u32,
     time: u32,
 ) -> Vec<LayerLayout<LS>> {
@@ -132,18 +142,21 @@ pub fn transition_layers_shift<LS: LayerStyle>(
                     duration,
                     old: old_layer.bottom.get(time),
                     new: new_layer.bottom.new,
+                    easing,
                 },
                 top: Transition {
                     old_time,
                     duration,
                     old: old_layer.top.get(time),
                     new: new_layer.top.new,
+                    easing,
                 },
                 exists: Transition {
                     old_time,
                     duration,
                     old: old_layer.exists.get(time),
                     new: new_layer.exists.new,
+                    easing,
                 },
                 ..new_layer.clone()
             }),
@@ -153,18 +166,21 @@ pub fn transition_layers_shift<LS: LayerStyle>(
                     duration,
                     old: prev_bottom.unwrap_or(new_layer.top.new),
                     new: new_layer.top.new,
+                    easing,
                 },
                 bottom: Transition {
                     old_time,
                     duration,
                     old: prev_bottom.unwrap_or(new_layer.bottom.new),
                     new: new_layer.bottom.new,
+                    easing,
                 },
                 exists: Transition {
                     old_time,
                     duration,
                     old: 0.,
                     new: new_layer.exists.new,
+                    easing,
                 },
                 ..new_layer.clone()
             }),
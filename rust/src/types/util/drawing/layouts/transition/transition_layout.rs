@@ -24,7 +24,12 @@ use crate::{
             grouped_graph_structure::{EdgeData, GroupedGraphStructure, SourceReader},
         },
     },
-    util::{logging::console, point::Point, transition::Transition},
+    util::{
+        logging::console,
+        point::Point,
+        random::SeededRandom,
+        transition::{Easing, Transition},
+    },
     wasm_interface::NodeGroupID,
 };
 
@@ -44,21 +49,25 @@ pub struct TransitionLayout<L: LayoutRules> {
 
 impl<L: LayoutRules> TransitionLayout<L> {
     pub fn new(layout: L) -> Self {
-        let speed_modifier = 1; // for testing
-                                // TODO: add parameters
         TransitionLayout {
             layout,
-            // TODO: make this more finely grained configurable
-            durations: TransitionDurations {
-                insert_duration: 900 * speed_modifier,
-                transition_duration: 600 * speed_modifier,
-                delete_duration: 300 * speed_modifier,
-            },
+            durations: TransitionDurations::default(),
         }
     }
     pub fn get_layout_rules(&mut self) -> &mut L {
         &mut self.layout
     }
+    /// Sets the durations of the transitions that start at the next layout
+    pub fn set_durations(&mut self, durations: TransitionDurations) {
+        self.durations = TransitionDurations {
+            easing: self.durations.easing,
+            ..durations
+        };
+    }
+    /// Sets the curve of the transitions that start at the next layout, which should equal the easing of the renderer
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.durations.easing = easing;
+    }
 }
 
 #[derive(Clone)]
@@ -66,6 +75,29 @@ pub struct TransitionDurations {
     transition_duration: u32,
     delete_duration: u32,
     insert_duration: u32,
+    easing: Easing,
+}
+
+impl TransitionDurations {
+    /// Creates the durations in milliseconds, where every duration is at least 1ms such that the progress of a transition is always defined
+    pub fn new(
+        transition_duration: u32,
+        delete_duration: u32,
+        insert_duration: u32,
+    ) -> TransitionDurations {
+        TransitionDurations {
+            transition_duration: transition_duration.max(1),
+            delete_duration: delete_duration.max(1),
+            insert_duration: insert_duration.max(1),
+            easing: Easing::Linear,
+        }
+    }
+}
+
+impl Default for TransitionDurations {
+    fn default() -> TransitionDurations {
+        TransitionDurations::new(600, 300, 900)
+    }
 }
 
 impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
@@ -81,10 +113,12 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &Self::Tracker,
         time: u32,
+        random: &mut SeededRandom,
     ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
         let duration = self.durations.transition_duration;
+        let easing = self.durations.easing;
         let old_time = time;
-        let new = self.layout.layout(graph, old, sources, time);
+        let new = self.layout.layout(graph, old, sources, time, random);
 
         let relations = relate_elements(graph, old, &new, sources, time);
         let ElementRelations {
@@ -179,7 +213,7 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
             .collect::<HashMap<_, _>>();
         DiagramLayout {
             groups,
-            layers: transition_layers(&old.layers, &new.layers, duration, old_time, time),
+            layers: transition_layers(&old.layers, &new.layers, duration, easing, old_time, time),
         }
     }
 }
@@ -197,6 +231,7 @@ fn layout_updated_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
 ) -> NodeGroupLayout<T, S> {
     let old_time = time;
     let duration = durations.transition_duration;
+    let easing = durations.easing;
 
     let cur_size = old_group.size.get(time);
     let cur_old_group_position = old_group.position.get(time);
@@ -222,18 +257,21 @@ fn layout_updated_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
             duration,
             old: old_position,
             new: group.position.new,
+            easing,
         },
         size: Transition {
             old_time,
             duration: duration,
             old: start_size,
             new: group.size.new,
+            easing,
         },
         exists: Transition {
             old_time,
             duration,
             old: old_group.exists.get(time),
             new: group.exists.new,
+            easing,
         },
         level_range: group.level_range.clone(),
         edges: group
@@ -262,6 +300,7 @@ fn layout_updated_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
             duration,
             old: old_group.style.get(time),
             new: group.style.new.clone(),
+            easing,
         },
     }
 }
@@ -277,6 +316,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
 ) -> NodeGroupLayout<T, S> {
     let old_time = time;
     let duration = durations.transition_duration;
+    let easing = durations.easing;
 
     if let Some(parent_id) = some_updated_parents.get(&id) {
         let parent = updated_groups.get(parent_id).unwrap();
@@ -286,9 +326,11 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 duration,
                 old: parent.position.get(time),
                 new: group.position.new,
+                easing,
             },
 
             style: Transition {
+                easing,
                 old_time,
                 duration,
                 old: parent.style.get(time),
@@ -310,6 +352,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                                         duration,
                                         old: parent.position.old,
                                         new: point.point.new.clone(),
+                                        easing,
                                     },
                                     ..point.clone()
                                 })
@@ -328,6 +371,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 duration: durations.insert_duration,
                 old: 0.,
                 new: group.exists.new,
+                easing,
             },
             edges: group
                 .edges
@@ -341,6 +385,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                                 duration: durations.insert_duration,
                                 old: 0.,
                                 new: edge.exists.new,
+                                easing,
                             },
                             ..edge.clone()
                         },
@@ -363,6 +408,7 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
 ) -> NodeGroupLayout<T, S> {
     let old_time = time;
     let duration = durations.transition_duration;
+    let easing = durations.easing;
 
     let target = target_data.as_ref().and_then(|target_group| {
         new.groups
@@ -392,18 +438,21 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                     duration,
                     old: cur_pos,
                     new: target.position.new + offset.clone(),
+                    easing,
                 },
                 style: Transition {
                     old_time,
                     duration,
                     old: group.style.get(time),
                     new: target.style.new.clone(),
+                    easing,
                 },
                 exists: Transition {
                     old_time: old_time + duration,
                     duration: durations.delete_duration,
                     old: group.exists.get(time),
                     new: 0.,
+                    easing,
                 },
                 size: Transition {
                     old_time,
@@ -413,6 +462,7 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                         x: group.size.new.x.min(target.size.new.x),
                         y: group.size.new.y.min(target.size.new.y),
                     },
+                    easing,
                 },
                 edges: deleted_edges_layout,
                 ..group.clone()
@@ -424,6 +474,7 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 duration: durations.delete_duration,
                 old: group.exists.get(time),
                 new: 0.,
+                easing,
             },
             edges: deleted_edges_layout,
             ..group.clone()
@@ -444,6 +495,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
 ) -> EdgeLayout {
     let old_time = time;
     let duration = durations.transition_duration;
+    let easing = durations.easing;
 
     let maybe_old_edge = relations
         .previous_edges
@@ -483,6 +535,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                         duration,
                         old: to_pos.get(time) + end_offset,
                         new: point.point.new,
+                        easing,
                     }
                 } else {
                     Transition {
@@ -490,6 +543,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                         duration,
                         old: old_edge_layout.points.get(index).unwrap().point.get(time),
                         new: point.point.new,
+                        easing,
                     }
                 };
 
@@ -514,12 +568,14 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                         old: point.point.get(time),
                         new: new.groups.get(&edge.to).unwrap().position.new
                             + edge_layout.end_offset.new,
+                        easing,
                     },
                     exists: Transition {
                         old_time,
                         duration,
                         old: point.exists.get(time),
                         new: 0.0,
+                        easing,
                     },
                 }),
         );
@@ -530,12 +586,14 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 old_time,
                 old: start_offset,
                 new: edge_layout.start_offset.new,
+                easing,
             },
             end_offset: Transition {
                 duration,
                 old_time,
                 old: end_offset,
                 new: edge_layout.end_offset.new,
+                easing,
             },
             points: new_points,
             exists: Transition {
@@ -543,12 +601,14 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 old_time,
                 old: old_edge_layout.exists.get(time),
                 new: edge_layout.exists.new,
+                easing,
             },
             curve_offset: Transition {
                 duration,
                 old_time,
                 old: old_edge_layout.curve_offset.get(time),
                 new: edge_layout.curve_offset.new,
+                easing,
             },
         }
     } else {
@@ -569,6 +629,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                     old_time,
                     old: old_group_position + start_offset,
                     new: point.point.new,
+                    easing,
                 },
                 exists: point.exists,
             })
@@ -587,6 +648,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                     duration: durations.insert_duration,
                     old: 0.,
                     new: edge_layout.exists.new,
+                    easing,
                 }
             },
             curve_offset: Transition {
@@ -594,6 +656,7 @@ fn layout_current_edge<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 duration: durations.insert_duration,
                 old: 0.,
                 new: edge_layout.curve_offset.new,
+                easing,
             },
         }
     }
@@ -609,6 +672,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
 ) -> HashMap<EdgeData<T>, EdgeLayout> {
     let old_time = time;
     let duration = durations.transition_duration;
+    let easing = durations.easing;
 
     relations
         .deleted_edges
@@ -641,6 +705,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                                 duration: 1,
                                 old: exists,
                                 new: 0.,
+                                easing,
                             }
                         } else {
                             Transition {
@@ -648,6 +713,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                                 duration: durations.delete_duration,
                                 old: exists,
                                 new: 0.,
+                                easing,
                             }
                         },
                         points: old_edge_layout
@@ -659,12 +725,14 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                                     duration: durations.delete_duration,
                                     old: point.exists.get(time),
                                     new: 0.,
+                                    easing,
                                 },
                                 point: Transition {
                                     old_time,
                                     duration,
                                     old: point.point.get(time),
                                     new: point_pos + old_edge_layout.start_offset.new,
+                                    easing,
                                 },
                             })
                             .collect(),
@@ -674,6 +742,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                                 duration,
                                 old: old_edge_layout.start_offset.get(time),
                                 new: old_edge_layout.start_offset.new + morph.start_offset,
+                                easing,
                             }
                         } else {
                             old_edge_layout.start_offset
@@ -684,6 +753,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                                 duration,
                                 old: old_edge_layout.end_offset.get(time),
                                 new: old_edge_layout.end_offset.new + morph.end_offset,
+                                easing,
                             }
                         } else {
                             old_edge_layout.end_offset
@@ -693,6 +763,7 @@ fn layout_deleted_edges<T: DrawTag, S: NodeStyle>(
                             duration: durations.insert_duration,
                             old: old_edge_layout.curve_offset.get(time),
                             new: 0.,
+                            easing,
                         },
                     },
                 ))
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 
 use itertools::Itertools;
 
@@ -67,7 +67,7 @@ fn get_equal_barycenter_groups(
     next_layer: &Order,
     edges: &EdgeMap,
 ) -> Vec<Vec<NodeID>> {
-    let mut equal_groups: HashMap<Ratio, Vec<NodeID>> = HashMap::new();
+    let mut equal_groups: BTreeMap<Ratio, Vec<NodeID>> = BTreeMap::new();
     for node in get_sequence(layer) {
         let barycenter = get_barycenter(node, next_layer, edges);
         equal_groups
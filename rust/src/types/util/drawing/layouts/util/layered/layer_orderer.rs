@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
 
@@ -23,8 +23,9 @@ pub trait LayerOrderer {
     fn is_sorted(&self, layer: &Order, next_layer: &Order, edges: &EdgeMap) -> bool;
 }
 
-pub type Order = HashMap<NodeID, usize>; // A mapping from node id to index in the order, hence this map should be a bijection from some X subset of NodeID, to the set of [0..|X|-1]
-pub type EdgeMap = HashMap<NodeID, HashMap<NodeID, EdgeLayoutData>>; // A mapping from node to node, with some edge data
+// The maps are ordered by node id, such that iterating them, and hence the layout, is reproducible
+pub type Order = BTreeMap<NodeID, usize>; // A mapping from node id to index in the order, hence this map should be a bijection from some X subset of NodeID, to the set of [0..|X|-1]
+pub type EdgeMap = BTreeMap<NodeID, BTreeMap<NodeID, EdgeLayoutData>>; // A mapping from node to node, with some edge data
 
 #[derive(Clone, Copy)]
 pub struct EdgeLayoutData {
@@ -32,7 +33,7 @@ pub struct EdgeLayoutData {
     pub order: i32, // Number used for ordering
 }
 
-pub type OrderedEdgeMap = HashMap<NodeID, Vec<NodeID>>;
+pub type OrderedEdgeMap = BTreeMap<NodeID, Vec<NodeID>>;
 
 // Counts the number of crossings between two layers
 pub fn count_crossings(order: (&Order, &Order), edges: &EdgeMap) -> usize {
@@ -73,11 +74,11 @@ pub fn count_crossings(order: (&Order, &Order), edges: &EdgeMap) -> usize {
 }
 
 pub fn swap_edges(edges: &EdgeMap) -> EdgeMap {
-    let mut out = HashMap::new();
+    let mut out = BTreeMap::new();
     for (from, node_edges) in edges {
         for (to, &data) in node_edges {
             out.entry(*to)
-                .or_insert_with(|| HashMap::new())
+                .or_insert_with(|| BTreeMap::new())
                 .insert(*from, data);
         }
     }
@@ -110,7 +111,7 @@ pub fn get_edge_index_sequence<'a, I: Iterator<Item = (&'a NodeID, &'a usize)>>(
         .collect()
 }
 pub fn get_ordered_edge_map(edge_map: &EdgeMap, orders: &Vec<Order>) -> OrderedEdgeMap {
-    let mut out: OrderedEdgeMap = HashMap::new();
+    let mut out: OrderedEdgeMap = BTreeMap::new();
 
     if orders.len() == 0 {
         return out;
@@ -1,6 +1,10 @@
+pub mod animation;
+pub mod diagram_drawer;
 pub mod diagram_layout;
 pub mod drawer;
+pub mod layout_metrics;
 pub mod layout_rules;
 pub mod layouts;
 pub mod renderer;
 pub mod renderers;
+pub mod theme;
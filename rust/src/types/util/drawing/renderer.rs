@@ -2,15 +2,21 @@ use oxidd_core::Tag;
 
 use crate::{
     types::util::graph_structure::{
-        graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
+        graph_structure::{DrawTag, EdgeType},
+        grouped_graph_structure::GroupedGraphStructure,
+    },
+    util::{
+        color::Color,
+        transformation::Transformation,
+        transition::{Easing, Interpolatable},
     },
-    util::{transformation::Transformation, transition::Interpolatable},
     wasm_interface::NodeGroupID,
 };
 
 use super::{
     diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
     layout_rules::LayoutRules,
+    renderers::util::rendering_config::RenderingColorConfig,
 };
 
 /// A trait for rendering a given layout
@@ -19,6 +25,21 @@ pub trait Renderer<L: LayoutRules> {
     fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>);
     fn render(&mut self, time: u32);
     fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection);
+    /// Highlights the groups and edges of the given path by dimming everything else, or removes the highlight if no path is given
+    fn highlight_path(&mut self, path: Option<GroupPath<L::T>>);
+    /// Sets the legend that explains the colors of the diagram, which is shown below the layer labels
+    fn set_legend(&mut self, legend: Vec<LegendEntry>);
+    /// Replaces the colors of the edges, layers and selections, the colors of nodes are obtained from their styles instead
+    fn set_colors(&mut self, colors: RenderingColorConfig<L::T>);
+    /// Sets the curve that the node, edge, layer and style transitions follow
+    fn set_easing(&mut self, easing: Easing);
+}
+
+/// A color used in the diagram, together with a description of what it represents
+#[derive(Clone, PartialEq)]
+pub struct LegendEntry {
+    pub label: String,
+    pub color: Color,
 }
 
 pub type GroupSelection<'a> = (
@@ -31,3 +52,10 @@ pub type GroupSelection<'a> = (
     // Partially hovered groups
     &'a [NodeGroupID],
 );
+
+pub type GroupPath<'a, T> = (
+    // Groups that the path passes through
+    &'a [NodeGroupID],
+    // Edges of the path, from the group of the parent to the group of the child
+    &'a [(NodeGroupID, NodeGroupID, EdgeType<T>)],
+);
@@ -7,13 +7,14 @@ use crate::{
         drawing::{
             diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
             layout_rules::LayoutRules,
-            renderer::{GroupSelection, Renderer},
+            renderer::{GroupPath, GroupSelection, LegendEntry, Renderer},
+            renderers::util::rendering_config::RenderingColorConfig,
         },
         graph_structure::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::{logging::console, transformation::Transformation},
+    util::{color::Color, logging::console, transformation::Transformation, transition::Easing},
 };
 
 pub struct LatexRenderer<L: LayoutRules>
@@ -23,6 +24,7 @@ where
 {
     output: String,
     layout: Option<DiagramLayout<L::T, L::NS, L::LS>>,
+    legend: Vec<LegendEntry>,
 }
 
 impl<L: LayoutRules> LatexRenderer<L>
@@ -34,6 +36,7 @@ where
         LatexRenderer {
             output: "".into(),
             layout: None,
+            legend: Vec::new(),
         }
     }
 
@@ -48,7 +51,7 @@ where
     L::LS: LatexLayerStyle,
 {
     fn set_transform(&mut self, transform: Transformation) {
-        todo!()
+        // The picture always contains the whole diagram, TikZ takes care of its bounds
     }
 
     fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
@@ -83,9 +86,13 @@ where
                 let x = pos.x;
                 let y = pos.y + 0.5 * size.y;
                 if let Some((terminal_type, terminal_label)) = style.is_terminal() {
+                    let fill = style
+                        .get_fill_color()
+                        .map(|color| format!(", fill={}", to_latex_color(color)))
+                        .unwrap_or_default();
                     Some(format!(
-                        "\\node[{}] (n{}) at ({}, {}) {{{}}};",
-                        terminal_type, id, x, y, terminal_label.unwrap_or_else(|| format!("\\pgfkeysvalueof{{/tikz/{}/label}}", terminal_type))
+                        "\\node[{}{}] (n{}) at ({}, {}) {{{}}};",
+                        terminal_type, fill, id, x, y, terminal_label.unwrap_or_else(|| format!("\\pgfkeysvalueof{{/tikz/{}/label}}", terminal_type))
                     ))
                 } else if style.is_group() {
                     Some(format!(
@@ -197,9 +204,16 @@ where
                         })
                         .join("");
 
+                    let complement = if edge_data.edge_type.tag.is_complemented() {
+                        ", complement"
+                    } else {
+                        ""
+                    };
+
                     Some(format!(
-                        "\\draw[choice{}] ({}n{}{}) to[bend left={}] {}({}n{}{});",
+                        "\\draw[choice{}{}] ({}n{}{}) to[bend left={}] {}({}n{}{});",
                         edge_data.edge_type.index,
+                        complement,
                         start_offset,
                         group_id,
                         start_side,
@@ -251,6 +265,29 @@ where
             })
             .join("\n    ");
 
+        let legend_top = layout
+            .layers
+            .iter()
+            .filter(|layer| layer.exists.get(time) >= 1.0)
+            .map(|layer| layer.bottom.get(time).min(layer.top.get(time)))
+            .reduce(f32::min)
+            .unwrap_or(0.0);
+        let legend = self
+            .legend
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                format!(
+                    "\\node[legendEntry, fill={}] (legend-{}) at ({}-\\ts, {}) {{{}}};",
+                    to_latex_color(entry.color),
+                    index,
+                    min_x,
+                    legend_top - 1.0 - 1.2 * index as f32,
+                    sanitize(entry.label.clone())
+                )
+            })
+            .join("\n    ");
+
         let out = format!(
             "\\begin{{tikzpicture}}\n    \
             \\pgfmathsetmacro{{\\margin}}{{0.5}} % spacing around diagram on left and right \n    \
@@ -265,16 +302,35 @@ where
             {}\n    \
             \n    \
             % Edges \n    \
+            {}\n    \
+            \n    \
+            % Legend \n    \
             {}\n\
             \\end{{tikzpicture}}",
-            layers, nodes, edges
+            layers, nodes, edges, legend
         );
 
         self.output = out;
     }
 
     fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {
-        todo!()
+        // Selections are not part of the exported picture
+    }
+
+    fn highlight_path(&mut self, path: Option<GroupPath<L::T>>) {
+        // Highlights are not part of the exported picture
+    }
+
+    fn set_legend(&mut self, legend: Vec<LegendEntry>) {
+        self.legend = legend;
+    }
+
+    fn set_easing(&mut self, easing: Easing) {
+        // The exported picture shows the diagram at a single point in time
+    }
+
+    fn set_colors(&mut self, colors: RenderingColorConfig<L::T>) {
+        // The output only uses the colors of the node styles, the remaining colors are defined by the tikz styles of the document
     }
 }
 
@@ -282,17 +338,31 @@ fn sanitize(text: String) -> String {
     text.replace("_", "\\_")
 }
 
+fn to_latex_color(color: Color) -> String {
+    format!(
+        "{{rgb,1:red,{:.3};green,{:.3};blue,{:.3}}}",
+        color.0.clamp(0.0, 1.0),
+        color.1.clamp(0.0, 1.0),
+        color.2.clamp(0.0, 1.0)
+    )
+}
+
 pub trait LatexNodeStyle: NodeStyle {
     /// Retrieves whether the given node is a terminal, and if so: retrieves the terminal type, and optionally a label
     fn is_terminal(&self) -> Option<(String, Option<String>)>;
     fn is_group(&self) -> bool;
     fn get_label(&self) -> Option<String>;
+    /// Retrieves the color to fill the node with instead of the color of its style
+    fn get_fill_color(&self) -> Option<Color> {
+        None
+    }
 }
 pub trait LatexLayerStyle: LayerStyle {
     fn get_label(&self) -> String;
 }
 
 pub const latex_headers: &str = "\\usepackage{tikz}
+\\usetikzlibrary{decorations.markings}
 \\tikzset{
     inner/.style={
             shape=circle,
@@ -346,6 +416,12 @@ pub const latex_headers: &str = "\\usepackage{tikz}
             font = {\\Huge\\bfseries\\sffamily}
         },
     terminal1/label/.initial=$\\mathbf{1}$,
+    suppressed/.style={
+            shape=circle,
+            draw=black!40,
+            dashed,
+            minimum size=10pt,
+        },
     choice0/.style={
             draw,
             color=green,
@@ -367,6 +443,21 @@ pub const latex_headers: &str = "\\usepackage{tikz}
             line width=2.5pt,
             ->
         },
+    choice3/.style={
+            draw,
+            color=red,
+            line width=1.5pt,
+            dotted,
+            ->,
+            shorten >=1pt,
+        },
+    complement/.style={
+            postaction={decorate},
+            decoration={
+                    markings,
+                    mark=at position -12pt with {\\fill circle (5pt);}
+                },
+        },
     layerDivider/.style={
             color=gray,
             dashed
@@ -375,4 +466,12 @@ pub const latex_headers: &str = "\\usepackage{tikz}
             anchor=west,
             font = {\\Huge\\bfseries\\sffamily}
         },
+    legendEntry/.style={
+            shape=rectangle,
+            draw=black,
+            rounded corners,
+            anchor=west,
+            minimum height=20pt,
+            font = {\\Large\\bfseries\\sffamily}
+        },
 }";
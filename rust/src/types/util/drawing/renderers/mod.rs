@@ -1,4 +1,7 @@
 pub mod latex_renderer;
+pub mod svg_renderer;
 pub mod util;
+#[cfg(feature = "web")]
 pub mod webgl;
+#[cfg(feature = "web")]
 pub mod webgl_renderer;
@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::{
+    types::util::{
+        drawing::{
+            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            layout_rules::LayoutRules,
+            renderer::{GroupPath, GroupSelection, LegendEntry, Renderer},
+        },
+        graph_structure::graph_structure::EdgeType,
+    },
+    util::{
+        color::{Color, TransparentColor},
+        point::Point,
+        transformation::Transformation,
+        transition::Easing,
+    },
+};
+
+use super::util::rendering_config::{
+    EdgeRenderingType, LayerRenderingColorConfig, RenderingColorConfig,
+};
+
+/// A renderer that creates a self-contained SVG image of the layout, using the same edge types and colors as the webgl renderer
+pub struct SvgRenderer<L: LayoutRules>
+where
+    L::NS: SvgNodeStyle,
+    L::LS: SvgLayerStyle,
+{
+    edge_types: HashMap<EdgeType<L::T>, EdgeRenderingType>,
+    layer_colors: LayerRenderingColorConfig,
+    text_color: Color,
+    output: String,
+    layout: Option<DiagramLayout<L::T, L::NS, L::LS>>,
+    legend: Vec<LegendEntry>,
+}
+
+// These settings mirror the constants of the webgl node renderer
+const CORNER_RADIUS: f32 = 0.3;
+const BORDER_OFFSET: f32 = 0.3;
+const BORDER_WIDTH: f32 = 0.2;
+/// The margin around the diagram and its layer labels
+const MARGIN: f32 = 0.5;
+/// An estimate of the width of a character of a label, relative to the font size
+const CHAR_WIDTH: f32 = 0.6;
+
+impl<L: LayoutRules> SvgRenderer<L>
+where
+    L::NS: SvgNodeStyle,
+    L::LS: SvgLayerStyle,
+{
+    pub fn new(
+        edge_types: HashMap<EdgeType<L::T>, EdgeRenderingType>,
+        layer_colors: LayerRenderingColorConfig,
+        text_color: Color,
+    ) -> SvgRenderer<L> {
+        SvgRenderer {
+            edge_types,
+            layer_colors,
+            text_color,
+            output: "".into(),
+            layout: None,
+            legend: Vec::new(),
+        }
+    }
+
+    pub fn get_output(&self) -> String {
+        self.output.clone()
+    }
+}
+
+impl<L: LayoutRules> Renderer<L> for SvgRenderer<L>
+where
+    L::NS: SvgNodeStyle,
+    L::LS: SvgLayerStyle,
+{
+    fn set_transform(&mut self, _transform: Transformation) {
+        // The image always contains the whole diagram, its viewBox is derived from the layout
+    }
+
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.layout = Some(layout.clone());
+    }
+
+    fn render(&mut self, time: u32) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut min_y = f32::MAX;
+        let mut max_y = f32::MIN;
+        let mut include = |p: Point| {
+            min_x = min_x.min(p.x);
+            max_x = max_x.max(p.x);
+            min_y = min_y.min(p.y);
+            max_y = max_y.max(p.y);
+        };
+
+        let nodes = layout
+            .groups
+            .iter()
+            .sorted_by_key(|(id, _)| **id)
+            .filter_map(|(_, group)| {
+                let exists = group.exists.get(time);
+                if exists <= 0.0 {
+                    return None;
+                }
+                let rect = group.get_rect(Some(time));
+                include(Point {
+                    x: rect.x,
+                    y: rect.y,
+                });
+                include(Point {
+                    x: rect.x + rect.width,
+                    y: rect.y + rect.height,
+                });
+                let style = group.style.get(time);
+                let radius = CORNER_RADIUS.min(0.5 * rect.width).min(0.5 * rect.height);
+
+                let mut out = format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"{}\"{}/>",
+                    rect.x,
+                    -(rect.y + rect.height),
+                    rect.width,
+                    rect.height,
+                    radius,
+                    to_rgb(style.get_color()),
+                    to_opacity("opacity", exists)
+                );
+                let outline = style.get_outline_color();
+                if outline.3 > 0.0 {
+                    // The outline is drawn around the node, at the same distance as in the webgl renderer
+                    let offset = BORDER_OFFSET - 0.5 * BORDER_WIDTH;
+                    out += &format!(
+                        "\n    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"{}/>",
+                        rect.x - offset,
+                        -(rect.y + rect.height) - offset,
+                        rect.width + 2.0 * offset,
+                        rect.height + 2.0 * offset,
+                        radius + offset,
+                        to_rgb(Color(outline.0, outline.1, outline.2)),
+                        BORDER_WIDTH,
+                        to_opacity("opacity", exists * outline.3)
+                    );
+                }
+                if let Some(label) = style.get_label() {
+                    out += &format!(
+                        "\n    <text x=\"{}\" y=\"{}\" class=\"label\"{}>{}</text>",
+                        rect.x + 0.5 * rect.width,
+                        -(rect.y + 0.5 * rect.height),
+                        to_opacity("opacity", exists),
+                        sanitize(&label)
+                    );
+                }
+                Some(out)
+            })
+            .join("\n    ");
+
+        let edges = layout
+            .groups
+            .iter()
+            .sorted_by_key(|(id, _)| **id)
+            .flat_map(|(_, group)| {
+                group
+                    .edges
+                    .iter()
+                    .map(move |(edge_data, edge)| (group, edge_data, edge))
+            })
+            .filter_map(|(group, edge_data, edge)| {
+                let exists = edge.exists.get(time);
+                if exists <= 0.0 {
+                    return None;
+                }
+                let edge_type = self.edge_types.get(&edge_data.edge_type)?;
+                let target_group = layout.groups.get(&edge_data.to)?;
+                let start = group.position.get(time) + edge.start_offset.get(time);
+                let end = target_group.position.get(time) + edge.end_offset.get(time);
+                let curve_offset = edge.curve_offset.get(time);
+
+                let points = Some(start)
+                    .into_iter()
+                    .chain(
+                        edge.points
+                            .iter()
+                            .filter(|p| p.exists.get(time) > 0.0)
+                            .map(|p| p.point.get(time)),
+                    )
+                    .chain(Some(end))
+                    .collect_vec();
+                for &point in &points {
+                    include(point);
+                }
+
+                let path = points
+                    .iter()
+                    .tuple_windows()
+                    .map(|(&from, &to)| get_segment_path(from, to, curve_offset))
+                    .join(" ");
+                let dashes = if edge_type.dash_transparent > 0.0 {
+                    format!(
+                        " stroke-dasharray=\"{} {}\"",
+                        edge_type.dash_solid, edge_type.dash_transparent
+                    )
+                } else {
+                    " stroke-linecap=\"round\"".to_string()
+                };
+                let mut out = format!(
+                    "<path d=\"M {} {} {}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"{}{}/>",
+                    start.x,
+                    -start.y,
+                    path,
+                    to_rgb(edge_type.color),
+                    edge_type.width,
+                    dashes,
+                    to_opacity("opacity", exists)
+                );
+
+                if edge_type.marker_radius > 0.0 {
+                    let from = points[points.len() - 2];
+                    let marker =
+                        get_marker_position(from, end, curve_offset, 0.5 + edge_type.marker_radius);
+                    out += &format!(
+                        "\n    <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"{}/>",
+                        marker.x,
+                        -marker.y,
+                        edge_type.marker_radius,
+                        to_rgb(edge_type.color),
+                        to_opacity("opacity", exists)
+                    );
+                }
+                Some(out)
+            })
+            .join("\n    ");
+
+        if min_x > max_x {
+            min_x = 0.0;
+            max_x = 0.0;
+            min_y = 0.0;
+            max_y = 0.0;
+        }
+
+        let label_width = layout
+            .layers
+            .iter()
+            .map(|layer| layer.style.get(time).get_label().chars().count())
+            .chain(self.legend.iter().map(|entry| entry.label.chars().count()))
+            .max()
+            .unwrap_or(0) as f32
+            * CHAR_WIDTH
+            + 2.0 * MARGIN;
+        let left = min_x - MARGIN - label_width;
+        let right = max_x + MARGIN;
+        for layer in &layout.layers {
+            if layer.exists.get(time) > 0.0 {
+                min_y = min_y.min(layer.bottom.get(time).min(layer.top.get(time)));
+                max_y = max_y.max(layer.bottom.get(time).max(layer.top.get(time)));
+            }
+        }
+
+        // The legend entries are listed below the layer labels
+        let legend_top = min_y;
+        let legend = self
+            .legend
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let top = legend_top - MARGIN - 1.2 * index as f32;
+                format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" rx=\"{}\" fill=\"{}\"/>\n    \
+                    <text x=\"{}\" y=\"{}\" class=\"legend\">{}</text>",
+                    left + MARGIN,
+                    -top,
+                    entry.label.chars().count() as f32 * CHAR_WIDTH + 1.0,
+                    CORNER_RADIUS,
+                    to_rgb(entry.color),
+                    left + MARGIN + 0.5,
+                    -(top - 0.5),
+                    sanitize(&entry.label)
+                )
+            })
+            .join("\n    ");
+        if !self.legend.is_empty() {
+            min_y = legend_top - MARGIN - 1.2 * (self.legend.len() - 1) as f32 - 1.0;
+        }
+
+        let layers = layout
+            .layers
+            .iter()
+            .filter_map(|layer| {
+                let exists = layer.exists.get(time);
+                if exists <= 0.0 {
+                    return None;
+                }
+                let top = layer.top.get(time).max(layer.bottom.get(time));
+                let bottom = layer.top.get(time).min(layer.bottom.get(time));
+                let background = if layer.index.get(time).round() as i64 % 2 == 0 {
+                    self.layer_colors.background1
+                } else {
+                    self.layer_colors.background2
+                };
+                Some(format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>\n    \
+                    <text x=\"{}\" y=\"{}\" class=\"layer\"{}>{}</text>",
+                    left,
+                    -top,
+                    right - left,
+                    top - bottom,
+                    to_rgb(Color(background.0, background.1, background.2)),
+                    to_opacity("fill-opacity", exists * background.3),
+                    left + MARGIN,
+                    -0.5 * (top + bottom),
+                    to_opacity("opacity", exists),
+                    sanitize(&layer.style.get(time).get_label())
+                ))
+            })
+            .join("\n    ");
+
+        let top = -max_y - MARGIN;
+        let height = max_y - min_y + 2.0 * MARGIN;
+        self.output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">\n    \
+            <style>\n        \
+            text {{ font-family: Roboto, sans-serif; font-weight: bold; font-size: 1px; dominant-baseline: central; fill: {}; }}\n        \
+            .label {{ text-anchor: middle; }}\n        \
+            .layer {{ text-anchor: start; fill: {}; }}\n        \
+            .legend {{ text-anchor: start; }}\n    \
+            </style>\n    \
+            <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n    \
+            <!-- Layers -->\n    \
+            {}\n    \
+            <!-- Edges -->\n    \
+            {}\n    \
+            <!-- Nodes -->\n    \
+            {}\n    \
+            <!-- Legend -->\n    \
+            {}\n\
+            </svg>",
+            left,
+            top,
+            right - left,
+            height,
+            (right - left) * 20.0,
+            height * 20.0,
+            to_rgb(self.text_color),
+            to_rgb(self.layer_colors.text),
+            left,
+            top,
+            right - left,
+            height,
+            to_rgb(Color(
+                self.layer_colors.background1.0,
+                self.layer_colors.background1.1,
+                self.layer_colors.background1.2
+            )),
+            layers,
+            edges,
+            nodes,
+            legend
+        );
+    }
+
+    fn select_groups(&mut self, _selection: GroupSelection, _old_selection: GroupSelection) {
+        // Selections are not part of the exported image
+    }
+
+    fn highlight_path(&mut self, _path: Option<GroupPath<L::T>>) {
+        // Highlights are not part of the exported image
+    }
+
+    fn set_legend(&mut self, legend: Vec<LegendEntry>) {
+        self.legend = legend;
+    }
+
+    fn set_easing(&mut self, _easing: Easing) {
+        // The exported image shows the diagram at a single point in time
+    }
+
+    fn set_colors(&mut self, colors: RenderingColorConfig<L::T>) {
+        self.edge_types = colors.edge_types;
+        self.layer_colors = colors.layers;
+        self.text_color = colors.nodes.text;
+    }
+}
+
+/// Retrieves the path command for a segment, which is curved along a circle in the same way as in the webgl edge renderer
+fn get_segment_path(from: Point, to: Point, curve_offset: f32) -> String {
+    let half_length = 0.5 * from.distance(&to);
+    let curve_width = curve_offset.abs().min(half_length);
+    if curve_width <= 0.0 {
+        return format!("L {} {}", to.x, -to.y);
+    }
+    let radius = (curve_width * curve_width + half_length * half_length) / (2.0 * curve_width);
+    // The y-axis is flipped in SVG, which turns a curve to the left of the direction into a clockwise arc
+    let sweep = (curve_offset > 0.0) as u8;
+    format!("A {} {} 0 0 {} {} {}", radius, radius, sweep, to.x, -to.y)
+}
+
+/// Retrieves the center of the marker that is placed at the given distance from the end of the segment
+fn get_marker_position(from: Point, to: Point, curve_offset: f32, distance: f32) -> Point {
+    let delta = to - from;
+    let length = delta.length();
+    if length <= 0.0 {
+        return to;
+    }
+    let dir = delta * (1.0 / length);
+    let half_length = 0.5 * length;
+    let curve_width = curve_offset.abs().min(half_length);
+    if curve_width <= 0.0 {
+        return to - dir * distance;
+    }
+
+    let dir_orth = Point {
+        x: -dir.y,
+        y: dir.x,
+    };
+    let center_delta =
+        (curve_width * curve_width - half_length * half_length) / (2.0 * curve_width);
+    let side = if curve_offset > 0.0 { 1.0 } else { -1.0 };
+    let center = (from + to) * 0.5 + dir_orth * (center_delta * side);
+    let radius = center_delta.abs() + curve_width;
+
+    // Rotate the end point around the center, towards the start of the segment
+    let angle = distance / radius;
+    let offset = to - center;
+    let rotate = |angle: f32| {
+        center
+            + Point {
+                x: offset.x * angle.cos() - offset.y * angle.sin(),
+                y: offset.x * angle.sin() + offset.y * angle.cos(),
+            }
+    };
+    let (option1, option2) = (rotate(angle), rotate(-angle));
+    if option1.distance(&from) < option2.distance(&from) {
+        option1
+    } else {
+        option2
+    }
+}
+
+fn to_rgb(color: Color) -> String {
+    format!(
+        "rgb({}, {}, {})",
+        (color.0.clamp(0.0, 1.0) * 255.0).round(),
+        (color.1.clamp(0.0, 1.0) * 255.0).round(),
+        (color.2.clamp(0.0, 1.0) * 255.0).round()
+    )
+}
+
+fn to_opacity(attribute: &str, opacity: f32) -> String {
+    if opacity >= 1.0 {
+        "".to_string()
+    } else {
+        format!(" {}=\"{}\"", attribute, opacity.max(0.0))
+    }
+}
+
+fn sanitize(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub trait SvgNodeStyle: NodeStyle {
+    fn get_color(&self) -> Color;
+    fn get_outline_color(&self) -> TransparentColor;
+    fn get_label(&self) -> Option<String>;
+}
+pub trait SvgLayerStyle: LayerStyle {
+    fn get_label(&self) -> String;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxidd::LevelNo;
+
+    use crate::{
+        types::util::{
+            drawing::{
+                diagram_layout::{EdgeLayout, LayerLayout, NodeGroupLayout},
+                layouts::random_test_layout::RandomTestLayout,
+            },
+            graph_structure::{
+                graph_manipulators::label_adjusters::group_label_adjuster::GroupLabelAdjuster,
+                grouped_graph_structure::EdgeData, oxidd_graph_structure::OxiddGraphStructure,
+            },
+            group_manager::GroupManager,
+        },
+        util::{
+            dummy_bdd::DummyBDDFunction,
+            transition::{Interpolatable, Transition},
+        },
+    };
+
+    #[derive(Clone)]
+    struct TestNodeStyle {
+        color: Color,
+        label: Option<String>,
+    }
+    impl Interpolatable for TestNodeStyle {
+        fn mix(&self, other: &Self, _frac: f32) -> Self {
+            other.clone()
+        }
+    }
+    impl NodeStyle for TestNodeStyle {}
+    impl SvgNodeStyle for TestNodeStyle {
+        fn get_color(&self) -> Color {
+            self.color.clone()
+        }
+        fn get_outline_color(&self) -> TransparentColor {
+            TransparentColor(0.0, 0.0, 0.0, 0.0)
+        }
+        fn get_label(&self) -> Option<String> {
+            self.label.clone()
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestLayerStyle(String);
+    impl Interpolatable for TestLayerStyle {
+        fn mix(&self, other: &Self, _frac: f32) -> Self {
+            other.clone()
+        }
+    }
+    impl LayerStyle for TestLayerStyle {
+        fn squash(layers: Vec<Self>) -> Self {
+            layers[0].clone()
+        }
+    }
+    impl SvgLayerStyle for TestLayerStyle {
+        fn get_label(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    type TestLayout = RandomTestLayout<
+        GroupLabelAdjuster<
+            TestNodeStyle,
+            TestLayerStyle,
+            GroupManager<OxiddGraphStructure<(), DummyBDDFunction, String>>,
+        >,
+    >;
+
+    fn edge_type(color: Color, dash_solid: f32, dash_transparent: f32) -> EdgeRenderingType {
+        EdgeRenderingType {
+            color: color.clone(),
+            select_color: color.clone(),
+            partial_select_color: color.clone(),
+            hover_color: color.clone(),
+            partial_hover_color: color,
+            width: 0.15,
+            dash_solid,
+            dash_transparent,
+            marker_radius: 0.0,
+        }
+    }
+
+    fn node(
+        x: f32,
+        y: f32,
+        label: &str,
+        edges: Vec<EdgeData<()>>,
+    ) -> NodeGroupLayout<(), TestNodeStyle> {
+        NodeGroupLayout {
+            position: Transition::plain(Point { x, y }),
+            size: Transition::plain(Point { x: 1.0, y: 1.0 }),
+            exists: Transition::plain(1.0),
+            edges: edges
+                .into_iter()
+                .map(|edge| {
+                    (
+                        edge,
+                        EdgeLayout {
+                            start_offset: Transition::plain(Point { x: 0.0, y: 0.0 }),
+                            end_offset: Transition::plain(Point { x: 0.0, y: 1.0 }),
+                            points: Vec::new(),
+                            exists: Transition::plain(1.0),
+                            curve_offset: Transition::plain(0.0),
+                        },
+                    )
+                })
+                .collect(),
+            level_range: (0, 0),
+            style: Transition::plain(TestNodeStyle {
+                color: Color(1.0, 0.0, 0.0),
+                label: Some(label.to_string()),
+            }),
+        }
+    }
+
+    fn layer(index: usize, label: &str) -> LayerLayout<TestLayerStyle> {
+        let top = 4.0 - 2.0 * index as f32;
+        LayerLayout {
+            start_layer: index as LevelNo,
+            end_layer: index as LevelNo,
+            top: Transition::plain(top),
+            bottom: Transition::plain(top - 2.0),
+            index: Transition::plain(index as f32),
+            exists: Transition::plain(1.0),
+            style: Transition::plain(TestLayerStyle(label.to_string())),
+        }
+    }
+
+    #[test]
+    fn renders_nodes_and_dashed_edges() {
+        let solid = EdgeType::new((), 0);
+        let dashed = EdgeType::new((), 1);
+        let mut renderer = SvgRenderer::<TestLayout>::new(
+            vec![
+                (solid, edge_type(Color(0.0, 0.0, 0.0), 1.0, 0.0)),
+                (dashed, edge_type(Color(0.0, 0.0, 1.0), 0.2, 0.1)),
+            ]
+            .into_iter()
+            .collect(),
+            LayerRenderingColorConfig {
+                background1: TransparentColor(1.0, 1.0, 1.0, 1.0),
+                background2: TransparentColor(0.9, 0.9, 0.9, 1.0),
+                text: Color(0.0, 0.0, 0.0),
+            },
+            Color(0.0, 0.0, 0.0),
+        );
+        let layout = DiagramLayout {
+            groups: vec![
+                (
+                    1,
+                    node(
+                        0.0,
+                        3.0,
+                        "a",
+                        vec![
+                            EdgeData::new(0, 0, 1, solid),
+                            EdgeData::new(0, 0, 1, dashed),
+                        ],
+                    ),
+                ),
+                (0, node(0.0, 0.0, "T", vec![])),
+            ]
+            .into_iter()
+            .collect(),
+            layers: vec![layer(0, "a"), layer(1, "<T>")],
+        };
+        renderer.update_layout(&layout);
+        renderer.render(0);
+        let output = renderer.get_output();
+
+        // The y-axis is flipped, such that the rectangles extend upwards from the bottom center of the nodes
+        assert!(output.contains(
+            "<rect x=\"-0.5\" y=\"-1\" width=\"1\" height=\"1\" rx=\"0.3\" fill=\"rgb(255, 0, 0)\"/>"
+        ));
+        assert!(output.contains(
+            "<rect x=\"-0.5\" y=\"-4\" width=\"1\" height=\"1\" rx=\"0.3\" fill=\"rgb(255, 0, 0)\"/>"
+        ));
+        assert!(output.contains("<text x=\"0\" y=\"-3.5\" class=\"label\">a</text>"));
+        assert!(output.contains(
+            "<path d=\"M 0 -3 L 0 -1\" fill=\"none\" stroke=\"rgb(0, 0, 0)\" stroke-width=\"0.15\" stroke-linecap=\"round\"/>"
+        ));
+        assert!(output.contains(
+            "<path d=\"M 0 -3 L 0 -1\" fill=\"none\" stroke=\"rgb(0, 0, 255)\" stroke-width=\"0.15\" stroke-dasharray=\"0.2 0.1\"/>"
+        ));
+        // Layer labels are escaped
+        assert!(output.contains(">&lt;T&gt;</text>"));
+        assert_eq!(output.matches("<path").count(), 2);
+    }
+}
@@ -1 +1,2 @@
 pub mod Font;
+pub mod rendering_config;
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::{
+    types::util::graph_structure::graph_structure::{DrawTag, EdgeType},
+    util::color::{Color, TransparentColor},
+};
+
+// The rendering settings that are shared between the renderers, such that exports look like the interactive diagram
+
+#[derive(Clone)]
+pub struct EdgeRenderingType {
+    pub color: Color,
+    pub select_color: Color,
+    pub partial_select_color: Color,
+    pub hover_color: Color,
+    pub partial_hover_color: Color,
+    pub width: f32,
+    pub dash_solid: f32, // The distance per period over which this dash should be solid
+    pub dash_transparent: f32, // The distance per
+    pub marker_radius: f32, // The radius of the dot drawn near the end of the edge, or 0 for no dot
+}
+
+#[derive(Clone)]
+pub struct LayerRenderingColorConfig {
+    pub background1: TransparentColor,
+    pub background2: TransparentColor,
+    pub text: Color,
+}
+
+#[derive(Clone)]
+pub struct NodeRenderingColorConfig {
+    pub select: TransparentColor,
+    pub partial_select: TransparentColor,
+    pub hover: TransparentColor,
+    pub partial_hover: TransparentColor,
+    pub text: Color,
+}
+
+/// All colors used by a renderer, which can be replaced while rendering to switch between themes
+#[derive(Clone)]
+pub struct RenderingColorConfig<T: DrawTag> {
+    pub edge_types: HashMap<EdgeType<T>, EdgeRenderingType>,
+    pub nodes: NodeRenderingColorConfig,
+    pub layers: LayerRenderingColorConfig,
+}
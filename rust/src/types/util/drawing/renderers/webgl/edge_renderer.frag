@@ -12,6 +12,7 @@ struct EdgeType {
     float width;
     float dashSolid;
     float dashTransparent;
+    float markerRadius;
 };
 
 out vec4 outColor;
@@ -23,6 +24,8 @@ in vec2 outPos;
 
 in float outType;
 in float outState;
+in float outMarker;
+in float outHighlight;
 in float curCurveOffset;
 in float radius;
 in vec2 center;
@@ -53,6 +56,8 @@ void main() {
     float proj;
     float projPer;
     bool onLine;
+    vec2 markerCenter; // The center of the dot drawn near the end, just outside of the node circle
+    float markerDist = 0.5f + typeData.markerRadius;
 
     if(abs(curCurveOffset) > 0.0f) {
         vec2 centerDelta = outPos - center;
@@ -84,6 +89,9 @@ void main() {
         if(distDelta > halfWidth) {
             onLine = false;
         }
+
+        float markerAngle = endAngle + sign(startAngle - endAngle) * markerDist / radius;
+        markerCenter = center + radius * vec2(cos(markerAngle), sin(markerAngle));
     } else {
         vec2 line = curEnd - curStart;
         vec2 point = outPos - curStart;
@@ -91,6 +99,8 @@ void main() {
         proj = dot(point, normalize(line));
         projPer = proj / length(line);
         onLine = projPer >= 0.0f && projPer <= 1.0f;
+
+        markerCenter = curEnd - normalize(line) * markerDist;
     }
 
     if(!onLine) {
@@ -113,6 +123,9 @@ void main() {
             alpha = 0.0f;
     }
 
+    if(outMarker > 0.0f && length(outPos - markerCenter) <= typeData.markerRadius)
+        alpha = 1.0f;
+
     vec3 typeColor = typeData.color;
     if(outState >= 1.f)
         typeColor = typeData.partialHoverColor;
@@ -122,5 +135,10 @@ void main() {
         typeColor = typeData.partialSelectColor;
     if(outState >= 4.f)
         typeColor = typeData.selectColor;
+    if(outHighlight >= 2.f)
+        typeColor = typeData.selectColor;
+    // Edges that are not part of the highlighted path are dimmed
+    if(outHighlight >= 1.f && outHighlight < 2.f)
+        alpha *= 0.15f;
     outColor = vec4(typeColor * curExists * alpha, curExists * alpha);
 }
\ No newline at end of file
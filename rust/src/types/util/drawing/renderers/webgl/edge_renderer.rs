@@ -13,17 +13,24 @@ use crate::{
         renderers::webgl::util::set_animated_data::{self, set_animated_data},
     },
     util::{
-        color::Color, logging::console, matrix4::Matrix4, point::Point, transition::Transition,
+        color::Color,
+        logging::console,
+        matrix4::Matrix4,
+        point::Point,
+        transition::{Easing, Transition},
     },
     wasm_interface::NodeGroupID,
 };
 
 use super::util::vertex_renderer::VertexRenderer;
+pub use crate::types::util::drawing::renderers::util::rendering_config::EdgeRenderingType;
 
 pub struct EdgeRenderer {
     vertex_renderer: VertexRenderer,
     edge_types: Vec<EdgeRenderingType>,
     node_edge_indices: MultiMap<NodeGroupID, usize>,
+    /// The start group, end group and type of the edge that each segment belongs to
+    segment_edges: Vec<(NodeGroupID, NodeGroupID, usize)>,
 }
 
 pub struct Edge {
@@ -37,23 +44,13 @@ pub struct Edge {
     pub shift: Transition<f32>, // Some sideways shift
 }
 
-#[derive(Clone)]
-pub struct EdgeRenderingType {
-    pub color: Color,
-    pub select_color: Color,
-    pub partial_select_color: Color,
-    pub hover_color: Color,
-    pub partial_hover_color: Color,
-    pub width: f32,
-    pub dash_solid: f32, // The distance per period over which this dash should be solid
-    pub dash_transparent: f32, // The distance per
-}
 type Segment = (
     Transition<Point>,
     Transition<Point>,
     f32,             /* type*/
     Transition<f32>, /* curvature */
     Transition<f32>, /* exists */
+    f32,             /* marker */
 );
 
 impl EdgeRenderer {
@@ -77,6 +74,18 @@ impl EdgeRenderer {
             vertex_renderer,
             edge_types,
             node_edge_indices: MultiMap::new(),
+            segment_edges: Vec::new(),
+        }
+    }
+
+    pub fn get_edge_types(&self) -> &Vec<EdgeRenderingType> {
+        &self.edge_types
+    }
+
+    /// Replaces the rendering data of the edge types, the number of types has to remain the same
+    pub fn set_edge_types(&mut self, edge_types: Vec<EdgeRenderingType>) {
+        if edge_types.len() == self.edge_types.len() {
+            self.edge_types = edge_types;
         }
     }
 
@@ -89,10 +98,13 @@ impl EdgeRenderer {
                 let curve_offset = edge.shift;
                 let exists = edge.exists;
                 points.push(edge.end);
+                let last = points.len() - 1;
                 let edge_segments = points
                     .iter()
-                    .scan(edge.start, |prev, item| {
-                        let out = (*prev, *item, edge_type as f32, curve_offset, exists);
+                    .enumerate()
+                    .scan(edge.start, |prev, (index, item)| {
+                        let marker = if index == last { 1. } else { 0. };
+                        let out = (*prev, *item, edge_type as f32, curve_offset, exists, marker);
                         *prev = *item;
                         Some((out, edge))
                     })
@@ -106,32 +118,42 @@ impl EdgeRenderer {
             .enumerate()
             .flat_map(|(index, (segment, edge))| [(edge.start_node, index), (edge.end_node, index)])
             .collect();
+        self.segment_edges = segments
+            .iter()
+            .map(|(_, edge)| (edge.start_node, edge.end_node, edge.edge_type))
+            .collect();
 
         let segments6 = segments.iter().flat_map(|(edge, _)| repeat(edge).take(6));
         set_animated_data(
             "start",
-            segments6.clone().map(|(start, _, _, _, _)| start.clone()),
+            segments6
+                .clone()
+                .map(|(start, _, _, _, _, _)| start.clone()),
             |start| [start.x, start.y],
             context,
             &mut self.vertex_renderer,
         );
         set_animated_data(
             "end",
-            segments6.clone().map(|(_, end, _, _, _)| end.clone()),
+            segments6.clone().map(|(_, end, _, _, _, _)| end.clone()),
             |end| [end.x, end.y],
             context,
             &mut self.vertex_renderer,
         );
         set_animated_data(
             "curveOffset",
-            segments6.clone().map(|(_, _, _, offset, _)| offset.clone()),
+            segments6
+                .clone()
+                .map(|(_, _, _, offset, _, _)| offset.clone()),
             |offset| [offset],
             context,
             &mut self.vertex_renderer,
         );
         set_animated_data(
             "exists",
-            segments6.clone().map(|(_, _, _, _, exists)| exists.clone()),
+            segments6
+                .clone()
+                .map(|(_, _, _, _, exists, _)| exists.clone()),
             |exists| [exists],
             context,
             &mut self.vertex_renderer,
@@ -142,13 +164,28 @@ impl EdgeRenderer {
             "type",
             &segments6
                 .clone()
-                .map(|(_, _, edge_type, _, _)| edge_type.clone())
+                .map(|(_, _, edge_type, _, _, _)| edge_type.clone())
+                .collect::<Box<_>>(),
+            1,
+        );
+        self.vertex_renderer.set_data(
+            context,
+            "marker",
+            &segments6
+                .clone()
+                .map(|(_, _, _, _, _, marker)| *marker)
                 .collect::<Box<_>>(),
             1,
         );
         self.vertex_renderer.set_data(
             context,
             "state",
+            &segments6.clone().map(|_| 0.).collect::<Box<_>>(),
+            1,
+        );
+        self.vertex_renderer.set_data(
+            context,
+            "highlight",
             &segments6.map(|_| 0.).collect::<Box<_>>(),
             1,
         );
@@ -237,9 +274,34 @@ impl EdgeRenderer {
         self.vertex_renderer.send_data(context);
     }
 
-    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+    /// Dims all edges that are not part of the given path, where the edges of the path are identified by their start group, end group and type. Without a path, no edges are dimmed
+    pub fn update_highlight(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        path: Option<&HashSet<(NodeGroupID, NodeGroupID, usize)>>,
+    ) {
+        let highlight = self
+            .segment_edges
+            .iter()
+            .flat_map(|edge| {
+                let value = match path {
+                    None => 0.,
+                    Some(path) if path.contains(edge) => 2.,
+                    Some(_) => 1.,
+                };
+                repeat(value).take(6)
+            })
+            .collect::<Box<_>>();
+        self.vertex_renderer
+            .set_data(context, "highlight", &highlight, 1);
+        self.vertex_renderer.send_data(context);
+    }
+
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing) {
         self.vertex_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
         for (index, edge_type) in self.edge_types.iter().enumerate() {
             let c = edge_type.color.clone();
             self.vertex_renderer
@@ -284,6 +346,11 @@ impl EdgeRenderer {
                 &format!("edgeTypes[{index}].dashTransparent"),
                 |u| context.uniform1f(u, edge_type.dash_transparent),
             );
+            self.vertex_renderer.set_uniform(
+                context,
+                &format!("edgeTypes[{index}].markerRadius"),
+                |u| context.uniform1f(u, edge_type.marker_radius),
+            );
         }
 
         self.vertex_renderer
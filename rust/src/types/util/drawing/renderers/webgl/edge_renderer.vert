@@ -8,6 +8,7 @@ struct EdgeType {
     float width;
     float dashSolid;
     float dashTransparent;
+    float markerRadius;
 };
 
 in vec2 start;
@@ -28,8 +29,12 @@ in vec2 existsTransition;
 
 in float type;
 in float state;
+in float marker;
+in float highlight;
 out float outType;
 out float outState;
+out float outMarker;
+out float outHighlight;
 
 out float curExists;
 out vec2 curStart;
@@ -42,18 +47,31 @@ out vec2 center;
 uniform EdgeType edgeTypes[/*$type_count {*/1/*}*/];
 uniform mat4 transform;
 uniform float time;
+uniform int easing;
+
+float ease(float per) {
+    if(easing == 1) {
+        return per * per * (3.0f - 2.0f * per);
+    } else if(easing == 2) {
+        float remaining = 1.0f - per;
+        return 1.0f - remaining * remaining * remaining;
+    }
+    return per;
+}
 
 float getPer(vec2 transition) {
-    return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
+    return ease(max(0.0f, min((time - transition.x) / transition.y, 1.0f)));
 }
 
 void main() {
     outType = type;
     outState = state;
+    outMarker = marker;
+    outHighlight = highlight;
 
     float startPer = getPer(startTransition);
     curStart = startPer * start + (1.0f - startPer) * startOld;
-    float halfWidth = 0.5f * edgeTypes[int(type)].width;
+    float halfWidth = max(0.5f * edgeTypes[int(type)].width, marker * edgeTypes[int(type)].markerRadius);
 
     float endPer = getPer(endTransition);
     curEnd = mix(endOld, end, endPer);
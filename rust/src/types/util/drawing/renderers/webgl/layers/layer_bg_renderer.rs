@@ -6,7 +6,12 @@ use crate::{
         text::text_renderer::Text,
         util::{set_animated_data::set_animated_data, vertex_renderer::VertexRenderer},
     },
-    util::{color::TransparentColor, logging::console, matrix4::Matrix4, transition::Transition},
+    util::{
+        color::TransparentColor,
+        logging::console,
+        matrix4::Matrix4,
+        transition::{Easing, Transition},
+    },
 };
 
 use super::{
@@ -99,9 +104,16 @@ impl LayerDivisionRenderer for LayerBgRenderer {
         });
     }
 
-    fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+    fn set_colors(&mut self, color1: TransparentColor, color2: TransparentColor) {
+        self.bg_color1 = color1;
+        self.bg_color2 = color2;
+    }
+
+    fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing) {
         self.bg_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.bg_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
 
         let TransparentColor(r1, g1, b1, a1) = self.bg_color1;
         self.bg_renderer
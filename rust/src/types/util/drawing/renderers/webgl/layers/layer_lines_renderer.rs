@@ -8,7 +8,11 @@ use crate::{
     types::util::drawing::renderers::webgl::{
         text::text_renderer::Text, util::vertex_renderer::VertexRenderer,
     },
-    util::{logging::console, matrix4::Matrix4, transition::Transition},
+    util::{
+        logging::console,
+        matrix4::Matrix4,
+        transition::{Easing, Transition},
+    },
 };
 
 use super::{
@@ -115,9 +119,11 @@ impl LayerDivisionRenderer for LayerLinesRenderer {
         });
     }
 
-    fn render(&mut self, context: &Gl, time: u32) {
+    fn render(&mut self, context: &Gl, time: u32, easing: Easing) {
         self.vertex_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
 
         let (r1, g1, b1, a1) = self.color;
         self.vertex_renderer
@@ -10,11 +10,22 @@ in float existsDuration;
 
 uniform mat4 transform;
 uniform float time;
+uniform int easing;
+
+float ease(float per) {
+    if(easing == 1) {
+        return per * per * (3.0f - 2.0f * per);
+    } else if(easing == 2) {
+        float remaining = 1.0f - per;
+        return 1.0f - remaining * remaining * remaining;
+    }
+    return per;
+}
 
 out float curExists;
 
 void main() {
-    float existsPer = min((time - existsStartTime) / existsDuration, 1.0f);
+    float existsPer = ease(max(0.0f, min((time - existsStartTime) / existsDuration, 1.0f)));
     curExists = existsPer * exists + (1.0f - existsPer) * existsOld;
 
     float side = gl_VertexID % 2 == 0 ? -1.f : 1.f;
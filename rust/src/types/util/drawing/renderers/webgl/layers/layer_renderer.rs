@@ -4,9 +4,16 @@ use itertools::Itertools;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
-    types::util::drawing::renderers::{util::Font::Font, webgl::text::text_renderer::Text},
+    types::util::drawing::renderers::{
+        util::{rendering_config::LayerRenderingColorConfig, Font::Font},
+        webgl::text::text_renderer::Text,
+    },
     util::{
-        color::Color, logging::console, matrix4::Matrix4, point::Point, transition::Transition,
+        color::{Color, TransparentColor},
+        logging::console,
+        matrix4::Matrix4,
+        point::Point,
+        transition::{Easing, Transition},
     },
 };
 
@@ -42,6 +49,12 @@ impl LayerRenderer {
         }
     }
 
+    pub fn set_colors(&mut self, colors: LayerRenderingColorConfig) {
+        self.division_renderer
+            .set_colors(colors.background1, colors.background2);
+        self.text_renderer.set_color(colors.text);
+    }
+
     pub fn set_layers(&mut self, context: &WebGl2RenderingContext, layers: &Vec<Layer>) {
         self.division_renderer.set_layers(context, layers);
 
@@ -58,6 +71,7 @@ impl LayerRenderer {
                             duration: b.duration,
                             old: Point { x: 0., y: b.old },
                             new: Point { x: 0., y: b.new },
+                            easing: b.easing,
                         },
                         exists: layer.exists,
                     }
@@ -89,10 +103,10 @@ impl LayerRenderer {
     //     self.text_renderer.set_screen_height(context, height);
     // }
 
-    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
-        self.division_renderer.render(context, time);
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing) {
+        self.division_renderer.render(context, time, easing);
 
-        self.text_renderer.render(context, time);
+        self.text_renderer.render(context, time, easing);
     }
 
     pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
@@ -104,6 +118,8 @@ impl LayerRenderer {
 pub trait LayerDivisionRenderer {
     fn set_layers(&mut self, context: &WebGl2RenderingContext, layers: &Vec<Layer>);
     fn set_transform(&mut self, context: &WebGl2RenderingContext, transform: &Matrix4);
-    fn render(&mut self, context: &WebGl2RenderingContext, time: u32);
+    /// Sets the colors of the (alternating) layer backgrounds
+    fn set_colors(&mut self, color1: TransparentColor, color2: TransparentColor);
+    fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing);
     fn dispose(&mut self, context: &WebGl2RenderingContext);
 }
@@ -19,6 +19,17 @@ in vec2 existsTransition;
 
 uniform mat4 transform;
 uniform float time;
+uniform int easing;
+
+float ease(float per) {
+    if(easing == 1) {
+        return per * per * (3.0f - 2.0f * per);
+    } else if(easing == 2) {
+        float remaining = 1.0f - per;
+        return 1.0f - remaining * remaining * remaining;
+    }
+    return per;
+}
 
 out vec2 cornerPos;
 out vec2 curSize;
@@ -26,7 +37,7 @@ out vec4 curColor;
 out float curExists;
 
 float getPer(vec2 transition) {
-    return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
+    return ease(max(0.0f, min((time - transition.x) / transition.y, 1.0f)));
 }
 
 void main() {
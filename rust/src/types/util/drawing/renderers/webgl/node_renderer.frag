@@ -6,6 +6,7 @@ in vec2 cornerPos;
 in vec2 curSize;
 in vec3 curColor;
 in float curExists;
+in float curHighlight;
 
 uniform float cornerSize;
 
@@ -25,6 +26,10 @@ void main() {
             alpha = 0.0f;
     }
 
+    // Nodes that are not part of the highlighted path are dimmed
+    if(curHighlight >= 1.0f && curHighlight < 2.0f)
+        alpha *= 0.25f;
+
     float a = max(0.0f, curExists * alpha);
     outColor = vec4(curColor * a, a);
 }
\ No newline at end of file
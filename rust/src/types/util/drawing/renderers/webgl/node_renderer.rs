@@ -16,7 +16,7 @@ use crate::{
         logging::console,
         matrix4::Matrix4,
         point::Point,
-        transition::{Interpolatable, Transition},
+        transition::{Easing, Interpolatable, Transition},
     },
     wasm_interface::NodeGroupID,
 };
@@ -25,6 +25,7 @@ use super::{
     text::text_renderer::{Text, TextRenderer, TextRendererSettings},
     util::vertex_renderer::VertexRenderer,
 };
+pub use crate::types::util::drawing::renderers::util::rendering_config::NodeRenderingColorConfig;
 
 pub struct NodeRenderer {
     vertex_renderer: VertexRenderer,
@@ -50,14 +51,6 @@ pub struct Node {
     pub exists: Transition<f32>, // A number between 0 and 1 of whether this node is visible (0-1)
 }
 
-pub struct NodeRenderingColorConfig {
-    pub select: TransparentColor,
-    pub partial_select: TransparentColor,
-    pub hover: TransparentColor,
-    pub partial_hover: TransparentColor,
-    pub text: Color,
-}
-
 pub struct TextRenderingConfig {
     pub screen_height: usize,
     pub font: Rc<Font>,
@@ -97,6 +90,12 @@ impl NodeRenderer {
         }
     }
 
+    /// Sets the colors of the labels and selections, where the selection colors are applied on the next selection update
+    pub fn set_colors(&mut self, colors: NodeRenderingColorConfig) {
+        self.text_renderer.set_color(colors.text);
+        self.colors = colors;
+    }
+
     pub fn set_nodes(&mut self, context: &WebGl2RenderingContext, nodes: &Vec<Node>) {
         self.node_indices = nodes
             .iter()
@@ -137,11 +136,17 @@ impl NodeRenderer {
         );
         set_animated_data(
             "color",
-            nodes6.map(|n| n.color.clone()),
+            nodes6.clone().map(|n| n.color.clone()),
             |v| [v.0, v.1, v.2],
             context,
             &mut self.vertex_renderer,
         );
+        self.vertex_renderer.set_data(
+            context,
+            "highlight",
+            &nodes6.map(|_| 0.).collect::<Box<_>>(),
+            1,
+        );
         self.vertex_renderer.send_data(context);
 
         // Outline shape
@@ -194,6 +199,7 @@ impl NodeRenderer {
                                 + &Transition {
                                     old_time: node.size.old_time,
                                     duration: node.size.duration,
+                                    easing: node.size.easing,
                                     old: Point {
                                         x: -0.5 * text_width,
                                         y: -0.5 * text_height,
@@ -303,6 +309,25 @@ impl NodeRenderer {
         self.vertex_renderer.send_data(context);
     }
 
+    /// Dims all nodes whose group is not part of the given path. Without a path, no nodes are dimmed
+    pub fn update_highlight(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        path: Option<&HashSet<NodeGroupID>>,
+    ) {
+        let mut highlight = vec![0.; self.node_indices.len() * 6];
+        if let Some(path) = path {
+            for (id, node_data) in &self.node_indices {
+                let value = if path.contains(id) { 2. } else { 1. };
+                let data_index = node_data.index * 6;
+                highlight[data_index..data_index + 6].fill(value);
+            }
+        }
+        self.vertex_renderer
+            .set_data(context, "highlight", &highlight, 1);
+        self.vertex_renderer.send_data(context);
+    }
+
     pub fn set_transform_and_screen_height(
         &mut self,
         context: &WebGl2RenderingContext,
@@ -321,7 +346,7 @@ impl NodeRenderer {
             .set_transform_and_screen_height(context, transform, screen_height);
     }
 
-    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing) {
         // TODO: add configuration
         let corner_radius = 0.3;
         let border_offset = 0.3;
@@ -329,6 +354,8 @@ impl NodeRenderer {
 
         self.vertex_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
         self.vertex_renderer
             .set_uniform(context, "selection", |u| context.uniform1f(u, time as f32));
         self.vertex_renderer
@@ -340,6 +367,8 @@ impl NodeRenderer {
 
         self.outline_vertex_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.outline_vertex_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
         self.outline_vertex_renderer
             .set_uniform(context, "selection", |u| context.uniform1f(u, time as f32));
         self.outline_vertex_renderer
@@ -353,7 +382,7 @@ impl NodeRenderer {
         self.outline_vertex_renderer
             .render(context, WebGl2RenderingContext::TRIANGLES);
 
-        self.text_renderer.render(context, time);
+        self.text_renderer.render(context, time, easing);
     }
 
     pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
@@ -17,16 +17,30 @@ in float exists;
 in float existsOld;
 in vec2 existsTransition;
 
+in float highlight;
+
 uniform mat4 transform;
 uniform float time;
+uniform int easing;
+
+float ease(float per) {
+    if(easing == 1) {
+        return per * per * (3.0f - 2.0f * per);
+    } else if(easing == 2) {
+        float remaining = 1.0f - per;
+        return 1.0f - remaining * remaining * remaining;
+    }
+    return per;
+}
 
 out vec2 cornerPos;
 out vec2 curSize;
 out vec3 curColor;
 out float curExists;
+out float curHighlight;
 
 float getPer(vec2 transition) {
-    return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
+    return ease(max(0.0f, min((time - transition.x) / transition.y, 1.0f)));
 }
 
 void main() {
@@ -42,6 +56,8 @@ void main() {
     float existsPer = getPer(existsTransition);
     curExists = mix(existsOld, exists, existsPer);
 
+    curHighlight = highlight;
+
     int corner = gl_VertexID % 6; // two triangles
     cornerPos = curSize * (
     /**/corner == 0 || corner == 3 ?  /**/ vec2(0.5f, 0.5f)  //
@@ -33,8 +33,12 @@ use crate::{
         },
     },
     util::{
-        color::Color, logging::console, matrix4::Matrix4, point::Point, rectangle::Rectangle,
-        transition::Transition,
+        color::Color,
+        logging::console,
+        matrix4::Matrix4,
+        point::Point,
+        rectangle::Rectangle,
+        transition::{Easing, Transition},
     },
 };
 
@@ -112,6 +116,10 @@ impl TextRenderer {
         }
     }
 
+    pub fn set_color(&mut self, c: Color) {
+        self.settings.rgb_color = (c.0, c.1, c.2);
+    }
+
     // Gets font size
     pub fn get_text_size(&self) -> f32 {
         self.font.text_size()
@@ -568,9 +576,11 @@ impl TextRenderer {
         }
     }
 
-    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32, easing: Easing) {
         self.vertex_renderer
             .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .set_uniform(context, "easing", |u| context.uniform1i(u, easing.get_id()));
 
         if let Some(char_atlas) = self.atlases.get(&self.cur_scale_index) {
             let (r, g, b) = self.settings.rgb_color;
@@ -19,14 +19,25 @@ out float curExists;
 
 uniform mat4 transform;
 uniform float time;
+uniform int easing;
+
+float ease(float per) {
+    if(easing == 1) {
+        return per * per * (3.0f - 2.0f * per);
+    } else if(easing == 2) {
+        float remaining = 1.0f - per;
+        return 1.0f - remaining * remaining * remaining;
+    }
+    return per;
+}
 uniform int boundTextureIndex;
 
 void main() {
-    float positionPer = min((time - positionStartTime) / positionDuration, 1.0f);
+    float positionPer = ease(max(0.0f, min((time - positionStartTime) / positionDuration, 1.0f)));
     vec2 curPosition = positionPer * position + (1.0f - positionPer) * positionOld;
     charCoordOut = charCoord;
 
-    float existsPer = min((time - existsStartTime) / existsDuration, 1.0f);
+    float existsPer = ease(max(0.0f, min((time - existsStartTime) / existsDuration, 1.0f)));
     curExists = existsPer * exists + (1.0f - existsPer) * existsOld;
 
     if(boundTextureIndex == int(textureIndex)) {
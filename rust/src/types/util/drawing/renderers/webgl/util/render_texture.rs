@@ -131,6 +131,9 @@ impl ScreenTexture {
         self.width = width;
         self.height = height;
     }
+    pub fn set_color(&mut self, color: (f32, f32, f32, f32)) {
+        self.color = color;
+    }
 }
 impl RenderTarget for ScreenTexture {
     fn bind_buffer(&self, context: &Gl) {
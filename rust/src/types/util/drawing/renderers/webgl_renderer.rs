@@ -1,4 +1,7 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use oxidd_core::Tag;
 use wasm_bindgen::prelude::*;
@@ -12,7 +15,8 @@ use crate::{
         drawing::{
             diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
             layout_rules::LayoutRules,
-            renderer::{GroupSelection, Renderer},
+            renderer::{GroupPath, GroupSelection, LegendEntry, Renderer},
+            renderers::util::rendering_config::RenderingColorConfig,
         },
         graph_structure::graph_structure::{DrawTag, EdgeType},
     },
@@ -21,11 +25,12 @@ use crate::{
         logging::console,
         point::Point,
         transformation::Transformation,
-        transition::{Interpolatable, Transition},
+        transition::{Easing, Interpolatable, Transition},
     },
     wasm_interface::NodeGroupID,
 };
 
+pub use super::util::rendering_config::LayerRenderingColorConfig;
 use super::{
     util::Font::Font,
     webgl::{
@@ -47,8 +52,13 @@ pub struct WebglRenderer<T: DrawTag> {
     node_renderer: NodeRenderer,
     edge_renderer: EdgeRenderer,
     layer_renderer: LayerRenderer,
+    legend_renderer: NodeRenderer,
     edge_type_ids: HashMap<EdgeType<T>, usize>,
     screen_texture: ScreenTexture,
+    font: Rc<Font>,
+    legend: Vec<LegendEntry>,
+    legend_top: f32,
+    easing: Easing,
 }
 
 impl<T: DrawTag> WebglRenderer<T> {
@@ -89,6 +99,15 @@ impl<T: DrawTag> WebglRenderer<T> {
         );
 
         Ok(WebglRenderer {
+            legend_renderer: NodeRenderer::new(
+                &context,
+                node_colors.clone(),
+                TextRenderingConfig {
+                    screen_height,
+                    font: font.clone(),
+                    font_settings: font_settings.clone(),
+                },
+            ),
             node_renderer: NodeRenderer::new(
                 &context,
                 node_colors,
@@ -104,12 +123,16 @@ impl<T: DrawTag> WebglRenderer<T> {
                 LayerBgRenderer::new(&context, layer_colors.background1, layer_colors.background2),
                 // LayerLinesRenderer::new(&context),
                 screen_height,
-                font,
+                font.clone(),
                 font_settings.color(layer_colors.text),
             ),
             webgl_context: context,
             screen_texture,
             edge_type_ids,
+            font,
+            legend: Vec::new(),
+            legend_top: 0.0,
+            easing: Easing::Linear,
         })
     }
     pub fn from_canvas(
@@ -141,6 +164,35 @@ impl<T: DrawTag> WebglRenderer<T> {
     }
 }
 
+impl<T: DrawTag> WebglRenderer<T> {
+    /// Lays out the legend entries as nodes below the lowest layer, which are positioned relative to the left of the screen like the layer labels
+    fn update_legend(&mut self) {
+        self.legend_renderer.set_nodes(
+            &self.webgl_context,
+            &self
+                .legend
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    let width = 1.0 + self.font.measure_width(&entry.label);
+                    Node {
+                        ID: index,
+                        center_position: Transition::plain(Point {
+                            x: 0.5 * width,
+                            y: self.legend_top - 1.0 - 1.2 * index as f32,
+                        }),
+                        size: Transition::plain(Point { x: width, y: 1.0 }),
+                        label: Some(entry.label.clone()),
+                        exists: Transition::plain(1.0),
+                        color: Transition::plain(entry.color),
+                        outline_color: Transition::plain(TransparentColor(0.0, 0.0, 0.0, 0.0)),
+                    }
+                })
+                .collect(),
+        );
+    }
+}
+
 impl<L: LayoutRules> Renderer<L> for WebglRenderer<L::T>
 where
     L::NS: WebglNodeStyle,
@@ -162,6 +214,15 @@ where
             .set_transform(&self.webgl_context, &matrix);
         self.layer_renderer
             .set_transform_and_screen_height(&self.webgl_context, &matrix, height);
+
+        let margin = 0.5 * self.font.text_size();
+        let legend_matrix = &mut matrix.clone();
+        legend_matrix.0[3] = -0.5 + margin * matrix.0[0]; // Align with the layer labels on the left of the screen
+        self.legend_renderer.set_transform_and_screen_height(
+            &self.webgl_context,
+            legend_matrix,
+            height,
+        );
     }
     fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
         self.node_renderer.set_nodes(
@@ -194,12 +255,14 @@ where
                             duration: style.duration,
                             old: style.old.get_color(),
                             new: style.new.get_color(),
+                            easing: style.easing,
                         },
                         outline_color: Transition {
                             old_time: style.old_time,
                             duration: style.duration,
                             old: style.old.get_outline_color(),
                             new: style.new.get_outline_color(),
+                            easing: style.easing,
                         },
                     }
                 })
@@ -243,6 +306,17 @@ where
                 })
                 .collect(),
         );
+
+        let legend_top = layout
+            .layers
+            .iter()
+            .map(|layer| layer.bottom.new.min(layer.top.new))
+            .reduce(f32::min)
+            .unwrap_or(0.0);
+        if legend_top != self.legend_top {
+            self.legend_top = legend_top;
+            self.update_legend();
+        }
     }
 
     fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {
@@ -251,18 +325,59 @@ where
         self.edge_renderer
             .update_selection(&self.webgl_context, &selection, &old_selection);
     }
+
+    fn highlight_path(&mut self, path: Option<GroupPath<L::T>>) {
+        let path = path.map(|(groups, edges)| {
+            let edges = edges
+                .iter()
+                .filter_map(|(from, to, edge_type)| {
+                    Some((*from, *to, *self.edge_type_ids.get(edge_type)?))
+                })
+                .collect::<HashSet<_>>();
+            (groups.iter().cloned().collect::<HashSet<_>>(), edges)
+        });
+        self.node_renderer
+            .update_highlight(&self.webgl_context, path.as_ref().map(|(groups, _)| groups));
+        self.edge_renderer
+            .update_highlight(&self.webgl_context, path.as_ref().map(|(_, edges)| edges));
+    }
     fn render(&mut self, time: u32) {
         self.screen_texture.clear(&self.webgl_context);
-        self.layer_renderer.render(&self.webgl_context, time);
-        self.edge_renderer.render(&self.webgl_context, time);
-        self.node_renderer.render(&self.webgl_context, time);
+        self.layer_renderer
+            .render(&self.webgl_context, time, self.easing);
+        self.edge_renderer
+            .render(&self.webgl_context, time, self.easing);
+        self.node_renderer
+            .render(&self.webgl_context, time, self.easing);
+        self.legend_renderer
+            .render(&self.webgl_context, time, self.easing);
     }
-}
 
-pub struct LayerRenderingColorConfig {
-    pub background1: TransparentColor,
-    pub background2: TransparentColor,
-    pub text: Color,
+    fn set_legend(&mut self, legend: Vec<LegendEntry>) {
+        if legend != self.legend {
+            self.legend = legend;
+            self.update_legend();
+        }
+    }
+
+    fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    fn set_colors(&mut self, colors: RenderingColorConfig<L::T>) {
+        let mut edge_rendering_types = self.edge_renderer.get_edge_types().clone();
+        for (edge_type, edge_rendering) in colors.edge_types {
+            if let Some(&index) = self.edge_type_ids.get(&edge_type) {
+                edge_rendering_types[index] = edge_rendering;
+            }
+        }
+        self.edge_renderer.set_edge_types(edge_rendering_types);
+        self.node_renderer.set_colors(colors.nodes.clone());
+        self.legend_renderer.set_colors(colors.nodes);
+        self.layer_renderer.set_colors(colors.layers.clone());
+        let c = colors.layers.background1;
+        self.screen_texture.set_color((c.0, c.1, c.2, c.3));
+    }
 }
 
 impl<T: DrawTag> Drop for WebglRenderer<T> {
@@ -270,6 +385,7 @@ impl<T: DrawTag> Drop for WebglRenderer<T> {
         self.node_renderer.dispose(&self.webgl_context);
         self.edge_renderer.dispose(&self.webgl_context);
         self.layer_renderer.dispose(&self.webgl_context);
+        self.legend_renderer.dispose(&self.webgl_context);
     }
 }
 
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::util::graph_structure::graph_structure::EdgeType,
+    util::{
+        color::{Color, TransparentColor},
+        parse_error::ParseError,
+    },
+};
+
+use super::renderers::util::rendering_config::{
+    EdgeRenderingType, LayerRenderingColorConfig, NodeRenderingColorConfig, RenderingColorConfig,
+};
+
+/// The colors of a diagram, shared by the drawers of the binary diagram types. Colors are given as (r, g, b) or (r, g, b, a) components between 0 and 1.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub edge_true: Color,
+    pub edge_false: Color,
+    pub edge_label: Color,
+    pub node_true: Color,
+    pub node_false: Color,
+    pub node_group: Color,
+    pub node_default: Color,
+    pub node_text: Color,
+    pub node_label: Color,
+    pub layer_background1: Color,
+    pub layer_background2: Color,
+    pub layer_text: Color,
+    pub selection: TransparentColor,
+    pub selection_partial: TransparentColor,
+    pub selection_hover: TransparentColor,
+    pub selection_hover_partial: TransparentColor,
+}
+
+impl Theme {
+    pub const LIGHT: Theme = Theme {
+        edge_true: Color(0.2, 1.0, 0.2),
+        edge_false: Color(1.0, 0.2, 0.2),
+        edge_label: Color(0.6, 0.6, 0.6),
+        node_true: Color(0.2, 1.0, 0.2),
+        node_false: Color(1.0, 0.2, 0.2),
+        node_group: Color(0.45, 0.45, 0.45),
+        node_default: Color(0.1, 0.1, 0.1),
+        node_text: Color(0.0, 0.0, 0.0),
+        node_label: Color(0.5, 0.5, 1.0),
+        layer_background1: Color(0.98, 0.98, 0.98),
+        layer_background2: Color(0.9, 0.9, 0.9),
+        layer_text: Color(0.0, 0.0, 0.0),
+        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
+        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
+    };
+
+    pub const DARK: Theme = Theme {
+        edge_true: Color(0.631, 0.749, 0.423),
+        edge_false: Color(0.835, 0.341, 0.341),
+        edge_label: Color(0.6, 0.6, 0.6),
+        node_true: Color(0.631, 0.749, 0.423),
+        node_false: Color(0.835, 0.341, 0.341),
+        node_group: Color(0.45, 0.45, 0.45),
+        node_default: Color(0.35, 0.35, 0.35),
+        node_text: Color(0.0, 0.0, 0.0),
+        node_label: Color(0.5, 0.5, 1.0),
+        layer_background1: Color(0.125, 0.125, 0.125),
+        layer_background2: Color(0.1875, 0.1875, 0.1875),
+        layer_text: Color(1.0, 1.0, 1.0),
+        selection: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_partial: TransparentColor(0.6, 0.0, 1.0, 0.7),
+        selection_hover: TransparentColor(0.0, 0.0, 1.0, 0.3),
+        selection_hover_partial: TransparentColor(1.0, 0.0, 0.8, 0.2),
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        edge_true: Color(0.0, 0.6, 0.0),
+        edge_false: Color(0.85, 0.0, 0.0),
+        edge_label: Color(0.0, 0.0, 0.0),
+        node_true: Color(0.0, 0.6, 0.0),
+        node_false: Color(0.85, 0.0, 0.0),
+        node_group: Color(0.3, 0.3, 0.3),
+        node_default: Color(0.0, 0.0, 0.0),
+        node_text: Color(0.0, 0.0, 0.0),
+        node_label: Color(1.0, 0.8, 0.0),
+        layer_background1: Color(1.0, 1.0, 1.0),
+        layer_background2: Color(0.85, 0.85, 0.85),
+        layer_text: Color(0.0, 0.0, 0.0),
+        selection: TransparentColor(0.0, 0.4, 1.0, 0.9),
+        selection_partial: TransparentColor(0.0, 0.4, 1.0, 0.9),
+        selection_hover: TransparentColor(1.0, 0.6, 0.0, 0.6),
+        selection_hover_partial: TransparentColor(1.0, 0.6, 0.0, 0.4),
+    };
+
+    /// Reads a theme from JSON, where any missing colors are taken from the light theme
+    pub fn from_json(json: &str) -> Result<Theme, ParseError> {
+        serde_json::from_str(json)
+            .map_err(|err| ParseError::at_line(err.line(), err.column(), err.to_string()))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// The rendering types of the true, false and label edges, which are shared by the webgl and SVG renderers
+    pub fn get_edge_types(&self) -> HashMap<EdgeType<()>, EdgeRenderingType> {
+        let edge_rendering_type =
+            |color: Color, width: f32, dash_solid: f32, dash_transparent: f32| EdgeRenderingType {
+                select_color: color.mix_transparent(&self.selection),
+                partial_select_color: color.mix_transparent(&self.selection_partial),
+                hover_color: color.mix_transparent(&self.selection_hover),
+                partial_hover_color: color.mix_transparent(&self.selection_hover_partial),
+                color,
+                width,
+                dash_solid,
+                dash_transparent,
+                marker_radius: 0.0,
+            };
+        HashMap::from([
+            // True edge
+            (
+                EdgeType::new((), 0),
+                edge_rendering_type(
+                    self.edge_true,
+                    0.2,
+                    1.0,
+                    0.0, // No dashing
+                ),
+            ),
+            // False edge
+            (
+                EdgeType::new((), 1),
+                edge_rendering_type(self.edge_false, 0.2, 0.3, 0.15),
+            ),
+            // Label edge
+            (
+                EdgeType::new((), 2),
+                edge_rendering_type(self.edge_label, 0.15, 1.0, 0.0),
+            ),
+        ])
+    }
+
+    pub fn get_node_colors(&self) -> NodeRenderingColorConfig {
+        NodeRenderingColorConfig {
+            select: self.selection,
+            partial_select: self.selection_partial,
+            hover: self.selection_hover,
+            partial_hover: self.selection_hover_partial,
+            text: self.node_text,
+        }
+    }
+
+    pub fn get_layer_colors(&self) -> LayerRenderingColorConfig {
+        LayerRenderingColorConfig {
+            background1: self.layer_background1.into(),
+            background2: self.layer_background2.into(),
+            text: self.layer_text,
+        }
+    }
+
+    pub fn get_rendering_colors(&self) -> RenderingColorConfig<()> {
+        RenderingColorConfig {
+            edge_types: self.get_edge_types(),
+            nodes: self.get_node_colors(),
+            layers: self.get_layer_colors(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::LIGHT
+    }
+}
+
+/// The themes that can be selected in the settings of a drawer
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ThemeKind {
+    Light,
+    Dark,
+    HighContrast,
+    /// A theme imported from JSON
+    Custom,
+}
+
+impl ThemeKind {
+    /// Retrieves the theme of this kind, where the JSON is only used for custom themes
+    pub fn get_theme(&self, json: &str) -> Result<Theme, ParseError> {
+        match self {
+            ThemeKind::Light => Ok(Theme::LIGHT),
+            ThemeKind::Dark => Ok(Theme::DARK),
+            ThemeKind::HighContrast => Ok(Theme::HIGH_CONTRAST),
+            ThemeKind::Custom => Theme::from_json(json),
+        }
+    }
+}
@@ -11,9 +11,9 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphStructure,
         },
-        storage::state_storage::StateStorage,
+        storage::{state_model::GraphState, state_storage::StateStorage},
     },
-    util::rc_refcell::MutRcRefCell,
+    util::{rc_refcell::MutRcRefCell, vis_error::VisError},
     wasm_interface::NodeID,
 };
 
@@ -53,11 +53,11 @@ impl<T: DrawTag, NL: Clone, LL: Clone> AbstractedGraph<T, NL, LL> {
 }
 
 impl<T: DrawTag, NL: Clone, LL: Clone> StateStorage for AbstractedGraph<T, NL, LL> {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)
     }
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        self.graph.write(stream)
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state)
     }
 }
 
@@ -3,7 +3,6 @@ use std::{
     marker::PhantomData,
 };
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::Itertools;
 
 use crate::{
@@ -11,9 +10,9 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::{Serializable, StateStorage},
+        storage::{state_model::GraphState, state_storage::StateStorage},
     },
-    util::logging::console,
+    util::{logging::console, vis_error::VisError},
     wasm_interface::NodeID,
 };
 
@@ -217,20 +216,17 @@ impl<G: GraphStructure> GraphStructure for ChildEdgeAdjuster<G> {
     }
 }
 
-impl<G: GraphStructure + StateStorage> StateStorage for ChildEdgeAdjuster<G>
-where
-    G::T: Serializable,
-{
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        self.graph.write(stream)?;
-
-        stream.write_u8(self.enabled as u8)?;
-        Ok(())
+impl<G: GraphStructure + StateStorage> StateStorage for ChildEdgeAdjuster<G> {
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+        state.child_edges_enabled = Some(self.enabled);
     }
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
 
-        self.enabled = stream.read_u8()? > 0;
+        if let Some(enabled) = state.child_edges_enabled {
+            self.enabled = enabled;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,459 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use oxidd_rules_bdd::complement_edge::EdgeTag;
+
+use crate::{
+    types::util::{
+        graph_structure::{
+            graph_structure::{
+                Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
+            },
+            oxidd_graph_structure::{NodeLabel, NodeType},
+        },
+        storage::{state_model::GraphState, state_storage::StateStorage},
+    },
+    util::vis_error::VisError,
+    wasm_interface::NodeID,
+};
+
+/// The ComplementExpansionAdjuster can replace complemented edges by explicit complement nodes, showing the equivalent diagram without complemented edges.
+///
+// Every source node is represented by 2 output node IDs:
+// - an even ID for the node itself
+// - an odd ID for the complement of the node, which only exists while the expansion is enabled
+pub struct ComplementExpansionAdjuster<G: GraphStructure<T = EdgeTag, NL = NodeLabel<String>>> {
+    graph: G,
+    event_writer: GraphEventsWriter,
+    graph_events: GraphEventsReader,
+
+    enabled: bool,
+    /// The output nodes of which the children have been requested
+    discovered: HashSet<NodeID>,
+    /// The complement nodes that have been handed out while the expansion was enabled
+    known_complements: HashSet<NodeID>,
+}
+
+fn to_sourced(id: NodeID) -> (NodeID, bool) {
+    (id / 2, id % 2 == 1)
+}
+fn from_sourced(node: NodeID, complemented: bool) -> NodeID {
+    node * 2 + complemented as NodeID
+}
+
+impl<G: GraphStructure<T = EdgeTag, NL = NodeLabel<String>>> ComplementExpansionAdjuster<G> {
+    pub fn new(mut graph: G) -> ComplementExpansionAdjuster<G> {
+        ComplementExpansionAdjuster {
+            graph_events: graph.create_event_reader(),
+            graph,
+            event_writer: GraphEventsWriter::new(),
+            enabled: false,
+            discovered: HashSet::new(),
+            known_complements: HashSet::new(),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled == enabled {
+            return;
+        }
+        self.process_graph_changes();
+        let old_roots = self.get_roots();
+        self.enabled = enabled;
+
+        if enabled {
+            // Discover the complement nodes that are reachable through nodes that were already discovered
+            let mut node_queue = self.get_roots();
+            self.known_complements
+                .extend(node_queue.iter().filter(|&&root| to_sourced(root).1));
+            let mut seen = HashSet::new();
+            while let Some(node) = node_queue.pop() {
+                if !seen.insert(node) || !self.discovered.contains(&from_sourced(node / 2, false)) {
+                    continue;
+                }
+                for (_, child) in self.get_children(node) {
+                    node_queue.push(child);
+                }
+            }
+            for &node in self.known_complements.iter().sorted() {
+                self.event_writer.write(Change::NodeInsertion {
+                    node,
+                    source: Some(from_sourced(node / 2, false)),
+                });
+            }
+        } else {
+            for node in self.known_complements.drain().sorted() {
+                self.discovered.remove(&node);
+                self.event_writer.write(Change::NodeRemoval { node });
+            }
+        }
+
+        for &node in self.discovered.iter().sorted() {
+            self.event_writer
+                .write(Change::NodeConnectionsChange { node });
+        }
+        // Pointers may move between a node and its complement
+        for node in old_roots
+            .into_iter()
+            .chain(self.get_roots())
+            .sorted()
+            .dedup()
+        {
+            self.event_writer.write(Change::NodeLabelChange { node });
+        }
+    }
+
+    /// Retrieves the output IDs that currently represent the given source node
+    fn get_outputs(&self, node: NodeID) -> Vec<NodeID> {
+        let complement = from_sourced(node, true);
+        Some(from_sourced(node, false))
+            .into_iter()
+            .chain(Some(complement).filter(|c| self.known_complements.contains(c)))
+            .collect()
+    }
+
+    fn process_graph_changes(&mut self) {
+        let events = self.graph.consume_events(&self.graph_events);
+        for event in events {
+            match event {
+                Change::NodeLabelChange { node } => {
+                    for node in self.get_outputs(node) {
+                        self.event_writer.write(Change::NodeLabelChange { node });
+                    }
+                }
+                Change::LevelChange { node } => {
+                    for node in self.get_outputs(node) {
+                        self.event_writer.write(Change::LevelChange { node });
+                    }
+                }
+                Change::LevelLabelChange { level } => {
+                    self.event_writer.write(Change::LevelLabelChange { level });
+                }
+                Change::NodeConnectionsChange { node } => {
+                    for node in self.get_outputs(node) {
+                        self.event_writer
+                            .write(Change::NodeConnectionsChange { node });
+                    }
+                }
+                Change::ParentDiscover { child } => {
+                    for child in self.get_outputs(child) {
+                        self.event_writer.write(Change::ParentDiscover { child });
+                    }
+                }
+                Change::NodeRemoval { node } => {
+                    for node in self.get_outputs(node) {
+                        self.discovered.remove(&node);
+                        self.known_complements.remove(&node);
+                        self.event_writer.write(Change::NodeRemoval { node });
+                    }
+                }
+                Change::NodeInsertion { node, source } => {
+                    self.event_writer.write(Change::NodeInsertion {
+                        node: from_sourced(node, false),
+                        source: source.map(|source| from_sourced(source, false)),
+                    });
+                }
+            }
+        }
+    }
+
+    fn get_edge(&self, edge: EdgeType<EdgeTag>) -> EdgeType<EdgeTag> {
+        if self.enabled {
+            EdgeType::new(EdgeTag::None, edge.index)
+        } else {
+            edge
+        }
+    }
+
+    /// Retrieves the output node that an edge with the given tag from the given output node refers to
+    fn get_target(&self, from_complemented: bool, tag: EdgeTag, to: NodeID) -> NodeID {
+        from_sourced(
+            to,
+            self.enabled && from_complemented != tag.is_complemented(),
+        )
+    }
+}
+
+fn complement_terminal(terminal: &str) -> String {
+    match terminal {
+        "T" => "F".to_string(),
+        "F" => "T".to_string(),
+        other => format!("¬{}", other),
+    }
+}
+
+impl<G: GraphStructure<T = EdgeTag, NL = NodeLabel<String>>> GraphStructure
+    for ComplementExpansionAdjuster<G>
+{
+    type T = EdgeTag;
+    type NL = NodeLabel<String>;
+    type LL = G::LL;
+
+    fn get_roots(&self) -> Vec<NodeID> {
+        self.graph
+            .get_roots()
+            .into_iter()
+            .flat_map(|root| {
+                if !self.enabled {
+                    return vec![from_sourced(root, false)];
+                }
+                let label = self.graph.get_node_label(root);
+                let has_complemented = !label.complemented_pointers.is_empty();
+                let has_regular = !label.pointers.is_empty() || !has_complemented;
+                Some(from_sourced(root, false))
+                    .filter(|_| has_regular)
+                    .into_iter()
+                    .chain(Some(from_sourced(root, true)).filter(|_| has_complemented))
+                    .collect_vec()
+            })
+            .dedup()
+            .collect()
+    }
+
+    fn get_terminals(&self) -> Vec<NodeID> {
+        self.graph
+            .get_terminals()
+            .into_iter()
+            .flat_map(|terminal| {
+                Some(from_sourced(terminal, false))
+                    .into_iter()
+                    .chain(Some(from_sourced(terminal, true)).filter(|_| self.enabled))
+            })
+            .collect()
+    }
+
+    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<EdgeTag>, NodeID)> {
+        self.process_graph_changes();
+        let (node, complemented) = to_sourced(node);
+        self.graph
+            .get_known_parents(node)
+            .into_iter()
+            .map(|(edge, parent)| {
+                (
+                    self.get_edge(edge),
+                    self.get_target(complemented, edge.tag, parent),
+                )
+            })
+            .filter(|(_, parent)| self.discovered.contains(parent))
+            .collect()
+    }
+
+    fn get_children(&mut self, node: NodeID) -> Vec<(EdgeType<EdgeTag>, NodeID)> {
+        self.process_graph_changes();
+        self.discovered.insert(node);
+        let (source, complemented) = to_sourced(node);
+        let children = self
+            .graph
+            .get_children(source)
+            .into_iter()
+            .map(|(edge, child)| {
+                (
+                    self.get_edge(edge),
+                    self.get_target(complemented, edge.tag, child),
+                )
+            })
+            .collect_vec();
+        if self.enabled {
+            self.known_complements.extend(
+                children
+                    .iter()
+                    .map(|&(_, child)| child)
+                    .chain(Some(node))
+                    .filter(|&child| to_sourced(child).1),
+            );
+        }
+        children
+    }
+
+    fn get_level(&mut self, node: NodeID) -> oxidd::LevelNo {
+        self.graph.get_level(to_sourced(node).0)
+    }
+
+    fn get_node_label(&self, node: NodeID) -> NodeLabel<String> {
+        let (node, complemented) = to_sourced(node);
+        let label = self.graph.get_node_label(node);
+        if !self.enabled {
+            return label;
+        }
+        if complemented {
+            NodeLabel {
+                pointers: label.complemented_pointers,
+                complemented_pointers: vec![],
+                kind: match label.kind {
+                    NodeType::Terminal(terminal) => {
+                        NodeType::Terminal(complement_terminal(&terminal))
+                    }
+                    NodeType::Inner(text) => NodeType::Inner(format!("¬{}", text)),
+                },
+            }
+        } else {
+            NodeLabel {
+                complemented_pointers: vec![],
+                ..label
+            }
+        }
+    }
+
+    fn get_level_label(&self, level: oxidd::LevelNo) -> G::LL {
+        self.graph.get_level_label(level)
+    }
+
+    fn create_event_reader(&mut self) -> GraphEventsReader {
+        self.event_writer.create_reader()
+    }
+
+    fn consume_events(&mut self, reader: &GraphEventsReader) -> Vec<Change> {
+        self.process_graph_changes();
+        self.event_writer.read(reader)
+    }
+
+    fn local_nodes_to_sources(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph.local_nodes_to_sources(
+            nodes
+                .into_iter()
+                .map(|node| to_sourced(node).0)
+                .sorted()
+                .dedup()
+                .collect(),
+        )
+    }
+
+    fn source_nodes_to_local(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph
+            .source_nodes_to_local(nodes)
+            .into_iter()
+            .flat_map(|node| self.get_outputs(node))
+            .collect()
+    }
+}
+
+impl<G: GraphStructure<T = EdgeTag, NL = NodeLabel<String>> + StateStorage> StateStorage
+    for ComplementExpansionAdjuster<G>
+{
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+        state.complement_expansion_enabled = Some(self.enabled);
+    }
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
+
+        if let Some(enabled) = state.complement_expansion_enabled {
+            self.set_enabled(enabled);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::util::graph_structure::oxidd_graph_structure::OxiddGraphStructure,
+        util::dummy_bcdd::{DummyBCDDFunction, DummyBCDDManager, DummyBCDDManagerRef},
+    };
+
+    /// The BCDD of `a ? b : ¬b`, where both edges to the terminal of `b` and the else edge of `a` are complemented
+    const XNOR: &str = "\
+.ver DDDMP-2.0
+.mode A
+.varinfo 0
+.dd f
+.nnodes 3
+.nvars 2
+.nsuppvars 2
+.suppvarnames a b
+.orderedvarnames a b
+.ids 0 1
+.permids 0 1
+.nroots 1
+.rootids 3
+.nodes
+1 T 1 0 0
+2 b 1 1 -1
+3 a 0 2 -2
+.end
+";
+
+    fn create_adjuster(
+    ) -> ComplementExpansionAdjuster<OxiddGraphStructure<EdgeTag, DummyBCDDFunction, String>> {
+        let mut manager_ref = DummyBCDDManagerRef::from(&DummyBCDDManager::new());
+        let (roots, levels) = DummyBCDDFunction::from_dddmp(&mut manager_ref, XNOR).unwrap();
+        ComplementExpansionAdjuster::new(OxiddGraphStructure::new(roots, levels))
+    }
+
+    /// Retrieves the children of the node, together with whether the edge to the child is complemented
+    fn get_children<G: GraphStructure<T = EdgeTag>>(
+        graph: &mut G,
+        node: NodeID,
+    ) -> Vec<(NodeID, bool)> {
+        graph
+            .get_children(node)
+            .into_iter()
+            .map(|(edge, child)| (child, edge.tag.is_complemented()))
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn complements_get_odd_ids() {
+        let mut adjuster = create_adjuster();
+        assert_eq!(adjuster.get_roots(), vec![6]);
+        assert_eq!(get_children(&mut adjuster, 6), vec![(4, false), (4, true)]);
+
+        adjuster.set_enabled(true);
+        assert_eq!(adjuster.get_roots(), vec![6]);
+        assert_eq!(get_children(&mut adjuster, 6), vec![(4, false), (5, false)]);
+        // Complementing the node of `b` swaps the terminals its edges point to
+        assert_eq!(get_children(&mut adjuster, 5), vec![(2, false), (3, false)]);
+        assert_eq!(get_children(&mut adjuster, 4), vec![(2, false), (3, false)]);
+        assert!(matches!(
+            adjuster.get_node_label(3).kind,
+            NodeType::Terminal(ref t) if t == "F"
+        ));
+        assert!(matches!(
+            adjuster.get_node_label(5).kind,
+            NodeType::Inner(ref text) if text.starts_with('¬')
+        ));
+        assert_eq!(adjuster.local_nodes_to_sources(vec![4, 5]), vec![2]);
+    }
+
+    #[test]
+    fn enabling_inserts_and_removes_the_complements() {
+        let mut adjuster = create_adjuster();
+        let reader = adjuster.create_event_reader();
+        get_children(&mut adjuster, 6);
+        adjuster.consume_events(&reader);
+
+        // The parents that are discovered while traversing the diagram are irrelevant here
+        let to_strings = |events: Vec<Change>| {
+            events
+                .iter()
+                .filter(|event| !matches!(event, Change::ParentDiscover { .. }))
+                .map(|event| event.to_string())
+                .collect_vec()
+        };
+        adjuster.set_enabled(true);
+        assert_eq!(
+            to_strings(adjuster.consume_events(&reader)),
+            to_strings(vec![
+                Change::NodeInsertion {
+                    node: 5,
+                    source: Some(4)
+                },
+                Change::NodeConnectionsChange { node: 6 },
+                Change::NodeLabelChange { node: 6 },
+            ])
+        );
+
+        adjuster.set_enabled(false);
+        assert_eq!(
+            to_strings(adjuster.consume_events(&reader)),
+            to_strings(vec![
+                Change::NodeRemoval { node: 5 },
+                Change::NodeConnectionsChange { node: 6 },
+                Change::NodeLabelChange { node: 6 },
+            ])
+        );
+        assert_eq!(get_children(&mut adjuster, 6), vec![(4, false), (4, true)]);
+    }
+}
@@ -3,7 +3,6 @@ use std::{
     marker::PhantomData,
 };
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::Itertools;
 
 use crate::{
@@ -11,8 +10,12 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::{Serializable, StateStorage},
+        storage::{
+            state_model::{EdgeState, GraphState},
+            state_storage::{StateStorage, StoredTag},
+        },
     },
+    util::vis_error::VisError,
     wasm_interface::NodeID,
 };
 
@@ -156,33 +159,26 @@ impl<G: GraphStructure> GraphStructure for EdgeToAdjuster<G> {
 
 impl<G: GraphStructure + StateStorage> StateStorage for EdgeToAdjuster<G>
 where
-    G::T: Serializable,
+    G::T: StoredTag,
 {
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        self.graph.write(stream)?;
-
-        let count = self.remove_edges.len();
-        stream.write_u32::<LittleEndian>(count as u32)?;
-        for (node_id, edge) in &self.remove_edges {
-            stream.write_u32::<LittleEndian>(*node_id as u32)?;
-            stream.write_i32::<LittleEndian>(edge.index)?;
-            edge.tag.serialize(stream)?;
-        }
-        Ok(())
-    }
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
-
-        let count = stream.read_u32::<LittleEndian>()?;
-        let mut remove_edges = HashSet::new();
-        for _ in 0..count {
-            let to = stream.read_u32::<LittleEndian>()? as usize;
-            let index = stream.read_i32::<LittleEndian>()?;
-            let tag = G::T::deserialize(stream)?;
-            remove_edges.insert((to, EdgeType { tag, index }));
-        }
-        self.remove_edges = remove_edges;
-
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+
+        state.removed_edges = self
+            .remove_edges
+            .iter()
+            .map(|(to, edge)| (*to, EdgeState::new(edge)))
+            .sorted_by_key(|&(to, edge)| (to, edge.index))
+            .collect();
+    }
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
+
+        self.remove_edges = state
+            .removed_edges
+            .iter()
+            .map(|&(to, edge)| Ok((to, edge.get_edge()?)))
+            .collect::<Result<_, VisError>>()?;
         Ok(())
     }
 }
@@ -1,11 +1,6 @@
-use std::{
-    collections::HashSet,
-    io::{Cursor, Result},
-    marker::PhantomData,
-    vec::IntoIter,
-};
+use std::{collections::HashSet, marker::PhantomData, vec::IntoIter};
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use itertools::Itertools;
 use oxidd::LevelNo;
 
 use crate::{
@@ -14,9 +9,9 @@ use crate::{
             graph_structure::{DrawTag, GraphStructure},
             grouped_graph_structure::{EdgeCountData, GroupedGraphStructure},
         },
-        storage::state_storage::StateStorage,
+        storage::{state_model::GraphState, state_storage::StateStorage},
     },
-    util::rc_refcell::MutRcRefCell,
+    util::{rc_refcell::MutRcRefCell, vis_error::VisError},
     wasm_interface::{NodeGroupID, NodeID},
 };
 
@@ -129,24 +124,13 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
 }
 
 impl<G: GroupedGraphStructure + StateStorage> StateStorage for GroupPresenceAdjuster<G> {
-    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
-        let hidden_count = self.hidden_groups.len();
-        stream.write_u32::<LittleEndian>(hidden_count as u32)?;
-        for &group_id in &self.hidden_groups {
-            stream.write_u32::<LittleEndian>(group_id as u32)?;
-        }
-        self.graph.read().write(stream)?;
-        Ok(())
+    fn write(&self, state: &mut GraphState) {
+        self.graph.read().write(state);
+        state.hidden_groups = self.hidden_groups.iter().cloned().sorted().collect();
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        self.hidden_groups.clear();
-        let group_count = stream.read_u32::<LittleEndian>()?;
-        for _ in 0..group_count {
-            let group_id = stream.read_u32::<LittleEndian>()? as usize;
-            self.hidden_groups.insert(group_id);
-        }
-        self.graph.get().read(stream)?;
-        Ok(())
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.hidden_groups = state.hidden_groups.iter().cloned().collect();
+        self.graph.get().read(state)
     }
 }
@@ -1,8 +1,4 @@
-use std::{
-    io::{Cursor, Result},
-    marker::PhantomData,
-    vec::IntoIter,
-};
+use std::{marker::PhantomData, vec::IntoIter};
 
 use oxidd::LevelNo;
 
@@ -12,9 +8,9 @@ use crate::{
             graph_structure::DrawTag,
             grouped_graph_structure::{EdgeCountData, GroupedGraphStructure},
         },
-        storage::state_storage::StateStorage,
+        storage::{state_model::GraphState, state_storage::StateStorage},
     },
-    util::rc_refcell::MutRcRefCell,
+    util::{rc_refcell::MutRcRefCell, vis_error::VisError},
     wasm_interface::{NodeGroupID, NodeID},
 };
 
@@ -96,13 +92,11 @@ impl<G: GroupedGraphStructure, NLL> GroupedGraphStructure for LevelLabelAdjuster
 }
 
 impl<G: GroupedGraphStructure + StateStorage, NLL> StateStorage for LevelLabelAdjuster<NLL, G> {
-    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
-        self.graph.read().write(stream)?;
-        Ok(())
+    fn write(&self, state: &mut GraphState) {
+        self.graph.read().write(state)
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        self.graph.get().read(stream)?;
-        Ok(())
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.get().read(state)
     }
 }
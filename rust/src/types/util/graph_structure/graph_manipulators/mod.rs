@@ -1,5 +1,6 @@
 pub mod abstracted_graph;
 pub mod child_edge_adjuster;
+pub mod complement_expansion_adjuster;
 pub mod edge_to_adjuster;
 pub mod group_presence_adjuster;
 pub mod label_adjusters;
@@ -7,3 +8,4 @@ pub mod node_presence_adjuster;
 pub mod pointer_node_adjuster;
 pub mod rc_graph;
 pub mod terminal_level_adjuster;
+pub mod zero_suppression_adjuster;
@@ -8,10 +8,10 @@ use std::{
     rc::Rc,
 };
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::{Either, Itertools};
 use multimap::MultiMap;
 use oxidd::{LevelNo, NodeID};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use crate::{
@@ -19,9 +19,15 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::{Serializable, StateStorage},
+        storage::{
+            state_model::{
+                EdgeConstraintState, EdgeState, GraphState, PresenceAdjustmentState, PresenceState,
+                ReplacementState,
+            },
+            state_storage::{StateStorage, StoredTag},
+        },
     },
-    util::{free_id_manager::FreeIdManager, logging::console},
+    util::{free_id_manager::FreeIdManager, logging::console, vis_error::VisError},
 };
 
 /// The NodePresenceAdjuster allows nodes to be hidden or duplicated in order to improve structural properties of the graph for better layouting.
@@ -85,7 +91,7 @@ impl<T: DrawTag> Display for EdgeConstraint<T> {
 }
 
 #[wasm_bindgen]
-#[derive(Eq, PartialEq, Clone)]
+#[derive(Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub enum PresenceRemainder {
     // Show this unique terminal the regular way (default)
     Show,
@@ -172,6 +178,13 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
         self.adjustments.get(&owner).cloned()
     }
 
+    /// Retrieves the number of parents that the node has in the source graph, regardless of its presence adjustments
+    pub fn get_source_parent_count(&mut self, out_node: NodeID) -> usize {
+        self.process_graph_changes();
+        let owner = self.get_owner_id(out_node);
+        self.graph.get_known_parents(owner).len()
+    }
+
     fn update_children_of_parents(&mut self, left_node_id: NodeID) {
         let source_parents = self.graph.get_known_parents(left_node_id);
         let parents = source_parents
@@ -618,121 +631,91 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
 impl<G: GraphStructure> StateStorage for NodePresenceAdjuster<G>
 where
     G: StateStorage,
-    G::T: Serializable,
+    G::T: StoredTag,
 {
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        let write_constraint = |stream: &mut std::io::Cursor<&mut Vec<u8>>,
-                                constraint: &EdgeConstraint<G::T>|
-         -> std::io::Result<()> {
-            match constraint {
-                EdgeConstraint::Any => stream.write_u8(0)?,
-                EdgeConstraint::Exact(et) => {
-                    stream.write_u8(1)?;
-                    stream.write_i32::<LittleEndian>(et.index)?;
-                    et.tag.serialize(stream)?;
-                }
-            }
-            Ok(())
-        };
-
-        self.graph.write(stream)?;
-        let adjustment_count = self.adjustments.len();
-        stream.write_u32::<LittleEndian>(adjustment_count as u32)?;
-        for (&node_id, presence) in &self.adjustments {
-            stream.write_u32::<LittleEndian>(node_id as u32)?;
-
-            stream.write_u8(match presence.remainder {
-                PresenceRemainder::Hide => 0,
-                PresenceRemainder::Show => 1,
-                PresenceRemainder::Duplicate => 2,
-                PresenceRemainder::DuplicateParent => 3,
-            })?;
-
-            let group_count = presence.groups.len();
-            stream.write_u32::<LittleEndian>(group_count as u32)?;
-            for group in &presence.groups {
-                let group_size = group.len();
-                stream.write_u32::<LittleEndian>(group_size as u32)?;
-
-                for (constraint, parent) in group {
-                    stream.write_u32::<LittleEndian>(*parent as u32)?;
-                    write_constraint(stream, constraint)?;
-                }
-            }
-        }
-
-        let replacement_count = self.replacements.len();
-        stream.write_u32::<LittleEndian>(replacement_count as u32)?;
-        for ((parent, constraint, node), replacement) in &self.replacements {
-            stream.write_u32::<LittleEndian>(*parent as u32)?;
-            write_constraint(stream, constraint)?;
-            stream.write_u32::<LittleEndian>(*node as u32)?;
-            stream.write_u32::<LittleEndian>(*replacement as u32)?;
-        }
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
 
-        Ok(())
-    }
-
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        let read_constraint =
-            |stream: &mut std::io::Cursor<&Vec<u8>>| -> std::io::Result<EdgeConstraint<G::T>> {
-                Ok(match stream.read_u8()? {
-                    0 => EdgeConstraint::Any,
-                    _ => {
-                        let index = stream.read_i32::<LittleEndian>()?;
-                        let tag = G::T::deserialize(stream)?;
-                        EdgeConstraint::Exact(EdgeType { tag, index })
-                    }
+        state.presence = PresenceState {
+            adjustments: self
+                .adjustments
+                .iter()
+                .sorted_by_key(|&(&node, _)| node)
+                .map(|(&node, presence)| PresenceAdjustmentState {
+                    node,
+                    remainder: presence.remainder.clone(),
+                    groups: presence
+                        .groups
+                        .iter()
+                        .map(|group| {
+                            group
+                                .iter()
+                                .map(|(constraint, parent)| {
+                                    (constraint_to_state(constraint), *parent)
+                                })
+                                .collect()
+                        })
+                        .collect(),
                 })
-            };
+                .collect(),
+            replacements: self
+                .replacements
+                .iter()
+                .map(
+                    |((parent, constraint, node), replacement)| ReplacementState {
+                        parent: *parent,
+                        constraint: constraint_to_state(constraint),
+                        node: *node,
+                        replacement: *replacement,
+                    },
+                )
+                .sorted_by_key(|replacement| {
+                    (
+                        replacement.node,
+                        replacement.replacement,
+                        replacement.parent,
+                    )
+                })
+                .collect(),
+        };
+    }
 
-        self.graph.read(stream)?;
-        let adjustment_count = stream.read_u32::<LittleEndian>()?;
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
 
         let mut adjustments = HashMap::new();
-        for _ in 0..adjustment_count {
-            let node_id = stream.read_u32::<LittleEndian>()? as usize;
-            let remainder = match stream.read_u8()? {
-                0 => PresenceRemainder::Hide,
-                1 => PresenceRemainder::Show,
-                2 => PresenceRemainder::Duplicate,
-                _ => PresenceRemainder::DuplicateParent,
+        for adjustment in &state.presence.adjustments {
+            let groups = adjustment
+                .groups
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|(constraint, parent)| {
+                            Ok((constraint_from_state(constraint)?, *parent))
+                        })
+                        .collect::<Result<Vec<_>, VisError>>()
+                })
+                .collect::<Result<Vec<_>, VisError>>()?;
+            let group = PresenceGroups {
+                groups,
+                remainder: adjustment.remainder.clone(),
             };
-
-            let group_count = stream.read_u32::<LittleEndian>()?;
-            let mut groups = Vec::new();
-            for _ in 0..group_count {
-                let group_size = stream.read_u32::<LittleEndian>()?;
-                let mut group = Vec::new();
-                for _ in 0..group_size {
-                    let parent = stream.read_u32::<LittleEndian>()? as usize;
-                    let constraint = read_constraint(stream)?;
-                    group.push((constraint, parent));
-                }
-                groups.push(group);
-            }
-
-            let group = PresenceGroups { groups, remainder };
-
-            adjustments.insert(node_id, group);
+            adjustments.insert(adjustment.node, group);
         }
 
-        let replacement_count = stream.read_u32::<LittleEndian>()?;
         let mut replacements: HashMap<
             NodeID,
             HashMap<NodeID, Vec<(EdgeConstraint<G::T>, NodeID)>>,
         > = HashMap::new();
-        for _ in 0..replacement_count {
-            let parent = stream.read_u32::<LittleEndian>()? as usize;
-            let constraint = read_constraint(stream)?;
-            let node = stream.read_u32::<LittleEndian>()? as usize;
-            let replacement = stream.read_u32::<LittleEndian>()? as usize;
+        for stored in &state.presence.replacements {
+            let constraint = constraint_from_state(&stored.constraint)?;
             replacements
-                .entry(node)
+                .entry(stored.node)
                 .or_insert_with(HashMap::new)
-                .entry(replacement)
+                .entry(stored.replacement)
                 .or_insert_with(Vec::new)
-                .push((constraint, parent));
+                .push((constraint, stored.parent));
         }
 
         self.known_parents.clear();
@@ -761,3 +744,19 @@ where
         Ok(())
     }
 }
+
+fn constraint_to_state<T: StoredTag>(constraint: &EdgeConstraint<T>) -> EdgeConstraintState {
+    match constraint {
+        EdgeConstraint::Any => EdgeConstraintState::Any,
+        EdgeConstraint::Exact(edge) => EdgeConstraintState::Exact(EdgeState::new(edge)),
+    }
+}
+
+fn constraint_from_state<T: StoredTag>(
+    constraint: &EdgeConstraintState,
+) -> Result<EdgeConstraint<T>, VisError> {
+    Ok(match constraint {
+        EdgeConstraintState::Any => EdgeConstraint::Any,
+        EdgeConstraintState::Exact(edge) => EdgeConstraint::Exact(edge.get_edge()?),
+    })
+}
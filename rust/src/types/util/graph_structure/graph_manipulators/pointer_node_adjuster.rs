@@ -1,6 +1,5 @@
 use std::{
     collections::{HashMap, HashSet},
-    iter::repeat,
     marker::PhantomData,
     ops::Deref,
 };
@@ -13,9 +12,12 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::StateStorage,
+        storage::{
+            state_model::{GraphState, PointerState},
+            state_storage::StateStorage,
+        },
     },
-    util::{free_id_manager::FreeIdManager, logging::console},
+    util::{free_id_manager::FreeIdManager, logging::console, vis_error::VisError},
 };
 
 /// The LabelNodeAdjuster inserts new nodes with some label text to be used as pointers, according to pointer labels provided for each node
@@ -33,6 +35,7 @@ pub struct PointerNodeAdjuster<G: GraphStructure> {
     graph_events: GraphEventsReader,
 
     pointer_edge: EdgeType<G::T>,
+    complemented_pointer_edge: Option<EdgeType<G::T>>,
     transfer_root_pointers: bool,
     dummy_level_label: G::LL,
 
@@ -43,6 +46,10 @@ pub struct PointerNodeAdjuster<G: GraphStructure> {
 
 pub trait WithPointerLabels {
     fn get_pointer_labels(&self) -> Vec<String>;
+    /// Retrieves the labels of the pointers that refer to the complement of this node
+    fn get_complemented_pointer_labels(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 type SourcedNodeID = Either<NodeID, NodeID>;
@@ -63,6 +70,7 @@ fn from_sourced(id: SourcedNodeID) -> NodeID {
 #[derive(Clone)]
 pub struct PointerNode {
     text: String,
+    complemented: bool,
     pointer_for: NodeID,
 }
 
@@ -101,6 +109,7 @@ where
             graph,
             event_writer: GraphEventsWriter::new(),
             pointer_edge,
+            complemented_pointer_edge: None,
             transfer_root_pointers,
             dummy_level_label,
             pointers_of: HashMap::new(),
@@ -113,6 +122,19 @@ where
         adjuster
     }
 
+    /// Sets the edge type that should be used for pointers that refer to the complement of a node, by default `pointer_edge` is used for these as well
+    pub fn with_complemented_pointer_edge(mut self, edge: EdgeType<G::T>) -> Self {
+        self.complemented_pointer_edge = Some(edge);
+        self
+    }
+
+    fn get_pointer_edge(&self, pointer: &PointerNode) -> EdgeType<G::T> {
+        match self.complemented_pointer_edge {
+            Some(edge) if pointer.complemented => edge,
+            _ => self.pointer_edge,
+        }
+    }
+
     fn process_graph_changes(&mut self) {
         let events = self.graph.consume_events(&self.graph_events).clone();
         for event in events {
@@ -126,20 +148,26 @@ where
                     let pointers = pointer_ids
                         .iter()
                         .map(|&id| (id, self.pointers.get(&id).unwrap().clone()));
-                    let pointer_texts = self.graph.get_node_label(node).get_pointer_labels();
+                    let pointer_texts = get_pointer_texts(&self.graph.get_node_label(node));
                     let new_pointer_texts = pointer_texts
                         .iter()
                         .cloned()
-                        .filter(|text| !pointers.clone().any(|(_, pointer)| pointer.text == *text))
+                        .filter(|(text, complemented)| {
+                            !pointers.clone().any(|(_, pointer)| {
+                                pointer.text == *text && pointer.complemented == *complemented
+                            })
+                        })
                         .collect_vec();
                     let removed_pointers = pointers
                         .filter(|(_, pointer)| {
-                            !pointer_texts.iter().any(|text| pointer.text == *text)
+                            !pointer_texts.iter().any(|(text, complemented)| {
+                                pointer.text == *text && pointer.complemented == *complemented
+                            })
                         })
                         .collect_vec();
 
-                    for text in new_pointer_texts {
-                        self.add_pointer(node, text);
+                    for (text, complemented) in new_pointer_texts {
+                        self.add_pointer(node, text, complemented);
                     }
                     for (id, _) in removed_pointers {
                         self.remove_pointer(id);
@@ -195,16 +223,17 @@ where
     }
 
     fn add_labels(&mut self, node: NodeID) {
-        let pointer_texts = self.graph.get_node_label(node).get_pointer_labels();
-        for text in pointer_texts {
-            self.add_pointer(node, text);
+        let pointer_texts = get_pointer_texts(&self.graph.get_node_label(node));
+        for (text, complemented) in pointer_texts {
+            self.add_pointer(node, text, complemented);
         }
     }
 
-    fn add_pointer(&mut self, to: NodeID, text: String) {
+    fn add_pointer(&mut self, to: NodeID, text: String, complemented: bool) {
         let pointer = PointerNode {
             pointer_for: to,
             text,
+            complemented,
         };
         let id = self.free_id.get_next();
 
@@ -243,6 +272,21 @@ where
     }
 }
 
+/// Retrieves all pointer texts of a node, together with whether they refer to the complement of the node
+fn get_pointer_texts<L: WithPointerLabels>(label: &L) -> Vec<(String, bool)> {
+    label
+        .get_pointer_labels()
+        .into_iter()
+        .map(|text| (text, false))
+        .chain(
+            label
+                .get_complemented_pointer_labels()
+                .into_iter()
+                .map(|text| (text, true)),
+        )
+        .collect()
+}
+
 impl<G: GraphStructure> GraphStructure for PointerNodeAdjuster<G>
 where
     G::NL: WithPointerLabels,
@@ -294,10 +338,12 @@ where
                     .map(|(edge, node)| (edge, from_sourced(Either::Left(node))));
                 match self.pointers_of.get(&node) {
                     Some(pointers) => or_parents
-                        .chain(
-                            repeat(self.pointer_edge)
-                                .zip(pointers.iter().map(|&p| from_sourced(Either::Right(p)))),
-                        )
+                        .chain(pointers.iter().map(|&p| {
+                            (
+                                self.get_pointer_edge(&self.pointers[&p]),
+                                from_sourced(Either::Right(p)),
+                            )
+                        }))
                         .collect(),
                     None => or_parents.collect(),
                 }
@@ -317,7 +363,7 @@ where
                 .collect(),
             Either::Right(node) => match self.pointers.get(&node) {
                 Some(pointer) => vec![(
-                    self.pointer_edge,
+                    self.get_pointer_edge(pointer),
                     from_sourced(Either::Left(pointer.pointer_for)),
                 )],
                 None => vec![],
@@ -404,10 +450,34 @@ where
 }
 
 impl<G: GraphStructure + StateStorage> StateStorage for PointerNodeAdjuster<G> {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
+
+        // The pointers are derived from the diagram itself, so they are only used to detect states of other diagrams
+        let conflicting = state.pointers.iter().any(|stored| {
+            self.pointers.get(&stored.id).map_or(false, |pointer| {
+                pointer.pointer_for != stored.target || pointer.text != stored.text
+            })
+        });
+        if conflicting {
+            return Err(VisError::invalid_state(
+                "the state belongs to a diagram with different roots",
+            ));
+        }
+        Ok(())
     }
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        self.graph.write(stream)
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+        state.pointers = self
+            .pointers
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .map(|(&id, pointer)| PointerState {
+                id,
+                target: pointer.pointer_for,
+                text: pointer.text.clone(),
+                complemented: pointer.complemented,
+            })
+            .collect();
     }
 }
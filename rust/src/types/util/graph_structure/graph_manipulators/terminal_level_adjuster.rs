@@ -13,9 +13,9 @@ use crate::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::StateStorage,
+        storage::{state_model::GraphState, state_storage::StateStorage},
     },
-    util::{logging::console, rc_refcell::MutRcRefCell},
+    util::{logging::console, rc_refcell::MutRcRefCell, vis_error::VisError},
     wasm_interface::NodeID,
 };
 
@@ -101,13 +101,13 @@ impl<G: GraphStructure> StateStorage for TerminalLevelAdjuster<G>
 where
     G: StateStorage,
 {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
         self.init_terminals_cache();
         Ok(())
     }
-    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
-        self.graph.write(stream)
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state)
     }
 }
 
@@ -0,0 +1,539 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::{Either, Itertools};
+use oxidd::{LevelNo, NodeID};
+
+use crate::{
+    types::util::{
+        graph_structure::graph_structure::{
+            Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
+        },
+        storage::{
+            state_model::{EdgeState, GraphState, SuppressionMarkerState, SuppressionState},
+            state_storage::{StateStorage, StoredTag},
+        },
+    },
+    util::{free_id_manager::FreeIdManager, vis_error::VisError},
+};
+
+use super::pointer_node_adjuster::WithPointerLabels;
+
+/// The ZeroSuppressionAdjuster makes the levels that edges of zero-suppressed diagrams skip explicit, by inserting a marker node on every skipped level
+///
+// We distinguish 2 different nodeID kinds:
+// - left node IDs, corresponding to the underlying graph we are wrapping
+// - right node IDs, corresponding to the created marker nodes
+pub struct ZeroSuppressionAdjuster<G: GraphStructure> {
+    graph: G,
+    event_writer: GraphEventsWriter,
+    graph_events: GraphEventsReader,
+
+    marker_edge: EdgeType<G::T>,
+    suppressible: fn(&G::NL) -> bool,
+    enabled: bool,
+
+    discovered: HashSet<NodeID>, // The left nodes of which the children have been requested
+    chains: HashMap<(NodeID, EdgeType<G::T>, NodeID), Vec<NodeID>>, // Maps source edges to their markers, ordered from top to bottom
+    markers: HashMap<NodeID, SuppressionMarker<G::T>>,
+    free_id: FreeIdManager<usize>,
+}
+
+#[derive(Clone)]
+struct SuppressionMarker<T: DrawTag> {
+    parent: NodeID,
+    edge: EdgeType<T>,
+    child: NodeID,
+    level: LevelNo,
+}
+
+#[derive(Clone)]
+pub enum SuppressionLabel<NL: Clone> {
+    Node(NL),
+    /// A level that is skipped by an edge, on which the variable is implicitly zero
+    Suppressed,
+}
+
+impl<NL: Clone + WithPointerLabels> WithPointerLabels for SuppressionLabel<NL> {
+    fn get_pointer_labels(&self) -> Vec<String> {
+        match self {
+            SuppressionLabel::Node(label) => label.get_pointer_labels(),
+            SuppressionLabel::Suppressed => Vec::new(),
+        }
+    }
+    fn get_complemented_pointer_labels(&self) -> Vec<String> {
+        match self {
+            SuppressionLabel::Node(label) => label.get_complemented_pointer_labels(),
+            SuppressionLabel::Suppressed => Vec::new(),
+        }
+    }
+}
+
+type SourcedNodeID = Either<NodeID, NodeID>;
+fn to_sourced(id: NodeID) -> SourcedNodeID {
+    if id % 2 == 0 {
+        Either::Left(id / 2)
+    } else {
+        Either::Right(id / 2)
+    }
+}
+fn from_sourced(id: SourcedNodeID) -> NodeID {
+    match id {
+        Either::Left(id) => id * 2,
+        Either::Right(id) => id * 2 + 1,
+    }
+}
+
+impl<G: GraphStructure> ZeroSuppressionAdjuster<G> {
+    /// Creates a new zero suppression adjuster.
+    ///
+    /// `marker_edge` indicates the edge type that should be used for edges leaving marker nodes
+    /// `suppressible` indicates whether edges towards a node with the given label imply zero-suppressed levels, which is not the case for e.g. the empty terminal
+    pub fn new(
+        mut graph: G,
+        marker_edge: EdgeType<G::T>,
+        suppressible: fn(&G::NL) -> bool,
+    ) -> ZeroSuppressionAdjuster<G> {
+        ZeroSuppressionAdjuster {
+            graph_events: graph.create_event_reader(),
+            graph,
+            event_writer: GraphEventsWriter::new(),
+            marker_edge,
+            suppressible,
+            enabled: true,
+            discovered: HashSet::new(),
+            chains: HashMap::new(),
+            markers: HashMap::new(),
+            free_id: FreeIdManager::new(0),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled == enabled {
+            return;
+        }
+        self.process_graph_changes();
+        self.enabled = enabled;
+
+        let discovered = self.discovered.iter().cloned().sorted().collect_vec();
+        if enabled {
+            for &node in &discovered {
+                for (edge, child) in self.graph.get_children(node) {
+                    for marker in self.get_chain(node, edge, child) {
+                        self.event_writer.write(Change::NodeInsertion {
+                            node: from_sourced(Either::Right(marker)),
+                            source: Some(from_sourced(Either::Left(node))),
+                        });
+                    }
+                }
+            }
+        } else {
+            for marker in self.markers.keys().cloned().sorted() {
+                self.event_writer.write(Change::NodeRemoval {
+                    node: from_sourced(Either::Right(marker)),
+                });
+            }
+            self.chains.clear();
+            self.markers.clear();
+            self.free_id = FreeIdManager::new(0);
+        }
+
+        for node in discovered {
+            self.event_writer.write(Change::NodeConnectionsChange {
+                node: from_sourced(Either::Left(node)),
+            });
+        }
+    }
+
+    /// Retrieves the markers of the given edge, creating them if they had not been created yet
+    fn get_chain(&mut self, parent: NodeID, edge: EdgeType<G::T>, child: NodeID) -> Vec<NodeID> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        if let Some(chain) = self.chains.get(&(parent, edge, child)) {
+            return chain.clone();
+        }
+
+        let from = self.graph.get_level(parent);
+        let to = self.graph.get_level(child);
+        // Terminals may be placed on the biggest possible level, in which case the skipped levels are unknown
+        let chain =
+            if (self.suppressible)(&self.graph.get_node_label(child)) && to < LevelNo::MAX / 2 {
+                ((from + 1)..to)
+                    .map(|level| {
+                        let id = self.free_id.get_next();
+                        self.markers.insert(
+                            id,
+                            SuppressionMarker {
+                                parent,
+                                edge,
+                                child,
+                                level,
+                            },
+                        );
+                        id
+                    })
+                    .collect_vec()
+            } else {
+                Vec::new()
+            };
+        self.chains.insert((parent, edge, child), chain.clone());
+        chain
+    }
+
+    fn remove_chains_of(&mut self, node: NodeID) {
+        let removed = self
+            .chains
+            .keys()
+            .filter(|&&(parent, _, child)| parent == node || child == node)
+            .cloned()
+            .collect_vec();
+        for key in removed {
+            for marker in self.chains.remove(&key).unwrap_or_default() {
+                self.markers.remove(&marker);
+                self.free_id.make_available(marker);
+                self.event_writer.write(Change::NodeRemoval {
+                    node: from_sourced(Either::Right(marker)),
+                });
+            }
+        }
+    }
+
+    fn process_graph_changes(&mut self) {
+        let events = self.graph.consume_events(&self.graph_events);
+        for event in events {
+            match event {
+                Change::NodeLabelChange { node } => {
+                    self.event_writer.write(Change::NodeLabelChange {
+                        node: from_sourced(Either::Left(node)),
+                    });
+                }
+                Change::LevelChange { node } => {
+                    // The number of skipped levels may have changed
+                    self.remove_chains_of(node);
+                    self.event_writer.write(Change::LevelChange {
+                        node: from_sourced(Either::Left(node)),
+                    });
+                }
+                Change::LevelLabelChange { level } => {
+                    self.event_writer.write(Change::LevelLabelChange { level });
+                }
+                Change::NodeConnectionsChange { node } => {
+                    self.remove_chains_of(node);
+                    self.event_writer.write(Change::NodeConnectionsChange {
+                        node: from_sourced(Either::Left(node)),
+                    });
+                }
+                Change::ParentDiscover { child } => {
+                    self.event_writer.write(Change::ParentDiscover {
+                        child: from_sourced(Either::Left(child)),
+                    });
+                }
+                Change::NodeRemoval { node } => {
+                    self.remove_chains_of(node);
+                    self.discovered.remove(&node);
+                    self.event_writer.write(Change::NodeRemoval {
+                        node: from_sourced(Either::Left(node)),
+                    });
+                }
+                Change::NodeInsertion { node, source } => {
+                    self.event_writer.write(Change::NodeInsertion {
+                        node: from_sourced(Either::Left(node)),
+                        source: source.map(|s| from_sourced(Either::Left(s))),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<G: GraphStructure> GraphStructure for ZeroSuppressionAdjuster<G> {
+    type T = G::T;
+    type NL = SuppressionLabel<G::NL>;
+    type LL = G::LL;
+
+    fn get_roots(&self) -> Vec<NodeID> {
+        self.graph
+            .get_roots()
+            .into_iter()
+            .map(|node| from_sourced(Either::Left(node)))
+            .collect()
+    }
+
+    fn get_terminals(&self) -> Vec<NodeID> {
+        self.graph
+            .get_terminals()
+            .into_iter()
+            .map(|node| from_sourced(Either::Left(node)))
+            .collect()
+    }
+
+    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        match to_sourced(node) {
+            Either::Left(node) => self
+                .graph
+                .get_known_parents(node)
+                .into_iter()
+                .map(|(edge, parent)| {
+                    match self
+                        .chains
+                        .get(&(parent, edge, node))
+                        .and_then(|chain| chain.last())
+                    {
+                        Some(&marker) => (self.marker_edge, from_sourced(Either::Right(marker))),
+                        None => (edge, from_sourced(Either::Left(parent))),
+                    }
+                })
+                .collect(),
+            Either::Right(marker) => {
+                let Some(data) = self.markers.get(&marker) else {
+                    return vec![];
+                };
+                let chain = &self.chains[&(data.parent, data.edge, data.child)];
+                let index = chain.iter().position(|&m| m == marker).unwrap_or(0);
+                if index == 0 {
+                    vec![(data.edge, from_sourced(Either::Left(data.parent)))]
+                } else {
+                    vec![(
+                        self.marker_edge,
+                        from_sourced(Either::Right(chain[index - 1])),
+                    )]
+                }
+            }
+        }
+    }
+
+    fn get_children(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        match to_sourced(node) {
+            Either::Left(node) => {
+                self.discovered.insert(node);
+                self.graph
+                    .get_children(node)
+                    .into_iter()
+                    .map(
+                        |(edge, child)| match self.get_chain(node, edge, child).first() {
+                            Some(&marker) => (edge, from_sourced(Either::Right(marker))),
+                            None => (edge, from_sourced(Either::Left(child))),
+                        },
+                    )
+                    .collect()
+            }
+            Either::Right(marker) => {
+                let Some(data) = self.markers.get(&marker) else {
+                    return vec![];
+                };
+                let chain = &self.chains[&(data.parent, data.edge, data.child)];
+                let index = chain.iter().position(|&m| m == marker).unwrap_or(0);
+                match chain.get(index + 1) {
+                    Some(&next) => vec![(self.marker_edge, from_sourced(Either::Right(next)))],
+                    None => vec![(self.marker_edge, from_sourced(Either::Left(data.child)))],
+                }
+            }
+        }
+    }
+
+    fn get_level(&mut self, node: NodeID) -> LevelNo {
+        match to_sourced(node) {
+            Either::Left(node) => self.graph.get_level(node),
+            Either::Right(marker) => self.markers.get(&marker).map_or(0, |data| data.level),
+        }
+    }
+
+    fn get_node_label(&self, node: NodeID) -> SuppressionLabel<G::NL> {
+        match to_sourced(node) {
+            Either::Left(node) => SuppressionLabel::Node(self.graph.get_node_label(node)),
+            Either::Right(_) => SuppressionLabel::Suppressed,
+        }
+    }
+
+    fn get_level_label(&self, level: LevelNo) -> G::LL {
+        self.graph.get_level_label(level)
+    }
+
+    fn create_event_reader(&mut self) -> GraphEventsReader {
+        self.event_writer.create_reader()
+    }
+
+    fn consume_events(&mut self, reader: &GraphEventsReader) -> Vec<Change> {
+        self.process_graph_changes();
+        self.event_writer.read(reader)
+    }
+
+    fn local_nodes_to_sources(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        // Markers do not correspond to any source node
+        self.graph.local_nodes_to_sources(
+            nodes
+                .into_iter()
+                .filter_map(|node| to_sourced(node).left())
+                .collect(),
+        )
+    }
+
+    fn source_nodes_to_local(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph
+            .source_nodes_to_local(nodes)
+            .into_iter()
+            .map(|node| from_sourced(Either::Left(node)))
+            .collect()
+    }
+}
+
+impl<G: GraphStructure + StateStorage> StateStorage for ZeroSuppressionAdjuster<G>
+where
+    G::T: StoredTag,
+{
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+
+        state.zero_suppression = Some(SuppressionState {
+            enabled: self.enabled,
+            markers: self
+                .markers
+                .iter()
+                .sorted_by_key(|&(&id, _)| id)
+                .map(|(&id, marker)| SuppressionMarkerState {
+                    id,
+                    parent: marker.parent,
+                    child: marker.child,
+                    edge: EdgeState::new(&marker.edge),
+                    level: marker.level,
+                })
+                .collect(),
+        });
+    }
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
+        self.graph.read(state)?;
+
+        let Some(suppression) = &state.zero_suppression else {
+            return Ok(());
+        };
+        self.enabled = suppression.enabled;
+        self.chains.clear();
+        self.markers.clear();
+        self.free_id = FreeIdManager::new(0);
+
+        for stored in &suppression.markers {
+            let edge = stored.edge.get_edge()?;
+            self.free_id.claim(stored.id);
+            self.markers.insert(
+                stored.id,
+                SuppressionMarker {
+                    parent: stored.parent,
+                    edge,
+                    child: stored.child,
+                    level: stored.level,
+                },
+            );
+            self.chains
+                .entry((stored.parent, edge, stored.child))
+                .or_insert_with(|| Vec::new())
+                .push(stored.id);
+        }
+        let markers = &self.markers;
+        for chain in self.chains.values_mut() {
+            chain.sort_by_key(|marker| markers[marker].level);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::util::graph_structure::oxidd_graph_structure::{
+            NodeLabel, NodeType, OxiddGraphStructure,
+        },
+        util::dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+    };
+
+    /// The ZBDD of the family `{{a, c}}`, whose then edge from `a` to `c` skips the level of `b`
+    const AC: &str = "\
+.ver DDDMP-2.0
+.mode A
+.varinfo 0
+.dd f
+.nnodes 4
+.nvars 3
+.nsuppvars 3
+.suppvarnames a b c
+.orderedvarnames a b c
+.ids 0 1 2
+.permids 0 1 2
+.nroots 1
+.rootids 4
+.nodes
+1 F 0 0 0
+2 T 1 0 0
+3 c 2 2 1
+4 a 0 3 1
+.end
+";
+
+    fn create_adjuster(
+    ) -> ZeroSuppressionAdjuster<OxiddGraphStructure<(), DummyBDDFunction, String>> {
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let (roots, levels, _) = DummyBDDFunction::from_dddmp(&mut manager_ref, AC).unwrap();
+        ZeroSuppressionAdjuster::new(
+            OxiddGraphStructure::new(roots, levels),
+            EdgeType::new((), 3),
+            |label: &NodeLabel<String>| !matches!(label.kind, NodeType::Terminal(ref t) if t == "F"),
+        )
+    }
+
+    /// Retrieves the children of the node, together with the index of the edge to the child
+    fn get_children<G: GraphStructure<T = ()>>(graph: &mut G, node: NodeID) -> Vec<(i32, NodeID)> {
+        graph
+            .get_children(node)
+            .into_iter()
+            .map(|(edge, child)| (edge.index, child))
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn markers_fill_the_skipped_levels() {
+        let mut adjuster = create_adjuster();
+        assert_eq!(adjuster.get_roots(), vec![8]);
+
+        // The then edge of `a` passes a marker on the level of `b`, the else edge to the empty terminal does not
+        assert_eq!(get_children(&mut adjuster, 8), vec![(0, 1), (1, 2)]);
+        assert_eq!(adjuster.get_level(1), 1);
+        assert!(matches!(
+            adjuster.get_node_label(1),
+            SuppressionLabel::Suppressed
+        ));
+        assert_eq!(get_children(&mut adjuster, 1), vec![(3, 6)]);
+        assert_eq!(
+            adjuster
+                .get_known_parents(1)
+                .into_iter()
+                .map(|(edge, parent)| (edge.index, parent))
+                .collect_vec(),
+            vec![(0, 8)]
+        );
+
+        // Edges between adjacent levels do not need markers
+        assert_eq!(get_children(&mut adjuster, 6), vec![(0, 4), (1, 2)]);
+        assert_eq!(adjuster.local_nodes_to_sources(vec![1, 6]), vec![3]);
+    }
+
+    #[test]
+    fn disabling_removes_the_markers() {
+        let mut adjuster = create_adjuster();
+        let reader = adjuster.create_event_reader();
+        get_children(&mut adjuster, 8);
+        adjuster.consume_events(&reader);
+
+        adjuster.set_enabled(false);
+        let events = adjuster.consume_events(&reader);
+        assert!(events.contains(&Change::NodeRemoval { node: 1 }));
+        assert!(events.contains(&Change::NodeConnectionsChange { node: 8 }));
+        assert_eq!(get_children(&mut adjuster, 8), vec![(0, 6), (1, 2)]);
+
+        adjuster.set_enabled(true);
+        assert_eq!(get_children(&mut adjuster, 8), vec![(0, 1), (1, 2)]);
+        assert_eq!(adjuster.get_level(1), 1);
+    }
+}
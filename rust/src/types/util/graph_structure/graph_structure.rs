@@ -9,9 +9,9 @@ use std::{
     usize,
 };
 
-use js_sys::Math::random;
 use oxidd::{Edge, Function, InnerNode, LevelNo, Manager};
 use oxidd_core::{DiagramRules, HasLevel, Node, Tag};
+use oxidd_rules_bdd::complement_edge::EdgeTag;
 
 use crate::{
     util::{logging::console, rc_refcell::MutRcRefCell},
@@ -48,10 +48,20 @@ pub trait GraphStructure {
 }
 // pub type GraphListener = dyn Fn(&Vec<Change>) -> ();
 
-pub trait DrawTag: Tag + Hash + Ord {}
+pub trait DrawTag: Tag + Hash + Ord {
+    /// Whether edges with this tag represent the complement of the node they point to
+    fn is_complemented(&self) -> bool {
+        false
+    }
+}
 impl DrawTag for () {}
+impl DrawTag for EdgeTag {
+    fn is_complemented(&self) -> bool {
+        *self == EdgeTag::Complemented
+    }
+}
 
-#[derive(Eq, PartialEq, Copy, Clone, PartialOrd, Ord, Hash)]
+#[derive(Eq, PartialEq, Copy, Clone, PartialOrd, Ord, Hash, Debug)]
 pub struct EdgeType<T: DrawTag> {
     pub tag: T,
     pub index: i32,
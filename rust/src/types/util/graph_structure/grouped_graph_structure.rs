@@ -28,7 +28,7 @@ pub trait GroupedGraphStructure {
     fn create_node_tracker(&mut self) -> Self::Tracker;
 }
 
-#[derive(PartialEq, Eq, Clone, Hash)]
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
 pub struct EdgeData<T: DrawTag> {
     pub to: NodeGroupID,
     pub from_level: LevelNo,
@@ -6,10 +6,16 @@ use std::{
     rc::Rc,
 };
 
+use itertools::Itertools;
 use oxidd::{Edge, Function, InnerNode, LevelNo, Manager, NodeID};
-use oxidd_core::{DiagramRules, HasLevel, Node};
+use oxidd_core::{HasLevel, Node};
+use oxidd_rules_bdd::simple::BDDTerminal;
+use oxidd_rules_mtbdd::terminal::Int64;
 
-use crate::{types::util::storage::state_storage::StateStorage, util::logging::console};
+use crate::{
+    types::util::storage::state_storage::StateStorage,
+    util::{dummy_mtbdd::MTBDDTerminal, logging::console},
+};
 
 use super::{
     graph_manipulators::pointer_node_adjuster::WithPointerLabels,
@@ -18,13 +24,15 @@ use super::{
     },
 };
 
-pub struct OxiddGraphStructure<DT: DrawTag, F: Function, T: ToString>
+pub struct OxiddGraphStructure<DT: DrawTag, F: Function, T>
 where
-    for<'id> F::Manager<'id>: Manager<EdgeTag = DT, Terminal = T>,
+    for<'id> F::Manager<'id>: Manager<EdgeTag = DT>,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<T>,
 {
     roots: Vec<F>,
     node_by_id: HashMap<NodeID, F>,
     pointers: HashMap<NodeID, Vec<String>>,
+    complemented_pointers: HashMap<NodeID, Vec<String>>,
     node_parents: HashMap<NodeID, HashSet<(EdgeType<DT>, NodeID)>>,
     level_labels: Vec<String>,
     terminal: PhantomData<T>,
@@ -34,12 +42,17 @@ where
 #[derive(Clone)]
 pub struct NodeLabel<T> {
     pub pointers: Vec<String>,
+    /// The pointers that refer to the complement of this node
+    pub complemented_pointers: Vec<String>,
     pub kind: NodeType<T>,
 }
 impl<T> WithPointerLabels for NodeLabel<T> {
     fn get_pointer_labels(&self) -> Vec<String> {
         self.pointers.clone()
     }
+    fn get_complemented_pointer_labels(&self) -> Vec<String> {
+        self.complemented_pointers.clone()
+    }
 }
 
 #[derive(Clone)]
@@ -48,9 +61,38 @@ pub enum NodeType<T> {
     Terminal(T),
 }
 
-impl<DT: DrawTag, F: Function, T: ToString> OxiddGraphStructure<DT, F, T>
+/// Converts the terminals of a manager into the terminal labels that drawers work with, such that drawers do not depend on the terminal type of a specific manager.
+///
+/// Managers with other terminal types, such as MTBDD managers with custom values, can be visualized by implementing this trait for their terminal type
+pub trait IntoTerminalLabel<T> {
+    fn to_terminal_label(&self) -> T;
+}
+impl<T: Clone> IntoTerminalLabel<T> for T {
+    fn to_terminal_label(&self) -> T {
+        self.clone()
+    }
+}
+impl IntoTerminalLabel<String> for BDDTerminal {
+    fn to_terminal_label(&self) -> String {
+        match self {
+            BDDTerminal::False => "F".to_string(),
+            BDDTerminal::True => "T".to_string(),
+        }
+    }
+}
+impl IntoTerminalLabel<MTBDDTerminal> for Int64 {
+    fn to_terminal_label(&self) -> MTBDDTerminal {
+        match self {
+            Int64::Num(value) => MTBDDTerminal(*value as f32),
+            Int64::NaN => MTBDDTerminal(f32::NAN),
+        }
+    }
+}
+
+impl<DT: DrawTag, F: Function, T> OxiddGraphStructure<DT, F, T>
 where
-    for<'id> F::Manager<'id>: Manager<EdgeTag = DT, Terminal = T>,
+    for<'id> F::Manager<'id>: Manager<EdgeTag = DT>,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<T>,
 {
     pub fn new(
         roots: Vec<(F, Vec<String>)>,
@@ -67,15 +109,8 @@ where
                 })
                 .collect(),
             roots: roots.iter().map(|(f, _)| f.clone()).collect(),
-            pointers: roots
-                .iter()
-                .map(|(f, pointers)| {
-                    (
-                        f.with_manager_shared(|_, edge| edge.node_id()),
-                        pointers.clone(),
-                    )
-                })
-                .collect(),
+            pointers: get_root_pointers(&roots, false),
+            complemented_pointers: get_root_pointers(&roots, true),
             level_labels,
             node_parents: HashMap::new(),
             event_writer: GraphEventsWriter::new(),
@@ -83,6 +118,77 @@ where
         }
     }
 
+    /// Replaces the diagram by the given roots, such as the same functions under a different variable order. Nodes of the new diagram whose source (the node of the replaced diagram they originate from, see `sources`) was discovered are inserted right away, such that they take the place of their source, while the other nodes are discovered as usual
+    pub fn replace_roots(
+        &mut self,
+        roots: Vec<(F, Vec<String>)>,
+        level_labels: Vec<String>,
+        sources: &HashMap<NodeID, NodeID>,
+    ) {
+        let old_nodes = self.node_by_id.keys().cloned().collect::<HashSet<_>>();
+        self.event_writer.write_vec(
+            old_nodes
+                .iter()
+                .map(|&node| Change::NodeRemoval { node })
+                .collect(),
+        );
+        let level_count = self.level_labels.len().max(level_labels.len());
+        self.event_writer.write_vec(
+            (0..level_count)
+                .map(|level| Change::LevelLabelChange {
+                    level: level as LevelNo,
+                })
+                .collect(),
+        );
+
+        let new_roots = roots.iter().map(|(f, _)| f.clone()).collect_vec();
+        self.node_by_id.clear();
+        self.node_parents.clear();
+        self.pointers = get_root_pointers(&roots, false);
+        self.complemented_pointers = get_root_pointers(&roots, true);
+        self.level_labels = level_labels;
+        self.roots = new_roots.clone();
+
+        // Nodes without a source of their own originate from their parent
+        let mut node_sources = HashMap::<NodeID, NodeID>::new();
+        let mut stack = new_roots;
+        while let Some(f) = stack.pop() {
+            let (id, children) = f.with_manager_shared(|manager, edge| {
+                let children: Vec<F> = match manager.get_node(edge) {
+                    Node::Inner(node) => node
+                        .children()
+                        .map(|child| F::from_edge_ref(manager, &child))
+                        .collect(),
+                    Node::Terminal(_) => Vec::new(),
+                };
+                (edge.node_id(), children)
+            });
+            let Some(&source) = sources.get(&id).or_else(|| node_sources.get(&id)) else {
+                continue;
+            };
+            if self.node_by_id.contains_key(&id) || !old_nodes.contains(&source) {
+                continue;
+            }
+            self.node_by_id.insert(id, f);
+            node_sources.insert(id, source);
+            for child in children {
+                let child_id = child.with_manager_shared(|_, edge| edge.node_id());
+                node_sources.entry(child_id).or_insert(source);
+                stack.push(child);
+            }
+        }
+        self.event_writer.write_vec(
+            node_sources
+                .iter()
+                .filter(|(node, _)| self.node_by_id.contains_key(node))
+                .map(|(&node, &source)| Change::NodeInsertion {
+                    node,
+                    source: Some(source),
+                })
+                .collect(),
+        );
+    }
+
     fn get_id_by_node(&mut self, node: &F) -> NodeID {
         node.with_manager_shared(|manager, edge| {
             let id = edge.node_id();
@@ -105,33 +211,85 @@ where
     }
 }
 
-impl<
-        ET: DrawTag + 'static,
-        T: Clone + ToString + 'static,
-        E: Edge<Tag = ET> + 'static,
-        N: InnerNode<E> + HasLevel + 'static,
-        R: DiagramRules<E, N, T> + 'static,
-        F: Function + 'static,
-    > StateStorage for OxiddGraphStructure<ET, F, T>
+/// Retrieves the names of the roots per node, considering only roots whose edge has the given complement status
+fn get_root_pointers<DT: DrawTag, F: Function>(
+    roots: &[(F, Vec<String>)],
+    complemented: bool,
+) -> HashMap<NodeID, Vec<String>>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = DT>,
+{
+    let mut pointers = HashMap::<NodeID, Vec<String>>::new();
+    for (f, names) in roots {
+        let (id, is_complemented) =
+            f.with_manager_shared(|_, edge| (edge.node_id(), edge.tag().is_complemented()));
+        if is_complemented == complemented {
+            pointers
+                .entry(id)
+                .or_default()
+                .extend(names.iter().cloned());
+        }
+    }
+    pointers
+}
+
+/// Retrieves the kind of the node the given edge points to
+fn get_node_type<M: Manager, T>(manager: &M, edge: &M::Edge) -> NodeType<T>
 where
-    for<'id> F::Manager<'id>:
-        Manager<EdgeTag = ET, Edge = E, InnerNode = N, Rules = R, Terminal = T>,
+    M::Terminal: IntoTerminalLabel<T>,
 {
+    match manager.get_node(edge) {
+        Node::Inner(_) => NodeType::Inner(edge.node_id().to_string()),
+        Node::Terminal(terminal) => {
+            let terminal: &M::Terminal = terminal.borrow();
+            NodeType::Terminal(terminal.to_terminal_label())
+        }
+    }
+}
+
+/// Retrieves all nodes reachable from the given roots, together with their number of children
+pub fn get_reachable_nodes<F: Function>(roots: &[F]) -> Vec<(NodeID, F, usize)> {
+    let mut seen = HashSet::new();
+    let mut nodes = Vec::new();
+    let mut stack = roots.to_vec();
+    while let Some(f) = stack.pop() {
+        let (id, children) = f.with_manager_shared(|manager, edge| {
+            let children: Vec<F> = match manager.get_node(edge) {
+                Node::Inner(node) => node
+                    .children()
+                    .map(|child| F::from_edge_ref(manager, &child))
+                    .collect(),
+                Node::Terminal(_) => Vec::new(),
+            };
+            (edge.node_id(), children)
+        });
+        if !seen.insert(id) {
+            continue;
+        }
+        nodes.push((id, f, children.len()));
+        stack.extend(children);
+    }
+    nodes
 }
 
-impl<
-        ET: DrawTag + 'static,
-        T: Clone + ToString + 'static,
-        E: Edge<Tag = ET> + 'static,
-        N: InnerNode<E> + HasLevel + 'static,
-        R: DiagramRules<E, N, T> + 'static,
-        F: Function + 'static,
-    > GraphStructure for OxiddGraphStructure<ET, F, T>
+// The bounds are stated per manager lifetime rather than by fixing the edge and node types, since those of real managers (unlike the dummy managers) depend on the lifetime
+impl<DT: DrawTag + 'static, T: Clone + 'static, F: Function + 'static> StateStorage
+    for OxiddGraphStructure<DT, F, T>
 where
-    for<'id> F::Manager<'id>:
-        Manager<EdgeTag = ET, Edge = E, InnerNode = N, Rules = R, Terminal = T>,
+    for<'id> F::Manager<'id>: Manager<EdgeTag = DT>,
+    for<'id> <F::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<T>,
 {
-    type T = ET;
+}
+
+impl<DT: DrawTag + 'static, T: Clone + 'static, F: Function + 'static> GraphStructure
+    for OxiddGraphStructure<DT, F, T>
+where
+    for<'id> F::Manager<'id>: Manager<EdgeTag = DT>,
+    for<'id> <F::Manager<'id> as Manager>::InnerNode: HasLevel,
+    for<'id> <F::Manager<'id> as Manager>::Terminal: IntoTerminalLabel<T>,
+{
+    type T = DT;
     type NL = NodeLabel<T>;
     type LL = String;
 
@@ -145,29 +303,37 @@ where
     fn get_terminals(&self) -> Vec<NodeID> {
         if let Some(root) = self.roots.first() {
             root.with_manager_shared(|manager, edge| {
-                manager.terminals().map(|t| t.node_id()).collect()
+                manager
+                    .terminals()
+                    .map(|terminal| {
+                        let id = terminal.node_id();
+                        // The terminal edges are owned, and must thus be returned to the manager
+                        manager.drop_edge(terminal);
+                        id
+                    })
+                    .collect()
             })
         } else {
             Vec::new()
         }
     }
 
-    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<ET>, NodeID)> {
+    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<DT>, NodeID)> {
         if let Some(edges) = self.node_parents.get(&node) {
             return Vec::from_iter(edges.iter().map(|&r| r));
         }
         return Vec::new();
     }
 
-    fn get_children(&mut self, node_id: NodeID) -> Vec<(EdgeType<ET>, NodeID)> {
+    fn get_children(&mut self, node_id: NodeID) -> Vec<(EdgeType<DT>, NodeID)> {
         let opt_node = &self.get_node_by_id(node_id);
         if let Some(node) = opt_node {
+            // The stored children are used instead of the cofactors, such that complemented edges are shown as-is rather than being resolved based on the edge the node was reached by
             let cofactors = node.with_manager_shared(move |manager, edge| {
-                let tag = edge.tag();
                 let internal_node = manager.get_node(edge);
                 if let Node::Inner(node) = internal_node {
                     Some(Vec::from_iter(
-                        R::cofactors(tag, node)
+                        node.children()
                             .map(|f| F::from_edge_ref(manager, &f))
                             .enumerate(),
                     ))
@@ -208,16 +374,18 @@ where
 
     fn get_node_label(&self, node: NodeID) -> NodeLabel<T> {
         let kind = if let Some(node) = self.get_node_by_id(node) {
-            node.with_manager_shared(|manager, edge| match manager.get_node(edge) {
-                Node::Inner(n) => NodeType::Inner(edge.node_id().to_string()),
-                Node::Terminal(t) => NodeType::Terminal(t.borrow().clone()),
-            })
+            node.with_manager_shared(|manager, edge| get_node_type(manager, edge))
         } else {
             NodeType::Inner("Not found".to_string())
         };
 
         NodeLabel {
             pointers: self.pointers.get(&node).cloned().unwrap_or_else(|| vec![]),
+            complemented_pointers: self
+                .complemented_pointers
+                .get(&node)
+                .cloned()
+                .unwrap_or_else(|| vec![]),
             kind,
         }
     }
@@ -4,21 +4,22 @@ use std::{
     collections::{HashMap, HashSet, LinkedList, VecDeque},
     fmt::Display,
     hash::Hash,
-    io::{Cursor, Result, Write},
     iter::FromIterator,
     marker::PhantomData,
     rc::Rc,
     vec::IntoIter,
 };
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::Itertools;
 use oxidd::{Edge, Function, InnerNode, LevelNo, Manager};
 use oxidd_core::{DiagramRules, Node, Tag};
 use priority_queue::PriorityQueue;
 
 use crate::{
-    util::{free_id_manager::FreeIdManager, logging::console, rc_refcell::MutRcRefCell},
+    util::{
+        free_id_manager::FreeIdManager, logging::console, rc_refcell::MutRcRefCell,
+        vis_error::VisError,
+    },
     wasm_interface::{NodeGroupID, NodeID, TargetID, TargetIDType},
 };
 
@@ -29,7 +30,10 @@ use super::{
         oxidd_graph_structure::NodeLabel,
     },
     node_tracker_manager::{NodeTrackerM, NodeTrackerManager},
-    storage::state_storage::StateStorage,
+    storage::{
+        state_model::{GraphState, GroupState},
+        state_storage::StateStorage,
+    },
 };
 
 pub struct GroupManager<G: GraphStructure> {
@@ -587,25 +591,10 @@ impl<G: GraphStructure> GroupManager<G> {
             } else if from_id == to {
                 continue;
             } else if from_id == 0 {
-                let Some(group) = self.group_by_id.get_mut(&from_id) else {
+                if !self.group_by_id.contains_key(&from_id) {
                     continue;
-                };
-                let init_nodes = group.nodes.keys();
-                let mut found: HashSet<NodeID> = init_nodes.clone().cloned().collect();
-                let mut queue: LinkedList<NodeID> = init_nodes.cloned().collect();
-
-                while !queue.is_empty() {
-                    let node_id = queue.pop_front().unwrap();
-                    for (_, child_id) in self.graph.get_children(node_id) {
-                        if found.contains(&child_id) {
-                            continue;
-                        }
-
-                        found.insert(child_id);
-                        queue.push_back(child_id);
-                    }
                 }
-
+                let found = self.explore_hidden_nodes();
                 self.set_group(
                     found
                         .into_iter()
@@ -663,6 +652,72 @@ impl<G: GraphStructure> GroupManager<G> {
         new_id
     }
 
+    /// Distributes the nodes of the given group over new groups that each contain the nodes of a band of consecutive levels, using as few levels per band as possible while creating at most `max_groups` groups. This way huge diagrams can be shown by their level profile, from which the groups of interest can be expanded. Returns the created groups from the top band downwards
+    pub fn group_by_levels(&mut self, from: NodeGroupID, max_groups: usize) -> Vec<NodeGroupID> {
+        if !self.group_by_id.contains_key(&from) {
+            return Vec::new();
+        }
+        // The hidden group only contains the roots until it is explored
+        let nodes = if from == 0 {
+            self.explore_hidden_nodes()
+                .into_iter()
+                .sorted()
+                .collect_vec()
+        } else {
+            self.get_nodes_of_group(from)
+        };
+        let levels = nodes
+            .into_iter()
+            .map(|node| (self.graph.get_level(node), node))
+            .collect_vec();
+        let (Some(min_level), Some(max_level)) = (
+            levels.iter().map(|&(level, _)| level).min(),
+            levels.iter().map(|&(level, _)| level).max(),
+        ) else {
+            return Vec::new();
+        };
+        let level_count = (max_level - min_level) as usize + 1;
+        let band_size = level_count.div_ceil(max_groups.max(1));
+
+        levels
+            .into_iter()
+            .into_group_map_by(|&(level, _)| (level - min_level) as usize / band_size)
+            .into_iter()
+            .sorted_by_key(|&(band, _)| band)
+            .map(|(_, nodes)| {
+                self.create_group(
+                    nodes
+                        .into_iter()
+                        .map(|(_, node)| TargetID(TargetIDType::NodeID, node))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Retrieves the nodes of the hidden group, including the nodes reachable from it that have not been discovered yet
+    fn explore_hidden_nodes(&mut self) -> HashSet<NodeID> {
+        let Some(group) = self.group_by_id.get(&0) else {
+            return HashSet::new();
+        };
+        let init_nodes = group.nodes.keys();
+        let mut found: HashSet<NodeID> = init_nodes.clone().cloned().collect();
+        let mut queue: LinkedList<NodeID> = init_nodes.cloned().collect();
+
+        while !queue.is_empty() {
+            let node_id = queue.pop_front().unwrap();
+            for (_, child_id) in self.graph.get_children(node_id) {
+                if found.contains(&child_id) {
+                    continue;
+                }
+
+                found.insert(child_id);
+                queue.push_back(child_id);
+            }
+        }
+        found
+    }
+
     pub fn split_edges(&mut self, node_ids: &[NodeID], max_layers: usize, mut max_nodes: usize) {
         // TODO: come up with a better splitting approach that considers nodes together
         let mut split = HashSet::new();
@@ -745,6 +800,111 @@ impl<G: GraphStructure> GroupManager<G> {
         //     }
         // }
     }
+
+    /// Reveals the most informative neighbourhood of at most `max_nodes` nodes around the focus nodes, while keeping at most `max_visible` nodes revealed in total. Previously revealed nodes stay revealed as context, from closest to furthest, as long as the budget allows, and the remaining ones are grouped back into a summary group per level
+    pub fn focus(&mut self, focus: &[NodeID], max_nodes: usize, max_visible: usize) {
+        let ranking = self.rank_neighbourhood(focus, max_visible * 4);
+        let rank_by_node: HashMap<NodeID, usize> = ranking
+            .iter()
+            .enumerate()
+            .map(|(rank, &node)| (node, rank))
+            .collect();
+        let reveal = ranking
+            .into_iter()
+            .take(max_nodes.min(max_visible))
+            .collect_vec();
+        let revealed: HashSet<NodeID> = reveal.iter().cloned().collect();
+
+        let context = self
+            .group_by_id
+            .iter()
+            .filter(|&(&group_id, group)| group_id != 0 && group.nodes.len() == 1)
+            .flat_map(|(_, group)| group.nodes.keys().cloned())
+            .filter(|node| !revealed.contains(node))
+            .sorted_by_key(|&node| (rank_by_node.get(&node).cloned().unwrap_or(usize::MAX), node))
+            .collect_vec();
+        let far = context
+            .into_iter()
+            .skip(max_visible - reveal.len())
+            .collect_vec();
+
+        for node in reveal {
+            let group_id = self.get_group(node);
+            if self.get_nodes_of_group(group_id).len() == 1 && group_id != 0 {
+                continue;
+            }
+            self.create_group(vec![TargetID(TargetIDType::NodeID, node)]);
+        }
+
+        let far_by_level = far
+            .into_iter()
+            .map(|node| (self.graph.get_level(node), node))
+            .into_group_map();
+        for (_, nodes) in far_by_level.into_iter().sorted_by_key(|&(level, _)| level) {
+            self.create_group(
+                nodes
+                    .into_iter()
+                    .map(|node| TargetID(TargetIDType::NodeID, node))
+                    .collect(),
+            );
+        }
+    }
+
+    /// Ranks the nodes around the given nodes by their distance to these nodes, until `limit` nodes are ranked. Steps towards shared nodes and nodes with a large subgraph are cheaper, such that these informative nodes are ranked higher
+    fn rank_neighbourhood(&mut self, from: &[NodeID], limit: usize) -> Vec<NodeID> {
+        let mut ranking = Vec::new();
+        let mut ranked = HashSet::new();
+        let mut queue = PriorityQueue::<NodeID, Reverse<(usize, NodeID)>>::new();
+        // A node is reached from each of its neighbours, while its step cost stays the same
+        let mut step_costs = HashMap::<NodeID, usize>::new();
+        for &node in from {
+            queue.push(node, Reverse((0, node)));
+        }
+
+        while let Some((node, Reverse((distance, _)))) = queue.pop() {
+            if ranking.len() >= limit {
+                break;
+            }
+            ranked.insert(node);
+            ranking.push(node);
+
+            let neighbours = self
+                .graph
+                .get_children(node)
+                .into_iter()
+                .chain(self.graph.get_known_parents(node))
+                .map(|(_, neighbour)| neighbour)
+                .filter(|neighbour| !ranked.contains(neighbour))
+                .collect_vec();
+            for neighbour in neighbours {
+                let step_cost = *step_costs
+                    .entry(neighbour)
+                    .or_insert_with(|| self.get_step_cost(neighbour));
+                let neighbour_distance = distance + step_cost;
+                queue.push_increase(neighbour, Reverse((neighbour_distance, neighbour)));
+            }
+        }
+        ranking
+    }
+
+    fn get_step_cost(&mut self, node: NodeID) -> usize {
+        let shared = self.graph.get_known_parents(node).len().max(1);
+
+        // Only count the subgraph up to a bound, since its size is only used as an indication
+        let max_size = 256;
+        let mut subgraph = HashSet::from([node]);
+        let mut frontier = vec![node];
+        while let Some(node) = frontier.pop() {
+            for (_, child) in self.graph.get_children(node) {
+                if subgraph.len() < max_size && subgraph.insert(child) {
+                    frontier.push(child);
+                }
+            }
+        }
+
+        let informativeness = 1.0 + (shared as f32).ln() + (subgraph.len() as f32).ln() / 4.0;
+        (100.0 / informativeness) as usize + 1
+    }
 }
 
 impl<G: GraphStructure> GroupedGraphStructure for GroupManager<G> {
@@ -885,27 +1045,24 @@ impl<G: GraphStructure> GroupedGraphStructure for GroupManager<G> {
 }
 
 impl<G: GraphStructure + StateStorage> StateStorage for GroupManager<G> {
-    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
-        self.graph.write(stream)?;
-        let group_count = self.group_by_id.len();
-        stream.write_u32::<LittleEndian>(group_count as u32)?;
-        for (group_id, group) in &self.group_by_id {
-            let nodes = group.nodes.keys();
-            let node_count = nodes.len();
-            stream.write_u32::<LittleEndian>(*group_id as u32)?;
-            stream.write_u32::<LittleEndian>(node_count as u32)?;
-            for node in nodes {
-                stream.write_u32::<LittleEndian>(*node as u32)?;
-            }
-        }
-        Ok(())
+    fn write(&self, state: &mut GraphState) {
+        self.graph.write(state);
+        state.groups = self
+            .group_by_id
+            .iter()
+            .map(|(&id, group)| GroupState {
+                id,
+                nodes: group.nodes.keys().cloned().sorted().collect(),
+            })
+            .sorted_by_key(|group| group.id)
+            .collect();
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
+    fn read(&mut self, state: &GraphState) -> Result<(), VisError> {
         self.graph.consume_events(&self.graph_events);
         self.reset();
 
-        self.graph.read(stream)?;
+        self.graph.read(state)?;
         // No events should be created, but just in case, throw away events
         let events = self.graph.consume_events(&self.graph_events);
         if events.len() > 0 {
@@ -917,18 +1074,15 @@ impl<G: GraphStructure + StateStorage> StateStorage for GroupManager<G> {
         }
 
         let mut all_found_nodes = HashSet::new();
-        let group_count = stream.read_u32::<LittleEndian>()?;
         let mut to_add = Vec::new();
-        for _ in 0..group_count {
-            let group_id = stream.read_u32::<LittleEndian>()? as usize;
-            let node_count = stream.read_u32::<LittleEndian>()?;
-
-            let mut targets = Vec::<TargetID>::new();
-            for _ in 0..node_count {
-                let node = stream.read_u32::<LittleEndian>()? as usize;
-                targets.push(TargetID::new(TargetIDType::NodeID, node));
-                all_found_nodes.insert(node);
-            }
+        for group in &state.groups {
+            let group_id = group.id;
+            let targets = group
+                .nodes
+                .iter()
+                .map(|&node| TargetID::new(TargetIDType::NodeID, node))
+                .collect_vec();
+            all_found_nodes.extend(group.nodes.iter().cloned());
 
             if !self.group_by_id.contains_key(&group_id) {
                 self.group_ids.add_group_id(group_id, true);
@@ -986,3 +1140,118 @@ impl<G: GraphStructure> GroupManager<G> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::util::graph_structure::oxidd_graph_structure::OxiddGraphStructure,
+        util::dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+    };
+
+    type TestManager = GroupManager<OxiddGraphStructure<(), DummyBDDFunction, String>>;
+
+    /// Creates the group manager of the BDD of the given formula, whose nodes are all hidden
+    fn create_manager(formula: &str) -> TestManager {
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let (roots, levels, _) =
+            DummyBDDFunction::from_formula(&mut manager_ref, formula, None).unwrap();
+        GroupManager::new(OxiddGraphStructure::new(roots, levels))
+    }
+
+    #[test]
+    fn hidden_nodes_are_grouped_by_levels() {
+        // One node per variable level, and both terminals in the level below
+        let mut manager = create_manager("f = x0 & x1 & x2 & x3");
+        let bands = manager.group_by_levels(0, 3);
+
+        let node_counts = bands
+            .iter()
+            .map(|&band| manager.get_nodes_of_group(band).len())
+            .collect_vec();
+        assert_eq!(node_counts, vec![2, 2, 2]);
+        let levels = bands
+            .iter()
+            .map(|&band| {
+                manager
+                    .get_nodes_of_group(band)
+                    .into_iter()
+                    .map(|node| manager.graph.get_level(node))
+                    .sorted()
+                    .collect_vec()
+            })
+            .collect_vec();
+        assert_eq!(levels, vec![vec![0, 1], vec![2, 3], vec![4, 4]]);
+
+        // The bands are created directly, rather than from a group that first explores the hidden nodes
+        let groups = manager
+            .get_groups()
+            .iter()
+            .filter(|(_, group)| !group.nodes.is_empty())
+            .map(|(&id, _)| id)
+            .sorted()
+            .collect_vec();
+        assert_eq!(groups, bands.iter().cloned().sorted().collect_vec());
+    }
+
+    #[test]
+    fn bands_use_as_few_levels_as_possible() {
+        let mut manager = create_manager("f = x0 & x1 & x2 & x3");
+        let bands = manager.group_by_levels(0, 2);
+        let node_counts = bands
+            .iter()
+            .map(|&band| manager.get_nodes_of_group(band).len())
+            .collect_vec();
+        assert_eq!(node_counts, vec![3, 3]);
+    }
+
+    /// Reveals every node of the diagram in a group of its own
+    fn reveal_all(manager: &mut TestManager) {
+        let explored = manager.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
+        for node in manager.get_nodes_of_group(explored) {
+            manager.create_group(vec![TargetID(TargetIDType::NodeID, node)]);
+        }
+    }
+
+    /// Retrieves the levels of the nodes per visible group
+    fn get_group_levels(manager: &TestManager) -> Vec<Vec<LevelNo>> {
+        manager
+            .get_groups()
+            .iter()
+            .filter(|&(&id, group)| id != 0 && !group.nodes.is_empty())
+            .map(|(_, group)| {
+                group
+                    .nodes
+                    .keys()
+                    .map(|&node| manager.graph.get_level(node))
+                    .sorted()
+                    .collect_vec()
+            })
+            .sorted()
+            .collect_vec()
+    }
+
+    #[test]
+    fn focus_reveals_at_most_the_visible_nodes() {
+        // The parity function has a single root, followed by two nodes on each level
+        let mut manager = create_manager("f = x0 ^ x1 ^ x2");
+        let root = manager.graph.get_roots()[0];
+        manager.focus(&[root], 10, 3);
+        assert_eq!(get_group_levels(&manager), vec![vec![0], vec![1], vec![1]]);
+    }
+
+    #[test]
+    fn focus_groups_the_far_nodes_per_level() {
+        let mut manager = create_manager("f = x0 ^ x1 ^ x2");
+        reveal_all(&mut manager);
+        assert_eq!(get_group_levels(&manager).len(), 7);
+
+        // Only the root is revealed, while its children are the nearest context that remains visible
+        let root = manager.graph.get_roots()[0];
+        manager.focus(&[root], 1, 3);
+        assert_eq!(
+            get_group_levels(&manager),
+            vec![vec![0], vec![1], vec![1], vec![2, 2], vec![3, 3]]
+        );
+    }
+}
@@ -1,5 +1,9 @@
+pub mod assignment_path;
+pub mod chain_grouping;
 pub mod drawing;
 pub mod graph_structure;
 pub mod group_manager;
 pub mod node_tracker_manager;
+pub mod selection_stats;
+pub mod step_trace;
 pub mod storage;
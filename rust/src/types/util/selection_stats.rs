@@ -0,0 +1,432 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    configuration::types::{
+        composite_config::CompositeConfig,
+        label_config::LabelConfig,
+        location_config::{Location, LocationConfig},
+        panel_config::{OpenSide, PanelConfig},
+        text_output_config::TextOutputConfig,
+    },
+    wasm_interface::NodeID,
+};
+
+use super::graph_structure::graph_structure::GraphStructure;
+
+/// Statistics of the subgraph consisting of a selection of nodes and their descendants
+pub struct SelectionStats {
+    /// The number of inner nodes per level, from the top level downwards
+    pub level_counts: Vec<(String, usize)>,
+    /// The number of distinct terminals that can be reached
+    pub terminal_count: usize,
+    pub subgraph_size: usize,
+    /// The number of satisfying assignments of each selected node, over the variables from its own level downwards. This is only available for nodes of Boolean diagrams
+    pub sat_counts: Vec<Option<f64>>,
+    /// The number of nodes per number of (known) parents
+    pub in_degrees: BTreeMap<usize, usize>,
+    /// The number of nodes per number of children
+    pub out_degrees: BTreeMap<usize, usize>,
+    /// The number of nodes that the subgraph would consist of without any sharing, relative to its actual size
+    pub sharing_factor: f64,
+}
+
+impl SelectionStats {
+    /// Computes the statistics of the subgraph below the selected nodes of a diagram with `level_count` variable levels, where `get_terminal` retrieves the name of a terminal, together with its truth value in case of a Boolean terminal
+    pub fn compute<G: GraphStructure>(
+        graph: &mut G,
+        selection: &[NodeID],
+        level_count: LevelNo,
+        get_terminal: impl Fn(G::NL) -> Option<(String, Option<bool>)>,
+    ) -> SelectionStats
+    where
+        G::LL: Display,
+    {
+        let selection = selection.iter().cloned().unique().collect_vec();
+        let terminals: HashSet<NodeID> = graph.get_terminals().into_iter().collect();
+
+        let mut subgraph = Vec::new();
+        let mut found: HashSet<NodeID> = selection.iter().cloned().collect();
+        let mut stack = selection.clone();
+        while let Some(node) = stack.pop() {
+            subgraph.push(node);
+            for (_, child) in graph.get_children(node) {
+                if found.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+
+        let levels: HashMap<NodeID, LevelNo> = subgraph
+            .iter()
+            .map(|&node| (node, graph.get_level(node)))
+            .collect();
+        let level_counts = subgraph
+            .iter()
+            .filter(|node| !terminals.contains(node))
+            .map(|node| levels[node])
+            .counts()
+            .into_iter()
+            .sorted()
+            .map(|(level, count)| (format!("{}", graph.get_level_label(level)), count))
+            .collect_vec();
+
+        let terminal_values: HashMap<NodeID, (String, Option<bool>)> = subgraph
+            .iter()
+            .filter(|node| terminals.contains(node))
+            .filter_map(|&node| get_terminal(graph.get_node_label(node)).map(|t| (node, t)))
+            .collect();
+        let terminal_count = terminal_values
+            .values()
+            .map(|(name, _)| name)
+            .unique()
+            .count();
+
+        let in_degrees = subgraph
+            .iter()
+            .map(|&node| graph.get_known_parents(node).len())
+            .counts()
+            .into_iter()
+            .collect();
+        let children: HashMap<NodeID, Vec<(i32, NodeID)>> = subgraph
+            .iter()
+            .map(|&node| {
+                let children = graph
+                    .get_children(node)
+                    .into_iter()
+                    .map(|(edge_type, child)| (edge_type.index, child))
+                    .sorted()
+                    .collect_vec();
+                (node, children)
+            })
+            .collect();
+        let out_degrees = children
+            .values()
+            .map(|children| children.len())
+            .counts()
+            .into_iter()
+            .collect();
+
+        // Children are always on lower levels than their parents, so we can process the nodes bottom up
+        let bottom_up = subgraph
+            .iter()
+            .cloned()
+            .sorted_by_key(|node| std::cmp::Reverse(levels[node]))
+            .collect_vec();
+        let mut tree_sizes = HashMap::<NodeID, f64>::new();
+        for &node in &bottom_up {
+            let size = 1.0
+                + children[&node]
+                    .iter()
+                    .map(|(_, child)| tree_sizes.get(child).cloned().unwrap_or(1.0))
+                    .sum::<f64>();
+            tree_sizes.insert(node, size);
+        }
+        let tree_size: f64 = selection.iter().map(|node| tree_sizes[node]).sum();
+        let sharing_factor = if subgraph.is_empty() {
+            1.0
+        } else {
+            tree_size / subgraph.len() as f64
+        };
+
+        let mut sat_counts = HashMap::<NodeID, Option<f64>>::new();
+        for &node in &bottom_up {
+            let count = if terminals.contains(&node) {
+                terminal_values
+                    .get(&node)
+                    .and_then(|&(_, value)| value)
+                    .map(|value| if value { 1.0 } else { 0.0 })
+            } else {
+                match &children[&node][..] {
+                    &[(0, then_child), (1, else_child)] => {
+                        let level = levels[&node];
+                        let get_count = |child: NodeID| {
+                            // Terminals are treated as being on the level below the lowest variable of the diagram, rather than on the level at which they are drawn, since the latter depends on the other nodes of the graph
+                            let child_level = if terminals.contains(&child) {
+                                level_count
+                            } else {
+                                levels[&child]
+                            };
+                            let skipped = child_level.saturating_sub(level + 1);
+                            sat_counts
+                                .get(&child)
+                                .cloned()
+                                .flatten()
+                                .map(|count| count * 2f64.powi(skipped as i32))
+                        };
+                        get_count(then_child)
+                            .zip(get_count(else_child))
+                            .map(|(then_count, else_count)| then_count + else_count)
+                    }
+                    _ => None,
+                }
+            };
+            sat_counts.insert(node, count);
+        }
+
+        SelectionStats {
+            level_counts,
+            terminal_count,
+            subgraph_size: subgraph.len(),
+            sat_counts: selection.iter().map(|node| sat_counts[node]).collect(),
+            in_degrees,
+            out_degrees,
+            sharing_factor,
+        }
+    }
+}
+
+pub type SelectionStatsConfig = LocationConfig<
+    PanelConfig<
+        CompositeConfig<(
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+            LabelConfig<TextOutputConfig>,
+        )>,
+    >,
+>;
+
+/// Shows the statistics of the selected nodes in a separate panel, where the statistics are only recomputed when the selection changes
+pub struct SelectionStatsPanel {
+    config: SelectionStatsConfig,
+    selection: Vec<NodeID>,
+    level_count: LevelNo,
+}
+
+impl SelectionStatsPanel {
+    /// Creates the panel for a diagram with the given number of variable levels
+    pub fn new(level_count: LevelNo) -> SelectionStatsPanel {
+        let mut panel = SelectionStatsPanel {
+            config: LocationConfig::new(
+                Location::TOP_RIGHT,
+                PanelConfig::builder()
+                    .set_button_icon("BarChartVertical")
+                    .set_button_icon_description("Open selection statistics")
+                    .set_name("Selection statistics")
+                    .set_category("selection-statistics")
+                    .set_open_side(OpenSide::Right)
+                    .set_open_size(0.3)
+                    .build(CompositeConfig::new((
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                        LabelConfig::new("", TextOutputConfig::new(false)),
+                    ))),
+            ),
+            selection: Vec::new(),
+            level_count,
+        };
+        panel.show(None);
+        panel
+    }
+
+    pub fn get_config(&self) -> SelectionStatsConfig {
+        self.config.clone()
+    }
+
+    /// Updates the shown statistics if the selection differs from the previous selection, where `compute` receives the selection and the number of variable levels
+    pub fn update(
+        &mut self,
+        selection: &[NodeID],
+        compute: impl FnOnce(&[NodeID], LevelNo) -> SelectionStats,
+    ) {
+        if self.selection == selection {
+            return;
+        }
+        self.selection = selection.to_vec();
+        if selection.is_empty() {
+            self.show(None);
+        } else {
+            self.show(Some(compute(selection, self.level_count)));
+        }
+    }
+
+    fn show(&mut self, stats: Option<SelectionStats>) {
+        let (levels, terminals, size, sat_counts, in_degrees, out_degrees, sharing) =
+            &****self.config;
+        fn set(config: &LabelConfig<TextOutputConfig>, label: String, details: Option<String>) {
+            config.clone().set_label(&label).commit();
+            let mut output = (**config).clone();
+            match details {
+                Some(details) => output.set(details).commit(),
+                None => output.reset().commit(),
+            };
+        }
+        fn format_distribution(distribution: &BTreeMap<usize, usize>) -> String {
+            distribution
+                .iter()
+                .map(|(degree, count)| format!("{}: {}", degree, count))
+                .join("\n")
+        }
+
+        let Some(stats) = stats else {
+            set(levels, "No nodes selected".into(), None);
+            for config in [
+                terminals,
+                size,
+                sat_counts,
+                in_degrees,
+                out_degrees,
+                sharing,
+            ] {
+                set(config, "".into(), None);
+            }
+            return;
+        };
+
+        set(
+            levels,
+            format!("Levels: {}", stats.level_counts.len()),
+            Some(
+                stats
+                    .level_counts
+                    .iter()
+                    .map(|(level, count)| format!("{}: {}", level, count))
+                    .join("\n"),
+            ),
+        );
+        set(
+            terminals,
+            format!("Reachable terminals: {}", stats.terminal_count),
+            None,
+        );
+        set(
+            size,
+            format!("Subgraph size: {}", stats.subgraph_size),
+            None,
+        );
+        let sat_text = if stats.sat_counts.iter().all(|count| count.is_some()) {
+            stats
+                .sat_counts
+                .iter()
+                .map(|count| format!("{}", count.unwrap_or(0.0)))
+                .join("\n")
+        } else {
+            "unavailable".into()
+        };
+        set(
+            sat_counts,
+            if stats.sat_counts.len() == 1 {
+                format!("Satisfying assignments: {}", sat_text)
+            } else {
+                "Satisfying assignments per node".into()
+            },
+            if stats.sat_counts.len() == 1 {
+                None
+            } else {
+                Some(sat_text)
+            },
+        );
+        set(
+            in_degrees,
+            format!(
+                "Max in-degree: {}",
+                stats.in_degrees.keys().max().unwrap_or(&0)
+            ),
+            Some(format_distribution(&stats.in_degrees)),
+        );
+        set(
+            out_degrees,
+            format!(
+                "Max out-degree: {}",
+                stats.out_degrees.keys().max().unwrap_or(&0)
+            ),
+            Some(format_distribution(&stats.out_degrees)),
+        );
+        set(
+            sharing,
+            format!("Sharing factor: {:.2}", stats.sharing_factor),
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::util::graph_structure::{
+        graph_manipulators::terminal_level_adjuster::TerminalLevelAdjuster,
+        oxidd_graph_structure::{NodeLabel, NodeType, OxiddGraphStructure},
+    };
+    use crate::util::dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef};
+    use oxidd::{Edge, Function};
+
+    type Graph = TerminalLevelAdjuster<OxiddGraphStructure<(), DummyBDDFunction, String>>;
+
+    /// Creates the graph of the BDDs of the given formulas, and retrieves the root node per function name
+    fn create_graph(formulas: &str) -> (Graph, HashMap<String, NodeID>) {
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let (roots, levels, _) =
+            DummyBDDFunction::from_formula(&mut manager_ref, formulas, None).unwrap();
+        let root_ids = roots
+            .iter()
+            .flat_map(|(root, names)| {
+                let id = root.with_manager_shared(|_, edge| edge.node_id());
+                names.iter().map(move |name| (name.clone(), id))
+            })
+            .collect();
+        let graph = TerminalLevelAdjuster::new(OxiddGraphStructure::new(roots, levels));
+        (graph, root_ids)
+    }
+
+    fn compute(graph: &mut Graph, selection: &[NodeID]) -> SelectionStats {
+        SelectionStats::compute(
+            graph,
+            selection,
+            3,
+            |label: NodeLabel<String>| match label.kind {
+                NodeType::Terminal(terminal) => {
+                    let value = terminal == "T";
+                    Some((terminal, Some(value)))
+                }
+                NodeType::Inner(_) => None,
+            },
+        )
+    }
+
+    #[test]
+    fn subgraph_statistics() {
+        let (mut graph, roots) = create_graph("f = a; g = b & c");
+        let stats = compute(&mut graph, &[roots["g"]]);
+        assert_eq!(
+            stats.level_counts,
+            vec![("b".to_string(), 1), ("c".to_string(), 1)]
+        );
+        assert_eq!(stats.terminal_count, 2);
+        assert_eq!(stats.subgraph_size, 4);
+        assert_eq!(stats.sat_counts, vec![Some(1.0)]);
+        assert_eq!(stats.out_degrees, BTreeMap::from([(0, 2), (2, 2)]));
+    }
+
+    #[test]
+    fn sat_counts_do_not_depend_on_the_selection() {
+        let (mut graph, roots) = create_graph("f = a; g = b & c");
+        let (_, c) = graph
+            .get_children(roots["g"])
+            .into_iter()
+            .find(|(edge_type, _)| edge_type.index == 0)
+            .unwrap();
+
+        // The assignments of `a` count the variables `b` and `c` below it, whichever other nodes are selected
+        assert_eq!(
+            compute(&mut graph, &[roots["f"]]).sat_counts,
+            vec![Some(4.0)]
+        );
+        assert_eq!(
+            compute(&mut graph, &[roots["f"], c]).sat_counts,
+            vec![Some(4.0), Some(1.0)]
+        );
+    }
+}
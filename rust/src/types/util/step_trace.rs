@@ -0,0 +1,404 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    types::util::{
+        graph_structure::{
+            graph_manipulators::{
+                node_presence_adjuster::{NodePresenceAdjuster, PresenceGroups, PresenceRemainder},
+                rc_graph::RCGraph,
+            },
+            graph_structure::GraphStructure,
+            grouped_graph_structure::GroupedGraphStructure,
+        },
+        group_manager::GroupManager,
+    },
+    util::{parse_error::ParseError, rc_refcell::MutRcRefCell},
+    wasm_interface::{NodeGroupID, NodeID, StepData, StepGroup, TargetID, TargetIDType},
+};
+
+/// A recorded trace of a decision diagram computation (e.g. apply or ITE), which can be stepped through.
+///
+/// The trace format is line based, where every line starts with a directive:
+/// ```text
+/// # Comments start with a hash
+/// .name and(f, g)          Optional description of the whole trace
+/// .group and(3, 5)         Opens a (nested) group of steps, e.g. a recursive call
+/// .step terminal case      Starts a new step
+/// .active 3 5              The nodes of the active recursion pair of the last step
+/// .created 12              The nodes created in the last step
+/// .end                     Closes the last opened group
+/// ```
+/// Node IDs refer to the IDs of the nodes in the source diagram (e.g. the IDs of a dddmp file).
+pub struct StepTrace {
+    name: String,
+    steps: Vec<TraceStep>,
+    groups: Vec<TraceGroup>,
+}
+
+struct TraceStep {
+    description: String,
+    active: Vec<NodeID>,
+    created: Vec<NodeID>,
+    group: Option<usize>,
+}
+
+struct TraceGroup {
+    start: usize,
+    end: usize,
+    description: String,
+    parent: Option<usize>,
+}
+
+impl StepTrace {
+    pub fn parse(text: &str) -> Result<StepTrace, ParseError> {
+        let mut name = "trace".to_string();
+        let mut steps: Vec<TraceStep> = Vec::new();
+        let mut groups: Vec<TraceGroup> = Vec::new();
+        let mut open_groups: Vec<usize> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            // Every part is a slice of the line, hence its column follows from its offset, also for parts that occur multiple times
+            let column = |part: &str| part.as_ptr() as usize - line.as_ptr() as usize + 1;
+            let parse_ids = |text: &str| {
+                text.split_whitespace()
+                    .map(|id| {
+                        id.parse::<NodeID>().map_err(|_| {
+                            ParseError::at_line(
+                                line_number,
+                                column(id),
+                                format!("expected a node id, found \"{}\"", id),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let no_step =
+                || ParseError::at_line(line_number, 1, "expected a .step before node listings");
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let (directive, rest) = trimmed.split_once(' ').unwrap_or((trimmed, ""));
+            let rest = rest.trim();
+            match directive {
+                ".name" => name = rest.to_string(),
+                ".group" => {
+                    groups.push(TraceGroup {
+                        start: steps.len(),
+                        end: steps.len(),
+                        description: rest.to_string(),
+                        parent: open_groups.last().cloned(),
+                    });
+                    open_groups.push(groups.len() - 1);
+                }
+                ".end" => {
+                    let Some(group) = open_groups.pop() else {
+                        return Err(ParseError::at_line(
+                            line_number,
+                            column(directive),
+                            "no group to end",
+                        ));
+                    };
+                    groups[group].end = steps.len();
+                }
+                ".step" => steps.push(TraceStep {
+                    description: rest.to_string(),
+                    active: Vec::new(),
+                    created: Vec::new(),
+                    group: open_groups.last().cloned(),
+                }),
+                ".active" => {
+                    let ids = parse_ids(rest)?;
+                    steps.last_mut().ok_or_else(no_step)?.active.extend(ids)
+                }
+                ".created" => {
+                    let ids = parse_ids(rest)?;
+                    steps.last_mut().ok_or_else(no_step)?.created.extend(ids)
+                }
+                _ => {
+                    return Err(ParseError::at_line(
+                        line_number,
+                        column(directive),
+                        format!("unknown directive \"{}\"", directive),
+                    ))
+                }
+            }
+        }
+
+        // Groups that were not explicitly closed span until the end of the trace
+        for group in open_groups {
+            groups[group].end = steps.len();
+        }
+
+        Ok(StepTrace {
+            name,
+            steps,
+            groups,
+        })
+    }
+
+    pub fn get_step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Retrieves the source nodes that have not been created yet after performing the given step
+    pub fn get_hidden_nodes(&self, step: usize) -> HashSet<NodeID> {
+        self.steps
+            .iter()
+            .skip(step + 1)
+            .flat_map(|step| step.created.iter().cloned())
+            .collect()
+    }
+
+    pub fn get_created_nodes(&self, step: usize) -> Vec<NodeID> {
+        self.steps
+            .get(step)
+            .map(|step| step.created.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_active_nodes(&self, step: usize) -> Vec<NodeID> {
+        self.steps
+            .get(step)
+            .map(|step| step.active.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_step_data(&self, step: usize) -> Option<StepData> {
+        let trace_step = self.steps.get(step)?;
+        let root = StepGroup::new(0, self.steps.len() as i32, self.name.clone(), None);
+        let mut cache = HashMap::new();
+        let group = match trace_step.group {
+            Some(group) => self.get_step_group(group, &root, &mut cache),
+            None => root,
+        };
+        Some(StepData {
+            description: trace_step.description.clone(),
+            group,
+        })
+    }
+
+    fn get_step_group(
+        &self,
+        group: usize,
+        root: &StepGroup,
+        cache: &mut HashMap<usize, StepGroup>,
+    ) -> StepGroup {
+        if let Some(step_group) = cache.get(&group) {
+            return step_group.clone();
+        }
+        let data = &self.groups[group];
+        let parent = match data.parent {
+            Some(parent) => self.get_step_group(parent, root, cache),
+            None => root.clone(),
+        };
+        let step_group = StepGroup::new(
+            data.start as i32,
+            data.end as i32,
+            data.description.clone(),
+            Some(parent),
+        );
+        cache.insert(group, step_group.clone());
+        step_group
+    }
+}
+
+/// Keeps track of the step of a trace that is currently shown, and applies steps to the graph
+pub struct StepTracker {
+    trace: Rc<StepTrace>,
+    step: Option<usize>,
+    hidden: HashSet<NodeID>,
+    /// The local nodes that the shown step moved into their own group, together with the group they were taken from and the group they were moved to
+    moved: Vec<(NodeID, NodeGroupID, NodeGroupID)>,
+}
+
+impl StepTracker {
+    pub fn new(trace: Rc<StepTrace>) -> StepTracker {
+        StepTracker {
+            trace,
+            step: None,
+            hidden: HashSet::new(),
+            moved: Vec::new(),
+        }
+    }
+
+    pub fn get_step(&self) -> Option<usize> {
+        self.step
+    }
+
+    /// Hides all nodes that are created after the given step, and reveals all others. A negative step stops stepping, showing the full diagram. Returns the source nodes that should be highlighted, or None if the step is out of range
+    pub fn set_step<G: GraphStructure, GG: GraphStructure>(
+        &mut self,
+        step: i32,
+        presence_adjuster: &RCGraph<NodePresenceAdjuster<G>>,
+        graph: &GG,
+        group_manager: &MutRcRefCell<GroupManager<GG>>,
+    ) -> Option<Vec<NodeID>> {
+        let step = if step < 0 {
+            None
+        } else if (step as usize) < self.trace.get_step_count() {
+            Some(step as usize)
+        } else {
+            return None;
+        };
+
+        let hidden = step
+            .map(|step| self.trace.get_hidden_nodes(step))
+            .unwrap_or_default();
+        let revealed = self.hidden.difference(&hidden).cloned().collect_vec();
+        let concealed = hidden.difference(&self.hidden).cloned().collect_vec();
+        {
+            let mut adjuster = presence_adjuster.get();
+            let mut update_presence = |nodes: &Vec<NodeID>, presence: PresenceRemainder| {
+                for &node in nodes {
+                    let Some(&local) = adjuster.source_nodes_to_local(vec![node]).first() else {
+                        continue;
+                    };
+                    adjuster.set_node_presence(local, PresenceGroups::remainder(presence.clone()));
+                }
+            };
+            update_presence(&concealed, PresenceRemainder::Hide);
+            update_presence(&revealed, PresenceRemainder::Show);
+        }
+        self.hidden = hidden;
+        self.step = step;
+
+        let mut group_manager = group_manager.get();
+        // Restore the grouping from before the previous step, such that stepping back and forth does not pile up groups. Nodes that have been regrouped since are left alone
+        let mut recreated = HashMap::new();
+        for (node, old_group, new_group) in self.moved.drain(..).rev() {
+            if group_manager.get_group(node) != new_group {
+                continue;
+            }
+            let target = || vec![TargetID(TargetIDType::NodeID, node)];
+            let group = recreated.get(&old_group).cloned().unwrap_or(old_group);
+            if !group_manager.set_group(target(), group) {
+                // The old group was removed once it became empty
+                recreated.insert(old_group, group_manager.create_group(target()));
+            }
+        }
+
+        let Some(step) = step else {
+            return Some(Vec::new());
+        };
+
+        // Make sure the nodes created in this step are visible on their own
+        let created = graph.source_nodes_to_local(self.trace.get_created_nodes(step));
+        for node in created {
+            let old_group = group_manager.get_group(node);
+            let new_group = group_manager.create_group(vec![TargetID(TargetIDType::NodeID, node)]);
+            self.moved.push((node, old_group, new_group));
+        }
+
+        Some(self.trace.get_active_nodes(step))
+    }
+
+    pub fn get_step_data(&self) -> Option<StepData> {
+        self.trace.get_step_data(self.step?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE: &str = "\
+# and of two functions
+.name and(f, g)
+.group and(3, 5)
+.step recurse
+.active 3 5
+.group and(4, 5)
+.step terminal case
+.active 4 5
+.created 12
+.end
+.step combine
+.created 13 14
+";
+
+    #[test]
+    fn parses_steps() {
+        let trace = StepTrace::parse(TRACE).unwrap();
+        assert_eq!(trace.get_step_count(), 3);
+        assert_eq!(trace.get_active_nodes(0), vec![3, 5]);
+        assert_eq!(trace.get_active_nodes(1), vec![4, 5]);
+        assert_eq!(trace.get_created_nodes(2), vec![13, 14]);
+        assert!(trace.get_active_nodes(3).is_empty());
+    }
+
+    #[test]
+    fn hides_nodes_created_later() {
+        let trace = StepTrace::parse(TRACE).unwrap();
+        assert_eq!(trace.get_hidden_nodes(0), HashSet::from([12, 13, 14]));
+        assert_eq!(trace.get_hidden_nodes(1), HashSet::from([13, 14]));
+        assert!(trace.get_hidden_nodes(2).is_empty());
+    }
+
+    #[test]
+    fn nests_groups() {
+        let trace = StepTrace::parse(TRACE).unwrap();
+        let data = trace.get_step_data(1).unwrap();
+        assert_eq!(data.description, "terminal case");
+        assert_eq!(data.group.description, "and(4, 5)");
+        assert_eq!((data.group.start, data.group.end), (1, 2));
+        let parent = data.group.get_parent().unwrap();
+        assert_eq!(parent.description, "and(3, 5)");
+        // The outer group is never closed, so it spans until the end of the trace
+        assert_eq!((parent.start, parent.end), (0, 3));
+        assert_eq!(parent.get_parent().unwrap().description, "and(f, g)");
+        assert!(trace.get_step_data(3).is_none());
+    }
+
+    #[test]
+    fn reports_error_locations() {
+        assert_eq!(
+            StepTrace::parse(".step a\n.active 3 x").err(),
+            Some(ParseError::at_line(
+                2,
+                11,
+                "expected a node id, found \"x\""
+            ))
+        );
+        assert_eq!(
+            StepTrace::parse(".active 3").err(),
+            Some(ParseError::at_line(
+                1,
+                1,
+                "expected a .step before node listings"
+            ))
+        );
+        assert_eq!(
+            StepTrace::parse("  .steps").err(),
+            Some(ParseError::at_line(1, 3, "unknown directive \".steps\""))
+        );
+        assert_eq!(
+            StepTrace::parse(".end").err(),
+            Some(ParseError::at_line(1, 1, "no group to end"))
+        );
+        // Tokens that also occur earlier on the line are reported at their own column
+        assert_eq!(
+            StepTrace::parse(".step a\n.active 3 e").err(),
+            Some(ParseError::at_line(
+                2,
+                11,
+                "expected a node id, found \"e\""
+            ))
+        );
+        assert_eq!(
+            StepTrace::parse(".step a\n.created a").err(),
+            Some(ParseError::at_line(
+                2,
+                10,
+                "expected a node id, found \"a\""
+            ))
+        );
+    }
+}
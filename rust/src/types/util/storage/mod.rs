@@ -1 +1,3 @@
+pub mod snapshot;
+pub mod state_model;
 pub mod state_storage;
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    configuration::{
+        configuration_object::Abstractable,
+        types::{
+            choice_config::ChoiceConfig, composite_config::CompositeConfig,
+            float_config::FloatConfig, int_config::IntConfig, list_config::ListConfig,
+            text_config::TextConfig,
+        },
+    },
+    util::{parse_error::ParseError, vis_error::VisError},
+};
+
+use super::state_model::{SettingValue, VisualizationState};
+
+/// The bytes that every binary encoded state starts with
+const MAGIC: [u8; 4] = *b"OXVS";
+/// The version of the state model, where version 0 refers to the headerless states from before states were versioned, which can not be restored anymore. The section based snapshots that preceded the state model were never released, hence the state model is the first versioned format
+pub const STATE_VERSION: u16 = 1;
+
+impl VisualizationState {
+    pub fn new() -> VisualizationState {
+        VisualizationState {
+            version: STATE_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Encodes the state compactly, for persisting it in the browser
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        // Encoding the state model into a vector can not fail
+        let _ = bincode::serialize_into(&mut out, self);
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<VisualizationState, VisError> {
+        if data.is_empty() {
+            return Ok(VisualizationState::new());
+        }
+        let header_length = MAGIC.len() + 2;
+        if data.len() < header_length || !data.starts_with(&MAGIC) {
+            return Err(VisError::invalid_state(
+                "the state was stored before states were versioned, and can not be restored",
+            ));
+        }
+        let version = u16::from_le_bytes([data[MAGIC.len()], data[MAGIC.len() + 1]]);
+        check_version(version)?;
+        bincode::deserialize(&data[header_length..])
+            .map_err(|err| VisError::invalid_state(format!("the state is malformed: {}", err)))
+    }
+
+    /// Encodes the state as human-readable JSON, which can be diffed and edited by hand
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<VisualizationState, VisError> {
+        let state: VisualizationState = serde_json::from_str(json)
+            .map_err(|err| ParseError::at_line(err.line(), err.column(), format!("{}", err)))?;
+        check_version(state.version)?;
+        Ok(state)
+    }
+}
+
+fn check_version(version: u16) -> Result<(), VisError> {
+    if version > STATE_VERSION {
+        Err(VisError::invalid_state(format!(
+            "the state has version {}, while at most version {} is supported",
+            version, STATE_VERSION
+        )))
+    } else if version < STATE_VERSION {
+        Err(VisError::invalid_state(format!(
+            "the state has version {}, which can no longer be restored",
+            version
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// A setting whose value is part of the state of a visualization
+pub trait StoredSetting {
+    fn get_value(&self) -> SettingValue;
+    /// Sets the value of the setting, where values of a different kind are ignored
+    fn set_value(&mut self, value: &SettingValue);
+}
+
+/// Creates a handle to the given setting for storing it in the state, which shares its value with the setting
+pub fn stored<S: StoredSetting + Clone + 'static>(setting: &S) -> Box<dyn StoredSetting> {
+    Box::new(setting.clone())
+}
+
+/// Retrieves the values of the given settings by name
+pub fn get_settings<N: AsRef<str>>(
+    settings: &[(N, Box<dyn StoredSetting>)],
+) -> BTreeMap<String, SettingValue> {
+    settings
+        .iter()
+        .map(|(name, setting)| (name.as_ref().to_string(), setting.get_value()))
+        .collect()
+}
+
+/// Sets the values of the given settings, where settings that are missing from the values keep their current value
+pub fn set_settings<N: AsRef<str>>(
+    settings: &mut [(N, Box<dyn StoredSetting>)],
+    values: &BTreeMap<String, SettingValue>,
+) {
+    for (name, setting) in settings {
+        if let Some(value) = values.get(name.as_ref()) {
+            setting.set_value(value);
+        }
+    }
+}
+
+impl StoredSetting for IntConfig {
+    fn get_value(&self) -> SettingValue {
+        SettingValue::Int(self.get() as i64)
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        if let &SettingValue::Int(value) = value {
+            self.set(value as isize).commit();
+        }
+    }
+}
+
+impl StoredSetting for FloatConfig {
+    fn get_value(&self) -> SettingValue {
+        SettingValue::Float(self.get())
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        if let &SettingValue::Float(value) = value {
+            self.set(value).commit();
+        }
+    }
+}
+
+impl StoredSetting for TextConfig {
+    fn get_value(&self) -> SettingValue {
+        SettingValue::Text(self.get())
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        if let SettingValue::Text(value) = value {
+            self.set(value.clone()).commit();
+        }
+    }
+}
+
+/// Choices are stored by the name of the selected option, such that the options may be reordered
+impl<C: Clone + 'static> StoredSetting for ChoiceConfig<C> {
+    fn get_value(&self) -> SettingValue {
+        let name = self
+            .get_options()
+            .get(self.get_index())
+            .map(|choice| choice.name.clone())
+            .unwrap_or_default();
+        SettingValue::Choice(name)
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        let SettingValue::Choice(name) = value else {
+            return;
+        };
+        if let Some(index) = self
+            .get_options()
+            .iter()
+            .position(|choice| &choice.name == name)
+        {
+            self.set_index(index).commit();
+        }
+    }
+}
+
+/// Lists are stored by the values of their items, such that restoring them also restores the number of items
+impl<C: StoredSetting + Abstractable + Clone + 'static> StoredSetting for ListConfig<C> {
+    fn get_value(&self) -> SettingValue {
+        SettingValue::List(
+            self.get_items()
+                .iter()
+                .map(|item| item.get_value())
+                .collect(),
+        )
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        let SettingValue::List(values) = value else {
+            return;
+        };
+        self.set_len(values.len()).commit();
+        for (mut item, value) in self.get_items().into_iter().zip(values) {
+            item.set_value(value);
+        }
+    }
+}
+
+impl<A, B, C> StoredSetting for CompositeConfig<(A, B, C)>
+where
+    A: StoredSetting + Clone + 'static,
+    B: StoredSetting + Clone + 'static,
+    C: StoredSetting + Clone + 'static,
+{
+    fn get_value(&self) -> SettingValue {
+        let (a, b, c) = &**self;
+        SettingValue::List(vec![a.get_value(), b.get_value(), c.get_value()])
+    }
+    fn set_value(&mut self, value: &SettingValue) {
+        let SettingValue::List(values) = value else {
+            return;
+        };
+        let [a_value, b_value, c_value] = &values[..] else {
+            return;
+        };
+        // The parts share their values with the parts of this setting
+        let (a, b, c) = &**self;
+        a.clone().set_value(a_value);
+        b.clone().set_value(b_value);
+        c.clone().set_value(c_value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::util::storage::state_model::{GroupState, PointerState},
+        util::{point::Point, transformation::Transformation},
+    };
+
+    fn create_state() -> VisualizationState {
+        let mut state = VisualizationState::new();
+        state.graph.groups = vec![
+            GroupState {
+                id: 0,
+                nodes: vec![1, 2],
+            },
+            GroupState {
+                id: 3,
+                nodes: vec![4],
+            },
+        ];
+        state.graph.hidden_groups = vec![0];
+        state.graph.pointers = vec![PointerState {
+            id: 5,
+            target: 4,
+            text: "f".to_string(),
+            complemented: true,
+        }];
+        state.graph.complement_expansion_enabled = Some(false);
+        state.settings = BTreeMap::from([
+            ("layers".to_string(), SettingValue::Int(-4)),
+            ("duration".to_string(), SettingValue::Float(0.5)),
+            (
+                "assignment".to_string(),
+                SettingValue::Text("x=1".to_string()),
+            ),
+            (
+                "theme".to_string(),
+                SettingValue::Choice("dark".to_string()),
+            ),
+        ]);
+        state.camera = Some(Transformation {
+            width: 800.0,
+            height: 600.0,
+            angle: 0.0,
+            position: Point { x: 1.5, y: -2.0 },
+            scale: 3.0,
+        });
+        state
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = create_state().to_bytes();
+        assert!(bytes.starts_with(&MAGIC));
+        let state = VisualizationState::from_bytes(&bytes).unwrap();
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.settings, create_state().settings);
+        assert_eq!(state.graph.pointers, create_state().graph.pointers);
+        assert_eq!(state.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let json = create_state().to_json();
+        let state = VisualizationState::from_json(&json).unwrap();
+        assert_eq!(state.settings, create_state().settings);
+        assert_eq!(state.graph.hidden_groups, vec![0]);
+        assert_eq!(state.to_json(), json);
+    }
+
+    #[test]
+    fn unsupported_states() {
+        assert!(VisualizationState::from_bytes(&[]).is_ok());
+        assert_eq!(
+            VisualizationState::from_bytes(&[1, 2, 3]).err(),
+            Some(VisError::invalid_state(
+                "the state was stored before states were versioned, and can not be restored"
+            ))
+        );
+        let mut newer = create_state().to_bytes();
+        newer[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            VisualizationState::from_bytes(&newer).err(),
+            Some(VisError::invalid_state(format!(
+                "the state has version {}, while at most version {} is supported",
+                STATE_VERSION + 1,
+                STATE_VERSION
+            )))
+        );
+        assert!(VisualizationState::from_json("{\"version\": 0}").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use oxidd::LevelNo;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
+    util::transformation::Transformation,
+    wasm_interface::{NodeGroupID, NodeID},
+};
+
+/// The complete state of a visualization. The state can be encoded compactly for persistence in the browser, or as human-readable JSON that can be diffed, edited by hand and stored next to the diagram files
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct VisualizationState {
+    /// The version of the state model that the state was written with
+    pub version: u16,
+    pub graph: GraphState,
+    /// The values of the settings of the drawer by name, such that settings that are added or removed later on do not affect the other settings
+    pub settings: BTreeMap<String, SettingValue>,
+    /// The transformation of the view, if it was stored
+    pub camera: Option<Transformation>,
+}
+
+/// The state of the group manager and the graph adjusters it wraps, where every adjuster only fills in its own part of the state
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GraphState {
+    pub groups: Vec<GroupState>,
+    pub hidden_groups: Vec<NodeGroupID>,
+    /// The pointer nodes of the diagram, which are used to verify that the state belongs to the diagram it is loaded for
+    pub pointers: Vec<PointerState>,
+    pub presence: PresenceState,
+    /// The edges that were removed from the graph, identified by the node they go to and their type
+    pub removed_edges: Vec<(NodeID, EdgeState)>,
+    /// Whether the edges to children are adjusted, if the diagram has such an adjuster
+    pub child_edges_enabled: Option<bool>,
+    /// Whether complemented edges are expanded, if the diagram has such an adjuster
+    pub complement_expansion_enabled: Option<bool>,
+    pub zero_suppression: Option<SuppressionState>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GroupState {
+    pub id: NodeGroupID,
+    pub nodes: Vec<NodeID>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PointerState {
+    pub id: NodeID,
+    pub target: NodeID,
+    pub text: String,
+    pub complemented: bool,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct PresenceState {
+    pub adjustments: Vec<PresenceAdjustmentState>,
+    pub replacements: Vec<ReplacementState>,
+}
+
+/// The way a node is shown, where the groups list the parents that share a copy of the node
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PresenceAdjustmentState {
+    pub node: NodeID,
+    pub remainder: PresenceRemainder,
+    pub groups: Vec<Vec<(EdgeConstraintState, NodeID)>>,
+}
+
+/// The copy of a node that replaces it for the given parent edge
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReplacementState {
+    pub parent: NodeID,
+    pub constraint: EdgeConstraintState,
+    pub node: NodeID,
+    pub replacement: NodeID,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum EdgeConstraintState {
+    Any,
+    Exact(EdgeState),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct EdgeState {
+    pub index: i32,
+    pub tag: TagState,
+}
+
+/// A draw tag as it is stored, such that the state model does not depend on the tag types of the diagrams
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TagState {
+    None,
+    Complemented,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SuppressionState {
+    pub enabled: bool,
+    pub markers: Vec<SuppressionMarkerState>,
+}
+
+/// A node that represents a level skipped by the edge from parent to child
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SuppressionMarkerState {
+    pub id: NodeID,
+    pub parent: NodeID,
+    pub child: NodeID,
+    pub edge: EdgeState,
+    pub level: LevelNo,
+}
+
+/// The value of a setting, where choices are stored by the name of the selected option such that the options may be reordered
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingValue {
+    Int(i64),
+    Float(f32),
+    Text(String),
+    Choice(String),
+    /// The values of the items of a list, or of the parts of a composite setting
+    List(Vec<SettingValue>),
+}
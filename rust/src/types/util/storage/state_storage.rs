@@ -1,25 +1,70 @@
-use std::io::{Cursor, Result};
+use oxidd_rules_bdd::complement_edge::EdgeTag;
+
+use crate::{
+    types::util::graph_structure::graph_structure::{DrawTag, EdgeType},
+    util::vis_error::VisError,
+};
+
+use super::state_model::{EdgeState, GraphState, TagState};
 
 pub trait StateStorage {
-    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+    /// Stores the state of this graph, and of the graphs it wraps, in the given state
+    fn write(&self, _state: &mut GraphState) {}
+    /// Restores the state of this graph, and of the graphs it wraps, from the given state
+    fn read(&mut self, _state: &GraphState) -> Result<(), VisError> {
         Ok(())
     }
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        Ok(())
+}
+
+/// A draw tag that can be stored in the state model
+pub trait StoredTag: DrawTag + Sized {
+    fn to_state(&self) -> TagState;
+    fn from_state(tag: TagState) -> Option<Self>;
+}
+
+impl StoredTag for () {
+    fn to_state(&self) -> TagState {
+        TagState::None
+    }
+    fn from_state(tag: TagState) -> Option<()> {
+        match tag {
+            TagState::None => Some(()),
+            _ => None,
+        }
     }
 }
 
-// TODO: remove this, and rely on something like serde
-pub trait Serializable: Sized {
-    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()>;
-    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<Self>;
+impl StoredTag for EdgeTag {
+    fn to_state(&self) -> TagState {
+        match self {
+            EdgeTag::None => TagState::None,
+            EdgeTag::Complemented => TagState::Complemented,
+        }
+    }
+    fn from_state(tag: TagState) -> Option<EdgeTag> {
+        Some(match tag {
+            TagState::None => EdgeTag::None,
+            TagState::Complemented => EdgeTag::Complemented,
+        })
+    }
 }
 
-impl Serializable for () {
-    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        Ok(())
+impl EdgeState {
+    pub fn new<T: StoredTag>(edge: &EdgeType<T>) -> EdgeState {
+        EdgeState {
+            index: edge.index,
+            tag: edge.tag.to_state(),
+        }
     }
-    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
-        Ok(())
+
+    /// Retrieves the edge type, which fails if the tag does not exist for the diagram the state is loaded for
+    pub fn get_edge<T: StoredTag>(&self) -> Result<EdgeType<T>, VisError> {
+        let tag = T::from_state(self.tag).ok_or_else(|| {
+            VisError::invalid_state("the state contains edge tags that this diagram does not use")
+        })?;
+        Ok(EdgeType {
+            tag,
+            index: self.index,
+        })
     }
 }
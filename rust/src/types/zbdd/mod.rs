@@ -0,0 +1 @@
+pub mod zbdd_drawer;
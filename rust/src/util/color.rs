@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::transition::Interpolatable;
 
 // pub type Color = (f32, f32, f32);
-#[derive(Clone, PartialEq, Copy)]
+#[derive(Clone, PartialEq, Copy, Debug, Serialize, Deserialize)]
 pub struct Color(pub f32, pub f32, pub f32);
 impl Interpolatable for Color {
     fn mix(&self, c2: &Self, per: f32) -> Self {
@@ -24,7 +26,7 @@ impl Into<TransparentColor> for Color {
     }
 }
 
-#[derive(Clone, PartialEq, Copy)]
+#[derive(Clone, PartialEq, Copy, Debug, Serialize, Deserialize)]
 pub struct TransparentColor(pub f32, pub f32, pub f32, pub f32);
 impl Interpolatable for TransparentColor {
     fn mix(&self, c2: &Self, per: f32) -> Self {
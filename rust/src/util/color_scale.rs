@@ -0,0 +1,186 @@
+use std::cmp::Ordering;
+
+use super::{color::Color, transition::Interpolatable};
+
+/// The colors that values are mapped onto by a `ColorScale`
+#[derive(Clone, Copy, PartialEq)]
+pub enum Palette {
+    /// A sequential palette from the first to the second color
+    Gradient(Color, Color),
+    /// A sequential palette from dark purple through blue and green to yellow
+    Viridis,
+    /// A sequential palette from light to dark blue
+    Blues,
+    /// A sequential palette from light to dark grey
+    Greys,
+    /// A diverging palette from red through white to blue
+    RedBlue,
+    /// A diverging palette from purple through white to green
+    PurpleGreen,
+}
+
+impl Palette {
+    fn get_stops(&self) -> Vec<Color> {
+        match self {
+            Palette::Gradient(from, to) => vec![*from, *to],
+            Palette::Viridis => vec![
+                Color(0.267, 0.005, 0.329),
+                Color(0.231, 0.322, 0.545),
+                Color(0.129, 0.569, 0.549),
+                Color(0.369, 0.788, 0.384),
+                Color(0.993, 0.906, 0.144),
+            ],
+            Palette::Blues => vec![
+                Color(0.969, 0.984, 1.0),
+                Color(0.620, 0.792, 0.882),
+                Color(0.031, 0.188, 0.420),
+            ],
+            Palette::Greys => vec![Color(0.95, 0.95, 0.95), Color(0.15, 0.15, 0.15)],
+            Palette::RedBlue => vec![
+                Color(0.792, 0.0, 0.125),
+                Color(0.969, 0.969, 0.969),
+                Color(0.020, 0.443, 0.690),
+            ],
+            Palette::PurpleGreen => vec![
+                Color(0.463, 0.165, 0.514),
+                Color(0.969, 0.969, 0.969),
+                Color(0.106, 0.471, 0.216),
+            ],
+        }
+    }
+
+    /// Whether this palette diverges from a neutral center color, which is then used for the value 0
+    pub fn is_diverging(&self) -> bool {
+        matches!(self, Palette::RedBlue | Palette::PurpleGreen)
+    }
+
+    /// Retrieves the color at the given fraction (0-1) of the palette
+    pub fn get_color(&self, per: f32) -> Color {
+        let stops = self.get_stops();
+        let pos = per.max(0.0).min(1.0) * (stops.len() - 1) as f32;
+        let index = (pos.floor() as usize).min(stops.len() - 2);
+        stops[index].mix(&stops[index + 1], pos - index as f32)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScaleType {
+    Linear,
+    /// A logarithmic scale that is mirrored around 0, such that negative values and 0 itself can also be shown
+    Logarithmic,
+}
+
+impl ScaleType {
+    fn apply(&self, value: f32) -> f32 {
+        match self {
+            ScaleType::Linear => value,
+            ScaleType::Logarithmic => value.signum() * (1.0 + value.abs()).log10(),
+        }
+    }
+
+    fn invert(&self, value: f32) -> f32 {
+        match self {
+            ScaleType::Linear => value,
+            ScaleType::Logarithmic => value.signum() * (10f32.powf(value.abs()) - 1.0),
+        }
+    }
+}
+
+/// Maps values of the range `min` to `max` onto the colors of a palette, values outside of this range are clamped to its bounds and NaN is given the separate `NAN_COLOR`
+#[derive(Clone, Copy, PartialEq)]
+pub struct ColorScale {
+    pub palette: Palette,
+    pub scale: ScaleType,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ColorScale {
+    /// The color of NaN, which lies outside of every palette
+    pub const NAN_COLOR: Color = Color(1.0, 0.0, 1.0);
+
+    pub fn new(palette: Palette, scale: ScaleType, min: f32, max: f32) -> ColorScale {
+        ColorScale {
+            palette,
+            scale,
+            min,
+            max,
+        }
+    }
+
+    /// Retrieves the fraction (0-1) of the palette that the given value maps to
+    pub fn get_fraction(&self, value: f32) -> f32 {
+        let min = self.scale.apply(self.min);
+        let max = self.scale.apply(self.max);
+        let value = self.scale.apply(value.max(self.min).min(self.max));
+        if max.partial_cmp(&min) != Some(Ordering::Greater) {
+            return 0.5;
+        }
+
+        let per = if self.palette.is_diverging() && min < 0.0 && max > 0.0 {
+            // Keep the neutral center of the palette at 0
+            if value < 0.0 {
+                0.5 - 0.5 * value / min
+            } else {
+                0.5 + 0.5 * value / max
+            }
+        } else {
+            (value - min) / (max - min)
+        };
+        per.max(0.0).min(1.0)
+    }
+
+    pub fn get_color(&self, value: f32) -> Color {
+        if value.is_nan() {
+            return ColorScale::NAN_COLOR;
+        }
+        self.palette.get_color(self.get_fraction(value))
+    }
+
+    /// Retrieves `count` values that are evenly spread over the scale from the minimum to the maximum, together with their colors
+    pub fn get_samples(&self, count: usize) -> Vec<(f32, Color)> {
+        let min = self.scale.apply(self.min);
+        let max = self.scale.apply(self.max);
+        if count < 2 || max.partial_cmp(&min) != Some(Ordering::Greater) {
+            return vec![(self.min, self.get_color(self.min))];
+        }
+        (0..count)
+            .map(|i| {
+                let value = self
+                    .scale
+                    .invert(min + (max - min) * i as f32 / (count - 1) as f32);
+                (value, self.get_color(value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nan_has_its_own_color() {
+        let scale = ColorScale::new(Palette::Viridis, ScaleType::Linear, 0.0, 10.0);
+        assert!(scale.get_color(f32::NAN) == ColorScale::NAN_COLOR);
+        assert!(scale.get_color(0.0) != ColorScale::NAN_COLOR);
+        assert!(scale.get_color(0.0) == Palette::Viridis.get_color(0.0));
+    }
+
+    #[test]
+    fn values_are_clamped() {
+        let scale = ColorScale::new(Palette::Blues, ScaleType::Linear, 0.0, 10.0);
+        assert_eq!(scale.get_fraction(-5.0), 0.0);
+        assert_eq!(scale.get_fraction(5.0), 0.5);
+        assert_eq!(scale.get_fraction(20.0), 1.0);
+        assert!(scale.get_color(f32::INFINITY) == Palette::Blues.get_color(1.0));
+    }
+
+    #[test]
+    fn diverging_palettes_center_zero() {
+        let scale = ColorScale::new(Palette::RedBlue, ScaleType::Linear, -1.0, 3.0);
+        assert_eq!(scale.get_fraction(0.0), 0.5);
+        assert_eq!(scale.get_fraction(-1.0), 0.0);
+        assert_eq!(scale.get_fraction(3.0), 1.0);
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    traits::DiagramSection,
+    types::{
+        qdd::qdd_drawer::QDDDiagramSection,
+        util::graph_structure::{
+            graph_structure::GraphStructure,
+            oxidd_graph_structure::{
+                get_reachable_nodes, NodeLabel, NodeType, OxiddGraphStructure,
+            },
+        },
+    },
+    util::{
+        dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+        vis_error::VisError,
+    },
+    wasm_interface::NodeID,
+};
+
+/// The formats that diagrams can be converted between
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiagramFormat {
+    /// DDDMP files, binary files can be read but are always written in text mode
+    Dddmp,
+    /// BuDDy files, whose variable names are stored in a separate file
+    Buddy,
+    /// The `a>b,c>d` edge list read by `DummyBDDFunction::try_from`, which only describes the structure of a single root: levels are derived from the node ids and terminals are not marked
+    EdgeList,
+    /// `;` separated propositional formulas, which can only be read
+    Formula,
+    /// The TikZ picture of the initial layout, which can only be written and requires the `latex_headers`
+    Latex,
+}
+
+impl DiagramFormat {
+    pub fn from_name(name: &str) -> Option<DiagramFormat> {
+        match name {
+            "dddmp" => Some(DiagramFormat::Dddmp),
+            "buddy" => Some(DiagramFormat::Buddy),
+            "edges" => Some(DiagramFormat::EdgeList),
+            "formula" => Some(DiagramFormat::Formula),
+            "latex" => Some(DiagramFormat::Latex),
+            _ => None,
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<DiagramFormat> {
+        match extension {
+            "dddmp" => Some(DiagramFormat::Dddmp),
+            "bdd" => Some(DiagramFormat::Buddy),
+            "edges" => Some(DiagramFormat::EdgeList),
+            "formula" => Some(DiagramFormat::Formula),
+            "tex" => Some(DiagramFormat::Latex),
+            _ => None,
+        }
+    }
+}
+
+/// The result of a conversion, where formats that store variable names separately (BuDDy) provide these as `vars`
+pub struct ConvertedDiagram {
+    pub data: String,
+    pub vars: Option<String>,
+}
+
+/// Converts the diagram data from one format into another. The `vars` are the variable names for BuDDy input, or the variable order for formula input
+pub fn convert(
+    input: &str,
+    vars: Option<&str>,
+    from: DiagramFormat,
+    to: DiagramFormat,
+) -> Result<ConvertedDiagram, VisError> {
+    let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+    let (roots, levels, is_bdd) = match from {
+        DiagramFormat::Dddmp => DummyBDDFunction::from_dddmp(&mut manager_ref, input)?,
+        DiagramFormat::Buddy => DummyBDDFunction::from_buddy(&mut manager_ref, input, vars)?,
+        DiagramFormat::Formula => DummyBDDFunction::from_formula(&mut manager_ref, input, vars)?,
+        DiagramFormat::EdgeList => {
+            let root = DummyBDDFunction::try_from(&mut manager_ref, input)?;
+            let is_bdd = get_reachable_nodes(&[root.clone()])
+                .iter()
+                .all(|&(_, _, children)| children <= 2);
+            (vec![(root, vec!["f".to_string()])], Vec::new(), is_bdd)
+        }
+        DiagramFormat::Latex => return Err(VisError::unsupported("LaTeX output can not be read")),
+    };
+
+    if to == DiagramFormat::Latex {
+        return Ok(ConvertedDiagram {
+            data: QDDDiagramSection::new(roots, is_bdd, levels)
+                .create_latex_drawer()
+                .get_output(),
+            vars: None,
+        });
+    }
+
+    let mut graph = OxiddGraphStructure::<(), DummyBDDFunction, String>::new(roots, levels);
+    let diagram = ExportedDiagram::from_graph(&mut graph);
+    match to {
+        DiagramFormat::Dddmp => Ok(ConvertedDiagram {
+            data: diagram.to_dddmp(),
+            vars: None,
+        }),
+        DiagramFormat::Buddy => diagram.to_buddy(),
+        DiagramFormat::EdgeList => Ok(ConvertedDiagram {
+            data: diagram.to_edge_list()?,
+            vars: None,
+        }),
+        _ => Err(VisError::unsupported(format!(
+            "Diagrams can not be written as {:?}",
+            to
+        ))),
+    }
+}
+
+/// A snapshot of all nodes reachable from the roots of a graph, in which children always precede their parents
+struct ExportedDiagram {
+    nodes: Vec<ExportedNode>,
+    /// The roots together with the names that point to them
+    roots: Vec<(NodeID, Vec<String>)>,
+    level_names: Vec<String>,
+}
+
+struct ExportedNode {
+    id: NodeID,
+    level: LevelNo,
+    kind: NodeType<String>,
+    children: Vec<NodeID>,
+}
+
+impl ExportedDiagram {
+    fn from_graph<G: GraphStructure<T = (), NL = NodeLabel<String>, LL = String>>(
+        graph: &mut G,
+    ) -> ExportedDiagram {
+        let roots = graph
+            .get_roots()
+            .into_iter()
+            .map(|root| (root, graph.get_node_label(root).pointers))
+            .collect_vec();
+
+        // Post-order traversal, such that children are listed before their parents
+        let mut nodes = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = roots
+            .iter()
+            .rev()
+            .map(|&(root, _)| (root, None))
+            .collect_vec();
+        while let Some((node, children)) = stack.pop() {
+            if let Some(children) = children {
+                nodes.push(ExportedNode {
+                    id: node,
+                    level: graph.get_level(node),
+                    kind: graph.get_node_label(node).kind,
+                    children,
+                });
+                continue;
+            }
+            if !visited.insert(node) {
+                continue;
+            }
+            let children = graph
+                .get_children(node)
+                .into_iter()
+                .sorted_by_key(|(edge, _)| edge.index)
+                .map(|(_, child)| child)
+                .collect_vec();
+            stack.push((node, Some(children.clone())));
+            stack.extend(children.into_iter().rev().map(|child| (child, None)));
+        }
+
+        let var_count = nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeType::Inner(_)))
+            .map(|node| node.level as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let level_names = (0..var_count)
+            .map(|level| {
+                let name = graph.get_level_label(level as LevelNo);
+                if name.is_empty() {
+                    format!("x{}", level)
+                } else {
+                    name
+                }
+            })
+            .collect();
+
+        ExportedDiagram {
+            nodes,
+            roots,
+            level_names,
+        }
+    }
+
+    fn to_dddmp(&self) -> String {
+        let ids = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index + 1))
+            .collect::<HashMap<_, _>>();
+        let var_count = self.level_names.len();
+        let var_names = self.level_names.iter().map(|name| to_word(name)).join(" ");
+        let indices = (0..var_count).join(" ");
+        let roots = self
+            .roots
+            .iter()
+            .flat_map(|(root, names)| {
+                let id = ids[root];
+                names.iter().map(move |name| (id, to_word(name)))
+            })
+            .collect_vec();
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| match &node.kind {
+                NodeType::Terminal(value) => format!("{} {} 0 0", ids[&node.id], to_word(value)),
+                NodeType::Inner(_) => format!(
+                    "{} {} {}",
+                    ids[&node.id],
+                    node.level,
+                    node.children.iter().map(|child| ids[child]).join(" ")
+                ),
+            })
+            .join("\n");
+
+        format!(
+            ".ver DDDMP-2.0\n\
+            .mode A\n\
+            .varinfo 4\n\
+            .nnodes {}\n\
+            .nvars {}\n\
+            .nsuppvars {}\n\
+            .suppvarnames {}\n\
+            .orderedvarnames {}\n\
+            .ids {}\n\
+            .permids {}\n\
+            .nroots {}\n\
+            .rootids {}\n\
+            .rootnames {}\n\
+            .nodes\n\
+            {}\n\
+            .end\n",
+            self.nodes.len(),
+            var_count,
+            var_count,
+            var_names,
+            var_names,
+            indices,
+            indices,
+            roots.len(),
+            roots.iter().map(|(id, _)| id).join(" "),
+            roots.iter().map(|(_, name)| name).join(" "),
+            nodes
+        )
+    }
+
+    fn to_buddy(&self) -> Result<ConvertedDiagram, VisError> {
+        if self.roots.len() != 1 {
+            return Err(VisError::unsupported(
+                "Buddy files can only contain a single root",
+            ));
+        }
+
+        // Buddy reserves ids 0 and 1 for the false and true terminals
+        let mut ids = HashMap::new();
+        let mut next_id = 2;
+        for node in &self.nodes {
+            let id = match &node.kind {
+                NodeType::Terminal(value) if value == "F" => 0,
+                NodeType::Terminal(value) if value == "T" => 1,
+                NodeType::Terminal(value) => {
+                    return Err(VisError::unsupported(format!(
+                        "Buddy files can not contain terminal {}",
+                        value
+                    )))
+                }
+                NodeType::Inner(_) if node.children.len() != 2 => {
+                    return Err(VisError::unsupported(
+                        "Buddy files can only contain nodes with two children",
+                    ))
+                }
+                NodeType::Inner(_) => {
+                    next_id += 1;
+                    next_id - 1
+                }
+            };
+            ids.insert(node.id, id);
+        }
+
+        let inner = self
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeType::Inner(_)))
+            .collect_vec();
+        let var_count = self.level_names.len();
+        let header = format!(
+            "{} {}\n{} ",
+            inner.len(),
+            var_count,
+            (0..var_count).join(" ")
+        );
+        // The nodes are written as: id, variable, false child, true child. The last node is the root
+        let nodes = inner.iter().map(|node| {
+            format!(
+                "{} {} {} {}",
+                ids[&node.id], node.level, ids[&node.children[1]], ids[&node.children[0]]
+            )
+        });
+
+        Ok(ConvertedDiagram {
+            data: Some(header).into_iter().chain(nodes).join("\n"),
+            vars: Some(self.level_names.join("\n")),
+        })
+    }
+
+    fn to_edge_list(&self) -> Result<String, VisError> {
+        if self.roots.len() != 1 {
+            return Err(VisError::unsupported(
+                "Edge lists can only contain a single root",
+            ));
+        }
+
+        // Node ids double as levels, so we number the nodes from the top level downwards
+        let ids = self
+            .nodes
+            .iter()
+            .sorted_by_key(|node| (node.level, node.id))
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect::<HashMap<_, _>>();
+        // The first edge has to start at the root
+        let root = self.roots[0].0;
+        let edges = self
+            .nodes
+            .iter()
+            .sorted_by_key(|node| (node.id != root, ids[&node.id]))
+            .flat_map(|node| {
+                node.children
+                    .iter()
+                    .map(|child| format!("{}>{}", ids[&node.id], ids[child]))
+                    .collect_vec()
+            })
+            .join(",");
+        if edges.is_empty() {
+            return Err(VisError::unsupported(
+                "Edge lists can only describe diagrams with at least one edge",
+            ));
+        }
+        Ok(edges)
+    }
+}
+
+/// Replaces whitespace by underscores, such that the text can be used as a single entry of a line
+fn to_word(text: &str) -> String {
+    text.split_whitespace().join("_")
+}
@@ -0,0 +1,160 @@
+//! Converts diagrams between the formats that the visualizer reads, or renders them as LaTeX.
+//!
+//! Usage: `convert_data <input> [output] [options]`, where the formats default to the ones of the file extensions:
+//! - `--from <format>`: the input format, one of dddmp, buddy, edges or formula
+//! - `--to <format>`: the output format, one of dddmp, buddy, edges or latex
+//! - `--vars <file>`: the variable names of a buddy input, or the variable order of a formula input
+//! - `--vars-out <file>`: where to write the variable names of a buddy output, defaults to the output with extension `.bddv`
+//! - `--standalone`: wraps LaTeX output in a document including the required headers
+use std::{env, fs, path::Path, process};
+
+use oxidd_vis_rust::{convert, latex_headers, DiagramFormat};
+
+struct Arguments {
+    input: String,
+    output: Option<String>,
+    from: Option<DiagramFormat>,
+    to: Option<DiagramFormat>,
+    vars: Option<String>,
+    vars_out: Option<String>,
+    standalone: bool,
+}
+
+const USAGE: &str = "Usage: convert_data <input> [output] [--from <format>] [--to <format>] [--vars <file>] [--vars-out <file>] [--standalone]
+Formats: dddmp (.dddmp), buddy (.bdd), edges (.edges), formula (.formula, input only), latex (.tex, output only)";
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_arguments(env::args().skip(1).collect())?;
+
+    let from = args
+        .from
+        .or_else(|| get_extension_format(&args.input))
+        .ok_or("Could not determine the input format, specify it using --from")?;
+    let to = args
+        .to
+        .or_else(|| args.output.as_deref().and_then(get_extension_format))
+        .ok_or("Could not determine the output format, specify it using --to")?;
+
+    let input = read_input(&args.input, from)?;
+    let vars = match &args.vars {
+        Some(path) => Some(
+            fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?,
+        ),
+        None => None,
+    };
+
+    let converted = convert(&input, vars.as_deref(), from, to)
+        .map_err(|err| format!("Could not convert {}: {}", args.input, err))?;
+
+    let data = if to == DiagramFormat::Latex && args.standalone {
+        format!(
+            "\\documentclass[tikz]{{standalone}}\n{}\n\\begin{{document}}\n{}\n\\end{{document}}\n",
+            latex_headers, converted.data
+        )
+    } else {
+        converted.data
+    };
+    write_output(args.output.as_deref(), &data)?;
+
+    if let Some(vars) = converted.vars {
+        let vars_path = args.vars_out.clone().or_else(|| {
+            args.output.as_ref().map(|output| {
+                Path::new(output)
+                    .with_extension("bddv")
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        });
+        match vars_path {
+            Some(path) => write_output(Some(&path), &vars)?,
+            None => {
+                eprintln!("The variable names were not written, specify a file using --vars-out")
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, String> {
+    let mut paths = Vec::new();
+    let mut from = None;
+    let mut to = None;
+    let mut vars = None;
+    let mut vars_out = None;
+    let mut standalone = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}\n{}", name, USAGE))
+        };
+        match arg.as_str() {
+            "--from" => from = Some(parse_format(&value("--from")?)?),
+            "--to" => to = Some(parse_format(&value("--to")?)?),
+            "--vars" => vars = Some(value("--vars")?),
+            "--vars-out" => vars_out = Some(value("--vars-out")?),
+            "--standalone" => standalone = true,
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option {}\n{}", flag, USAGE))
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    let mut paths = paths.into_iter();
+    let input = paths.next().ok_or(USAGE)?;
+    let output = paths.next();
+    if paths.next().is_some() {
+        return Err(format!("Too many arguments\n{}", USAGE));
+    }
+
+    Ok(Arguments {
+        input,
+        output,
+        from,
+        to,
+        vars,
+        vars_out,
+        standalone,
+    })
+}
+
+fn parse_format(name: &str) -> Result<DiagramFormat, String> {
+    DiagramFormat::from_name(name).ok_or_else(|| format!("Unknown format {}\n{}", name, USAGE))
+}
+
+fn get_extension_format(path: &str) -> Option<DiagramFormat> {
+    let extension = Path::new(path).extension()?.to_str()?;
+    DiagramFormat::from_extension(extension)
+}
+
+fn read_input(path: &str, format: DiagramFormat) -> Result<String, String> {
+    let data = fs::read(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+    if format == DiagramFormat::Dddmp {
+        // Binary dddmp files are passed on as strings with one character per byte, like the web interface does
+        Ok(data.into_iter().map(|byte| byte as char).collect())
+    } else {
+        String::from_utf8(data).map_err(|_| format!("{} is not valid UTF-8", path))
+    }
+}
+
+fn write_output(path: Option<&str>, data: &str) -> Result<(), String> {
+    match path {
+        Some(path) => {
+            fs::write(path, data).map_err(|err| format!("Could not write {}: {}", path, err))
+        }
+        None => {
+            println!("{}", data);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,857 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
+
+use itertools::Itertools;
+use oxidd_core::NodeID;
+
+use crate::util::parse_error::ParseError;
+
+/// A reader for DDDMP files (up to version 3.0) as written by CUDD and OxiDD.
+/// Supports both the text (`.mode A`) and binary (`.mode B`) node encodings, the variable order and permutation headers, and complemented edges.
+#[derive(Default)]
+pub struct DddmpFile {
+    pub version: Option<String>,
+    pub mode: DddmpMode,
+    pub var_info: Option<usize>,
+    pub name: Option<String>,
+    pub is_add: bool,
+    pub node_count: Option<usize>,
+    pub var_count: Option<usize>,
+    pub supp_var_count: Option<usize>,
+    pub var_names: Option<Vec<String>>,
+    pub supp_var_names: Option<Vec<String>>,
+    pub ordered_var_names: Option<Vec<String>>,
+    pub ids: Option<Vec<usize>>,
+    pub perm_ids: Option<Vec<usize>>,
+    pub aux_ids: Option<Vec<usize>>,
+    pub roots: Vec<DddmpEdge>,
+    /// The line of the `.rootids` header, used for reporting errors in the root references
+    pub roots_line: usize,
+    pub root_names: Option<Vec<String>>,
+    pub nodes: Vec<DddmpNode>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum DddmpMode {
+    #[default]
+    Text,
+    Binary,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DddmpEdge {
+    pub node: NodeID,
+    pub complemented: bool,
+}
+
+#[derive(Clone)]
+pub struct DddmpNode {
+    pub id: NodeID,
+    pub kind: DddmpNodeKind,
+    /// The line that defined this node, used for error reporting
+    pub line: usize,
+}
+
+#[derive(Clone)]
+pub enum DddmpNodeKind {
+    /// A terminal node, with its value as written in the file (e.g. `T`, `1` or `0.5`)
+    Terminal(String),
+    /// An inner node, with the index of its variable among the support variables (i.e. its level)
+    Inner {
+        index: usize,
+        children: Vec<DddmpEdge>,
+    },
+}
+
+impl DddmpNode {
+    pub fn get_children(&self) -> &[DddmpEdge] {
+        match &self.kind {
+            DddmpNodeKind::Terminal(_) => &[],
+            DddmpNodeKind::Inner { children, .. } => children,
+        }
+    }
+}
+
+// The codes used by the binary node encoding
+const BINARY_TERMINAL: u8 = 0;
+const BINARY_ABSOLUTE: u8 = 1;
+const BINARY_RELATIVE: u8 = 2;
+const BINARY_RELATIVE_1: u8 = 3;
+
+impl DddmpFile {
+    /// Parses dddmp data, where binary data is provided as a string in which every character represents a single byte
+    pub fn parse_str(data: &str) -> Result<DddmpFile, ParseError> {
+        let is_binary = data
+            .lines()
+            .take_while(|line| !line.trim_start().starts_with(".nodes"))
+            .any(|line| {
+                let mut parts = line.split_whitespace();
+                parts.next() == Some(".mode") && parts.next() == Some("B")
+            });
+        if !is_binary {
+            return DddmpFile::parse(data.as_bytes());
+        }
+
+        let bytes = data
+            .char_indices()
+            .map(|(offset, c)| {
+                u8::try_from(c as u32).map_err(|_| {
+                    ParseError::at_offset(
+                        data,
+                        offset,
+                        "binary dddmp data should only contain bytes",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        DddmpFile::parse(&bytes)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<DddmpFile, ParseError> {
+        let mut file = DddmpFile::default();
+        let mut reader = LineReader {
+            data,
+            pos: 0,
+            line: 0,
+        };
+
+        loop {
+            let Some((line, text)) = reader.next_line() else {
+                return Err(ParseError::missing_section(".nodes"));
+            };
+            let mut parts = text.split_whitespace();
+            let Some(key) = parts.next() else {
+                continue;
+            };
+            let values = parts.collect_vec();
+            let column = |value: &str| text.find(value).map(|i| i + 1).unwrap_or(1);
+            let number = |value: &str| {
+                value.parse::<usize>().map_err(|_| {
+                    ParseError::at_line(
+                        line,
+                        column(value),
+                        format!("expected a number, found \"{}\"", value),
+                    )
+                })
+            };
+            let single_number = || match values.first() {
+                Some(value) => number(value),
+                None => Err(ParseError::at_line(
+                    line,
+                    text.len() + 1,
+                    format!("expected a number after {}", key),
+                )),
+            };
+            let numbers = || {
+                values
+                    .iter()
+                    .map(|value| number(value))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let strings = || values.iter().map(|value| value.to_string()).collect_vec();
+
+            match key {
+                ".ver" => file.version = values.first().map(|v| v.to_string()),
+                ".add" => file.is_add = true,
+                ".mode" => {
+                    file.mode = match values.first() {
+                        Some(&"A") => DddmpMode::Text,
+                        Some(&"B") => DddmpMode::Binary,
+                        _ => {
+                            return Err(ParseError::at_line(
+                                line,
+                                column(key) + key.len(),
+                                "expected mode A or B",
+                            ))
+                        }
+                    }
+                }
+                ".varinfo" => file.var_info = Some(single_number()?),
+                ".dd" => file.name = Some(values.join(" ")),
+                ".nnodes" => file.node_count = Some(single_number()?),
+                ".nvars" => file.var_count = Some(single_number()?),
+                ".nsuppvars" => file.supp_var_count = Some(single_number()?),
+                ".varnames" => file.var_names = Some(strings()),
+                ".suppvarnames" => file.supp_var_names = Some(strings()),
+                ".orderedvarnames" => file.ordered_var_names = Some(strings()),
+                ".ids" => file.ids = Some(numbers()?),
+                ".permids" => file.perm_ids = Some(numbers()?),
+                ".auxids" => file.aux_ids = Some(numbers()?),
+                ".nroots" => {
+                    single_number()?;
+                }
+                ".rootids" => {
+                    file.roots_line = line;
+                    file.roots = values
+                        .iter()
+                        .map(|value| parse_edge(value, line, column(value)))
+                        .collect::<Result<_, _>>()?
+                }
+                ".rootnames" => file.root_names = Some(strings()),
+                ".nodes" => break,
+                // Unknown headers are skipped, such that files of newer versions may still be read
+                _ => {}
+            }
+        }
+
+        file.nodes = match file.mode {
+            DddmpMode::Text => file.parse_text_nodes(&mut reader)?,
+            DddmpMode::Binary => file.parse_binary_nodes(&mut reader)?,
+        };
+        file.check_references()?;
+        Ok(file)
+    }
+
+    fn parse_text_nodes(&self, reader: &mut LineReader) -> Result<Vec<DddmpNode>, ParseError> {
+        // Var info 0 to 3 adds a field with extra variable information to every node
+        let extra = match self.var_info {
+            Some(0..=3) => 1,
+            _ => 0,
+        };
+
+        let mut nodes = Vec::new();
+        loop {
+            let Some((line, text)) = reader.next_line() else {
+                return Err(ParseError::at_line(reader.line, 1, "expected .end"));
+            };
+            if text.trim() == ".end" {
+                return Ok(nodes);
+            }
+
+            let fields = text.split_whitespace().collect_vec();
+            if fields.is_empty() {
+                continue;
+            }
+            if fields.len() < 4 + extra {
+                return Err(ParseError::at_line(
+                    line,
+                    1,
+                    format!(
+                        "expected at least {} fields, found {}",
+                        4 + extra,
+                        fields.len()
+                    ),
+                ));
+            }
+            let column = |field: &str| text.find(field).map(|i| i + 1).unwrap_or(1);
+
+            let id = fields[0].parse::<NodeID>().map_err(|_| {
+                ParseError::at_line(
+                    line,
+                    1,
+                    format!("expected a node id, found \"{}\"", fields[0]),
+                )
+            })?;
+            let children = fields[2 + extra..]
+                .iter()
+                .map(|field| parse_edge(field, line, column(field)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Terminals are identified by not having any children
+            let var = fields[1 + extra];
+            let kind = if children.iter().all(|child| child.node == 0) {
+                DddmpNodeKind::Terminal(var.to_string())
+            } else {
+                let index = var.parse::<usize>().map_err(|_| {
+                    ParseError::at_line(
+                        line,
+                        column(var),
+                        format!("expected a variable index, found \"{}\"", var),
+                    )
+                })?;
+                DddmpNodeKind::Inner { index, children }
+            };
+            nodes.push(DddmpNode { id, kind, line });
+        }
+    }
+
+    fn parse_binary_nodes(&self, reader: &mut LineReader) -> Result<Vec<DddmpNode>, ParseError> {
+        let Some(count) = self.node_count else {
+            return Err(ParseError::missing_section(".nnodes"));
+        };
+        // Terminals are considered to be below all support variables
+        let terminal_var = self
+            .supp_var_count
+            .or(self.ids.as_ref().map(|ids| ids.len()))
+            .unwrap_or(0);
+
+        let line = reader.line;
+        let mut nodes = Vec::with_capacity(count);
+        let mut vars = Vec::with_capacity(count);
+        let mut terminal = None;
+        for id in 1..=count {
+            let code = reader.read_byte()?;
+            let var_code = (code >> 5) & 3;
+            let then_code = (code >> 3) & 3;
+            let else_complemented = (code >> 2) & 1 == 1;
+            let else_code = code & 3;
+
+            if var_code == BINARY_TERMINAL {
+                terminal = terminal.or(Some(id));
+                vars.push(terminal_var);
+                nodes.push(DddmpNode {
+                    id,
+                    kind: DddmpNodeKind::Terminal("1".to_string()),
+                    line,
+                });
+                continue;
+            }
+
+            let var_value = match var_code {
+                BINARY_ABSOLUTE | BINARY_RELATIVE => Some(reader.read_int()?),
+                _ => None,
+            };
+            let mut read_child = |child_code: u8| -> Result<NodeID, ParseError> {
+                let child = match child_code {
+                    BINARY_TERMINAL => terminal,
+                    BINARY_ABSOLUTE => Some(reader.read_int()?),
+                    BINARY_RELATIVE => id.checked_sub(reader.read_int()?),
+                    _ => id.checked_sub(1),
+                };
+                match child {
+                    Some(child) if child >= 1 && child < id => Ok(child),
+                    _ => Err(reader.error(format!("node {} has an invalid child", id))),
+                }
+            };
+            let then_id = read_child(then_code)?;
+            let else_id = read_child(else_code)?;
+
+            let min_var = vars[then_id - 1].min(vars[else_id - 1]);
+            let index = match (var_code, var_value) {
+                (BINARY_ABSOLUTE, Some(var)) => Some(var),
+                (BINARY_RELATIVE, Some(offset)) => min_var.checked_sub(offset),
+                _ => min_var.checked_sub(1),
+            };
+            let Some(index) = index else {
+                return Err(reader.error(format!("node {} has an invalid variable", id)));
+            };
+            debug_assert!(var_code == BINARY_RELATIVE_1 || var_value.is_some());
+
+            vars.push(index);
+            nodes.push(DddmpNode {
+                id,
+                kind: DddmpNodeKind::Inner {
+                    index,
+                    children: vec![
+                        DddmpEdge {
+                            node: then_id,
+                            complemented: false,
+                        },
+                        DddmpEdge {
+                            node: else_id,
+                            complemented: else_complemented,
+                        },
+                    ],
+                },
+                line,
+            });
+        }
+        Ok(nodes)
+    }
+
+    fn check_references(&self) -> Result<(), ParseError> {
+        let defined = self
+            .nodes
+            .iter()
+            .map(|node| node.id)
+            .collect::<HashSet<_>>();
+        for node in &self.nodes {
+            if let Some(child) = node
+                .get_children()
+                .iter()
+                .find(|child| !defined.contains(&child.node))
+            {
+                return Err(ParseError::Reference {
+                    line: node.line,
+                    message: format!("node {} refers to undefined node {}", node.id, child.node),
+                });
+            }
+        }
+        if let Some(root) = self.roots.iter().find(|root| !defined.contains(&root.node)) {
+            return Err(ParseError::Reference {
+                line: self.roots_line,
+                message: format!("root refers to undefined node {}", root.node),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn has_complemented_edges(&self) -> bool {
+        self.roots.iter().any(|root| root.complemented)
+            || self
+                .nodes
+                .iter()
+                .any(|node| node.get_children().iter().any(|child| child.complemented))
+    }
+
+    /// Retrieves the names of the roots, defaulting to f0, f1, ...
+    pub fn get_root_names(&self) -> Vec<String> {
+        match &self.root_names {
+            Some(names) => names.clone(),
+            None => (0..self.roots.len()).map(|i| format!("f{i}")).collect(),
+        }
+    }
+
+    /// Retrieves the names of the support variables, in order of their level
+    pub fn get_level_names(&self) -> Vec<String> {
+        if let (Some(supp_names), Some(ordered_names)) =
+            (&self.supp_var_names, &self.ordered_var_names)
+        {
+            let supp_names = supp_names.iter().collect::<HashSet<_>>();
+            return ordered_names
+                .iter()
+                .filter(|name| supp_names.contains(name))
+                .cloned()
+                .collect();
+        }
+
+        let supp_count = self
+            .supp_var_names
+            .as_ref()
+            .map(|names| names.len())
+            .or(self.ids.as_ref().map(|ids| ids.len()))
+            .or(self.supp_var_count)
+            .unwrap_or(0);
+        let ids = self
+            .ids
+            .clone()
+            .unwrap_or_else(|| (0..supp_count).collect());
+        let positions = self.perm_ids.clone().unwrap_or_else(|| ids.clone());
+        let name = |index: usize| {
+            let id = ids.get(index).cloned().unwrap_or(index);
+            self.supp_var_names
+                .as_ref()
+                .and_then(|names| names.get(index))
+                .or(self.var_names.as_ref().and_then(|names| names.get(id)))
+                .cloned()
+                .unwrap_or_else(|| format!("x{id}"))
+        };
+        (0..supp_count)
+            .sorted_by_key(|&index| positions.get(index).cloned().unwrap_or(index))
+            .map(name)
+            .collect()
+    }
+
+    /// Removes all complemented edges, by adding the complements of nodes as explicit nodes. Nodes keep their original id, while created complements get fresh ids.
+    /// Returns the resulting nodes, together with the root nodes
+    pub fn expand_complements(&self) -> Result<(Vec<DddmpNode>, Vec<NodeID>), ParseError> {
+        let by_id = self
+            .nodes
+            .iter()
+            .map(|node| (node.id, node))
+            .collect::<HashMap<_, _>>();
+
+        // Find all complemented nodes that are reachable, starting from the complemented edges of the non-complemented nodes and roots
+        let mut complemented = BTreeSet::<NodeID>::new();
+        let mut queue = self
+            .roots
+            .iter()
+            .filter(|root| root.complemented)
+            .map(|root| (root.node, self.roots_line))
+            .collect_vec();
+        let mut to_visit = self.nodes.iter().map(|node| (node.id, false)).collect_vec();
+        loop {
+            while let Some((id, is_complemented)) = to_visit.pop() {
+                let node = by_id[&id];
+                for child in node.get_children() {
+                    if child.complemented != is_complemented && !complemented.contains(&child.node)
+                    {
+                        queue.push((child.node, node.line));
+                    }
+                }
+            }
+            let Some((id, line)) = queue.pop() else {
+                break;
+            };
+            if !by_id.contains_key(&id) {
+                return Err(ParseError::Reference {
+                    line,
+                    message: format!("undefined node {} is complemented", id),
+                });
+            }
+            if complemented.insert(id) {
+                to_visit.push((id, true));
+            }
+        }
+
+        // Assign ids to all complemented nodes
+        let mut next_id = self.nodes.iter().map(|node| node.id).max().unwrap_or(0) + 1;
+        let terminal_ids = self
+            .nodes
+            .iter()
+            .filter_map(|node| match &node.kind {
+                DddmpNodeKind::Terminal(value) => Some((normalize_terminal(value), node.id)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let mut created_terminals = Vec::new();
+        let mut complement_ids = HashMap::<NodeID, NodeID>::new();
+        for &id in &complemented {
+            let node = by_id[&id];
+            let complement_id = match &node.kind {
+                DddmpNodeKind::Terminal(value) => {
+                    let Some(complement) = complement_terminal(value) else {
+                        return Err(ParseError::at_line(
+                            node.line,
+                            1,
+                            format!("terminal {} can not be complemented", value),
+                        ));
+                    };
+                    match terminal_ids.get(complement) {
+                        Some(&existing) => existing,
+                        None => {
+                            created_terminals.push(DddmpNode {
+                                id: next_id,
+                                kind: DddmpNodeKind::Terminal(complement.to_string()),
+                                line: node.line,
+                            });
+                            next_id += 1;
+                            next_id - 1
+                        }
+                    }
+                }
+                DddmpNodeKind::Inner { .. } => {
+                    next_id += 1;
+                    next_id - 1
+                }
+            };
+            complement_ids.insert(id, complement_id);
+        }
+
+        let get_id = |edge: &DddmpEdge, negate: bool| {
+            if edge.complemented != negate {
+                complement_ids[&edge.node]
+            } else {
+                edge.node
+            }
+        };
+        let map_node = |node: &DddmpNode, negate: bool| match &node.kind {
+            DddmpNodeKind::Inner { index, children } => Some(DddmpNode {
+                id: if negate {
+                    complement_ids[&node.id]
+                } else {
+                    node.id
+                },
+                kind: DddmpNodeKind::Inner {
+                    index: *index,
+                    children: children
+                        .iter()
+                        .map(|child| DddmpEdge {
+                            node: get_id(child, negate),
+                            complemented: false,
+                        })
+                        .collect(),
+                },
+                line: node.line,
+            }),
+            DddmpNodeKind::Terminal(_) if negate => None,
+            DddmpNodeKind::Terminal(_) => Some(node.clone()),
+        };
+
+        let nodes = self
+            .nodes
+            .iter()
+            .filter_map(|node| map_node(node, false))
+            .chain(created_terminals)
+            .chain(
+                complemented
+                    .iter()
+                    .filter_map(|id| map_node(by_id[id], true)),
+            )
+            .collect();
+        let roots = self.roots.iter().map(|root| get_id(root, false)).collect();
+        Ok((nodes, roots))
+    }
+}
+
+/// Retrieves a canonical name for boolean terminals
+pub fn normalize_terminal(value: &str) -> &str {
+    match value {
+        "1" | "T" => "T",
+        "0" | "F" => "F",
+        other => other,
+    }
+}
+
+fn complement_terminal(value: &str) -> Option<&'static str> {
+    match normalize_terminal(value) {
+        "T" => Some("F"),
+        "F" => Some("T"),
+        _ => None,
+    }
+}
+
+/// Parses a (possibly negative, hence complemented) node reference
+fn parse_edge(value: &str, line: usize, column: usize) -> Result<DddmpEdge, ParseError> {
+    let (complemented, id) = match value.strip_prefix('-') {
+        Some(id) => (true, id),
+        None => (false, value),
+    };
+    let node = id.parse::<NodeID>().map_err(|_| {
+        ParseError::at_line(
+            line,
+            column,
+            format!("expected a node id, found \"{}\"", value),
+        )
+    })?;
+    Ok(DddmpEdge { node, complemented })
+}
+
+struct LineReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> LineReader<'a> {
+    /// Retrieves the next line and its (1-based) number
+    fn next_line(&mut self) -> Option<(usize, String)> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+        let rest = &self.data[self.pos..];
+        let length = rest.iter().position(|&c| c == b'\n').unwrap_or(rest.len());
+        let text = String::from_utf8_lossy(&rest[..length])
+            .trim_end_matches('\r')
+            .to_string();
+        self.pos += length + 1;
+        self.line += 1;
+        Some((self.line, text))
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError::at_offset(self.data, self.pos, message)
+    }
+
+    /// Reads a byte of binary data, in which some characters are escaped
+    fn read_byte(&mut self) -> Result<u8, ParseError> {
+        let Some(&byte) = self.data.get(self.pos) else {
+            return Err(self.error("unexpected end of binary data".to_string()));
+        };
+        self.pos += 1;
+        if byte != 0x1b {
+            return Ok(byte);
+        }
+        let Some(&escaped) = self.data.get(self.pos) else {
+            return Err(self.error("unexpected end of binary data".to_string()));
+        };
+        self.pos += 1;
+        Ok(escaped ^ 0x40)
+    }
+
+    /// Reads a binary integer, stored as up to 4 bytes of 7 bits (most significant first), where the lowest bit indicates whether more bytes follow
+    fn read_int(&mut self) -> Result<usize, ParseError> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let byte = self.read_byte()?;
+            value = (value << 7) | (byte >> 1) as usize;
+            if byte & 1 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.error("binary integer exceeds 4 bytes".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "\
+.ver DDDMP-2.0
+.mode A
+.varinfo 0
+.dd f
+.nnodes 3
+.nvars 2
+.nsuppvars 2
+.suppvarnames a b
+.orderedvarnames a b
+.ids 0 1
+.permids 0 1
+.nroots 1
+";
+
+    fn edge(node: NodeID, complemented: bool) -> DddmpEdge {
+        DddmpEdge { node, complemented }
+    }
+
+    fn get_node(nodes: &[DddmpNode], id: NodeID) -> &DddmpNode {
+        nodes.iter().find(|node| node.id == id).unwrap()
+    }
+
+    fn get_terminal(nodes: &[DddmpNode], id: NodeID) -> Option<&str> {
+        match &get_node(nodes, id).kind {
+            DddmpNodeKind::Terminal(value) => Some(value),
+            DddmpNodeKind::Inner { .. } => None,
+        }
+    }
+
+    #[test]
+    fn parses_text_mode() {
+        let data = format!(
+            "{}.rootids 3\n.nodes\n1 T 1 0 0\n2 b 1 1 -1\n3 a 0 2 -2\n.end\n",
+            HEADER
+        );
+        let file = DddmpFile::parse_str(&data).unwrap();
+        assert!(file.mode == DddmpMode::Text);
+        assert_eq!(file.name.as_deref(), Some("f"));
+        assert_eq!(file.roots, vec![edge(3, false)]);
+        assert_eq!(file.get_level_names(), vec!["a", "b"]);
+        assert_eq!(file.nodes.len(), 3);
+        assert_eq!(get_terminal(&file.nodes, 1), Some("1"));
+        assert_eq!(
+            get_node(&file.nodes, 2).get_children(),
+            &[edge(1, false), edge(1, true)]
+        );
+        match &get_node(&file.nodes, 3).kind {
+            DddmpNodeKind::Inner { index, children } => {
+                assert_eq!(*index, 0);
+                assert_eq!(children, &vec![edge(2, false), edge(2, true)]);
+            }
+            DddmpNodeKind::Terminal(_) => panic!("node 3 should be an inner node"),
+        }
+    }
+
+    #[test]
+    fn reports_text_mode_errors() {
+        let data = format!(
+            "{}.rootids 3\n.nodes\n1 T 1 0 0\n2 b x 1 -1\n.end\n",
+            HEADER
+        );
+        assert_eq!(
+            DddmpFile::parse_str(&data).err(),
+            Some(ParseError::at_line(
+                16,
+                5,
+                "expected a variable index, found \"x\""
+            ))
+        );
+        let data = format!("{}.rootids 3\n.nodes\n1 T 1 0 0\n3 a 0 2 1\n.end\n", HEADER);
+        assert!(matches!(
+            DddmpFile::parse_str(&data),
+            Err(ParseError::Reference { line: 16, .. })
+        ));
+        // Undefined roots are reported at the line of the root references
+        let data = format!("{}.rootids 4\n.nodes\n1 T 1 0 0\n.end\n", HEADER);
+        assert_eq!(
+            DddmpFile::parse_str(&data).err(),
+            Some(ParseError::Reference {
+                line: 13,
+                message: "root refers to undefined node 4".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn parses_binary_mode() {
+        let mut data = HEADER.replace(".mode A", ".mode B").into_bytes();
+        data.extend_from_slice(b".rootids -3\n.nodes\n");
+        data.extend_from_slice(&[
+            // A terminal
+            0x00,
+            // Variable 1 (absolute), then the terminal, else the complemented terminal
+            0x24, 0x02,
+            // Variable 0 (absolute), then the previous node, else the terminal
+            0x38, 0x00,
+        ]);
+        data.extend_from_slice(b"\n.end\n");
+
+        let file = DddmpFile::parse(&data).unwrap();
+        assert!(file.mode == DddmpMode::Binary);
+        assert_eq!(file.roots, vec![edge(3, true)]);
+        assert_eq!(file.nodes.len(), 3);
+        assert_eq!(get_terminal(&file.nodes, 1), Some("1"));
+        assert_eq!(
+            get_node(&file.nodes, 2).get_children(),
+            &[edge(1, false), edge(1, true)]
+        );
+        assert_eq!(
+            get_node(&file.nodes, 3).get_children(),
+            &[edge(2, false), edge(1, false)]
+        );
+        match (
+            &get_node(&file.nodes, 2).kind,
+            &get_node(&file.nodes, 3).kind,
+        ) {
+            (DddmpNodeKind::Inner { index: 1, .. }, DddmpNodeKind::Inner { index: 0, .. }) => {}
+            _ => panic!("the nodes should have the variables 1 and 0"),
+        }
+    }
+
+    #[test]
+    fn expands_complemented_inner_edges_of_plain_roots() {
+        let data = format!(
+            "{}.rootids 3\n.nodes\n1 T 1 0 0\n2 b 1 1 -1\n3 a 0 2 -2\n.end\n",
+            HEADER
+        );
+        let file = DddmpFile::parse_str(&data).unwrap();
+        let (nodes, roots) = file.expand_complements().unwrap();
+        assert_eq!(roots, vec![3]);
+        assert_eq!(nodes.len(), 5);
+        assert!(nodes
+            .iter()
+            .flat_map(|node| node.get_children())
+            .all(|child| !child.complemented));
+        // The complement of the terminal gets id 4, and the complement of node 2 gets id 5
+        assert_eq!(get_terminal(&nodes, 4), Some("F"));
+        assert_eq!(
+            get_node(&nodes, 2).get_children(),
+            &[edge(1, false), edge(4, false)]
+        );
+        assert_eq!(
+            get_node(&nodes, 3).get_children(),
+            &[edge(2, false), edge(5, false)]
+        );
+        assert_eq!(
+            get_node(&nodes, 5).get_children(),
+            &[edge(4, false), edge(1, false)]
+        );
+    }
+
+    #[test]
+    fn expands_complemented_roots() {
+        let data = format!(
+            "{}.rootids -2\n.nodes\n1 T 1 0 0\n2 b 1 1 -1\n.end\n",
+            HEADER
+        );
+        let file = DddmpFile::parse_str(&data).unwrap();
+        let (nodes, roots) = file.expand_complements().unwrap();
+        assert_eq!(roots, vec![4]);
+        assert_eq!(get_terminal(&nodes, 3), Some("F"));
+        assert_eq!(
+            get_node(&nodes, 4).get_children(),
+            &[edge(3, false), edge(1, false)]
+        );
+    }
+
+    #[test]
+    fn rejects_complements_of_undefined_nodes() {
+        let file = DddmpFile {
+            roots: vec![edge(5, true)],
+            roots_line: 4,
+            ..Default::default()
+        };
+        assert!(matches!(
+            file.expand_complements(),
+            Err(ParseError::Reference { line: 4, .. })
+        ));
+
+        let file = DddmpFile {
+            roots: vec![edge(2, false)],
+            nodes: vec![DddmpNode {
+                id: 2,
+                kind: DddmpNodeKind::Inner {
+                    index: 0,
+                    children: vec![edge(1, false), edge(7, true)],
+                },
+                line: 3,
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            file.expand_complements(),
+            Err(ParseError::Reference { line: 3, .. })
+        ));
+    }
+}
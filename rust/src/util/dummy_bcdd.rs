@@ -0,0 +1,107 @@
+use itertools::Itertools;
+use oxidd::{Edge, ManagerRef};
+use oxidd_rules_bdd::complement_edge::EdgeTag;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use oxidd_core::LevelNo;
+use oxidd_core::NodeID;
+
+use crate::util::dddmp::{normalize_terminal, DddmpFile, DddmpNodeKind};
+use crate::util::dummy_bdd::{DummyEdge, DummyFunction, DummyManager, DummyManagerRef, DummyTag};
+use crate::util::vis_error::VisError;
+
+impl DummyTag for EdgeTag {
+    const UNTAGGED: EdgeTag = EdgeTag::None;
+}
+
+/// The dummy manager with complement edges, which shares its implementation with the dummy BDD manager
+pub type DummyBCDDManagerRef = DummyManagerRef<EdgeTag>;
+pub type DummyBCDDFunction = DummyFunction<EdgeTag>;
+pub type DummyBCDDEdge = DummyEdge<EdgeTag>;
+pub type DummyBCDDManager = DummyManager<EdgeTag>;
+
+impl DummyBCDDFunction {
+    pub fn from_dddmp(
+        manager_ref: &mut DummyBCDDManagerRef,
+        data: &str,
+    ) -> Result<(Vec<(DummyBCDDFunction, Vec<String>)>, Vec<String>), VisError> {
+        let file = DddmpFile::parse_str(data)?;
+
+        manager_ref.with_manager_exclusive(|manager| {
+            let mut terminals = HashMap::new();
+
+            let max_level = file
+                .nodes
+                .iter()
+                .filter_map(|node| match node.kind {
+                    DddmpNodeKind::Inner { index, .. } => Some(index as LevelNo),
+                    DddmpNodeKind::Terminal(_) => None,
+                })
+                .max()
+                .unwrap_or(0);
+
+            for node in &file.nodes {
+                match &node.kind {
+                    DddmpNodeKind::Inner { index, .. } => {
+                        manager.add_node_level(node.id, *index as LevelNo, None);
+                    }
+                    DddmpNodeKind::Terminal(value) => {
+                        // Terminal nodes don't define a level, we have to assign it
+                        let name = normalize_terminal(value).to_string();
+                        manager.add_node_level(node.id, max_level + 1, Some(name.clone()));
+                        terminals.insert(
+                            name,
+                            DummyBCDDEdge::new(Arc::new(node.id), manager_ref.clone()),
+                        );
+                    }
+                }
+            }
+
+            for node in &file.nodes {
+                if manager.has_edges(node.id) {
+                    continue; // This node was already loaded
+                }
+                for child in node.get_children() {
+                    manager.add_tagged_edge(
+                        node.id,
+                        child.node,
+                        get_tag(child.complemented),
+                        manager_ref.clone(),
+                    );
+                }
+            }
+
+            manager.init_terminals(terminals);
+
+            let mut func_map = HashMap::<(NodeID, bool), (DummyBCDDFunction, Vec<String>)>::new();
+            for (root, name) in file.roots.iter().zip(file.get_root_names()) {
+                func_map
+                    .entry((root.node, root.complemented))
+                    .or_insert_with(|| {
+                        (
+                            DummyFunction(
+                                DummyBCDDEdge::new(Arc::new(root.node), manager_ref.clone())
+                                    .with_tag_owned(get_tag(root.complemented)),
+                            ),
+                            vec![],
+                        )
+                    })
+                    .1
+                    .push(name);
+            }
+            let funcs = func_map.values().cloned().collect_vec();
+
+            Ok((funcs, file.get_level_names()))
+        })
+    }
+}
+
+fn get_tag(complemented: bool) -> EdgeTag {
+    if complemented {
+        EdgeTag::Complemented
+    } else {
+        EdgeTag::None
+    }
+}
@@ -1,4 +1,5 @@
 use itertools::{EitherOrBoth, Itertools};
+use oxidd::bdd::{BDDFunction, BDDManagerRef};
 use oxidd::util::OutOfMemory;
 use oxidd::{util::Borrowed, Edge, InnerNode, Manager, ManagerRef};
 use oxidd::{BooleanFunction, Function};
@@ -12,6 +13,7 @@ use std::convert::TryInto;
 use std::hash::Hasher;
 use std::hash::{DefaultHasher, Hash};
 use std::iter::Cloned;
+use std::marker::PhantomData;
 use std::rc::Rc;
 use std::slice::Iter;
 use std::sync::Arc;
@@ -27,254 +29,196 @@ use oxidd_core::ReducedOrNew;
 use oxidd_core::WorkerManager;
 use oxidd_core::{BroadcastContext, HasLevel};
 
+use crate::types::util::graph_structure::graph_structure::DrawTag;
+use crate::types::util::graph_structure::oxidd_graph_structure::{
+    get_reachable_nodes, IntoTerminalLabel,
+};
+use crate::util::dddmp::{normalize_terminal, DddmpFile, DddmpNodeKind};
+use crate::util::formula::FormulaFile;
+use crate::util::level_swap::{get_or_create_node, set_level_order};
 use crate::util::logging::console;
+use crate::util::parse_error::ParseError;
+use crate::util::vis_error::VisError;
+
+/// The tags of the edges of the dummy managers, which have to be able to create edges without a tag
+pub trait DummyTag: DrawTag {
+    /// The tag of edges that are not tagged
+    const UNTAGGED: Self;
+}
+impl DummyTag for () {
+    const UNTAGGED: () = ();
+}
+
+pub type DummyBDDManagerRef = DummyManagerRef<()>;
+pub type DummyBDDFunction = DummyFunction<()>;
+pub type DummyBDDEdge = DummyEdge<()>;
+pub type DummyBDDManager = DummyManager<()>;
+pub type DummyBDDNode = DummyNode<()>;
 
 // #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Clone, PartialEq, Eq)]
-pub struct DummyBDDManagerRef(Rc<RefCell<DummyBDDManager>>);
+pub struct DummyManagerRef<T: DummyTag>(Rc<RefCell<DummyManager<T>>>);
 
-impl Hash for DummyBDDManagerRef {
+impl<T: DummyTag> Hash for DummyManagerRef<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.borrow().hash(state);
     }
 }
-impl<'a> From<&'a DummyBDDManager> for DummyBDDManagerRef {
-    fn from(value: &'a DummyBDDManager) -> Self {
-        DummyBDDManagerRef(Rc::new(RefCell::new(value.clone())))
+impl<'a, T: DummyTag> From<&'a DummyManager<T>> for DummyManagerRef<T> {
+    fn from(value: &'a DummyManager<T>) -> Self {
+        DummyManagerRef(Rc::new(RefCell::new(value.clone())))
     }
 }
-impl ManagerRef for DummyBDDManagerRef {
-    type Manager<'id> = DummyBDDManager;
+impl<T: DummyTag> ManagerRef for DummyManagerRef<T> {
+    type Manager<'id> = DummyManager<T>;
 
-    fn with_manager_shared<F, T>(&self, f: F) -> T
+    fn with_manager_shared<F, R>(&self, f: F) -> R
     where
-        F: for<'id> FnOnce(&Self::Manager<'id>) -> T,
+        F: for<'id> FnOnce(&Self::Manager<'id>) -> R,
     {
         f(&self.0.borrow())
     }
 
-    fn with_manager_exclusive<F, T>(&self, f: F) -> T
+    fn with_manager_exclusive<F, R>(&self, f: F) -> R
     where
-        F: for<'id> FnOnce(&mut Self::Manager<'id>) -> T,
+        F: for<'id> FnOnce(&mut Self::Manager<'id>) -> R,
     {
         f(&mut self.0.borrow_mut())
     }
 }
 
 #[derive(Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct DummyBDDFunction(pub DummyBDDEdge);
+pub struct DummyFunction<T: DummyTag>(pub DummyEdge<T>);
 impl DummyBDDFunction {
+    /// Reads the root of an `a>b,c>d` edge list, see `try_from`, and panics if the list is malformed
     pub fn from(manager_ref: &mut DummyBDDManagerRef, data: &str) -> DummyBDDFunction {
-        manager_ref.with_manager_exclusive(|manager| {
-            let mut root = Option::None;
-            let transition_texts = data.split(",");
-            let edges = transition_texts.flat_map(|item| {
-                let trans = item.split(">");
-                let mut out = Vec::new();
-                let mut prev_node = Option::None;
-                for node in trans {
-                    let node: NodeID = node.trim().parse().unwrap();
-
-                    if let Some(prev) = prev_node {
-                        out.push((prev, node.clone()));
-                    }
-                    prev_node = Some(node);
-                }
-                out
-            });
-            for (from, to) in edges.clone() {
-                if root == None {
-                    root = Some(from.clone());
+        DummyBDDFunction::try_from(manager_ref, data).unwrap()
+    }
+    /// Reads an `a>b,c>d` edge list of node IDs, where the first node of the list is the root
+    pub fn try_from(
+        manager_ref: &mut DummyBDDManagerRef,
+        data: &str,
+    ) -> Result<DummyBDDFunction, ParseError> {
+        let mut edges = Vec::new();
+        let mut offset = 0;
+        for item in data.split(',') {
+            let mut prev_node = Option::None;
+            for node in item.split('>') {
+                let node_offset = offset + node.len() - node.trim_start().len();
+                offset += node.len() + 1;
+                let node = node.trim();
+                let node: LevelNo = node.parse().map_err(|_| {
+                    let found = if node.is_empty() {
+                        "nothing".to_string()
+                    } else {
+                        format!("\"{}\"", node)
+                    };
+                    ParseError::at_offset(
+                        data,
+                        node_offset,
+                        format!("expected a node ID, but found {}", found),
+                    )
+                })?;
+
+                // Levels are derived from the node IDs, hence these have to fit in a level number
+                let node = node as NodeID;
+
+                if let Some(prev) = prev_node {
+                    edges.push((prev, node));
                 }
+                prev_node = Some(node);
+            }
+        }
+        let Some(&(root, _)) = edges.first() else {
+            return Err(ParseError::at_offset(data, data.len(), "no edges given"));
+        };
+
+        manager_ref.with_manager_exclusive(|manager| {
+            for &(from, to) in &edges {
                 manager.add_node(from);
                 manager.add_node(to);
             }
-            for (from, to) in edges {
+            for &(from, to) in &edges {
                 manager.add_edge(from, to, manager_ref.clone());
             }
-
-            DummyBDDFunction(DummyBDDEdge::new(
-                Arc::new(root.unwrap()),
-                manager_ref.clone(),
-            ))
-        })
+        });
+        Ok(DummyFunction(DummyBDDEdge::new(
+            Arc::new(root),
+            manager_ref.clone(),
+        )))
     }
     pub fn from_dddmp(
         manager_ref: &mut DummyBDDManagerRef,
         data: &str,
-    ) -> (Vec<(DummyBDDFunction, Vec<String>)>, Vec<String>, bool) {
+    ) -> Result<(Vec<(DummyBDDFunction, Vec<String>)>, Vec<String>, bool), VisError> {
+        let file = DddmpFile::parse_str(data)?;
+        // The dummy manager has no notion of complemented edges, so we make all complements explicit
+        let (nodes, roots) = file.expand_complements()?;
+        let is_bdd = nodes.iter().all(|node| node.get_children().len() <= 2);
+
         manager_ref.with_manager_exclusive(|manager| {
             let mut terminals = HashMap::new();
 
-            let get_text = |from: &str, to: &str| {
-                let start = data.find(from).unwrap() + from.len();
-                Box::new(&data[start + 1..start + data[start..].find(to).unwrap()])
-            };
-
-            let roots_text = get_text(".rootids", "\n");
-            let roots = roots_text
-                .trim()
-                .split(" ")
-                .flat_map(|n| n.parse::<usize>())
-                .collect_vec();
-            let root_names = if data.find(".rootnames").is_some() {
-                let roots_names_text = get_text(".rootnames", "\n");
-                roots_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .collect_vec()
-            } else {
-                roots
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| format!("f{i}"))
-                    .collect_vec()
-            };
-
-            let mut is_bdd = true;
-            let node_text = get_text(".nodes", ".end");
-            let nodes_data = node_text
-                .split("\n")
-                .filter_map(|node| {
-                    let parts = node.trim().split(" ").collect::<Vec<&str>>();
-                    if parts.len() >= 4 {
-                        let id: NodeID = parts[0].parse().unwrap();
-                        let level = parts[1];
-                        let children = parts[2..].iter().map(|v| v.parse().unwrap()).collect_vec();
-                        if children.len() > 2 {
-                            is_bdd = false;
-                        }
-                        Some((id, level, children))
-                    } else {
-                        None
-                    }
+            let max_level = nodes
+                .iter()
+                .filter_map(|node| match node.kind {
+                    DddmpNodeKind::Inner { index, .. } => Some(index as LevelNo),
+                    DddmpNodeKind::Terminal(_) => None,
                 })
-                .collect::<Vec<_>>();
-            let mut max_level = 0;
-            for (_, level, _) in nodes_data.clone() {
-                let Ok(level) = level.parse() else { continue };
+                .max()
+                .unwrap_or(0);
 
-                if level > max_level {
-                    max_level = level;
-                }
-            }
-
-            for (id, level, children) in nodes_data.clone() {
-                let level_num = level.parse();
-                manager.add_node_level(
-                    id.clone(),
-                    if let Ok(level) = level_num {
-                        level
-                    } else {
-                        max_level + 1 // Terminal nodes don't define a level, we have to assign it
-                    },
-                    if level_num.is_ok() {
-                        None
-                    } else {
-                        Some(level.to_string())
-                    },
-                );
-
-                if level_num.is_err() {
-                    terminals.insert(
-                        level.to_string(),
-                        DummyBDDEdge::new(Arc::new(id), manager_ref.clone()),
-                    );
+            for node in &nodes {
+                match &node.kind {
+                    DddmpNodeKind::Inner { index, .. } => {
+                        manager.add_node_level(node.id, *index as LevelNo, None);
+                    }
+                    DddmpNodeKind::Terminal(value) => {
+                        // Terminal nodes don't define a level, we have to assign it
+                        let name = normalize_terminal(value).to_string();
+                        manager.add_node_level(node.id, max_level + 1, Some(name.clone()));
+                        terminals.insert(
+                            name,
+                            DummyBDDEdge::new(Arc::new(node.id), manager_ref.clone()),
+                        );
+                    }
                 }
             }
 
-            for (id, level, children) in nodes_data {
-                if manager.has_edges(id) {
+            for node in &nodes {
+                if manager.has_edges(node.id) {
                     continue; // This node was already loaded
                 }
-                if level.parse::<i32>().is_err() {
-                    continue;
-                }; // Filter out terminals
-
-                let is_terminal = |_: NodeID| false;
-                // let is_terminal = |to: NodeID| to == 1 || to == 2;
-                // let is_terminal = |to: NodeID| to == 1; // Only filter connections to false
-
-                for child in children {
-                    if !is_terminal(child) {
-                        manager.add_edge(id.clone(), child, manager_ref.clone());
-                    }
+                for child in node.get_children() {
+                    manager.add_edge(node.id, child.node, manager_ref.clone());
                 }
             }
 
             manager.init_terminals(terminals);
 
             let mut func_map = HashMap::<NodeID, (DummyBDDFunction, Vec<String>)>::new();
-            for (root, name) in roots.into_iter().zip(root_names.into_iter()) {
+            for (root, name) in roots.into_iter().zip(file.get_root_names()) {
                 func_map
                     .entry(root)
                     .or_insert_with(|| {
                         (
-                            DummyBDDFunction(DummyBDDEdge::new(
-                                Arc::new(root),
-                                manager_ref.clone(),
-                            )),
+                            DummyFunction(DummyBDDEdge::new(Arc::new(root), manager_ref.clone())),
                             vec![],
                         )
                     })
                     .1
-                    .push(name.to_string());
+                    .push(name);
             }
             let funcs = func_map.values().cloned().collect_vec();
 
-            let var_names = if data.find(".suppvarnames").is_some() {
-                let supp_names_text = get_text(".suppvarnames", ".orderedvarnames");
-                let supp_var_names = supp_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .collect::<HashSet<_>>();
-
-                let ord_names_text = if data.find(".orderedvarnames").is_some() {
-                    get_text(".orderedvarnames", ".ids")
-                } else {
-                    supp_names_text
-                };
-                ord_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .filter(|t| supp_var_names.contains(t))
-                    .collect::<Vec<_>>()
-            } else {
-                let supp_vars = get_text(".ids", ".permids")
-                    .trim()
-                    .split(" ")
-                    .filter_map(|var| var.parse::<usize>().ok())
-                    .collect::<Vec<_>>();
-                let id_positions = get_text(".permids", ".nroots");
-                let mut var_levels = id_positions
-                    .trim()
-                    .split(" ")
-                    .enumerate()
-                    .filter_map(|(id, level)| {
-                        level
-                            .parse::<usize>()
-                            .ok()
-                            .and_then(|level| supp_vars.get(id).map(|var| (level, var)))
-                    })
-                    .collect::<Vec<_>>();
-                var_levels.sort_by_key(|(level, _var)| *level);
-                var_levels
-                    .iter()
-                    .map(|(_level, var)| format!("x{var}"))
-                    .collect::<Vec<_>>()
-            };
-
-            (funcs, var_names, is_bdd)
+            Ok((funcs, file.get_level_names(), is_bdd))
         })
     }
     pub fn from_buddy(
         manager_ref: &mut DummyBDDManagerRef,
         data: &str,
         var_data: Option<&str>,
-    ) -> (Vec<(DummyBDDFunction, Vec<String>)>, Vec<String>, bool) {
+    ) -> Result<(Vec<(DummyBDDFunction, Vec<String>)>, Vec<String>, bool), VisError> {
         manager_ref.with_manager_exclusive(|manager| {
             let mut variables = Vec::new();
             let mut layer_levels = Vec::<usize>::new(); // Specifies per "layer", what level it should have. Variable names and nodes refer to layers, not levels.
@@ -293,14 +237,32 @@ impl DummyBDDFunction {
                             .collect();
                         let mut order = vec![0; layer_levels.len()];
                         for (layer, &index) in layer_levels.iter().enumerate() {
-                            order[index] = layer;
+                            let Some(entry) = order.get_mut(index) else {
+                                return Err(ParseError::at_line(
+                                    line + 1,
+                                    1,
+                                    format!("level {} exceeds the number of variables", index),
+                                )
+                                .into());
+                            };
+                            *entry = layer;
                         }
 
                         variables = match var_data {
                             Some(vars) => {
                                 let var_names =
                                     vars.split("\n").map(|v| v.trim().to_string()).collect_vec();
-                                order.iter().map(|&i| var_names[i].clone()).collect()
+                                order
+                                    .iter()
+                                    .map(|&i| {
+                                        var_names.get(i).cloned().ok_or_else(|| {
+                                            ParseError::Reference {
+                                                line: i + 1,
+                                                message: format!("missing name for variable {}", i),
+                                            }
+                                        })
+                                    })
+                                    .collect::<Result<_, _>>()?
                             }
                             _ => order.iter().map(|v| format!("{}", v)).collect(),
                         };
@@ -362,10 +324,10 @@ impl DummyBDDFunction {
                 .collect();
             manager.init_terminals(terminals);
 
-            (
+            Ok((
                 root.map(|root| {
                     (
-                        DummyBDDFunction(DummyBDDEdge::new(Arc::new(root), manager_ref.clone())),
+                        DummyFunction(DummyBDDEdge::new(Arc::new(root), manager_ref.clone())),
                         vec!["f".to_string()],
                     )
                 })
@@ -373,20 +335,333 @@ impl DummyBDDFunction {
                 .collect(),
                 variables,
                 true,
-            )
+            ))
+        })
+    }
+    /// Builds the BDDs of the given formulas (see `FormulaFile`), with the levels ordered according to the optional whitespace separated variable order
+    pub fn from_formula(
+        manager_ref: &mut DummyBDDManagerRef,
+        data: &str,
+        var_order: Option<&str>,
+    ) -> Result<(Vec<(DummyBDDFunction, Vec<String>)>, Vec<String>, bool), VisError> {
+        let file = FormulaFile::parse_str(data)?;
+        let variables = file.get_variable_order(var_order);
+
+        // The dummy manager can not apply operations, so we build the diagram in a real manager and copy its nodes afterwards
+        let roots = build_in_bdd_manager(INITIAL_BUILD_CAPACITY, |bdd_manager| {
+            let (vars, t, f) = bdd_manager.with_manager_exclusive(|manager| -> AllocResult<_> {
+                let vars = variables
+                    .iter()
+                    .map(|name| Ok((name.clone(), BDDFunction::new_var(manager)?)))
+                    .collect::<AllocResult<HashMap<_, _>>>()?;
+                Ok((vars, BDDFunction::t(manager), BDDFunction::f(manager)))
+            })?;
+            let roots = file
+                .roots
+                .iter()
+                .map(|(name, formula)| Ok((name.clone(), formula.build(&vars, &t, &f)?)))
+                .collect::<AllocResult<Vec<_>>>()?;
+            Ok(roots)
+        })?;
+
+        let terminal_level = variables.len() as LevelNo;
+        let (funcs, _) = DummyBDDFunction::copy_bdd_functions(manager_ref, &roots, terminal_level);
+        Ok((funcs, variables, true))
+    }
+
+    /// Rebuilds the given BDD functions with their levels in the given order, which lists the current levels from the top level downwards. Next to the rebuilt functions, it retrieves per node of the rebuilt diagram the node of the given diagram that represents the same function, if any
+    pub fn reorder(
+        manager_ref: &mut DummyBDDManagerRef,
+        roots: &[(DummyBDDFunction, Vec<String>)],
+        order: &[LevelNo],
+    ) -> Result<
+        (
+            Vec<(DummyBDDFunction, Vec<String>)>,
+            HashMap<NodeID, NodeID>,
+        ),
+        VisError,
+    > {
+        let is_permutation = order.iter().cloned().sorted().eq(0..order.len() as LevelNo);
+        if !is_permutation {
+            return Err(VisError::unsupported(
+                "The level order has to contain every level exactly once",
+            ));
+        }
+
+        // The dummy manager can not swap levels, so we copy the functions into a real manager in which the levels are swapped in place, and copy its nodes back afterwards. The nodes keep their IDs while swapping, which provides the sources. Reordering can grow the diagram, hence we leave room for twice its nodes up front
+        let node_count =
+            get_reachable_nodes(&roots.iter().map(|(root, _)| root.clone()).collect_vec()).len();
+        let (named_roots, built) = build_in_bdd_manager(2 * node_count, |bdd_manager| {
+            let (t, f) = bdd_manager.with_manager_exclusive(|manager| -> AllocResult<_> {
+                for _ in order {
+                    BDDFunction::new_var(manager)?;
+                }
+                Ok((BDDFunction::t(manager), BDDFunction::f(manager)))
+            })?;
+
+            let mut built = HashMap::<NodeID, BDDFunction>::new();
+            let mut named_roots = Vec::new();
+            for (root, names) in roots {
+                let function = manager_ref.with_manager_shared(|manager| {
+                    copy_to_bdd_manager(
+                        manager,
+                        bdd_manager,
+                        root.0.node_id(),
+                        order.len() as LevelNo,
+                        (&t, &f),
+                        &mut built,
+                    )
+                })?;
+                named_roots.extend(names.iter().map(|name| (name.clone(), function.clone())));
+            }
+            bdd_manager.with_manager_exclusive(|manager| set_level_order(manager, order))?;
+            Ok((named_roots, built))
+        })?;
+
+        let terminal_level = order.len() as LevelNo;
+        let (funcs, ids) =
+            DummyBDDFunction::copy_bdd_functions(manager_ref, &named_roots, terminal_level);
+        let sources = built
+            .iter()
+            .filter_map(|(&source, function)| {
+                let id = ids.get(&function.with_manager_shared(|_, edge| edge.node_id()))?;
+                Some((*id, source))
+            })
+            .collect();
+        Ok((funcs, sources))
+    }
+
+    /// Removes the nodes reachable from the given functions, which have to be the result of an earlier `reorder` that is no longer shown, such that moving levels repeatedly does not keep every intermediate diagram alive. Terminals that are still referenced by the manager are kept
+    pub fn release(
+        manager_ref: &mut DummyBDDManagerRef,
+        roots: &[(DummyBDDFunction, Vec<String>)],
+    ) {
+        manager_ref.with_manager_exclusive(|manager| {
+            let terminals: HashSet<NodeID> =
+                manager.1.values().map(|edge| edge.node_id()).collect();
+            let mut stack = roots.iter().map(|(root, _)| root.0.node_id()).collect_vec();
+            while let Some(id) = stack.pop() {
+                if terminals.contains(&id) {
+                    continue;
+                }
+                let Some(DummyNode(_, children, _)) = manager.0.remove(&id) else {
+                    continue;
+                };
+                for child in children {
+                    stack.push(child.node_id());
+                    manager.drop_edge(child);
+                }
+            }
+        });
+    }
+
+    /// Copies the nodes reachable from the given named functions of a real BDD manager into the dummy manager, and retrieves the copied functions together with the ID of the copy per node of the real manager
+    fn copy_bdd_functions(
+        manager_ref: &mut DummyBDDManagerRef,
+        roots: &[(String, BDDFunction)],
+        terminal_level: LevelNo,
+    ) -> (
+        Vec<(DummyBDDFunction, Vec<String>)>,
+        HashMap<NodeID, NodeID>,
+    ) {
+        manager_ref.with_manager_exclusive(|manager| {
+            // Diagrams may be loaded next to other data, so we assign fresh IDs
+            let first_id = manager.0.keys().next_back().map_or(0, |&id| id + 1);
+            let mut ids = HashMap::<NodeID, NodeID>::new();
+            let mut terminals = HashMap::new();
+            let mut edges = Vec::new();
+            let mut stack = roots.iter().map(|(_, f)| f.clone()).collect_vec();
+            while let Some(f) = stack.pop() {
+                let (id, level, terminal, children) =
+                    f.with_manager_shared(|manager, edge| match manager.get_node(edge) {
+                        Node::Inner(node) => (
+                            edge.node_id(),
+                            node.level(),
+                            None,
+                            node.children()
+                                .map(|child| BDDFunction::from_edge_ref(manager, &child))
+                                .collect_vec(),
+                        ),
+                        Node::Terminal(terminal) => {
+                            let terminal: &BDDTerminal = std::borrow::Borrow::borrow(&terminal);
+                            (
+                                edge.node_id(),
+                                terminal_level,
+                                Some(terminal.to_terminal_label()),
+                                Vec::new(),
+                            )
+                        }
+                    });
+                if ids.contains_key(&id) {
+                    continue;
+                }
+                let local_id = first_id + ids.len();
+                ids.insert(id, local_id);
+                manager.add_node_level(local_id, level, terminal.clone());
+                if let Some(name) = terminal {
+                    terminals.insert(
+                        name,
+                        DummyBDDEdge::new(Arc::new(local_id), manager_ref.clone()),
+                    );
+                }
+                let child_ids = children
+                    .iter()
+                    .map(|child| child.with_manager_shared(|_, edge| edge.node_id()))
+                    .collect_vec();
+                edges.push((local_id, child_ids));
+                stack.extend(children);
+            }
+
+            for (node, children) in edges {
+                for child in children {
+                    manager.add_edge(node, ids[&child], manager_ref.clone());
+                }
+            }
+            manager.init_terminals(terminals);
+
+            let mut funcs = Vec::<(DummyBDDFunction, Vec<String>)>::new();
+            for (name, f) in roots {
+                let root = ids[&f.with_manager_shared(|_, edge| edge.node_id())];
+                match funcs.iter_mut().find(|(func, _)| func.0.node_id() == root) {
+                    Some((_, names)) => names.push(name.clone()),
+                    None => funcs.push((
+                        DummyFunction(DummyBDDEdge::new(Arc::new(root), manager_ref.clone())),
+                        vec![name.clone()],
+                    )),
+                }
+            }
+
+            (funcs, ids)
         })
     }
 }
 
-unsafe impl Function for DummyBDDFunction {
-    type Manager<'id> = DummyBDDManager;
+/// The number of nodes of the first manager in which diagrams are built
+const INITIAL_BUILD_CAPACITY: usize = 1 << 10;
+/// The number of nodes of the largest manager in which diagrams are built, beyond which building is given up
+const MAX_BUILD_CAPACITY: usize = 1 << 24;
+
+/// The reasons for which building a diagram in a real BDD manager can fail
+enum BuildError {
+    OutOfMemory,
+    Invalid(VisError),
+}
+impl From<OutOfMemory> for BuildError {
+    fn from(_: OutOfMemory) -> Self {
+        BuildError::OutOfMemory
+    }
+}
+impl From<VisError> for BuildError {
+    fn from(error: VisError) -> Self {
+        BuildError::Invalid(error)
+    }
+}
+
+/// Runs the given build in a fresh real BDD manager with room for the given number of nodes, and retries it in a manager twice as large whenever it runs out of memory
+fn build_in_bdd_manager<T>(
+    capacity: usize,
+    build: impl Fn(&BDDManagerRef) -> Result<T, BuildError>,
+) -> Result<T, VisError> {
+    let mut capacity = capacity.clamp(INITIAL_BUILD_CAPACITY, MAX_BUILD_CAPACITY);
+    loop {
+        let manager = oxidd::bdd::new_manager(capacity, capacity, 1);
+        match build(&manager) {
+            Ok(result) => return Ok(result),
+            Err(BuildError::Invalid(error)) => return Err(error),
+            Err(BuildError::OutOfMemory) if capacity < MAX_BUILD_CAPACITY => {
+                capacity = (capacity * 2).min(MAX_BUILD_CAPACITY);
+            }
+            Err(BuildError::OutOfMemory) => {
+                return Err(VisError::unsupported(
+                    "Not enough memory to build the diagram",
+                ))
+            }
+        }
+    }
+}
 
-    type ManagerRef = DummyBDDManagerRef;
+/// Copies the given dummy node into a real BDD manager with the given number of levels, which are the levels of the dummy manager. The nodes are created directly in the levels of the real manager, such that they keep the structure of the dummy diagram
+fn copy_to_bdd_manager(
+    manager: &DummyBDDManager,
+    bdd_manager: &BDDManagerRef,
+    node: NodeID,
+    level_count: LevelNo,
+    terminals: (&BDDFunction, &BDDFunction),
+    built: &mut HashMap<NodeID, BDDFunction>,
+) -> Result<BDDFunction, BuildError> {
+    if let Some(function) = built.get(&node) {
+        return Ok(function.clone());
+    }
+    let DummyNode(level, children, terminal) =
+        manager.0.get(&node).ok_or(VisError::UnknownNode { node })?;
+    let function = match (terminal, &children[..]) {
+        (Some(terminal), _) => match normalize_terminal(terminal) {
+            "T" => terminals.0.clone(),
+            "F" => terminals.1.clone(),
+            other => {
+                return Err(VisError::unsupported(format!(
+                    "Only true and false terminals can be reordered, not {}",
+                    other
+                ))
+                .into())
+            }
+        },
+        (None, [then_child, else_child]) => {
+            let then_case = copy_to_bdd_manager(
+                manager,
+                bdd_manager,
+                then_child.node_id(),
+                level_count,
+                terminals,
+                built,
+            )?;
+            let else_case = copy_to_bdd_manager(
+                manager,
+                bdd_manager,
+                else_child.node_id(),
+                level_count,
+                terminals,
+                built,
+            )?;
+            if *level >= level_count {
+                return Err(VisError::unsupported(format!(
+                    "Node {} lies outside of the reordered levels",
+                    node
+                ))
+                .into());
+            }
+            bdd_manager.with_manager_shared(|bdd| -> AllocResult<_> {
+                let mut level_view = bdd.level(*level);
+                let edge = get_or_create_node(
+                    bdd,
+                    &mut level_view,
+                    *level,
+                    bdd.clone_edge(then_case.as_edge(bdd)),
+                    bdd.clone_edge(else_case.as_edge(bdd)),
+                )?;
+                Ok(BDDFunction::from_edge(bdd, edge))
+            })?
+        }
+        (None, _) => {
+            return Err(VisError::unsupported(
+                "Only diagrams whose nodes have two children can be reordered",
+            )
+            .into())
+        }
+    };
+    built.insert(node, function.clone());
+    Ok(function)
+}
+
+unsafe impl<T: DummyTag> Function for DummyFunction<T> {
+    type Manager<'id> = DummyManager<T>;
+
+    type ManagerRef = DummyManagerRef<T>;
     fn from_edge<'id>(
         manager: &Self::Manager<'id>,
         edge: oxidd_core::function::EdgeOfFunc<'id, Self>,
     ) -> Self {
-        DummyBDDFunction(edge)
+        DummyFunction(edge)
     }
 
     fn as_edge<'id>(
@@ -404,24 +679,24 @@ unsafe impl Function for DummyBDDFunction {
     }
 
     fn manager_ref(&self) -> Self::ManagerRef {
-        todo!()
+        self.0 .1.clone()
     }
 
-    fn with_manager_shared<F, T>(&self, f: F) -> T
+    fn with_manager_shared<F, R>(&self, f: F) -> R
     where
-        F: for<'id> FnOnce(&Self::Manager<'id>, &oxidd_core::function::EdgeOfFunc<'id, Self>) -> T,
+        F: for<'id> FnOnce(&Self::Manager<'id>, &oxidd_core::function::EdgeOfFunc<'id, Self>) -> R,
     {
         self.0
              .1
             .with_manager_shared(|manager| f(manager, self.as_edge(manager)))
     }
 
-    fn with_manager_exclusive<F, T>(&self, f: F) -> T
+    fn with_manager_exclusive<F, R>(&self, f: F) -> R
     where
         F: for<'id> FnOnce(
             &mut Self::Manager<'id>,
             &oxidd_core::function::EdgeOfFunc<'id, Self>,
-        ) -> T,
+        ) -> R,
     {
         self.0
              .1
@@ -429,36 +704,37 @@ unsafe impl Function for DummyBDDFunction {
     }
 }
 
-/// Simple dummy edge implementation based on [`Arc`]
+/// Simple dummy edge implementation based on [`Arc`], carrying a tag
 ///
 /// The implementation is very limited but perfectly fine to test e.g. an apply
 /// cache.
 #[derive(Clone)]
-pub struct DummyBDDEdge(Arc<NodeID>, DummyBDDManagerRef);
+pub struct DummyEdge<T: DummyTag>(Arc<NodeID>, DummyManagerRef<T>, T);
 
-impl PartialEq for DummyBDDEdge {
+impl<T: DummyTag> PartialEq for DummyEdge<T> {
     fn eq(&self, other: &Self) -> bool {
-        Arc::ptr_eq(&self.0, &other.0)
+        Arc::ptr_eq(&self.0, &other.0) && self.2 == other.2
     }
 }
-impl Eq for DummyBDDEdge {}
-impl PartialOrd for DummyBDDEdge {
+impl<T: DummyTag> Eq for DummyEdge<T> {}
+impl<T: DummyTag> PartialOrd for DummyEdge<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
-impl Ord for DummyBDDEdge {
+impl<T: DummyTag> Ord for DummyEdge<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        Arc::as_ptr(&self.0).cmp(&Arc::as_ptr(&other.0))
+        (Arc::as_ptr(&self.0), self.2).cmp(&(Arc::as_ptr(&other.0), other.2))
     }
 }
-impl Hash for DummyBDDEdge {
+impl<T: DummyTag> Hash for DummyEdge<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         Arc::as_ptr(&self.0).hash(state);
+        self.2.hash(state);
     }
 }
 
-impl Drop for DummyBDDEdge {
+impl<T: DummyTag> Drop for DummyEdge<T> {
     fn drop(&mut self) {
         eprintln!(
             "Edges must not be dropped. Use Manager::drop_edge(). Backtrace:\n{}",
@@ -467,28 +743,34 @@ impl Drop for DummyBDDEdge {
     }
 }
 
-impl DummyBDDEdge {
-    /// Create a new `DummyEdge`
-    pub fn new(to: Arc<NodeID>, mr: DummyBDDManagerRef) -> Self {
-        DummyBDDEdge(to, mr.clone())
+impl<T: DummyTag> DummyEdge<T> {
+    /// Create a new `DummyEdge` without a tag
+    pub fn new(to: Arc<NodeID>, mr: DummyManagerRef<T>) -> Self {
+        DummyEdge(to, mr.clone(), T::UNTAGGED)
     }
 }
 
-impl Edge for DummyBDDEdge {
-    type Tag = ();
+impl<T: DummyTag> Edge for DummyEdge<T> {
+    type Tag = T;
 
     fn borrowed(&self) -> Borrowed<'_, Self> {
-        let ptr = Arc::as_ptr(&self.0);
-        Borrowed::new(DummyBDDEdge(unsafe { Arc::from_raw(ptr) }, self.1.clone()))
+        self.with_tag(self.2)
     }
-    fn with_tag(&self, _tag: ()) -> Borrowed<'_, Self> {
+    fn with_tag(&self, tag: T) -> Borrowed<'_, Self> {
         let ptr = Arc::as_ptr(&self.0);
-        Borrowed::new(DummyBDDEdge(unsafe { Arc::from_raw(ptr) }, self.1.clone()))
-    }
-    fn with_tag_owned(self, _tag: ()) -> Self {
+        Borrowed::new(DummyEdge(
+            unsafe { Arc::from_raw(ptr) },
+            self.1.clone(),
+            tag,
+        ))
+    }
+    fn with_tag_owned(mut self, tag: T) -> Self {
+        self.2 = tag;
         self
     }
-    fn tag(&self) -> Self::Tag {}
+    fn tag(&self) -> Self::Tag {
+        self.2
+    }
 
     fn node_id(&self) -> NodeID {
         *self.0
@@ -499,97 +781,106 @@ impl Edge for DummyBDDEdge {
 /// clone and drop edges.
 // #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Clone, PartialEq, Eq)]
-pub struct DummyBDDManager(
-    BTreeMap<NodeID, DummyBDDNode>,
-    HashMap<String, DummyBDDEdge>,
+pub struct DummyManager<T: DummyTag>(
+    BTreeMap<NodeID, DummyNode<T>>,
+    HashMap<String, DummyEdge<T>>,
 );
-impl DummyBDDManager {
-    pub fn new() -> DummyBDDManager {
-        DummyBDDManager(BTreeMap::new(), HashMap::new())
+impl<T: DummyTag> DummyManager<T> {
+    pub fn new() -> DummyManager<T> {
+        DummyManager(BTreeMap::new(), HashMap::new())
     }
-    fn init_terminals(&mut self, terminals: HashMap<String, DummyBDDEdge>) {
+    pub(crate) fn init_terminals(&mut self, terminals: HashMap<String, DummyEdge<T>>) {
         self.1.extend(terminals);
     }
 }
-impl Hash for DummyBDDManager {
+impl<T: DummyTag> Hash for DummyManager<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.hash(state);
     }
 }
 
 /// Dummy diagram rules
-pub struct DummyBDDRules;
-impl DiagramRules<DummyBDDEdge, DummyBDDNode, String> for DummyBDDRules {
+pub struct DummyRules<T: DummyTag>(PhantomData<T>);
+impl<T: DummyTag> DiagramRules<DummyEdge<T>, DummyNode<T>, String> for DummyRules<T> {
     // type Cofactors<'a> = Iter<'a, Borrowed<'a, DummyEdge>>;
     type Cofactors<'a>
-        = <DummyBDDNode as InnerNode<DummyBDDEdge>>::ChildrenIter<'a>
+        = <DummyNode<T> as InnerNode<DummyEdge<T>>>::ChildrenIter<'a>
     where
-        DummyBDDNode: 'a,
-        DummyBDDEdge: 'a;
+        DummyNode<T>: 'a,
+        DummyEdge<T>: 'a;
 
     fn reduce<M>(
         _manager: &M,
         level: LevelNo,
-        children: impl IntoIterator<Item = DummyBDDEdge>,
-    ) -> ReducedOrNew<DummyBDDEdge, DummyBDDNode>
+        children: impl IntoIterator<Item = DummyEdge<T>>,
+    ) -> ReducedOrNew<DummyEdge<T>, DummyNode<T>>
     where
-        M: Manager<Edge = DummyBDDEdge, InnerNode = DummyBDDNode>,
+        M: Manager<Edge = DummyEdge<T>, InnerNode = DummyNode<T>>,
     {
-        ReducedOrNew::New(DummyBDDNode::new(level, children), ())
+        ReducedOrNew::New(DummyNode::new(level, children), ())
     }
 
-    fn cofactors(_tag: (), node: &DummyBDDNode) -> Self::Cofactors<'_> {
+    fn cofactors(_tag: T, node: &DummyNode<T>) -> Self::Cofactors<'_> {
         node.children()
     }
 }
 
-impl DummyBDDManager {
-    fn add_node_level(
+impl<T: DummyTag> DummyManager<T> {
+    pub(crate) fn add_node_level(
         &mut self,
         from: NodeID,
         level: LevelNo,
         terminal: Option<String>,
-    ) -> &mut DummyBDDNode {
+    ) -> &mut DummyNode<T> {
         self.0.entry(from).or_insert_with(|| {
             if terminal.is_some() {
-                DummyBDDNode(level, Vec::new(), terminal)
+                DummyNode(level, Vec::new(), terminal)
             } else {
-                DummyBDDNode::new(level, Vec::new())
+                DummyNode::new(level, Vec::new())
             }
         })
     }
-    fn add_node(&mut self, from: NodeID) -> &mut DummyBDDNode {
+    fn add_node(&mut self, from: NodeID) -> &mut DummyNode<T> {
         self.add_node_level(from, from.try_into().unwrap(), None)
     }
-    fn add_edge(&mut self, from: NodeID, to: NodeID, mr: DummyBDDManagerRef) {
+    fn add_edge(&mut self, from: NodeID, to: NodeID, mr: DummyManagerRef<T>) {
+        self.add_tagged_edge(from, to, T::UNTAGGED, mr);
+    }
+    pub(crate) fn add_tagged_edge(
+        &mut self,
+        from: NodeID,
+        to: NodeID,
+        tag: T,
+        mr: DummyManagerRef<T>,
+    ) {
         let from_children = &mut self.0.get_mut(&from).unwrap().1;
-        let edge = DummyBDDEdge::new(Arc::new(to), mr);
+        let edge = DummyEdge::new(Arc::new(to), mr).with_tag_owned(tag);
         from_children.push(edge);
     }
-    fn has_edges(&self, node: NodeID) -> bool {
+    pub(crate) fn has_edges(&self, node: NodeID) -> bool {
         let from_children = &self.0.get(&node).unwrap().1;
         from_children.len() > 0
     }
 }
 
-unsafe impl Manager for DummyBDDManager {
-    type Edge = DummyBDDEdge;
-    type EdgeTag = ();
-    type InnerNode = DummyBDDNode;
+unsafe impl<T: DummyTag> Manager for DummyManager<T> {
+    type Edge = DummyEdge<T>;
+    type EdgeTag = T;
+    type InnerNode = DummyNode<T>;
     type Terminal = String;
     type TerminalRef<'a> = &'a String;
     type TerminalIterator<'a>
-        = Cloned<std::collections::hash_map::Values<'a, String, DummyBDDEdge>>
+        = Cloned<std::collections::hash_map::Values<'a, String, DummyEdge<T>>>
     where
         Self: 'a;
-    type Rules = DummyBDDRules;
+    type Rules = DummyRules<T>;
     type NodeSet = HashSet<NodeID>;
     type LevelView<'a>
-        = DummyBDDLevelView
+        = DummyLevelView<T>
     where
         Self: 'a;
     type LevelIterator<'a>
-        = std::iter::Empty<DummyBDDLevelView>
+        = std::iter::Empty<DummyLevelView<T>>
     where
         Self: 'a;
 
@@ -606,7 +897,7 @@ unsafe impl Manager for DummyBDDManager {
     }
 
     fn clone_edge(&self, edge: &Self::Edge) -> Self::Edge {
-        DummyBDDEdge(edge.0.clone(), edge.1.clone())
+        DummyEdge(edge.0.clone(), edge.1.clone(), edge.2)
     }
 
     fn drop_edge(&self, edge: Self::Edge) {
@@ -660,7 +951,7 @@ unsafe impl Manager for DummyBDDManager {
         0
     }
 
-    fn reorder<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+    fn reorder<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
         f(self)
     }
 
@@ -670,14 +961,14 @@ unsafe impl Manager for DummyBDDManager {
 }
 
 /// Dummy level view (not constructible)
-pub struct DummyBDDLevelView;
+pub struct DummyLevelView<T: DummyTag>(PhantomData<T>);
 
-unsafe impl LevelView<DummyBDDEdge, DummyBDDNode> for DummyBDDLevelView {
+unsafe impl<T: DummyTag> LevelView<DummyEdge<T>, DummyNode<T>> for DummyLevelView<T> {
     type Iterator<'a>
-        = std::iter::Empty<&'a DummyBDDEdge>
+        = std::iter::Empty<&'a DummyEdge<T>>
     where
         Self: 'a,
-        DummyBDDEdge: 'a;
+        DummyEdge<T>: 'a;
 
     type Taken = Self;
 
@@ -693,15 +984,15 @@ unsafe impl LevelView<DummyBDDEdge, DummyBDDNode> for DummyBDDLevelView {
         unreachable!()
     }
 
-    fn get(&self, _node: &DummyBDDNode) -> Option<&DummyBDDEdge> {
+    fn get(&self, _node: &DummyNode<T>) -> Option<&DummyEdge<T>> {
         unreachable!()
     }
 
-    fn insert(&mut self, _edge: DummyBDDEdge) -> bool {
+    fn insert(&mut self, _edge: DummyEdge<T>) -> bool {
         unreachable!()
     }
 
-    fn get_or_insert(&mut self, _node: DummyBDDNode) -> AllocResult<DummyBDDEdge> {
+    fn get_or_insert(&mut self, _node: DummyNode<T>) -> AllocResult<DummyEdge<T>> {
         unreachable!()
     }
 
@@ -709,7 +1000,7 @@ unsafe impl LevelView<DummyBDDEdge, DummyBDDNode> for DummyBDDLevelView {
         unreachable!()
     }
 
-    unsafe fn remove(&mut self, _node: &DummyBDDNode) -> bool {
+    unsafe fn remove(&mut self, _node: &DummyNode<T>) -> bool {
         unreachable!()
     }
 
@@ -728,15 +1019,15 @@ unsafe impl LevelView<DummyBDDEdge, DummyBDDNode> for DummyBDDLevelView {
 
 /// Dummy node
 #[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-pub struct DummyBDDNode(LevelNo, Vec<DummyBDDEdge>, Option<String>);
+pub struct DummyNode<T: DummyTag>(LevelNo, Vec<DummyEdge<T>>, Option<String>);
 
-impl DropWith<DummyBDDEdge> for DummyBDDNode {
-    fn drop_with(self, _drop_edge: impl Fn(DummyBDDEdge)) {
+impl<T: DummyTag> DropWith<DummyEdge<T>> for DummyNode<T> {
+    fn drop_with(self, _drop_edge: impl Fn(DummyEdge<T>)) {
         unimplemented!()
     }
 }
 
-unsafe impl HasLevel for DummyBDDNode {
+unsafe impl<T: DummyTag> HasLevel for DummyNode<T> {
     fn level(&self) -> LevelNo {
         self.0
     }
@@ -746,19 +1037,19 @@ unsafe impl HasLevel for DummyBDDNode {
     }
 }
 
-impl InnerNode<DummyBDDEdge> for DummyBDDNode {
+impl<T: DummyTag> InnerNode<DummyEdge<T>> for DummyNode<T> {
     const ARITY: usize = 0;
 
     // type ChildrenIter<'a> = std::iter::Empty<Borrowed<'a, DummyEdge>>
     // where
     //     Self: 'a;
     type ChildrenIter<'a>
-        = BorrowedEdgeIter<'a, DummyBDDEdge, Iter<'a, DummyBDDEdge>>
+        = BorrowedEdgeIter<'a, DummyEdge<T>, Iter<'a, DummyEdge<T>>>
     where
         Self: 'a;
 
-    fn new(level: LevelNo, children: impl IntoIterator<Item = DummyBDDEdge>) -> Self {
-        DummyBDDNode(level, children.into_iter().collect(), None)
+    fn new(level: LevelNo, children: impl IntoIterator<Item = DummyEdge<T>>) -> Self {
+        DummyNode(level, children.into_iter().collect(), None)
     }
 
     fn check_level(&self, _check: impl FnOnce(LevelNo) -> bool) -> bool {
@@ -769,11 +1060,11 @@ impl InnerNode<DummyBDDEdge> for DummyBDDNode {
         BorrowedEdgeIter::from(self.1.iter())
     }
 
-    fn child(&self, _n: usize) -> Borrowed<DummyBDDEdge> {
+    fn child(&self, _n: usize) -> Borrowed<DummyEdge<T>> {
         unimplemented!()
     }
 
-    unsafe fn set_child(&self, _n: usize, _child: DummyBDDEdge) -> DummyBDDEdge {
+    unsafe fn set_child(&self, _n: usize, _child: DummyEdge<T>) -> DummyEdge<T> {
         unimplemented!()
     }
 
@@ -813,3 +1104,138 @@ macro_rules! assert_ref_counts {
         assert_ref_counts!($($($edgess),+ = $counts);+);
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_manager_ref() -> DummyBDDManagerRef {
+        DummyBDDManagerRef::from(&DummyBDDManager::new())
+    }
+
+    fn get_children(function: &DummyBDDFunction, node: NodeID) -> Vec<NodeID> {
+        function.with_manager_shared(|manager, _| {
+            manager.0[&node]
+                .1
+                .iter()
+                .map(|child| child.node_id())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn read_edge_list() {
+        let root = DummyBDDFunction::try_from(&mut new_manager_ref(), "1>2>4, 1 > 3,3>4").unwrap();
+        assert_eq!(root.0.node_id(), 1);
+        assert_eq!(get_children(&root, 1), vec![2, 3]);
+        assert_eq!(get_children(&root, 3), vec![4]);
+        assert_eq!(get_children(&root, 4), Vec::<NodeID>::new());
+    }
+
+    #[test]
+    fn edge_list_errors() {
+        let error = |data: &str| match DummyBDDFunction::try_from(&mut new_manager_ref(), data) {
+            Ok(_) => panic!("\"{}\" should not be read", data),
+            Err(error) => error,
+        };
+        assert_eq!(
+            error("1>2,\n2> x"),
+            ParseError::at_line(2, 4, "expected a node ID, but found \"x\"")
+        );
+        assert_eq!(
+            error("1>>2"),
+            ParseError::at_line(1, 3, "expected a node ID, but found nothing")
+        );
+        assert_eq!(error("1"), ParseError::at_line(1, 2, "no edges given"));
+    }
+
+    /// Evaluates the named function under the assignment of the given variables, where `levels` names the variable per level
+    fn evaluate(
+        roots: &[(DummyBDDFunction, Vec<String>)],
+        name: &str,
+        levels: &[&str],
+        assignment: &HashSet<&str>,
+    ) -> bool {
+        let (root, _) = roots
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| n == name))
+            .unwrap();
+        root.with_manager_shared(|manager, edge| {
+            let mut node = edge.node_id();
+            loop {
+                let DummyNode(level, children, terminal) = &manager.0[&node];
+                if let Some(terminal) = terminal {
+                    return normalize_terminal(terminal) == "T";
+                }
+                let then_case = assignment.contains(levels[*level as usize]);
+                node = children[if then_case { 0 } else { 1 }].node_id();
+            }
+        })
+    }
+
+    #[test]
+    fn reorder_preserves_functions() {
+        let mut manager_ref = new_manager_ref();
+        let (roots, variables, _) =
+            DummyBDDFunction::from_formula(&mut manager_ref, "f = (a & b) | c; g = a ^ c", None)
+                .unwrap();
+        assert_eq!(variables, vec!["a", "b", "c"]);
+        let node_count = manager_ref.with_manager_shared(|manager| manager.0.len());
+
+        let (reordered, _) =
+            DummyBDDFunction::reorder(&mut manager_ref, &roots, &[2, 0, 1]).unwrap();
+        for assignment in ["a", "b", "c"].iter().cloned().powerset() {
+            let assignment: HashSet<&str> = assignment.into_iter().collect();
+            for name in ["f", "g"] {
+                assert_eq!(
+                    evaluate(&roots, name, &["a", "b", "c"], &assignment),
+                    evaluate(&reordered, name, &["c", "a", "b"], &assignment),
+                    "{} differs under {:?}",
+                    name,
+                    assignment
+                );
+            }
+        }
+
+        // Moving the levels back and releasing the intermediate copy leaves only the original diagram and the newest copy
+        let (restored, _) =
+            DummyBDDFunction::reorder(&mut manager_ref, &reordered, &[1, 2, 0]).unwrap();
+        DummyBDDFunction::release(&mut manager_ref, &reordered);
+        assert_eq!(
+            manager_ref.with_manager_shared(|manager| manager.0.len()),
+            2 * node_count
+        );
+        for assignment in ["a", "b", "c"].iter().cloned().powerset() {
+            let assignment: HashSet<&str> = assignment.into_iter().collect();
+            for name in ["f", "g"] {
+                assert_eq!(
+                    evaluate(&roots, name, &["a", "b", "c"], &assignment),
+                    evaluate(&restored, name, &["a", "b", "c"], &assignment)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reorder_keeps_the_nodes_of_swapped_levels() {
+        let mut manager_ref = new_manager_ref();
+        let (roots, _, _) =
+            DummyBDDFunction::from_formula(&mut manager_ref, "f = a & b", None).unwrap();
+        let root = roots[0].0 .0.node_id();
+
+        // Swapping the levels turns the root into a node of b, whose then child is a new node of a
+        let (reordered, sources) =
+            DummyBDDFunction::reorder(&mut manager_ref, &roots, &[1, 0]).unwrap();
+        let new_root = reordered[0].0 .0.node_id();
+        assert_eq!(sources.get(&new_root), Some(&root));
+        let level = |node: NodeID| {
+            reordered[0]
+                .0
+                .with_manager_shared(|manager, _| manager.0[&node].0)
+        };
+        let children = get_children(&reordered[0].0, new_root);
+        assert_eq!(level(new_root), 0);
+        assert_eq!(level(children[0]), 1);
+        assert_eq!(sources.get(&children[0]), None);
+    }
+}
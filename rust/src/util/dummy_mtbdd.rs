@@ -28,7 +28,10 @@ use oxidd_core::ReducedOrNew;
 use oxidd_core::WorkerManager;
 use oxidd_core::{BroadcastContext, HasLevel};
 
+use crate::util::dddmp::{normalize_terminal, DddmpFile, DddmpNodeKind};
 use crate::util::logging::console;
+use crate::util::parse_error::ParseError;
+use crate::util::vis_error::VisError;
 
 #[derive(Clone, Copy, PartialOrd)]
 pub struct MTBDDTerminal(pub f32);
@@ -92,106 +95,64 @@ impl DummyMTBDDFunction {
     pub fn from_dddmp(
         manager_ref: &mut DummyMTBDDManagerRef,
         data: &str,
-    ) -> (Vec<(DummyMTBDDFunction, Vec<String>)>, Vec<String>) {
+    ) -> Result<(Vec<(DummyMTBDDFunction, Vec<String>)>, Vec<String>), VisError> {
+        let file = DddmpFile::parse_str(data)?;
+        // Complemented edges are only meaningful for boolean terminals, which are expanded here
+        let (nodes, roots) = file.expand_complements()?;
+
         manager_ref.with_manager_exclusive(|manager| {
             let mut terminals = HashMap::new();
 
-            let get_text = |from: &str, to: &str| {
-                let start = data.find(from).unwrap() + from.len();
-                Box::new(&data[start + 1..start + data[start..].find(to).unwrap()])
-            };
-
-            let roots_text = get_text(".rootids", "\n");
-            let roots = roots_text
-                .trim()
-                .split(" ")
-                .flat_map(|n| n.parse::<usize>())
-                .collect_vec();
-            let root_names = if data.find(".rootnames").is_some() {
-                let roots_names_text = get_text(".rootnames", "\n");
-                roots_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .collect_vec()
-            } else {
-                roots
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| format!("f{i}"))
-                    .collect_vec()
-            };
-
-            let node_text = get_text(".nodes", ".end");
-            let nodes_data = node_text.split("\n").filter_map(|node| {
-                let parts = node.trim().split(" ").collect::<Vec<&str>>();
-                if parts.len() >= 4 {
-                    let id: NodeID = parts[0].parse().unwrap();
-                    let level = parts[1];
-                    let children = parts[2..].iter().map(|v| v.parse().unwrap()).collect_vec();
-                    Some((id, level, children))
-                } else {
-                    None
-                }
-            });
-            let mut max_level = 0;
-            for (_, level, _) in nodes_data.clone() {
-                let Ok(level) = level.parse() else { continue };
-
-                if level > max_level {
-                    max_level = level;
-                }
-            }
-
-            for (id, level, children) in nodes_data.clone() {
-                let level_num = level.parse();
-                let term_num = (level.parse() as Result<f32, _>).map(|r| MTBDDTerminal(r));
-                let is_terminal = children[0] == 0;
-                manager.add_node_level(
-                    id.clone(),
-                    if is_terminal {
-                        max_level + 1 // Terminal nodes don't define a level, we have to assign it
-                    } else {
-                        level_num.clone().unwrap()
-                    },
-                    if is_terminal {
-                        term_num.clone().ok()
-                    } else {
-                        None
-                    },
-                );
-
-                if is_terminal {
-                    terminals.insert(
-                        term_num.unwrap(),
-                        DummyMTBDDEdge::new(Arc::new(id), manager_ref.clone()),
-                    );
+            let max_level = nodes
+                .iter()
+                .filter_map(|node| match node.kind {
+                    DddmpNodeKind::Inner { index, .. } => Some(index as LevelNo),
+                    DddmpNodeKind::Terminal(_) => None,
+                })
+                .max()
+                .unwrap_or(0);
+
+            for node in &nodes {
+                match &node.kind {
+                    DddmpNodeKind::Inner { index, .. } => {
+                        manager.add_node_level(node.id, *index as LevelNo, None);
+                    }
+                    DddmpNodeKind::Terminal(value) => {
+                        let value = match normalize_terminal(value) {
+                            "T" => 1.0,
+                            "F" => 0.0,
+                            value => value.parse::<f32>().map_err(|_| {
+                                ParseError::at_line(
+                                    node.line,
+                                    1,
+                                    format!("expected a terminal value, found \"{}\"", value),
+                                )
+                            })?,
+                        };
+                        let terminal = MTBDDTerminal(value);
+                        // Terminal nodes don't define a level, we have to assign it
+                        manager.add_node_level(node.id, max_level + 1, Some(terminal));
+                        terminals.insert(
+                            terminal,
+                            DummyMTBDDEdge::new(Arc::new(node.id), manager_ref.clone()),
+                        );
+                    }
                 }
             }
 
-            for (id, level, children) in nodes_data {
-                if manager.has_edges(id) {
+            for node in &nodes {
+                if manager.has_edges(node.id) {
                     continue; // This node was already loaded
                 }
-                if level.parse::<i32>().is_err() {
-                    continue;
-                }; // Filter out terminals
-
-                let is_terminal = |_: NodeID| false;
-                // let is_terminal = |to: NodeID| to == 1 || to == 2;
-                // let is_terminal = |to: NodeID| to == 1; // Only filter connections to false
-
-                for child in children {
-                    if !is_terminal(child) {
-                        manager.add_edge(id.clone(), child, manager_ref.clone());
-                    }
+                for child in node.get_children() {
+                    manager.add_edge(node.id, child.node, manager_ref.clone());
                 }
             }
 
             manager.init_terminals(terminals);
 
             let mut func_map = HashMap::<NodeID, (DummyMTBDDFunction, Vec<String>)>::new();
-            for (root, name) in roots.into_iter().zip(root_names.into_iter()) {
+            for (root, name) in roots.into_iter().zip(file.get_root_names()) {
                 func_map
                     .entry(root)
                     .or_insert_with(|| {
@@ -204,55 +165,11 @@ impl DummyMTBDDFunction {
                         )
                     })
                     .1
-                    .push(name.to_string());
+                    .push(name);
             }
             let funcs = func_map.values().cloned().collect_vec();
 
-            let var_names = if data.find(".suppvarnames").is_some() {
-                let supp_names_text = get_text(".suppvarnames", ".orderedvarnames");
-                let supp_var_names = supp_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .collect::<HashSet<_>>();
-
-                let ord_names_text = if data.find(".orderedvarnames").is_some() {
-                    get_text(".orderedvarnames", ".ids")
-                } else {
-                    supp_names_text
-                };
-                ord_names_text
-                    .trim()
-                    .split(" ")
-                    .map(|t| t.to_string())
-                    .filter(|t| supp_var_names.contains(t))
-                    .collect::<Vec<_>>()
-            } else {
-                let supp_vars = get_text(".ids", ".permids")
-                    .trim()
-                    .split(" ")
-                    .filter_map(|var| var.parse::<usize>().ok())
-                    .collect::<Vec<_>>();
-                let id_positions = get_text(".permids", ".nroots");
-                let mut var_levels = id_positions
-                    .trim()
-                    .split(" ")
-                    .enumerate()
-                    .filter_map(|(id, level)| {
-                        level
-                            .parse::<usize>()
-                            .ok()
-                            .and_then(|level| supp_vars.get(id).map(|var| (level, var)))
-                    })
-                    .collect::<Vec<_>>();
-                var_levels.sort_by_key(|(level, _var)| *level);
-                var_levels
-                    .iter()
-                    .map(|(_level, var)| format!("x{var}"))
-                    .collect::<Vec<_>>()
-            };
-
-            (funcs, var_names)
+            Ok((funcs, file.get_level_names()))
         })
     }
 }
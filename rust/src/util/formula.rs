@@ -0,0 +1,495 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use oxidd::{util::AllocResult, BooleanFunction};
+
+use crate::util::parse_error::ParseError;
+
+/// A list of propositional formulas, written as `name = formula` entries separated by `;`.
+/// Formulas may use variables, the constants `true`/`false` (or `1`/`0`), parentheses and the operators below, from highest to lowest precedence:
+/// - `!` (not)
+/// - `&` (and)
+/// - `^` (exclusive or)
+/// - `|` (or)
+/// - `->` (implication, right associative)
+/// - `<->` (equivalence)
+///
+/// The `name =` part of an entry is optional, unnamed formulas are named `f` (or `f1`, `f2`, ... when there are multiple entries).
+pub struct FormulaFile {
+    pub roots: Vec<(String, Formula)>,
+    /// The variables in order of first appearance
+    pub variables: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Formula {
+    Constant(bool),
+    Variable(String),
+    Not(Box<Formula>),
+    Binary(BinaryOperator, Box<Formula>, Box<Formula>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOperator {
+    And,
+    Or,
+    Xor,
+    Implies,
+    Equivalent,
+}
+
+impl Formula {
+    /// Builds the function for this formula, using the given functions for the variables and constants
+    pub fn build<F: BooleanFunction>(
+        &self,
+        variables: &HashMap<String, F>,
+        t: &F,
+        f: &F,
+    ) -> AllocResult<F> {
+        Ok(match self {
+            Formula::Constant(true) => t.clone(),
+            Formula::Constant(false) => f.clone(),
+            Formula::Variable(name) => variables[name].clone(),
+            Formula::Not(formula) => formula.build(variables, t, f)?.not()?,
+            Formula::Binary(operator, left, right) => {
+                let left = left.build(variables, t, f)?;
+                let right = right.build(variables, t, f)?;
+                match operator {
+                    BinaryOperator::And => left.and(&right)?,
+                    BinaryOperator::Or => left.or(&right)?,
+                    BinaryOperator::Xor => left.xor(&right)?,
+                    BinaryOperator::Implies => left.imp(&right)?,
+                    BinaryOperator::Equivalent => left.equiv(&right)?,
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Constant(bool),
+    Not,
+    Operator(BinaryOperator),
+    Open,
+    Close,
+    Assign,
+    Separator,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Identifier(name) => format!("variable \"{}\"", name),
+            Token::Constant(value) => format!("constant \"{}\"", value),
+            Token::Not => "\"!\"".to_string(),
+            Token::Operator(operator) => format!("\"{}\"", operator_symbol(*operator)),
+            Token::Open => "\"(\"".to_string(),
+            Token::Close => "\")\"".to_string(),
+            Token::Assign => "\"=\"".to_string(),
+            Token::Separator => "\";\"".to_string(),
+            Token::End => "end of input".to_string(),
+        }
+    }
+}
+
+fn operator_symbol(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::And => "&",
+        BinaryOperator::Or => "|",
+        BinaryOperator::Xor => "^",
+        BinaryOperator::Implies => "->",
+        BinaryOperator::Equivalent => "<->",
+    }
+}
+
+/// Splits the text into tokens, together with the byte offset at which they start
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        let token = match c {
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                i += 1;
+                continue;
+            }
+            b'!' => Token::Not,
+            b'&' => Token::Operator(BinaryOperator::And),
+            b'|' => Token::Operator(BinaryOperator::Or),
+            b'^' => Token::Operator(BinaryOperator::Xor),
+            b'(' => Token::Open,
+            b')' => Token::Close,
+            b'=' => Token::Assign,
+            b';' => Token::Separator,
+            b'-' if bytes.get(i + 1) == Some(&b'>') => {
+                i += 1;
+                Token::Operator(BinaryOperator::Implies)
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'-') && bytes.get(i + 2) == Some(&b'>') => {
+                i += 2;
+                Token::Operator(BinaryOperator::Equivalent)
+            }
+            b'0' => Token::Constant(false),
+            b'1' => Token::Constant(true),
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while i + 1 < bytes.len()
+                    && (bytes[i + 1].is_ascii_alphanumeric()
+                        || bytes[i + 1] == b'_'
+                        || bytes[i + 1] == b'\'')
+                {
+                    i += 1;
+                }
+                match &text[start..=i] {
+                    "true" => Token::Constant(true),
+                    "false" => Token::Constant(false),
+                    name => Token::Identifier(name.to_string()),
+                }
+            }
+            _ => {
+                let character = text[start..].chars().next().unwrap_or('?');
+                return Err(ParseError::at_offset(
+                    text,
+                    start,
+                    format!("unexpected character \"{}\"", character),
+                ));
+            }
+        };
+        i += 1;
+        tokens.push((token, start));
+    }
+    tokens.push((Token::End, text.len()));
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    variables: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+    fn peek_second(&self) -> &Token {
+        &self.tokens[(self.index + 1).min(self.tokens.len() - 1)].0
+    }
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.index].0.clone();
+        if token != Token::End {
+            self.index += 1;
+        }
+        token
+    }
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let (token, offset) = &self.tokens[self.index];
+        ParseError::at_offset(
+            self.text,
+            *offset,
+            format!("expected {}, but found {}", expected, token.describe()),
+        )
+    }
+
+    fn parse_entry(&mut self) -> Result<(Option<String>, Formula), ParseError> {
+        let name = match (self.peek(), self.peek_second()) {
+            (Token::Identifier(name), Token::Assign) => {
+                let name = name.clone();
+                self.next();
+                self.next();
+                Some(name)
+            }
+            _ => None,
+        };
+        Ok((name, self.parse_binary(0)?))
+    }
+
+    /// Parses a formula whose top level operators have at least the given precedence
+    fn parse_binary(&mut self, min_precedence: usize) -> Result<Formula, ParseError> {
+        let mut left = self.parse_unary()?;
+        while let Token::Operator(operator) = *self.peek() {
+            let precedence = get_precedence(operator);
+            if precedence < min_precedence {
+                break;
+            }
+            self.next();
+            let right_precedence = if operator == BinaryOperator::Implies {
+                precedence // Right associative
+            } else {
+                precedence + 1
+            };
+            let right = self.parse_binary(right_precedence)?;
+            left = Formula::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Formula, ParseError> {
+        match self.peek().clone() {
+            Token::Not => {
+                self.next();
+                Ok(Formula::Not(Box::new(self.parse_unary()?)))
+            }
+            Token::Constant(value) => {
+                self.next();
+                Ok(Formula::Constant(value))
+            }
+            Token::Identifier(name) => {
+                self.next();
+                if !self.variables.contains(&name) {
+                    self.variables.push(name.clone());
+                }
+                Ok(Formula::Variable(name))
+            }
+            Token::Open => {
+                self.next();
+                let formula = self.parse_binary(0)?;
+                if *self.peek() != Token::Close {
+                    return Err(self.unexpected("\")\""));
+                }
+                self.next();
+                Ok(formula)
+            }
+            _ => Err(self.unexpected("a variable, constant, \"!\" or \"(\"")),
+        }
+    }
+}
+
+fn get_precedence(operator: BinaryOperator) -> usize {
+    match operator {
+        BinaryOperator::Equivalent => 0,
+        BinaryOperator::Implies => 1,
+        BinaryOperator::Or => 2,
+        BinaryOperator::Xor => 3,
+        BinaryOperator::And => 4,
+    }
+}
+
+impl FormulaFile {
+    pub fn parse_str(text: &str) -> Result<FormulaFile, ParseError> {
+        let mut parser = Parser {
+            text,
+            tokens: tokenize(text)?,
+            index: 0,
+            variables: Vec::new(),
+        };
+
+        let mut entries = Vec::new();
+        loop {
+            match parser.peek() {
+                Token::End => break,
+                Token::Separator => {
+                    parser.next();
+                    continue;
+                }
+                _ => {}
+            }
+            entries.push(parser.parse_entry()?);
+            match parser.peek() {
+                Token::Separator | Token::End => {}
+                _ => return Err(parser.unexpected("an operator, \";\" or the end of the input")),
+            }
+        }
+        if entries.is_empty() {
+            return Err(ParseError::at_offset(text, text.len(), "no formula given"));
+        }
+
+        let count = entries.len();
+        let roots = entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, formula))| {
+                let name = name.unwrap_or_else(|| {
+                    if count == 1 {
+                        "f".to_string()
+                    } else {
+                        format!("f{}", i + 1)
+                    }
+                });
+                (name, formula)
+            })
+            .collect_vec();
+
+        Ok(FormulaFile {
+            roots,
+            variables: parser.variables,
+        })
+    }
+
+    /// Retrieves the variables from top to bottom level, using the given whitespace or comma separated order for the variables it lists and appending the remaining variables in order of appearance
+    pub fn get_variable_order(&self, order: Option<&str>) -> Vec<String> {
+        let ordered = order
+            .unwrap_or("")
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string());
+        ordered
+            .chain(self.variables.iter().cloned())
+            .unique()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxidd::{bdd::BDDFunction, ManagerRef};
+
+    use super::*;
+
+    fn parse(text: &str) -> Formula {
+        let mut file = FormulaFile::parse_str(text).unwrap();
+        file.roots.remove(0).1
+    }
+
+    fn parse_error(text: &str) -> ParseError {
+        match FormulaFile::parse_str(text) {
+            Ok(_) => panic!("\"{}\" should not parse", text),
+            Err(error) => error,
+        }
+    }
+
+    fn var(name: &str) -> Formula {
+        Formula::Variable(name.to_string())
+    }
+
+    fn not(formula: Formula) -> Formula {
+        Formula::Not(Box::new(formula))
+    }
+
+    fn binary(operator: BinaryOperator, left: Formula, right: Formula) -> Formula {
+        Formula::Binary(operator, Box::new(left), Box::new(right))
+    }
+
+    #[test]
+    fn operator_precedence() {
+        use BinaryOperator::*;
+        let expected = binary(
+            Equivalent,
+            binary(
+                Implies,
+                binary(
+                    Or,
+                    binary(Xor, binary(And, not(var("a")), var("b")), var("c")),
+                    var("d"),
+                ),
+                var("e"),
+            ),
+            var("g"),
+        );
+        assert_eq!(parse("!a & b ^ c | d -> e <-> g"), expected);
+        assert_eq!(
+            parse("a | b & c"),
+            binary(Or, var("a"), binary(And, var("b"), var("c")))
+        );
+        assert_eq!(
+            parse("(a | b) & c"),
+            binary(And, binary(Or, var("a"), var("b")), var("c"))
+        );
+    }
+
+    #[test]
+    fn associativity() {
+        use BinaryOperator::*;
+        assert_eq!(
+            parse("a -> b -> c"),
+            binary(Implies, var("a"), binary(Implies, var("b"), var("c")))
+        );
+        assert_eq!(
+            parse("a & b & c"),
+            binary(And, binary(And, var("a"), var("b")), var("c"))
+        );
+        assert_eq!(
+            parse("a <-> b <-> c"),
+            binary(Equivalent, binary(Equivalent, var("a"), var("b")), var("c"))
+        );
+    }
+
+    #[test]
+    fn constants() {
+        use BinaryOperator::*;
+        assert_eq!(
+            parse("true | 0 & false ^ 1"),
+            binary(
+                Or,
+                Formula::Constant(true),
+                binary(
+                    Xor,
+                    binary(And, Formula::Constant(false), Formula::Constant(false)),
+                    Formula::Constant(true)
+                )
+            )
+        );
+        assert_eq!(parse("x1 & truth"), binary(And, var("x1"), var("truth")));
+    }
+
+    #[test]
+    fn names_and_variables() {
+        let file = FormulaFile::parse_str("g = b & a;; c | a").unwrap();
+        let names = file
+            .roots
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect_vec();
+        assert_eq!(names, vec!["g", "f2"]);
+        assert_eq!(file.variables, vec!["b", "a", "c"]);
+        assert_eq!(file.get_variable_order(Some("c, a")), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn error_locations() {
+        assert_eq!(
+            parse_error("a $ b"),
+            ParseError::at_line(1, 3, "unexpected character \"$\"")
+        );
+        assert_eq!(
+            parse_error("f = a &\n  | b"),
+            ParseError::at_line(
+                2,
+                3,
+                "expected a variable, constant, \"!\" or \"(\", but found \"|\""
+            )
+        );
+        assert_eq!(
+            parse_error("(a & b"),
+            ParseError::at_line(1, 7, "expected \")\", but found end of input")
+        );
+        assert_eq!(
+            parse_error("a b"),
+            ParseError::at_line(
+                1,
+                3,
+                "expected an operator, \";\" or the end of the input, but found variable \"b\""
+            )
+        );
+        assert_eq!(
+            parse_error(" ; "),
+            ParseError::at_line(1, 4, "no formula given")
+        );
+    }
+
+    #[test]
+    fn build_functions() {
+        let manager_ref = oxidd::bdd::new_manager(1024, 1024, 1);
+        let (vars, t, f) = manager_ref
+            .with_manager_exclusive(|manager| -> AllocResult<_> {
+                let vars = ["a", "b"]
+                    .iter()
+                    .map(|name| Ok((name.to_string(), BDDFunction::new_var(manager)?)))
+                    .collect::<AllocResult<HashMap<_, _>>>()?;
+                Ok((vars, BDDFunction::t(manager), BDDFunction::f(manager)))
+            })
+            .unwrap();
+        let build = |text: &str| parse(text).build(&vars, &t, &f).unwrap();
+
+        assert!(build("a <-> b") == build("(a -> b) & (b -> a)"));
+        assert!(build("a -> b") == build("!a | b"));
+        assert!(build("a ^ a") == f);
+        assert!(build("a | !a") == t);
+        assert!(build("1 & !false") == t);
+    }
+}
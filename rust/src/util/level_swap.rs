@@ -0,0 +1,142 @@
+use itertools::Itertools;
+use oxidd::{Edge, InnerNode, Manager};
+use oxidd_core::util::AllocResult;
+use oxidd_core::{DiagramRules, HasLevel, LevelNo, LevelView, Node, ReducedOrNew};
+
+/// Reorders the levels of the manager in place, such that they follow the given order, which lists the current levels from the top level downwards and has to be a permutation of the top levels of the manager.
+///
+/// The order is established by swapping adjacent levels, such that every node keeps its ID and the function it represents. Since the levels belong to the manager, every diagram of the manager is reordered. If the manager runs out of memory, the levels are left in an inconsistent state.
+pub fn set_level_order<M: Manager<EdgeTag = ()>>(
+    manager: &mut M,
+    order: &[LevelNo],
+) -> AllocResult<()>
+where
+    M::InnerNode: HasLevel,
+{
+    manager.reorder(|manager| {
+        // The original level per current position
+        let mut current = (0..order.len() as LevelNo).collect_vec();
+        for (position, &level) in order.iter().enumerate() {
+            let from = current.iter().position(|&l| l == level).unwrap();
+            // The levels above the position are final already, so the level only moves up
+            for upper in (position..from).rev() {
+                swap_levels(&*manager, upper as LevelNo)?;
+            }
+            let moved = current.remove(from);
+            current.insert(position, moved);
+        }
+        Ok(())
+    })
+}
+
+/// Swaps the given level with the level below it
+fn swap_levels<M: Manager<EdgeTag = ()>>(manager: &M, upper_no: LevelNo) -> AllocResult<()>
+where
+    M::InnerNode: HasLevel,
+{
+    let lower_no = upper_no + 1;
+    let mut upper = manager.level(upper_no);
+    let mut lower = manager.level(lower_no);
+
+    // We hold a reference to every node of the upper level while it is restructured, the taken level keeps the references of the unique table until it is dropped
+    let taken = upper.take();
+    let upper_nodes = taken
+        .iter()
+        .map(|edge| manager.clone_edge(edge))
+        .collect_vec();
+
+    // The nodes of the lower level move up, and keep representing the same function
+    unsafe { upper.swap(&mut lower) };
+    for edge in upper.iter() {
+        if let Node::Inner(node) = manager.get_node(edge) {
+            unsafe { node.set_level(upper_no) };
+        }
+    }
+
+    // The nodes that do not depend on the moved up variable only move down. They are inserted before any node is created in the lower level, such that the created nodes are not duplicates of them
+    let (independent, dependent): (Vec<_>, Vec<_>) = upper_nodes
+        .into_iter()
+        .map(|edge| {
+            let cofactors = match manager.get_node(&edge) {
+                Node::Inner(node) => {
+                    let then_edge = node.child(0);
+                    let else_edge = node.child(1);
+                    let then_cofactors = get_cofactors(manager, &then_edge, upper_no);
+                    let else_cofactors = get_cofactors(manager, &else_edge, upper_no);
+                    if then_cofactors.is_none() && else_cofactors.is_none() {
+                        None
+                    } else {
+                        let [then_then, then_else] = then_cofactors.unwrap_or_else(|| {
+                            [
+                                manager.clone_edge(&then_edge),
+                                manager.clone_edge(&then_edge),
+                            ]
+                        });
+                        let [else_then, else_else] = else_cofactors.unwrap_or_else(|| {
+                            [
+                                manager.clone_edge(&else_edge),
+                                manager.clone_edge(&else_edge),
+                            ]
+                        });
+                        // The children of the new then and else child respectively
+                        Some([[then_then, else_then], [then_else, else_else]])
+                    }
+                }
+                Node::Terminal(_) => None,
+            };
+            (edge, cofactors)
+        })
+        .partition(|(_, cofactors)| cofactors.is_none());
+    for (edge, _) in independent {
+        if let Node::Inner(node) = manager.get_node(&edge) {
+            unsafe { node.set_level(lower_no) };
+        }
+        lower.insert(edge);
+    }
+
+    // The other nodes now test the moved up variable first, and get children in the lower level that test their original variable
+    for (edge, cofactors) in dependent {
+        let [[then_then, else_then], [then_else, else_else]] = cofactors.unwrap();
+        let then_child = get_or_create_node(manager, &mut lower, lower_no, then_then, else_then)?;
+        let else_child = get_or_create_node(manager, &mut lower, lower_no, then_else, else_else)?;
+        if let Node::Inner(node) = manager.get_node(&edge) {
+            unsafe {
+                manager.drop_edge(node.set_child(0, then_child));
+                manager.drop_edge(node.set_child(1, else_child));
+            }
+        }
+        upper.insert(edge);
+    }
+    drop(taken);
+    Ok(())
+}
+
+/// Retrieves the children of the given edge if it points to a node of the given level
+fn get_cofactors<M: Manager>(manager: &M, edge: &M::Edge, level: LevelNo) -> Option<[M::Edge; 2]>
+where
+    M::InnerNode: HasLevel,
+{
+    match manager.get_node(edge) {
+        Node::Inner(node) if node.level() == level => Some([
+            manager.clone_edge(&node.child(0)),
+            manager.clone_edge(&node.child(1)),
+        ]),
+        _ => None,
+    }
+}
+
+/// Retrieves the node of the given level with the given children, which may be reduced to one of its children according to the rules of the manager
+pub fn get_or_create_node<'a, M: Manager>(
+    manager: &'a M,
+    level_view: &mut M::LevelView<'a>,
+    level: LevelNo,
+    then_edge: M::Edge,
+    else_edge: M::Edge,
+) -> AllocResult<M::Edge> {
+    Ok(
+        match M::Rules::reduce(manager, level, vec![then_edge, else_edge]) {
+            ReducedOrNew::Reduced(edge) => edge,
+            ReducedOrNew::New(node, tag) => level_view.get_or_insert(node)?.with_tag_owned(tag),
+        },
+    )
+}
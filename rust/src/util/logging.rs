@@ -5,6 +5,7 @@ use wasm_bindgen::prelude::*;
 // manually ourselves, and the correctness of our program relies on the
 // correctness of these annotations!
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     // Use `js_namespace` here to bind `console.log(..)` instead of just
@@ -29,6 +30,14 @@ extern "C" {
     fn log_many(a: &str, b: &str);
 }
 
+// Native builds (such as the convert_data tool) have no console to log to, so the messages are dropped
+#[cfg(not(target_arch = "wasm32"))]
+pub fn log(_s: &str) {}
+#[cfg(not(target_arch = "wasm32"))]
+pub fn time(_s: &str) {}
+#[cfg(not(target_arch = "wasm32"))]
+pub fn time_end(_s: &str) {}
+
 macro_rules! log {
     // Note that this is using the `log` function imported above during
     // `bare_bones`
@@ -1,12 +1,21 @@
 pub mod color;
+pub mod color_scale;
+pub mod conversion;
+pub mod dddmp;
+pub mod dummy_bcdd;
 pub mod dummy_bdd;
 pub mod dummy_mtbdd;
+pub mod formula;
 pub mod free_id_manager;
+pub mod level_swap;
 pub mod logging;
 pub mod matrix4;
 pub mod panic_hook;
+pub mod parse_error;
 pub mod point;
+pub mod random;
 pub mod rc_refcell;
 pub mod rectangle;
 pub mod transformation;
 pub mod transition;
+pub mod vis_error;
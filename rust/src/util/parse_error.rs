@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+/// An error encountered while reading diagram data, traces or states, see `VisError` for the errors of other operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A required section (e.g. `.nodes` in a dddmp file) could not be found
+    MissingSection { section: String },
+    /// The contents at the given location (1-based) are malformed
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The data refers to something that does not exist, such as an undefined node
+    Reference { line: usize, message: String },
+}
+
+impl ParseError {
+    pub fn missing_section(section: &str) -> ParseError {
+        ParseError::MissingSection {
+            section: section.to_string(),
+        }
+    }
+
+    /// Creates a syntax error for the given byte offset in the text
+    pub fn at_offset(
+        text: impl AsRef<[u8]>,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> ParseError {
+        let (line, column) = get_position(text, offset);
+        ParseError::Syntax {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError::Syntax {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+/// Retrieves the line and column (both 1-based) of the given byte offset in the text
+pub fn get_position(text: impl AsRef<[u8]>, offset: usize) -> (usize, usize) {
+    let text = text.as_ref();
+    let before = &text[..offset.min(text.len())];
+    let line = before.iter().filter(|&&c| c == b'\n').count() + 1;
+    let line_start = before
+        .iter()
+        .rposition(|&c| c == b'\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    (line, before.len() - line_start + 1)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingSection { section } => write!(f, "missing section {}", section),
+            ParseError::Syntax {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            ParseError::Reference { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
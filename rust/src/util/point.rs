@@ -4,7 +4,9 @@ use std::{
     ops::{Add, Mul, Sub},
 };
 
-#[derive(Copy, Clone, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -0,0 +1,20 @@
+use seeded_random::{Random, Seed};
+
+/// A generator of random numbers that is seeded explicitly, such that every layout computed from the same seed is identical, both in the browser and in native builds
+pub struct SeededRandom(Random);
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        SeededRandom(Random::from_seed(Seed::unsafe_new(seed)))
+    }
+
+    /// Generates a number in the range [start, end)
+    pub fn range(&mut self, start: u32, end: u32) -> u32 {
+        self.0.range(start, end)
+    }
+
+    /// Generates a number in the range [0, 1)
+    pub fn random(&mut self) -> f64 {
+        self.range(0, u32::MAX) as f64 / u32::MAX as f64
+    }
+}
@@ -16,12 +16,44 @@ where
     }
 }
 
-#[derive(Copy, Clone)]
+/// The curve that transitions follow from their old to their new value
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Easing {
+    Linear,
+    /// Starts and ends slowly, following the smoothstep curve
+    EaseInOut,
+    /// Starts quickly and slows down towards the end, following a cubic curve
+    Cubic,
+}
+impl Easing {
+    /// The id of the easing in the `ease` function of the shaders, which interpolate the transitions
+    pub fn get_id(&self) -> i32 {
+        match self {
+            Easing::Linear => 0,
+            Easing::EaseInOut => 1,
+            Easing::Cubic => 2,
+        }
+    }
+    /// Maps the linear progress of a transition onto this curve, matching the `ease` function of the shaders
+    pub fn apply(&self, per: f32) -> f32 {
+        match self {
+            Easing::Linear => per,
+            Easing::EaseInOut => per * per * (3.0 - 2.0 * per),
+            Easing::Cubic => {
+                let remaining = 1.0 - per;
+                1.0 - remaining * remaining * remaining
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Transition<T: Interpolatable> {
     pub old_time: u32, // ms
     pub duration: u32, // ms
     pub old: T,
     pub new: T,
+    pub easing: Easing,
 }
 impl<T: Interpolatable + Clone> Transition<T> {
     pub fn get(&self, time: u32) -> T {
@@ -32,7 +64,7 @@ impl<T: Interpolatable + Clone> Transition<T> {
 impl<T: Interpolatable + Clone> Transition<T> {
     pub fn get_per(&self, time: u32) -> f32 {
         let per = (time as f32 - self.old_time as f32) / self.duration as f32;
-        f32::max(0.0, f32::min(per, 1.0))
+        self.easing.apply(f32::max(0.0, f32::min(per, 1.0)))
     }
 }
 impl<T: Interpolatable + Clone> Transition<T> {
@@ -42,6 +74,7 @@ impl<T: Interpolatable + Clone> Transition<T> {
             new: val,
             old_time: 0,
             duration: 0,
+            easing: Easing::Linear,
         }
     }
 }
@@ -61,6 +94,7 @@ where
             duration,
             old: self.get(old_time) + rhs.get(old_time),
             new: self.get(old_time + duration) + rhs.get(old_time + duration),
+            easing: self.easing,
         }
     }
 }
@@ -98,6 +132,52 @@ where
             duration: self.duration,
             old: &self.old * rhs.clone(),
             new: &self.new * rhs,
+            easing: self.easing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(easing: Easing) -> Transition<f32> {
+        Transition {
+            old_time: 100,
+            duration: 100,
+            old: 0.0,
+            new: 10.0,
+            easing,
+        }
+    }
+
+    #[test]
+    fn linear_progress() {
+        let t = transition(Easing::Linear);
+        assert_eq!(t.get(150), 5.0);
+        assert_eq!(t.get(125), 2.5);
+    }
+
+    #[test]
+    fn eased_progress() {
+        let ease_in_out = transition(Easing::EaseInOut);
+        assert_eq!(ease_in_out.get(150), 5.0);
+        assert!(ease_in_out.get(125) < 2.5);
+        assert!(ease_in_out.get(175) > 7.5);
+
+        let cubic = transition(Easing::Cubic);
+        assert!(cubic.get(125) > 2.5);
+        assert!(cubic.get(150) > 5.0);
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        for easing in [Easing::Linear, Easing::EaseInOut, Easing::Cubic] {
+            let t = transition(easing);
+            assert_eq!(t.get(0), 0.0);
+            assert_eq!(t.get(100), 0.0);
+            assert_eq!(t.get(200), 10.0);
+            assert_eq!(t.get(500), 10.0);
         }
     }
 }
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use wasm_bindgen::JsValue;
+
+use super::parse_error::ParseError;
+
+/// An error encountered while loading, manipulating or restoring a visualization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VisError {
+    /// The diagram data, a trace or a state could not be read
+    Parse(ParseError),
+    /// The operation is not supported for this kind of diagram
+    Unsupported { message: String },
+    /// The given node is not part of any section of the diagram
+    UnknownNode { node: usize },
+    /// An assignment refers to variables that the diagram does not have
+    UnknownVariables { names: Vec<String> },
+    /// An assignment does not assign the given variable, which its path depends on
+    UnassignedVariable { name: String },
+    /// A stored visualization state is malformed, or was written by a newer version
+    InvalidState { message: String },
+}
+
+impl VisError {
+    pub fn unsupported(message: impl Into<String>) -> VisError {
+        VisError::Unsupported {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_state(message: impl Into<String>) -> VisError {
+        VisError::InvalidState {
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ParseError> for VisError {
+    fn from(error: ParseError) -> Self {
+        VisError::Parse(error)
+    }
+}
+
+impl Display for VisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VisError::Parse(error) => write!(f, "{}", error),
+            VisError::Unsupported { message } => write!(f, "{}", message),
+            VisError::UnknownNode { node } => write!(f, "unknown node {}", node),
+            VisError::UnknownVariables { names } => write!(
+                f,
+                "The diagram has no variable named {}",
+                names.iter().join(", ")
+            ),
+            VisError::UnassignedVariable { name } => write!(
+                f,
+                "No value is assigned to {}, which the path depends on",
+                name
+            ),
+            VisError::InvalidState { message } => write!(f, "invalid state: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for VisError {}
+
+impl From<VisError> for JsValue {
+    fn from(error: VisError) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
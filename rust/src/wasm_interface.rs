@@ -2,13 +2,18 @@ use std::rc::Rc;
 
 use crate::{
     configuration::configuration_object::AbstractConfigurationObject,
-    types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
-    util::rectangle::Rectangle,
+    types::util::{
+        graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
+        storage::state_model::VisualizationState,
+    },
+    util::{rectangle::Rectangle, vis_error::VisError},
 };
 
 use super::traits::{Diagram, DiagramSection, DiagramSectionDrawer};
 use itertools::Itertools;
+use oxidd::LevelNo;
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "web")]
 use web_sys::HtmlCanvasElement;
 
 #[wasm_bindgen]
@@ -22,24 +27,36 @@ impl DiagramBox {
 // Mirror Diagram trait in terms of interface, but using non-dynamic structs
 #[wasm_bindgen()]
 impl DiagramBox {
-    pub fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(self.0.create_section_from_dddmp(dddmp)?))
+    pub fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<DiagramSectionBox, VisError> {
+        Ok(DiagramSectionBox(self.0.create_section_from_dddmp(dddmp)?))
     }
     pub fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(
+    ) -> Result<DiagramSectionBox, VisError> {
+        Ok(DiagramSectionBox(
             self.0.create_section_from_other(data, vars)?,
         ))
     }
+    pub fn create_section_from_formula(
+        &mut self,
+        formula: String,
+        var_order: Option<String>,
+    ) -> Result<DiagramSectionBox, VisError> {
+        Ok(DiagramSectionBox(
+            self.0.create_section_from_formula(formula, var_order)?,
+        ))
+    }
     pub fn create_section_from_ids(
         &self,
         ids: &[NodeID],
         section: &DiagramSectionBox,
-    ) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(self.0.create_section_from_ids(
+    ) -> Result<DiagramSectionBox, VisError> {
+        Ok(DiagramSectionBox(self.0.create_section_from_ids(
             &ids.iter().map(|&id| (id, &section.0)).collect_vec(),
         )?))
     }
@@ -54,6 +71,7 @@ impl DiagramSectionBox {
     }
 }
 // Mirror Diagram trait in terms of interface, but using non-dynamic structs
+#[cfg(feature = "web")]
 #[wasm_bindgen]
 impl DiagramSectionBox {
     pub fn create_drawer(&self, canvas: HtmlCanvasElement) -> DiagramSectionDrawerBox {
@@ -61,6 +79,13 @@ impl DiagramSectionBox {
     }
 }
 #[wasm_bindgen]
+impl DiagramSectionBox {
+    /// Loads a trace of the computation that created this section, which drawers created afterwards can step through
+    pub fn load_step_trace(&mut self, trace: String) -> Result<(), VisError> {
+        self.0.load_step_trace(trace)
+    }
+}
+#[wasm_bindgen]
 pub struct DiagramSectionDrawerBox(Box<dyn DiagramSectionDrawer>);
 #[wasm_bindgen]
 impl DiagramSectionDrawerBox {
@@ -89,6 +114,9 @@ impl DiagramSectionDrawerBox {
     pub fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
         self.0.split_edges(nodes, fully);
     }
+    pub fn focus_nodes(&mut self, nodes: &[NodeID]) {
+        self.0.focus_nodes(nodes);
+    }
 
     /** Node interaction */
     /// Coordinates in screen space (-0.5 to 0.5), not in world space. Additionally the max_group_expansion should be provided for determining the maximum number of nodes to select for every given group
@@ -115,12 +143,30 @@ impl DiagramSectionDrawerBox {
         self.0.source_nodes_to_local(nodes)
     }
 
+    /** Level interaction */
+    /// Coordinates in screen space (-0.5 to 0.5), like for `get_nodes`
+    pub fn get_level(&self, y: f32) -> Option<LevelNo> {
+        self.0.get_level(y)
+    }
+    /// Moves the given level to the position of the target level, rebuilding the diagram under the resulting variable order
+    pub fn move_level(&mut self, level: LevelNo, to: LevelNo) -> Result<(), VisError> {
+        self.0.move_level(level, to)
+    }
+
     /** Storage */
+    /// Encodes the state of the visualization compactly, for persisting it in the browser
     pub fn serialize_state(&self) -> Vec<u8> {
-        self.0.serialize_state()
+        self.0.get_state().to_bytes()
+    }
+    pub fn deserialize_state(&mut self, state: Vec<u8>) -> Result<(), VisError> {
+        self.0.set_state(VisualizationState::from_bytes(&state)?)
     }
-    pub fn deserialize_state(&mut self, state: Vec<u8>) {
-        self.0.deserialize_state(state)
+    /// Encodes the state of the visualization as human-readable JSON, which can be diffed, edited by hand and stored next to the diagram files
+    pub fn serialize_state_json(&self) -> String {
+        self.0.get_state().to_json()
+    }
+    pub fn deserialize_state_json(&mut self, json: String) -> Result<(), VisError> {
+        self.0.set_state(VisualizationState::from_json(&json)?)
     }
 
     /** Settings */
@@ -150,6 +196,17 @@ pub struct StepGroup {
     parent: Option<Rc<StepGroup>>,
 }
 
+impl StepGroup {
+    pub fn new(start: i32, end: i32, description: String, parent: Option<StepGroup>) -> StepGroup {
+        StepGroup {
+            start,
+            end,
+            description,
+            parent: parent.map(Rc::new),
+        }
+    }
+}
+
 #[wasm_bindgen]
 impl StepGroup {
     pub fn get_parent(&self) -> Option<StepGroup> {
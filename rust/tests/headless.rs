@@ -0,0 +1,98 @@
+//! Tests of the graph pipeline, layouts and LaTeX export outside of the browser, run using `cargo test --no-default-features`
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use oxidd::{
+    bdd::BDDFunction, mtbdd::MTBDDFunction, util::AllocResult, BooleanFunction, ManagerRef,
+    PseudoBooleanFunction,
+};
+use oxidd_rules_mtbdd::terminal::Int64;
+use oxidd_vis_rust::{
+    convert, Diagram, DiagramFormat, DiagramSectionDrawer, LayoutMetrics, MTBDDDiagram, QDDDiagram,
+};
+
+#[test]
+fn formula_to_latex() {
+    let output = convert(
+        "f = (a & b) | !c",
+        None,
+        DiagramFormat::Formula,
+        DiagramFormat::Latex,
+    )
+    .unwrap();
+    assert!(output.data.contains("\\begin{tikzpicture}"));
+    assert!(output.data.contains("\\end{tikzpicture}"));
+}
+
+#[test]
+fn dddmp_round_trip() {
+    let dddmp = convert(
+        "f = a ^ b; g = a -> b",
+        Some("b a"),
+        DiagramFormat::Formula,
+        DiagramFormat::Dddmp,
+    )
+    .unwrap();
+    let copy = convert(
+        &dddmp.data,
+        None,
+        DiagramFormat::Dddmp,
+        DiagramFormat::Dddmp,
+    )
+    .unwrap();
+    assert_eq!(dddmp.data, copy.data);
+}
+
+#[test]
+fn layout_metrics_are_reproducible() {
+    let metrics = || -> LayoutMetrics {
+        let section = QDDDiagram::new()
+            .create_section_from_formula("f = (a & b) | (c ^ d); g = a -> d".into(), None)
+            .unwrap();
+        let mut drawer = section.create_latex_drawer();
+        drawer.layout(0);
+        drawer.get_layout_metrics()
+    };
+    let (first, second) = (metrics(), metrics());
+    assert!(first.width > 0.0);
+    assert!(first.edge_length > 0.0);
+    assert_eq!(first.get_total_crossings(), second.get_total_crossings());
+    assert_eq!(first.bends, second.bends);
+    assert_eq!(first.overlaps, second.overlaps);
+}
+
+#[test]
+fn qdd_of_real_manager_to_latex() {
+    let manager_ref = oxidd::bdd::new_manager(1024, 1024, 1);
+    let f = manager_ref
+        .with_manager_exclusive(|manager| -> AllocResult<BDDFunction> {
+            let a = BDDFunction::new_var(manager)?;
+            let b = BDDFunction::new_var(manager)?;
+            a.and(&b)
+        })
+        .unwrap();
+    let section = QDDDiagram::from_manager(manager_ref).create_section_from_functions(
+        vec![(f, vec!["f".to_string()])],
+        vec!["a".to_string(), "b".to_string()],
+    );
+    let output = section.create_latex_drawer().get_output();
+    assert!(output.contains("\\begin{tikzpicture}"));
+}
+
+#[test]
+fn mtbdd_of_real_manager_to_latex() {
+    let manager_ref = oxidd::mtbdd::new_manager::<Int64>(1024, 16, 1024, 1);
+    let f = manager_ref
+        .with_manager_exclusive(|manager| -> AllocResult<MTBDDFunction<Int64>> {
+            let a = MTBDDFunction::new_var(manager)?;
+            let b = MTBDDFunction::new_var(manager)?;
+            a.add(&b)
+        })
+        .unwrap();
+    let section = MTBDDDiagram::from_manager(manager_ref).create_section_from_functions(
+        vec![(f, vec!["f".to_string()])],
+        vec!["a".to_string(), "b".to_string()],
+    );
+    let output = section.create_latex_drawer().get_output();
+    assert!(output.contains("\\begin{tikzpicture}"));
+}
@@ -0,0 +1,27 @@
+import React, {FC, useCallback, useEffect, useState} from "react";
+import {TextField} from "@fluentui/react";
+import {TextConfig} from "../../../state/configuration/types/TextConfig";
+import {useWatch} from "../../../watchables/react/useWatch";
+
+export const TextConfigComp: FC<{value: TextConfig}> = ({value}) => {
+    const watch = useWatch();
+    const text = watch(value);
+
+    // Only commit the text once editing finishes, since every commit may restyle the diagram
+    const [input, setInput] = useState(text);
+    useEffect(() => setInput(text), [text]);
+    const onChange = useCallback((e: unknown, v?: string) => setInput(v ?? ""), []);
+    const onBlur = useCallback(() => {
+        if (input != value.get()) value.set(input).commit();
+    }, [value, input]);
+
+    return (
+        <TextField
+            value={input}
+            multiline={watch(value.multiline)}
+            rows={5}
+            onChange={onChange}
+            onBlur={onBlur}
+        />
+    );
+};
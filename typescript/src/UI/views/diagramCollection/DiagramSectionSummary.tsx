@@ -16,6 +16,7 @@ export const DiagramSectionSummary: FC<{
     const theme = useTheme();
     const watch = useWatch();
     const visualization = watch(section.visualization);
+    const error = watch(section.error);
     const viewManager = useViewManager();
     const ref = useDragStart((position, offset) => {
         if (visualization) {
@@ -57,6 +58,18 @@ export const DiagramSectionSummary: FC<{
                     </Stack.Item>
                 )}
             </TitleBar>
+            {error && (
+                <div
+                    className={css({
+                        padding: theme.spacing.s1,
+                        paddingTop: 0,
+                        color: theme.semanticColors.errorText,
+                        whiteSpace: "pre-wrap",
+                        userSelect: "text",
+                    })}>
+                    {error}
+                </div>
+            )}
         </div>
     );
 };
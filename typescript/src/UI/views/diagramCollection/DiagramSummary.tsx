@@ -18,7 +18,11 @@ import {FileSource} from "../../../state/diagrams/sources/FileSource";
 import {BuddySelectionModal} from "./modals/BuddySelectionModal";
 import {mtbddDddmpSample} from "./samples/mtbddDddmpSample";
 import {bddDddmpSample} from "./samples/bddDddmpSample";
+import {bcddDddmpSample} from "./samples/bcddDddmpSample";
+import {zbddDddmpSample} from "./samples/zbddDddmpSample";
 import {bddBuddySample} from "./samples/bddBuddySample";
+import {FormulaInputModal} from "./modals/FormulaInputModal";
+import {bddFormulaSample} from "./samples/bddFormulaSample";
 
 export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> = ({
     diagram,
@@ -57,6 +61,21 @@ export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> =
         [diagram]
     );
 
+    const [showFormulaInputModal, setShowFormulaInputModal] = useState(false);
+    const startCreatingFormulaSection = useCallback(() => {
+        setShowFormulaInputModal(true);
+    }, []);
+    const stopCreatingFormulaSection = useCallback(() => {
+        setShowFormulaInputModal(false);
+    }, []);
+    const createFormulaSection = useCallback(
+        (input: string, varOrder?: string) => {
+            setShowFormulaInputModal(false);
+            diagram.createSectionFromFormula(input, varOrder).commit();
+        },
+        [diagram]
+    );
+
     const watchableCanCreateFromFile = usePersistentMemo(
         () =>
             new Derived(
@@ -130,7 +149,9 @@ export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> =
                         Load from dddump
                     </AddSectionButton>
 
-                    {diagram.type == "MTBDD" ? undefined : (
+                    {diagram.type == "MTBDD" ||
+                    diagram.type == "BCDD" ||
+                    diagram.type == "ZBDD" ? undefined : (
                         <AddSectionButton
                             onClick={startCreatingBuddySection}
                             hover={
@@ -148,6 +169,26 @@ export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> =
                             Load from Buddy
                         </AddSectionButton>
                     )}
+                    {diagram.type == "MTBDD" ||
+                    diagram.type == "BCDD" ||
+                    diagram.type == "ZBDD" ? undefined : (
+                        <AddSectionButton
+                            onClick={startCreatingFormulaSection}
+                            hover={
+                                <>
+                                    Create a diagram from propositional formulas
+                                    {!canCreateFromFile && (
+                                        <>
+                                            <br /> Only one file per diagram is supported
+                                            right now
+                                        </>
+                                    )}
+                                </>
+                            }
+                            disabled={!canCreateFromFile}>
+                            Enter formula
+                        </AddSectionButton>
+                    )}
                     <AddSectionButton
                         onClick={createSelectionSection}
                         hover={
@@ -170,7 +211,15 @@ export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> =
                 visible={showDDDMPInputModal}
                 onCancel={stopCreatingDDDMPSection}
                 onSelect={createDDDMPSection}
-                example={diagram.type == "MTBDD" ? mtbddDddmpSample : bddDddmpSample}
+                example={
+                    diagram.type == "MTBDD"
+                        ? mtbddDddmpSample
+                        : diagram.type == "BCDD"
+                        ? bcddDddmpSample
+                        : diagram.type == "ZBDD"
+                        ? zbddDddmpSample
+                        : bddDddmpSample
+                }
             />
             <BuddySelectionModal
                 visible={showBuddyInputModal}
@@ -178,6 +227,12 @@ export const DiagramSummary: FC<{diagram: DiagramState; onDelete: () => void}> =
                 onCancel={stopCreatingBuddySection}
                 onSelect={createBuddySection}
             />
+            <FormulaInputModal
+                visible={showFormulaInputModal}
+                example={bddFormulaSample}
+                onCancel={stopCreatingFormulaSection}
+                onSelect={createFormulaSection}
+            />
         </div>
     );
 };
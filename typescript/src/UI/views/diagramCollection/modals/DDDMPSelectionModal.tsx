@@ -33,7 +33,8 @@ export const DDDMPSelectionModal: FC<{
         setSelected("file");
 
         const reader = new FileReader();
-        reader.readAsText(file);
+        // Binary dddmp files are passed on as strings with one character per byte
+        reader.readAsBinaryString(file);
         reader.onload = () => {
             const result = reader.result;
             setFileLoading(false);
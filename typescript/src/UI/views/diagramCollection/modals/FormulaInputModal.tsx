@@ -0,0 +1,74 @@
+import React, {FC, useCallback, useEffect, useRef, useState} from "react";
+import {StyledModal} from "../../../components/StyledModal";
+import {ITextField, PrimaryButton, TextField} from "@fluentui/react";
+import {css} from "@emotion/css";
+import {InputOption} from "./DDDMPSelectionModal";
+
+export const FormulaInputModal: FC<{
+    visible: boolean;
+    example: [string, string];
+    onSelect: (text: string, varOrder?: string) => void;
+    onCancel: () => void;
+}> = ({visible, example: [sample, sampleOrder], onSelect, onCancel}) => {
+    const textRef = useRef<ITextField>(null);
+    const orderTextRef = useRef<ITextField>(null);
+    const [selected, setSelected] = useState<"text" | "sample">("sample");
+    const selectText = useCallback(() => setSelected("text"), []);
+
+    const onSubmit = useCallback(() => {
+        if (selected == "sample") onSelect(sample, sampleOrder);
+        else {
+            const field = textRef.current;
+            const orderField = orderTextRef.current;
+            if (field?.value)
+                onSelect(field.value, orderField?.value?.trim() || undefined);
+        }
+    }, [selected, onSelect, sample, sampleOrder]);
+
+    useEffect(() => {
+        if (!visible) {
+            setTimeout(() => setSelected("sample"), 500);
+        }
+    }, [visible]);
+
+    return (
+        <StyledModal title="Enter formulas" isOpen={visible} onDismiss={onCancel}>
+            <div className={css({minWidth: 500})}>
+                <InputOption
+                    name="Text contents"
+                    selected={selected == "text"}
+                    onSelect={selectText}>
+                    <TextField
+                        onChange={selectText}
+                        multiline
+                        placeholder="f = (a & b) | !c; g = a -> c"
+                        rows={selected == "text" ? 5 : 2}
+                        componentRef={textRef}
+                    />
+                    <TextField
+                        onChange={selectText}
+                        label="optional variable order"
+                        componentRef={orderTextRef}
+                    />
+                </InputOption>
+                <InputOption
+                    name="Load example"
+                    selected={selected == "sample"}
+                    onSelect={() => setSelected("sample")}>
+                    <TextField
+                        readOnly
+                        multiline
+                        rows={selected == "sample" ? 5 : 2}
+                        defaultValue={sample}
+                    />
+                    <TextField
+                        readOnly
+                        label="optional variable order"
+                        defaultValue={sampleOrder}
+                    />
+                </InputOption>
+            </div>
+            <PrimaryButton onClick={onSubmit}>Load</PrimaryButton>
+        </StyledModal>
+    );
+};
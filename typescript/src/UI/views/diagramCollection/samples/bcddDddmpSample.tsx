@@ -0,0 +1,21 @@
+export const bcddDddmpSample = `.ver DDDMP-2.0
+.mode A
+.varinfo 4
+.dd test
+.nnodes 4
+.nvars 2
+.nsuppvars 2
+.suppvarnames x1 x2
+.orderedvarnames x1 x2
+.ids 0 1
+.permids 0 1
+.nroots 2
+.rootids -3 -4
+.rootnames xor nand
+.nodes
+1 T 0 0
+2 1 1 -1
+3 0 2 -2
+4 0 2 -1
+.end
+`;
@@ -0,0 +1,6 @@
+export const bddFormulaSample = [
+    `carry = (a & b) | (c & (a ^ b));
+sum = a ^ b ^ c;
+valid = (a -> b) <-> !c`,
+    `a b c`,
+] as [string, string];
@@ -0,0 +1,23 @@
+export const zbddDddmpSample = `.ver DDDMP-2.0
+.mode A
+.varinfo 4
+.dd family
+.nnodes 6
+.nvars 4
+.nsuppvars 4
+.suppvarnames a b c d
+.orderedvarnames a b c d
+.ids 0 1 2 3
+.permids 0 1 2 3
+.nroots 1
+.rootids 6
+.rootnames family
+.nodes
+1 F 0 0
+2 T 0 0
+3 3 2 1
+4 2 2 1
+5 1 2 3
+6 0 4 5
+.end
+`;
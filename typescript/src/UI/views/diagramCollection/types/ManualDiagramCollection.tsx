@@ -55,6 +55,12 @@ export const ManualDiagramCollection: FC<{
                 <AddDiagramButton onClick={() => collection.addDiagram("QDD").commit()}>
                     Add local BDD
                 </AddDiagramButton>
+                <AddDiagramButton onClick={() => collection.addDiagram("BCDD").commit()}>
+                    Add local BCDD
+                </AddDiagramButton>
+                <AddDiagramButton onClick={() => collection.addDiagram("ZBDD").commit()}>
+                    Add local ZBDD
+                </AddDiagramButton>
                 <AddDiagramButton onClick={() => collection.addDiagram("MTBDD").commit()}>
                     Add local MTBDD
                 </AddDiagramButton>
@@ -10,6 +10,7 @@ import {ActionButton, PrimaryButton, useTheme} from "@fluentui/react";
 import {Toolbar} from "../toolbar/Toolbar";
 import {PresenceRemainder} from "oxidd-vis-rust";
 import {ConfigTypeComp} from "../../components/configuration/ConfigTypeComp";
+import {useLevelDrag} from "./useLevelDrag";
 
 export const DiagramVisualization: FC<{visualization: DiagramVisualizationState}> = ({
     visualization,
@@ -56,6 +57,7 @@ export const DiagramVisualization: FC<{visualization: DiagramVisualizationState}
         e.stopPropagation();
     }, []);
     const moveListeners = useTransformCallbacks(visualization.transform);
+    const levelDrag = useLevelDrag(visualization);
     return (
         <ViewContainer
             onContextMenu={e => e.preventDefault()}
@@ -63,7 +65,7 @@ export const DiagramVisualization: FC<{visualization: DiagramVisualizationState}
             {...moveListeners}
             css={{padding: 0, overflow: "hidden", backgroundColor: "white"}}>
             <BoxSelection
-                onStart={m => m.buttons == 1}
+                onStart={m => m.buttons == 1 && !levelDrag.onStart(m)}
                 onHighlight={(rect, e) => {
                     const nodes = visualization.getNodes(rect);
                     visualization.applyTool(toolbar, nodes, {
@@ -89,6 +91,7 @@ export const DiagramVisualization: FC<{visualization: DiagramVisualizationState}
                     <Toolbar toolbar={toolbar} visualization={visualization} />
                 </div>
                 <ConfigTypeComp value={watch(visualization.config)} />
+                {levelDrag.indicator}
             </BoxSelection>
         </ViewContainer>
     );
@@ -0,0 +1,62 @@
+import React, {ReactNode, useCallback, useState} from "react";
+import {css} from "@emotion/css";
+import {useTheme} from "@fluentui/react";
+import {DiagramVisualizationState} from "../../../state/diagrams/DiagramVisualizationState";
+
+/** The width of the area on the left of the visualization in which the level labels are shown */
+const levelLabelWidth = 80;
+
+/**
+ * Allows the level labels of the visualization to be dragged to another level, which reorders the variables of the diagram
+ * @param visualization The visualization whose levels can be dragged
+ * @returns The handler to start a drag, which returns whether a level drag was started, and the indicator of the drop position
+ */
+export function useLevelDrag(visualization: DiagramVisualizationState): {
+    onStart: (event: React.MouseEvent<HTMLElement, MouseEvent>) => boolean;
+    indicator: ReactNode;
+} {
+    const theme = useTheme();
+    const [target, setTarget] = useState<number | null>(null);
+    const onStart = useCallback(
+        (event: React.MouseEvent<HTMLElement, MouseEvent>) => {
+            const frame = visualization.canvas.getBoundingClientRect();
+            const x = event.clientX - frame.left;
+            const y = event.clientY - frame.top;
+            if (x > levelLabelWidth) return false;
+            const level = visualization.getLevel(y);
+            if (level == undefined) return false;
+
+            event.preventDefault();
+            setTarget(y);
+            const moveListener = (e: MouseEvent) => {
+                setTarget(e.clientY - frame.top);
+            };
+            const upListener = (e: MouseEvent) => {
+                const to = visualization.getLevel(e.clientY - frame.top);
+                if (to != undefined) visualization.moveLevel(level, to);
+                setTarget(null);
+                window.removeEventListener("mousemove", moveListener);
+                window.removeEventListener("mouseup", upListener);
+            };
+            window.addEventListener("mousemove", moveListener);
+            window.addEventListener("mouseup", upListener);
+            return true;
+        },
+        [visualization]
+    );
+
+    const indicator = target != null && (
+        <div
+            className={css({
+                position: "absolute",
+                left: 0,
+                right: 0,
+                height: 2,
+                backgroundColor: theme.palette.themePrimary,
+                pointerEvents: "none",
+            })}
+            style={{top: target - 1}}
+        />
+    );
+    return {onStart, indicator};
+}
@@ -48,11 +48,13 @@ export const Info: FC<{app: AppState}> = ({app}) => {
             <h3>Local</h3>
             <p>
                 To add a local diagram, simply open the{" "}
-                {link("diagrams panel", app.diagrams)} and click "Add local BDD" or "Add
-                local MTBDD". This should add a shared diagram, which you can now add
-                content into using either "Load from dddump" or 'Load from Buddy'. Here
+                {link("diagrams panel", app.diagrams)} and click "Add local BDD", "Add local
+                BCDD", "Add local ZBDD" or "Add local MTBDD". This should add a shared
+                diagram, which you can now add content into using either "Load from
+                dddump" or 'Load from Buddy'. Here
                 you can either select a file or supply text contents, and load the
-                diagrams. After the diagrams finished loading, a new section should appear
+                diagrams. BDDs can also be built from propositional formulas such as{" "}
+                <code>f = (a &amp; b) | !c; g = a -&gt; c</code> using "Enter formula". After the diagrams finished loading, a new section should appear
                 in the diagram. Clicking this section opens the visualization. Any diagram
                 allows new sections to be created by selecting a node in the visualization
                 and clicking the "Create from selection" button.
@@ -75,7 +77,7 @@ export const Info: FC<{app: AppState}> = ({app}) => {
                 <code>/diagrams</code> path, which provides a JSON response of the
                 following format:
                 <SyntaxHighlighter language="javascript" style={vs2015}>
-                    {`{\n\tname: string;\n\ttype: "BDD"|"MTBDD";\n\tdiagram: string;\n}[]`}
+                    {`{\n\tname: string;\n\ttype: "BDD"|"BCDD"|"ZBDD"|"MTBDD";\n\tdiagram: string;\n}[]`}
                 </SyntaxHighlighter>
                 The diagram should be the contents of a valid DDDMP file with the given
                 type. When no new diagrams have been created, this request should return a
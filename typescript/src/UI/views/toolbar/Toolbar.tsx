@@ -32,6 +32,12 @@ export const Toolbar: FC<{
                 title="Expand children of nodes"
                 onRenderItemLink={TooltipPivot}
             />
+            <PivotItem
+                itemIcon="BullseyeTarget"
+                itemKey="focus"
+                title="Focus on the neighbourhood of nodes"
+                onRenderItemLink={TooltipPivot}
+            />
             <PivotItem
                 itemIcon="Combine"
                 itemKey="grouping"
@@ -8,6 +8,7 @@ import {LabelConfig} from "../types/LabelConfig";
 import {LocationConfig} from "../types/LocationConfig";
 import {PanelConfig} from "../types/PanelConfig";
 import {TextOutputConfig} from "../types/TextOutputConfig";
+import {TextConfig} from "../types/TextConfig";
 
 export type IConfigObjectType =
     | IntConfig
@@ -17,6 +18,7 @@ export type IConfigObjectType =
     | CompositeConfig
     | ButtonConfig
     | TextOutputConfig
+    | TextConfig
     | PanelConfig
     | LocationConfig
     | ContainerConfig;
@@ -12,6 +12,7 @@ import {IOwnedAbstractConfig} from "./ConfigurationObject";
 import {LocationConfig} from "./types/LocationConfig";
 import {FloatConfig} from "./types/FloatConfig";
 import {ContainerConfig} from "./types/ContainerConfig";
+import {TextConfig} from "./types/TextConfig";
 
 /**
  * Creates the configuration object wrapper from the given abstract configuration object
@@ -42,6 +43,8 @@ export function getConfigurationObjectWrapper(
         return new LocationConfig(ownedConfig);
     } else if (type === ConfigurationObjectType.Container) {
         return new ContainerConfig(ownedConfig);
+    } else if (type === ConfigurationObjectType.Text) {
+        return new TextConfig(ownedConfig);
     }
 
     return null as never;
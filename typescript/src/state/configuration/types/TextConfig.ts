@@ -0,0 +1,59 @@
+import {IWatchable} from "../../../watchables/_types/IWatchable";
+import {ConfigurationObject, IOwnedAbstractConfig} from "../ConfigurationObject";
+import {Derived} from "../../../watchables/Derived";
+import {IMutator} from "../../../watchables/mutator/_types/IMutator";
+import {chain} from "../../../watchables/mutator/chain";
+import {IRunnable} from "../../../watchables/_types/IRunnable";
+
+/**
+ * A configuration object for text inputs
+ */
+export class TextConfig
+    extends ConfigurationObject<{
+        value: string;
+        multiline: boolean;
+    }>
+    implements IWatchable<string>
+{
+    /** The currently stored text */
+    public readonly value = new Derived(watch => watch(this._value).value);
+    /** Whether the text may span multiple lines */
+    public readonly multiline = new Derived(watch => watch(this._value).multiline);
+
+    /**
+     * Creates a new text config object
+     * @param object The rust configuration that represents a text input
+     */
+    public constructor(object: IOwnedAbstractConfig) {
+        super(object);
+    }
+
+    /**
+     * Sets the new text to store
+     * @param value The text to store
+     * @returns The mutator to commit the change
+     */
+    public set(value: string): IMutator {
+        return chain(push => {
+            push(
+                this.setValue({
+                    value,
+                    multiline: this.multiline.get(),
+                })
+            );
+        });
+    }
+
+    /** @override */
+    public get(): string {
+        return this.value.get();
+    }
+    /** @override */
+    public onDirty(listener: IRunnable): IRunnable {
+        return this.value.onDirty(listener);
+    }
+    /** @override */
+    public onChange(listener: IRunnable): IRunnable {
+        return this.value.onChange(listener);
+    }
+}
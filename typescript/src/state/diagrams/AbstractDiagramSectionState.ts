@@ -7,12 +7,14 @@ import {v4 as uuid} from "uuid";
 import {Derived} from "../../watchables/Derived";
 import {ISharedVisualizationState} from "./_types/ISharedVisualizationState";
 import {DiagramState} from "./DiagramState";
+import {ISectionError} from "./_types/ISectionError";
 
 export abstract class AbstractDiagramSectionState<T> implements IDiagramSection<T> {
     protected readonly diagram: DiagramState;
 
     public readonly ID = uuid();
     public readonly source: IWatchable<DiagramSectionBox | null>;
+    public readonly error: IWatchable<string | null>;
 
     protected sourceInitialized = false;
     protected visualizationInitialized = false;
@@ -35,18 +37,23 @@ export abstract class AbstractDiagramSectionState<T> implements IDiagramSection<
     /**
      * Creates a new abstract diagram section
      * @param diagram The diagram this section is for
-     * @param source The source to use for this diagram, note that data freeing is taken care of by this class, and doesn't have to be done by the source
+     * @param source The source to use for this diagram, or the error that prevented it from being created, note that data freeing is taken care of by this class, and doesn't have to be done by the source
      */
     public constructor(
         diagram: DiagramState,
-        source: IWatchable<DiagramSectionBox | undefined>
+        source: IWatchable<DiagramSectionBox | ISectionError | undefined>
     ) {
         this.diagram = diagram;
         this.source = new Derived((watch, prev) => {
             const sourceVal = watch(source);
             if (prev) prev.free();
             this.sourceInitialized = true;
-            return sourceVal ?? null;
+            if (!sourceVal || "error" in sourceVal) return null;
+            return sourceVal;
+        });
+        this.error = new Derived(watch => {
+            const sourceVal = watch(source);
+            return sourceVal && "error" in sourceVal ? sourceVal.error : null;
         });
     }
 
@@ -112,6 +112,32 @@ export class DiagramState extends ViewState {
         });
     }
 
+    /**
+     * Creates a new section for this diagram, based on the given propositional formulas
+     * @param text The `;` separated formulas, optionally named as `name = formula`
+     * @param varOrder The optional whitespace separated order of the variables, from the top level down
+     * @param name The name of the section
+     * @returns The mutator to commit the change, resulting in the created section
+     */
+    public createSectionFromFormula(
+        text: string,
+        varOrder?: string,
+        name?: string
+    ): IMutator<FileSource> {
+        return chain(push => {
+            const section = new FileSource(this, this.diagram, {formula: {text, varOrder}});
+            push(this._sections.set([...this._sections.get(), section]));
+            if (name)
+                try {
+                    const vis = section.visualization.get();
+                    if (vis) push(vis.name.set(name));
+                } catch (e) {
+                    console.error(e);
+                }
+            return section;
+        });
+    }
+
     /**
      * Creates a new section for this diagram, based on the passed nodes
      * @param nodes The nodes to make the section fro
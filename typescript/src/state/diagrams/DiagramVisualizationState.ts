@@ -157,6 +157,35 @@ export class DiagramVisualizationState extends ViewState {
         return this.drawer.get_nodes(xRel, -yRel - heightRel, widthRel, heightRel, 2000); // TODO: create selection number setting
     }
 
+    /**
+     * Retrieves the level whose layer is shown at the given height
+     * @param y The height relative to the top of the current view
+     * @returns The level, if the layer at this height consists of a single level
+     */
+    public getLevel(y: number): number | undefined {
+        const canvasArea = this.canvas.getBoundingClientRect();
+        const yRel = y / canvasArea.height - 0.5;
+        return this.drawer.get_level(-yRel);
+    }
+
+    /**
+     * Moves the given level to the position of the target level, such that the diagram is rebuilt under the resulting variable order and its nodes move to their new positions
+     * @param level The level to move
+     * @param to The level whose position the moved level should take
+     * @returns Whether the level could be moved
+     */
+    public moveLevel(level: number, to: number): boolean {
+        if (level == to) return false;
+        try {
+            this.drawer.move_level(level, to);
+        } catch (e) {
+            console.error(e);
+            return false;
+        }
+        this.relayout();
+        return true;
+    }
+
     /**
      * Converts the ids of local visualization nodes, to the source node ids (in the overall diagram) that they represent
      * @param nodes The nodes for which to obtain the source ids
@@ -166,6 +195,30 @@ export class DiagramVisualizationState extends ViewState {
         return this.drawer.local_nodes_to_sources(nodes);
     }
 
+    /**
+     * Retrieves the complete state of the visualization as human-readable JSON, which can be diffed, edited by hand and stored next to the diagram files
+     * @returns The JSON text of the state
+     */
+    public exportStateJSON(): string {
+        return this.drawer.serialize_state_json();
+    }
+
+    /**
+     * Restores a state obtained from `exportStateJSON`
+     * @param json The JSON text of the state
+     * @returns Whether the state could be restored
+     */
+    public importStateJSON(json: string): boolean {
+        try {
+            this.drawer.deserialize_state_json(json);
+        } catch (e) {
+            console.error(e);
+            return false;
+        }
+        this.relayout();
+        return true;
+    }
+
     /** Renders a frame to the canvas */
     public render() {
         const time = Date.now() - this.start;
@@ -203,7 +256,11 @@ export class DiagramVisualizationState extends ViewState {
             push(super.deserialize(data));
             push(this.transform.set(data.transform));
             const rustState = stringToBinary(data.rustState);
-            this.drawer.deserialize_state(rustState);
+            try {
+                this.drawer.deserialize_state(rustState);
+            } catch (e) {
+                console.error("Failed to restore the visualization state: ", e);
+            }
             push(this.config.get().deserialize(data.configuration as never));
         });
     }
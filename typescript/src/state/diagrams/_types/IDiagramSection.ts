@@ -11,6 +11,9 @@ export type IDiagramSection<T> = {
     /** The section box in rust */
     readonly source: IWatchable<DiagramSectionBox | null>;
 
+    /** The error that prevented the section box from being created, if any */
+    readonly error: IWatchable<string | null>;
+
     /** The visualization of this diagram */
     readonly visualization: IWatchable<DiagramVisualizationState | null>;
 
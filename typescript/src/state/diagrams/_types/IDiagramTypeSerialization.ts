@@ -4,4 +4,4 @@ export type IDiagramTypeSerialization = {
     type: IDiagramType;
     state: IDiagramSerialization;
 };
-export type IDiagramType = "BDD" | "QDD" | "MTBDD";
+export type IDiagramType = "BDD" | "QDD" | "BCDD" | "ZBDD" | "MTBDD";
@@ -0,0 +1,5 @@
+/** The reason that a diagram section could not be created */
+export type ISectionError = {
+    /** The message describing the error, e.g. a parse error including its line and column */
+    error: string;
+};
@@ -1,4 +1,10 @@
-import {create_mtbdd_diagram, create_qdd_diagram, DiagramBox} from "oxidd-vis-rust";
+import {
+    create_bcdd_diagram,
+    create_mtbdd_diagram,
+    create_qdd_diagram,
+    create_zbdd_diagram,
+    DiagramBox,
+} from "oxidd-vis-rust";
 import {IDiagramType} from "./_types/IDiagramTypeSerialization";
 
 /**
@@ -12,6 +18,14 @@ export function createDiagramBox(type: IDiagramType): DiagramBox {
         const diagramBox = create_mtbdd_diagram();
         if (!diagramBox) throw Error("Could not create a new DD");
         return diagramBox;
+    } else if (type == "BCDD") {
+        const diagramBox = create_bcdd_diagram();
+        if (!diagramBox) throw Error("Could not create a new DD");
+        return diagramBox;
+    } else if (type == "ZBDD") {
+        const diagramBox = create_zbdd_diagram();
+        if (!diagramBox) throw Error("Could not create a new DD");
+        return diagramBox;
     } else {
         const diagramBox = create_qdd_diagram();
         if (!diagramBox) throw Error("Could not create a new DD");
@@ -26,18 +26,21 @@ export class FileSource extends AbstractDiagramSectionState<IFileSourceSerializa
             new Derived(() => {
                 const data = this.data.get();
                 try {
-                    const diagram =
-                        "dddmp" in data
-                            ? diagramBox.create_section_from_dddmp(data.dddmp)
-                            : diagramBox.create_section_from_other(
-                                  data.buddy.data,
-                                  data.buddy.vars
-                              );
-                    if (!diagram) console.error("Diagram could not be created from data");
-                    return diagram;
+                    return "dddmp" in data
+                        ? diagramBox.create_section_from_dddmp(data.dddmp)
+                        : "formula" in data
+                        ? diagramBox.create_section_from_formula(
+                              data.formula.text,
+                              data.formula.varOrder
+                          )
+                        : diagramBox.create_section_from_other(
+                              data.buddy.data,
+                              data.buddy.vars
+                          );
                 } catch (e) {
-                    console.error(e);
-                    throw e;
+                    // Parse errors are thrown as exceptions, describing the line and column
+                    console.error("Diagram could not be created from data:", e);
+                    return {error: e instanceof Error ? e.message : String(e)};
                 }
             })
         );
@@ -50,13 +50,12 @@ export class ReferenceSource extends AbstractDiagramSectionState<IReferenceSourc
                     source_section = watch(parent.source) ?? source_section;
                 }
                 const roots = watch(this.roots);
-                const diagram = diagramBox.create_section_from_ids(
-                    roots,
-                    source_section!
-                );
-                if (!diagram)
-                    console.error("Diagram could not be created from reference");
-                return diagram;
+                try {
+                    return diagramBox.create_section_from_ids(roots, source_section!);
+                } catch (e) {
+                    console.error("Diagram could not be created from reference:", e);
+                    return {error: e instanceof Error ? e.message : String(e)};
+                }
             })
         );
 
@@ -1,3 +1,4 @@
-export type IFileSourceSerialization = IDddmpData | IBuddyData;
+export type IFileSourceSerialization = IDddmpData | IBuddyData | IFormulaData;
 export type IDddmpData = {dddmp: string};
 export type IBuddyData = {buddy: {data: string; vars?: string}};
+export type IFormulaData = {formula: {text: string; varOrder?: string}};
@@ -0,0 +1,24 @@
+import {DiagramSectionDrawerBox} from "oxidd-vis-rust";
+import {DiagramVisualizationState} from "../diagrams/DiagramVisualizationState";
+import {ITool} from "./_types/ITool";
+import {IToolEvent} from "./_types/IToolEvent";
+import {TempHighlightToolState} from "./util/TempHighlightToolState";
+
+export class FocusToolState extends TempHighlightToolState implements ITool {
+    public constructor() {
+        super("Focus Tool");
+    }
+
+    /** @override */
+    protected applyRelease(
+        visualization: DiagramVisualizationState,
+        drawer: DiagramSectionDrawerBox,
+        nodes: Uint32Array,
+        event: IToolEvent
+    ): boolean {
+        if (nodes.length == 0) return false;
+
+        drawer.focus_nodes(nodes);
+        return true;
+    }
+}
@@ -13,6 +13,7 @@ import {Constant} from "../../watchables/Constant";
 import {IToolEvent} from "./_types/IToolEvent";
 import {DiagramSectionDrawerBox} from "oxidd-vis-rust";
 import {GroupingToolState} from "./GroupingToolState";
+import {FocusToolState} from "./FocusToolState";
 
 export class ToolbarState extends ViewState implements ITool {
     /** The currently selected tool */
@@ -24,6 +25,9 @@ export class ToolbarState extends ViewState implements ITool {
     /** The node (children-)expansion tool */
     public readonly expansionTool = new ExpansionToolState();
 
+    /** The tool revealing the neighbourhood of nodes, while regrouping far-away nodes */
+    public readonly focusTool = new FocusToolState();
+
     /** The node combining tool */
     public readonly groupingTool = new GroupingToolState();
 
@@ -31,6 +35,7 @@ export class ToolbarState extends ViewState implements ITool {
     protected readonly selectedTool = new Derived(watch => {
         const selectedName = watch(this.selectedToolName);
         if (selectedName == "expansion") return this.expansionTool;
+        if (selectedName == "focus") return this.focusTool;
         if (selectedName == "grouping") return this.groupingTool;
         return this.selectionTool;
     });
@@ -58,6 +63,7 @@ export class ToolbarState extends ViewState implements ITool {
     public readonly children = new Constant<ViewState[]>([
         this.selectionTool,
         this.expansionTool,
+        this.focusTool,
         this.groupingTool,
     ]);
 
@@ -1 +1 @@
-export type IToolName = "selection" | "expansion" | "grouping";
+export type IToolName = "selection" | "expansion" | "focus" | "grouping";